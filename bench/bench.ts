@@ -8,9 +8,12 @@ import { benchBuffer } from './buffer'
 import { benchCreateArray } from './create-array'
 import { benchGetArray } from './get-array-from-js'
 import { benchGetSetProperty } from './get-set-property'
+import { benchHashMap } from './hash-map'
 import { benchNoop } from './noop'
 import { benchPlus } from './plus'
 import { benchQuery } from './query'
+import { benchRegisterModule } from './register-module'
+import { benchUnwrap } from './unwrap'
 
 async function run() {
   const output = [
@@ -20,8 +23,11 @@ async function run() {
     await benchCreateArray(),
     await benchGetArray(),
     await benchGetSetProperty(),
+    await benchHashMap(),
     await benchAsync(),
     await benchQuery(),
+    await benchRegisterModule(),
+    await benchUnwrap(),
   ]
     .map(formatSummary)
     .join('\n')
@@ -4,6 +4,8 @@ const {
   createArrayJson,
   createArray,
   createArrayWithSerdeTrait,
+  createArrayByPush,
+  createArraySetElements,
 } = require('./index.node')
 
 export const benchCreateArray = () =>
@@ -20,6 +22,14 @@ export const benchCreateArray = () =>
       createArrayWithSerdeTrait()
     }),
 
+    b.add('create array by push', () => {
+      createArrayByPush()
+    }),
+
+    b.add('create array with set_elements', () => {
+      createArraySetElements()
+    }),
+
     b.cycle(),
     b.complete(),
   )
@@ -0,0 +1,14 @@
+import b from 'benny'
+
+const { createHashMap } = require('./index.node')
+
+export const benchHashMap = () =>
+  b.suite(
+    'HashMap',
+    b.add('return HashMap<String, u32> with 10k entries', () => {
+      createHashMap()
+    }),
+
+    b.cycle(),
+    b.complete(),
+  )
@@ -0,0 +1,61 @@
+import { spawn } from 'child_process'
+import { join } from 'path'
+import { Worker } from 'worker_threads'
+
+import b from 'benny'
+
+// a separate addon, so the 10k exports don't slow down loading the addon of the other benchmarks
+const addon = join(__dirname, 'register-module', 'index.node')
+
+// every worker thread runs `napi_register_module_v1` again, with 10k exports in 10 namespaces
+function loadInWorker() {
+  return new Promise<void>((resolve, reject) => {
+    const w = new Worker(
+      `require(${JSON.stringify(addon)}); require('worker_threads').parentPort.postMessage(null)`,
+      { eval: true },
+    )
+    w.once('message', () => {
+      w.terminate().then(() => resolve(), reject)
+    })
+    w.once('error', reject)
+  })
+}
+
+// a cold start, the exports are registered without a namespace if `flat` is true
+function loadInProcess(flat: boolean) {
+  return new Promise<void>((resolve, reject) => {
+    const env = { ...process.env }
+    if (flat) {
+      env.NAPI_BENCH_FLAT_EXPORTS = '1'
+    }
+    const child = spawn(process.execPath, ['-e', `require(${JSON.stringify(addon)})`], {
+      env,
+      stdio: 'inherit',
+    })
+    child.once('error', reject)
+    child.once('exit', (code) => {
+      if (code === 0) {
+        resolve()
+      } else {
+        reject(new Error(`Loading the addon exited with code ${code}`))
+      }
+    })
+  })
+}
+
+export const benchRegisterModule = () =>
+  b.suite(
+    'Register module',
+    b.add('register 10k exports in 10 namespaces', async () => {
+      await loadInWorker()
+    }),
+    b.add('cold start with 10k exports in 10 namespaces', async () => {
+      await loadInProcess(false)
+    }),
+    b.add('cold start with 10k exports without a namespace', async () => {
+      await loadInProcess(true)
+    }),
+
+    b.cycle(),
+    b.complete(),
+  )
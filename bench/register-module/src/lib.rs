@@ -0,0 +1,25 @@
+use napi::{bindgen_prelude::register_module_export, sys, Result};
+
+const NAMESPACES: usize = 10;
+const EXPORTS_PER_NAMESPACE: usize = 1000;
+
+unsafe fn create_export(env: sys::napi_env) -> Result<sys::napi_value> {
+  let mut value = std::ptr::null_mut();
+  unsafe { sys::napi_get_undefined(env, &mut value) };
+  Ok(value)
+}
+
+/// Registers 10k exports across 10 namespaces, so loading the module measures the registration.
+///
+/// With `NAPI_BENCH_FLAT_EXPORTS` set, the 10k exports are registered without a namespace instead.
+#[napi::module_init]
+fn register_many_exports() {
+  let flat = std::env::var_os("NAPI_BENCH_FLAT_EXPORTS").is_some();
+  for ns in 0..NAMESPACES {
+    let js_mod: &'static str = Box::leak(format!("ns{}\0", ns).into_boxed_str());
+    for index in 0..EXPORTS_PER_NAMESPACE {
+      let name: &'static str = Box::leak(format!("fn{}_{}\0", ns, index).into_boxed_str());
+      register_module_export(if flat { None } else { Some(js_mod) }, name, create_export);
+    }
+  }
+}
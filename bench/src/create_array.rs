@@ -5,6 +5,8 @@ pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("createArrayJson", create_array_json)?;
   exports.create_named_method("createArray", create_array)?;
   exports.create_named_method("createArrayWithSerdeTrait", create_array_with_serde_trait)?;
+  exports.create_named_method("createArrayByPush", create_array_by_push)?;
+  exports.create_named_method("createArraySetElements", create_array_set_elements)?;
   Ok(())
 }
 
@@ -30,3 +32,21 @@ pub fn create_array_with_serde_trait(env: Env) -> ContextlessResult<JsUnknown> {
   let a: Vec<u32> = vec![42; 1000];
   env.to_js_value(&a).map(Some)
 }
+
+#[contextless_function]
+pub fn create_array_by_push(env: Env) -> ContextlessResult<JsObject> {
+  let a: Vec<u32> = vec![42; 1000];
+  let mut ret = env.create_array(0)?;
+  for item in a {
+    ret.insert(item)?;
+  }
+  ret.coerce_to_object().map(Some)
+}
+
+#[contextless_function]
+pub fn create_array_set_elements(env: Env) -> ContextlessResult<JsObject> {
+  let a: Vec<u32> = vec![42; 1000];
+  let mut ret = env.create_array(a.len() as u32)?;
+  ret.set_elements(0, a)?;
+  ret.coerce_to_object().map(Some)
+}
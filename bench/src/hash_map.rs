@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+
+use napi::{
+  bindgen_prelude::ToNapiValue, ContextlessResult, Env, JsObject, JsUnknown, NapiValue, Result,
+};
+
+pub fn register_js(exports: &mut JsObject) -> Result<()> {
+  exports.create_named_method("createHashMap", create_hash_map)?;
+  Ok(())
+}
+
+#[contextless_function]
+pub fn create_hash_map(env: Env) -> ContextlessResult<JsUnknown> {
+  let map: HashMap<String, u32> = (0..10_000).map(|i| (format!("key-{}", i), i)).collect();
+  let raw = unsafe { HashMap::to_napi_value(env.raw(), map)? };
+  Ok(Some(unsafe {
+    JsUnknown::from_raw_unchecked(env.raw(), raw)
+  }))
+}
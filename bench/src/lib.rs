@@ -18,9 +18,11 @@ mod buffer;
 mod create_array;
 mod get_set_property;
 mod get_value_from_js;
+mod hash_map;
 mod noop;
 mod plus;
 mod query;
+mod unwrap;
 
 #[module_exports]
 fn init(mut exports: JsObject, env: Env) -> Result<()> {
@@ -32,7 +34,9 @@ fn init(mut exports: JsObject, env: Env) -> Result<()> {
   get_set_property::register_js(&mut exports, &env)?;
   create_array::register_js(&mut exports)?;
   get_value_from_js::register_js(&mut exports)?;
+  hash_map::register_js(&mut exports)?;
   query::register_js(&mut exports)?;
+  unwrap::register_js(&mut exports)?;
 
   Ok(())
 }
@@ -0,0 +1,45 @@
+use napi::*;
+
+struct Counter {
+  count: u32,
+}
+
+#[js_function]
+fn create_counter(ctx: CallContext) -> Result<JsObject> {
+  let mut counter = ctx.env.create_object()?;
+  ctx.env.wrap(&mut counter, Counter { count: 0 })?;
+  Ok(counter)
+}
+
+#[js_function(2)]
+fn increase_counter(ctx: CallContext) -> Result<JsNumber> {
+  let counter = ctx.get::<JsObject>(0)?;
+  let times = ctx.get::<JsNumber>(1)?.get_uint32()?;
+  for _ in 0..times {
+    ctx.env.unwrap::<Counter>(&counter)?.count += 1;
+  }
+  ctx
+    .env
+    .create_uint32(ctx.env.unwrap::<Counter>(&counter)?.count)
+}
+
+#[js_function(2)]
+fn increase_counter_cached(ctx: CallContext) -> Result<JsNumber> {
+  let counter = ctx.get::<JsObject>(0)?;
+  let times = ctx.get::<JsNumber>(1)?.get_uint32()?;
+  ctx.env.with_unwrap_cache(|cache| {
+    for _ in 0..times {
+      cache.unwrap::<Counter>(&counter)?.count += 1;
+    }
+    ctx
+      .env
+      .create_uint32(cache.unwrap::<Counter>(&counter)?.count)
+  })
+}
+
+pub fn register_js(exports: &mut JsObject) -> Result<()> {
+  exports.create_named_method("createCounter", create_counter)?;
+  exports.create_named_method("increaseCounter", increase_counter)?;
+  exports.create_named_method("increaseCounterCached", increase_counter_cached)?;
+  Ok(())
+}
@@ -0,0 +1,23 @@
+import b from 'benny'
+
+const {
+  createCounter,
+  increaseCounter,
+  increaseCounterCached,
+} = require('./index.node')
+
+const counter = createCounter()
+
+export const benchUnwrap = () =>
+  b.suite(
+    'Unwrap the same object 100 times',
+    b.add('Env::unwrap', () => {
+      increaseCounter(counter, 100)
+    }),
+    b.add('UnwrapCache::unwrap', () => {
+      increaseCounterCached(counter, 100)
+    }),
+
+    b.cycle(),
+    b.complete(),
+  )
@@ -210,6 +210,12 @@ const BUILD_OPTIONS: CommandSchema = {
       description:
         'Whether to disable the default file header for generated type def file. Only works when `typedef` feature enabled.',
     },
+    {
+      name: 'dtsPerModule',
+      type: 'boolean',
+      description:
+        'Write each `js_mod` namespace to its own `[dts].[namespace].d.ts`, re-exported from the type def file',
+    },
     {
       name: 'strip',
       type: 'boolean',
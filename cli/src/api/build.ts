@@ -3,7 +3,7 @@ import { createHash } from 'node:crypto'
 import { existsSync, mkdirSync } from 'node:fs'
 import { createRequire } from 'node:module'
 import { tmpdir, homedir } from 'node:os'
-import { parse, join, resolve } from 'node:path'
+import { basename, dirname, parse, join, resolve } from 'node:path'
 
 import * as colors from 'colorette'
 
@@ -31,7 +31,7 @@ import {
   writeFileAsync,
 } from '../utils/index.js'
 
-import { createCjsBinding } from './templates/index.js'
+import { createCjsBinding, createCjsExports } from './templates/index.js'
 import {
   createWasiBinding,
   createWasiBrowserBinding,
@@ -614,7 +614,8 @@ class Builder {
 
     // only for cdylib
     if (this.cdyLibName) {
-      const idents = await this.generateTypeDef()
+      const { exports: idents, lazyExports: lazyIdents, main } =
+        await this.generateTypeDef()
       const intermediateWasiRegisterFile = this.envs.WASI_REGISTER_TMP_PATH
       const wasiRegisterFunctions =
         this.target.arch === 'wasm32'
@@ -640,11 +641,13 @@ class Builder {
                 })
             })()
           : []
-      const jsOutput = await this.writeJsBinding(idents)
+      const jsOutput = await this.writeJsBinding(idents, main, lazyIdents)
       const wasmBindingsOutput = await this.writeWasiBinding(
         wasiRegisterFunctions,
         dest ?? 'index.wasm',
         idents,
+        main,
+        lazyIdents,
       )
       if (jsOutput) {
         this.outputs.push(jsOutput)
@@ -726,19 +729,28 @@ class Builder {
     return []
   }
 
-  private async generateTypeDef() {
+  private async generateTypeDef(): Promise<{
+    exports: string[]
+    lazyExports: string[]
+    main?: string
+  }> {
     if (!(await fileExists(this.envs.TYPE_DEF_TMP_PATH))) {
-      return []
+      return { exports: [], lazyExports: [] }
     }
 
     const dest = join(this.outputDir, this.options.dts ?? 'index.d.ts')
+    const moduleFilePrefix = this.options.dtsPerModule
+      ? basename(dest, '.d.ts')
+      : undefined
 
-    const { dts, exports } = await processTypeDef(
+    const { dts, modules, exports, lazyExports, main } = await processTypeDef(
       this.envs.TYPE_DEF_TMP_PATH,
       this.options.constEnum ?? true,
       !this.options.noDtsHeader
         ? this.options.dtsHeader ?? DEFAULT_TYPE_DEF_HEADER
         : '',
+      moduleFilePrefix,
+      this.config.dtsModule,
     )
 
     try {
@@ -749,20 +761,36 @@ class Builder {
         kind: 'dts',
         path: dest,
       })
+      for (const { namespace, dts } of modules) {
+        const moduleDest = join(
+          dirname(dest),
+          `${moduleFilePrefix}.${namespace}.d.ts`,
+        )
+        debug('  %i', moduleDest)
+        await writeFileAsync(moduleDest, dts, 'utf-8')
+        this.outputs.push({
+          kind: 'dts',
+          path: moduleDest,
+        })
+      }
     } catch (e) {
       debug.error('Failed to write type def file')
       debug.error(e as Error)
     }
 
-    return exports
+    return { exports, lazyExports, main }
   }
 
-  private async writeJsBinding(idents: string[]) {
+  private async writeJsBinding(
+    idents: string[],
+    main?: string,
+    lazyIdents?: string[],
+  ) {
     if (
       !this.options.platform ||
       // eslint-disable-next-line @typescript-eslint/prefer-nullish-coalescing
       this.options.noJsBinding ||
-      idents.length === 0
+      (idents.length === 0 && !main)
     ) {
       return
     }
@@ -773,6 +801,8 @@ class Builder {
       this.config.binaryName,
       this.config.packageName,
       idents,
+      main,
+      lazyIdents,
     )
 
     try {
@@ -793,6 +823,8 @@ class Builder {
     wasiRegisterFunctions: string[],
     distFileName: string | undefined,
     idents: string[],
+    main?: string,
+    lazyIdents?: string[],
   ) {
     if (distFileName && wasiRegisterFunctions.length) {
       const { name, dir } = parse(distFileName)
@@ -804,11 +836,9 @@ class Builder {
       const workerPath = join(dir, 'wasi-worker.mjs')
       const browserWorkerPath = join(dir, 'wasi-worker-browser.mjs')
       const browserEntryPath = join(dir, 'browser.js')
-      const exportsCode = idents
-        .map(
-          (ident) => `module.exports.${ident} = __napiModule.exports.${ident}`,
-        )
-        .join('\n')
+      const exportsCode =
+        (main ? 'module.exports = __napiModule.exports\n' : '') +
+        createCjsExports('__napiModule.exports', idents, lazyIdents)
       await writeFileAsync(
         bindingPath,
         createWasiBinding(
@@ -823,10 +853,12 @@ class Builder {
       await writeFileAsync(
         browserBindingPath,
         createWasiBrowserBinding(name, wasiRegisterFunctions) +
-          idents
-            .map(
-              (ident) =>
-                `export const ${ident} = __napiModule.exports.${ident}`,
+          (main ? ['export default __napiModule.exports'] : [])
+            .concat(
+              idents.map(
+                (ident) =>
+                  `export const ${ident} = __napiModule.exports.${ident}`,
+              ),
             )
             .join('\n') +
           '\n',
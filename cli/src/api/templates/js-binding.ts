@@ -1,7 +1,28 @@
+/**
+ * Re-export `idents` of `binding` on `module.exports`.
+ *
+ * The `lazyIdents` are `#[napi(lazy)]` classes, they are re-exported with a getter so they are only defined on first access.
+ */
+export function createCjsExports(
+  binding: string,
+  idents: string[],
+  lazyIdents: string[] = [],
+): string {
+  return idents
+    .map((ident) =>
+      lazyIdents.includes(ident)
+        ? `Object.defineProperty(module.exports, '${ident}', { enumerable: true, get() { return ${binding}.${ident} } })`
+        : `module.exports.${ident} = ${binding}.${ident}`,
+    )
+    .join('\n')
+}
+
 export function createCjsBinding(
   localName: string,
   pkgName: string,
   idents: string[],
+  main?: string,
+  lazyIdents: string[] = [],
 ): string {
   return `// prettier-ignore
 /* eslint-disable */
@@ -347,8 +368,10 @@ if (!nativeBinding) {
   throw new Error(\`Failed to load native binding\`)
 }
 
-${idents
-  .map((ident) => `module.exports.${ident} = nativeBinding.${ident}`)
-  .join('\n')}
+${main ? 'module.exports = nativeBinding\n' : ''}${createCjsExports(
+  'nativeBinding',
+  idents,
+  lazyIdents,
+)}
 `
 }
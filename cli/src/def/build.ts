@@ -80,6 +80,11 @@ export abstract class BaseBuildCommand extends Command {
       'Whether to disable the default file header for generated type def file. Only works when `typedef` feature enabled.',
   })
 
+  dtsPerModule?: boolean = Option.Boolean('--dts-per-module', {
+    description:
+      'Write each `js_mod` namespace to its own `[dts].[namespace].d.ts`, re-exported from the type def file',
+  })
+
   strip?: boolean = Option.Boolean('--strip,-s', {
     description: 'Whether strip the library to achieve the minimum file size',
   })
@@ -153,6 +158,7 @@ export abstract class BaseBuildCommand extends Command {
       dts: this.dts,
       dtsHeader: this.dtsHeader,
       noDtsHeader: this.noDtsHeader,
+      dtsPerModule: this.dtsPerModule,
       strip: this.strip,
       release: this.release,
       verbose: this.verbose,
@@ -234,6 +240,10 @@ export interface BuildOptions {
    * Whether to disable the default file header for generated type def file. Only works when `typedef` feature enabled.
    */
   noDtsHeader?: boolean
+  /**
+   * Write each `js_mod` namespace to its own `[dts].[namespace].d.ts`, re-exported from the type def file
+   */
+  dtsPerModule?: boolean
   /**
    * Whether strip the library to achieve the minimum file size
    */
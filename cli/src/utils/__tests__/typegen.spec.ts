@@ -1,3 +1,5 @@
+import { mkdtemp, writeFile } from 'fs/promises'
+import { tmpdir } from 'os'
 import { join } from 'path'
 import { fileURLToPath } from 'url'
 
@@ -62,3 +64,215 @@ test('should process type def with noConstEnum correctly', async (t) => {
 
   t.snapshot(dts)
 })
+
+test('should process type def of each js_mod into its own module', async (t) => {
+  const { dts, modules } = await processTypeDef(
+    join(
+      fileURLToPath(import.meta.url),
+      '../',
+      '__fixtures__',
+      'napi_type_def',
+    ),
+    true,
+    undefined,
+    'index',
+  )
+
+  t.deepEqual(modules.map(({ namespace }) => namespace), ['xxh2', 'xxh3'])
+  t.true(dts.includes(`export * as xxh2 from './index.xxh2'`))
+  t.true(dts.includes(`export * as xxh3 from './index.xxh3'`))
+  t.false(dts.includes('export namespace'))
+  t.false(dts.includes('xxh3_64'))
+
+  const [xxh2, xxh3] = modules.map(({ dts }) => dts)
+  t.true(
+    xxh2.includes('export function xxh2Plus(a: number, b: number): number'),
+  )
+  t.false(xxh2.includes('xxh3_64'))
+  t.false(xxh2.includes('export class Animal'))
+  t.true(xxh3.includes('export class Xxh3 {'))
+  t.true(xxh3.includes('export function xxh3_64(input: Buffer): bigint'))
+  t.false(xxh3.includes('xxh2Plus'))
+  t.false(xxh3.includes('export class Animal'))
+})
+
+test('should wrap type def in the configured module shape', async (t) => {
+  const fixture = join(
+    fileURLToPath(import.meta.url),
+    '../',
+    '__fixtures__',
+    'napi_type_def',
+  )
+
+  const { dts: named } = await processTypeDef(fixture, true)
+  const { dts: declared } = await processTypeDef(
+    fixture,
+    true,
+    undefined,
+    undefined,
+    { name: 'addon' },
+  )
+  t.true(declared.startsWith(`declare module 'addon' {\n  export class`))
+  t.true(declared.includes('\n  export function getWords('))
+  t.true(declared.endsWith('\n}\n'))
+  t.is(declared.split('\n').length, named.trim().split('\n').length + 3)
+
+  const { dts: cjs } = await processTypeDef(
+    fixture,
+    true,
+    undefined,
+    undefined,
+    { exports: 'cjs' },
+  )
+  t.true(cjs.startsWith('declare namespace binding {\n  export class'))
+  t.true(cjs.includes('\n  export class ExternalObject<T> {'))
+  t.true(cjs.endsWith('\n}\n\nexport = binding\n'))
+
+  const { dts: declaredCjs } = await processTypeDef(
+    fixture,
+    true,
+    '/* header */',
+    undefined,
+    { name: 'addon', exports: 'cjs' },
+  )
+  t.true(
+    declaredCjs.startsWith(
+      `/* header */\ndeclare module 'addon' {\n  namespace binding {\n    export class`,
+    ),
+  )
+  t.true(declaredCjs.endsWith('\n  }\n\n  export = binding\n}\n'))
+
+  await t.throwsAsync(
+    processTypeDef(fixture, true, undefined, 'index', { name: 'addon' }),
+  )
+})
+
+test('should not export the main function by name', async (t) => {
+  const typeDefFile = join(
+    await mkdtemp(join(tmpdir(), 'napi-rs-typegen-')),
+    'type_def',
+  )
+  await writeFile(
+    typeDefFile,
+    [
+      {
+        kind: 'fn',
+        name: 'greet',
+        js_doc: '',
+        def: 'export default function greet(name: string): string',
+      },
+      {
+        kind: 'fn',
+        name: 'add',
+        js_doc: '',
+        def: 'export function add(a: number, b: number): number',
+      },
+    ]
+      .map((def) => JSON.stringify(def))
+      .join('\n'),
+  )
+
+  const { dts, exports, main } = await processTypeDef(typeDefFile, true)
+  t.is(main, 'greet')
+  t.deepEqual(exports, ['add'])
+  t.true(dts.includes('export default function greet(name: string): string'))
+})
+
+test('should not export type-only declarations', async (t) => {
+  const typeDefFile = join(
+    await mkdtemp(join(tmpdir(), 'napi-rs-typegen-')),
+    'type_def',
+  )
+  await writeFile(
+    typeDefFile,
+    [
+      {
+        kind: 'type',
+        name: 'Level',
+        js_doc: '',
+        def: "export type Level = 'low' | 'high'",
+      },
+      {
+        kind: 'enum',
+        name: 'Status',
+        js_doc: '',
+        def: "Ready = 'Ready'",
+      },
+    ]
+      .map((def) => JSON.stringify(def))
+      .join('\n'),
+  )
+
+  const { dts, exports } = await processTypeDef(typeDefFile, true)
+  t.deepEqual(exports, ['Status'])
+  t.true(dts.includes("export type Level = 'low' | 'high'"))
+  t.true(dts.includes('export const enum Status {'))
+})
+
+test('should add every distinct ts_import once', async (t) => {
+  const typeDefFile = join(
+    await mkdtemp(join(tmpdir(), 'napi-rs-typegen-')),
+    'type_def',
+  )
+  const statement = "import type { Readable } from 'node:stream'"
+  await writeFile(
+    typeDefFile,
+    [
+      { kind: 'import', name: statement, js_doc: '', def: statement },
+      {
+        kind: 'fn',
+        name: 'isReadable',
+        js_doc: '',
+        def: 'export function isReadable(stream: Readable): boolean',
+      },
+      { kind: 'import', name: statement, js_doc: '', def: statement },
+    ]
+      .map((def) => JSON.stringify(def))
+      .join('\n'),
+  )
+
+  const { dts, exports } = await processTypeDef(
+    typeDefFile,
+    true,
+    '/* header */',
+  )
+  t.true(dts.startsWith(`/* header */\n${statement}\n\n`))
+  t.is(dts.split(statement).length, 2)
+  t.true(dts.includes('export function isReadable(stream: Readable): boolean'))
+  t.deepEqual(exports, ['isReadable'])
+
+  const { dts: declared } = await processTypeDef(
+    typeDefFile,
+    true,
+    undefined,
+    undefined,
+    { name: 'addon' },
+  )
+  t.true(declared.startsWith(`declare module 'addon' {\n  ${statement}\n`))
+})
+
+test('should list lazy classes in the lazy exports', async (t) => {
+  const typeDefFile = join(
+    await mkdtemp(join(tmpdir(), 'napi-rs-typegen-')),
+    'type_def',
+  )
+  await writeFile(
+    typeDefFile,
+    [
+      {
+        kind: 'struct',
+        name: 'Report',
+        js_doc: '',
+        def: 'constructor()',
+        lazy: true,
+      },
+      { kind: 'struct', name: 'Session', js_doc: '', def: 'constructor()' },
+    ]
+      .map((def) => JSON.stringify(def))
+      .join('\n'),
+  )
+
+  const { exports, lazyExports } = await processTypeDef(typeDefFile, true)
+  t.deepEqual(exports, ['Report', 'Session'])
+  t.deepEqual(lazyExports, ['Report'])
+})
@@ -42,6 +42,20 @@ export interface UserNapiConfig {
    */
   constEnum?: boolean
 
+  /**
+   * Shape of the generated type def file, default to named exports of an ES module
+   */
+  dtsModule?: {
+    /**
+     * Wrap the declarations in `declare module '<name>' { ... }`, e.g. to ship them in a separate types package
+     */
+    name?: string
+    /**
+     * `named` to declare every item with `export`, `cjs` to declare them in a namespace assigned with `export =`
+     */
+    exports?: 'named' | 'cjs'
+  }
+
   /**
    * @deprecated binaryName instead
    */
@@ -105,6 +119,7 @@ export type NapiConfig = Required<
 > & {
   targets: Target[]
   packageJson: CommonPackageJsonFields
+  dtsModule?: UserNapiConfig['dtsModule']
 }
 
 export async function readNapiConfig(
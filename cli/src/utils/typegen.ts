@@ -1,5 +1,6 @@
 import { sortBy } from 'lodash-es'
 
+import type { UserNapiConfig } from './config.js'
 import { readFileAsync } from './misc.js'
 
 const TOP_LEVEL_NAMESPACE = '__TOP_LEVEL_MODULE__'
@@ -10,19 +11,26 @@ export const DEFAULT_TYPE_DEF_HEADER = `/* auto-generated by NAPI-RS */
 enum TypeDefKind {
   Const = 'const',
   Enum = 'enum',
+  ConstEnum = 'const_enum',
   Interface = 'interface',
   Fn = 'fn',
   Struct = 'struct',
   Impl = 'impl',
+  Import = 'import',
+  // type-only declarations, like `#[napi(string_enum, ts_union)]`, no runtime value is exported
+  Type = 'type',
 }
 
 interface TypeDefLine {
   kind: TypeDefKind
   name: string
   original_name?: string
+  extends?: string
   def: string
   js_doc?: string
   js_mod?: string
+  // a `#[napi(lazy)]` class, defined by a getter of the exports on first access
+  lazy?: boolean
 }
 
 function prettyPrint(
@@ -33,7 +41,9 @@ function prettyPrint(
   let s = line.js_doc ?? ''
   switch (line.kind) {
     case TypeDefKind.Interface:
-      s += `export interface ${line.name} {\n${line.def}\n}`
+      s += `export interface ${line.name}${
+        line.extends ? ` extends ${line.extends}` : ''
+      } {\n${line.def}\n}`
       break
 
     case TypeDefKind.Enum:
@@ -41,6 +51,11 @@ function prettyPrint(
       s += `export ${enumName} ${line.name} {\n${line.def}\n}`
       break
 
+    case TypeDefKind.ConstEnum:
+      // no runtime object is exported, so this must stay a `const enum` regardless of `constEnum`
+      s += `export const enum ${line.name} {\n${line.def}\n}`
+      break
+
     case TypeDefKind.Struct:
       s += `export class ${line.name} {\n${line.def}\n}`
       if (line.original_name && line.original_name !== line.name) {
@@ -55,16 +70,62 @@ function prettyPrint(
   return correctStringIdent(s, ident)
 }
 
+export type DtsModuleShape = NonNullable<UserNapiConfig['dtsModule']>
+
+/**
+ * The namespace the declarations are put in with `exports: 'cjs'`
+ */
+const CJS_NAMESPACE = 'binding'
+
+export interface TypeDefModule {
+  namespace: string
+  dts: string
+}
+
+/**
+ * Generate the type def from the intermediate type file.
+ *
+ * If `moduleFilePrefix` is set, the declarations of every `js_mod` namespace are returned in `modules` instead,
+ * to be written to `${moduleFilePrefix}.${namespace}.d.ts` next to the type def file,
+ * which re-exports them with `export * as namespace from './${moduleFilePrefix}.${namespace}'`.
+ *
+ * `moduleShape` controls the top-level structure of the type def file, see `dtsModule` of the napi config.
+ */
 export async function processTypeDef(
   intermediateTypeFile: string,
   constEnum: boolean,
   header?: string,
+  moduleFilePrefix?: string,
+  moduleShape?: DtsModuleShape,
 ) {
+  if (moduleFilePrefix && moduleShape?.name) {
+    throw new Error(
+      'The type def of each js_mod can not be emitted into its own module when `dtsModule.name` is set',
+    )
+  }
+
   const exports: string[] = []
+  // the `#[napi(lazy)]` classes in `exports`, the binding re-exports them with a getter
+  const lazyExports: string[] = []
+  // the `#[napi(main)]` function, the module itself
+  let main: string | undefined
+  const modules: TypeDefModule[] = []
   const defs = await readIntermediateTypeFile(intermediateTypeFile)
-  const groupedDefs = preprocessTypeDef(defs)
+  // `#[napi(ts_import)]` statements, each one is added once
+  const imports = Array.from(
+    new Set(
+      defs
+        .filter((def) => def.kind === TypeDefKind.Import)
+        .map((def) => def.def),
+    ),
+  ).sort()
+  const importsDts = imports.length ? imports.join('\n') + '\n\n' : ''
+  const groupedDefs = preprocessTypeDef(
+    defs.filter((def) => def.kind !== TypeDefKind.Import),
+  )
 
   header = header ? header + '\n' : ''
+  const moduleHeader = header
   let dts = ''
 
   sortBy(Array.from(groupedDefs), ([namespace]) => namespace).forEach(
@@ -72,21 +133,40 @@ export async function processTypeDef(
       if (namespace === TOP_LEVEL_NAMESPACE) {
         for (const def of defs) {
           dts += prettyPrint(def, constEnum, 0) + '\n\n'
+          if (
+            def.kind === TypeDefKind.Fn &&
+            def.def.startsWith('export default function')
+          ) {
+            main = def.name
+            continue
+          }
           switch (def.kind) {
             case TypeDefKind.Const:
             case TypeDefKind.Enum:
             case TypeDefKind.Fn:
             case TypeDefKind.Struct: {
               exports.push(def.name)
+              if (def.lazy) {
+                lazyExports.push(def.name)
+              }
               if (def.original_name && def.original_name !== def.name) {
                 exports.push(def.original_name)
               }
               break
             }
+            case TypeDefKind.Type:
             default:
               break
           }
         }
+      } else if (moduleFilePrefix) {
+        exports.push(namespace)
+        let moduleDts = ''
+        for (const def of defs) {
+          moduleDts += prettyPrint(def, constEnum, 0) + '\n\n'
+        }
+        modules.push({ namespace, dts: moduleDts })
+        dts += `export * as ${namespace} from './${moduleFilePrefix}.${namespace}'\n\n`
       } else {
         exports.push(namespace)
         dts += `export namespace ${namespace} {\n`
@@ -98,21 +178,80 @@ export async function processTypeDef(
     },
   )
 
-  if (dts.indexOf('ExternalObject<') > -1) {
-    header += `
+  // declarations the submodules may refer to, they are not in scope of the submodule files
+  const topLevelNames = new Set<string>()
+  for (const def of groupedDefs.get(TOP_LEVEL_NAMESPACE) ?? []) {
+    topLevelNames.add(def.name)
+    if (def.original_name) {
+      topLevelNames.add(def.original_name)
+    }
+  }
+
+  if (
+    dts.indexOf('ExternalObject<') > -1 ||
+    modules.some(({ dts }) => dts.indexOf('ExternalObject<') > -1)
+  ) {
+    topLevelNames.add('ExternalObject')
+    dts =
+      `
 export class ExternalObject<T> {
   readonly '': {
     readonly '': unique symbol
     [K: symbol]: T
   }
 }
-`
+` + dts
   }
 
   return {
-    dts: header + dts,
+    dts: header + shapeModule(dts, moduleShape, importsDts),
+    modules: modules.map(({ namespace, dts }) => {
+      const imports = Array.from(topLevelNames)
+        .filter((name) => new RegExp(`\\b${name}\\b`).test(dts))
+        .sort()
+      const importDts = imports.length
+        ? `import type { ${imports.join(', ')} } from './${moduleFilePrefix}'\n\n`
+        : ''
+      return {
+        namespace,
+        dts: moduleHeader + importsDts + importDts + dts,
+      }
+    }),
     exports,
+    lazyExports,
+    main,
+  }
+}
+
+/**
+ * Wrap the declarations as described by `shape`, they are returned as is for the default shape.
+ *
+ * The `imports` are added in front of the declarations, inside of `declare module` if the shape has a name.
+ */
+function shapeModule(
+  dts: string,
+  shape?: DtsModuleShape,
+  imports = '',
+): string {
+  const indent = (src: string) =>
+    src
+      .trim()
+      .split('\n')
+      .map((line) => (line ? `  ${line}` : line))
+      .join('\n')
+
+  if (shape?.exports === 'cjs') {
+    // `declare` is implied inside of `declare module`
+    const declare = shape.name ? '' : 'declare '
+    dts = `${declare}namespace ${CJS_NAMESPACE} {\n${indent(
+      dts,
+    )}\n}\n\nexport = ${CJS_NAMESPACE}\n`
+  }
+  dts = imports + dts
+  if (shape?.name) {
+    dts = `declare module '${shape.name}' {\n${indent(dts)}\n}\n`
   }
+  return dts
 }
 
 async function readIntermediateTypeFile(file: string) {
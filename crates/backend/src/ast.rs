@@ -23,11 +23,23 @@ pub struct NapiFn {
   pub skip_typescript: bool,
   pub comments: Vec<String>,
   pub parent_is_generator: bool,
+  pub parent_is_async_generator: bool,
   pub writable: bool,
   pub enumerable: bool,
   pub configurable: bool,
   pub catch_unwind: bool,
+  /// Reject numbers which can't be converted into the integer arguments losslessly
+  pub strict_numbers: bool,
   pub unsafe_: bool,
+  pub module_init: bool,
+  /// Only export the function if this environment variable is truthy at load time
+  pub enabled_if_env: Option<String>,
+  /// Also define the method as `[Symbol.toPrimitive]` of the class
+  pub to_primitive: bool,
+  /// Don't reject a `js_name` which is not a valid JavaScript identifier when the module is loaded
+  pub any_js_name: bool,
+  /// The function is returned as the module, every other export is defined on it
+  pub main: bool,
   pub register_name: Ident,
 }
 
@@ -49,6 +61,28 @@ impl NapiFnArg {
   pub fn use_overridden_type_or(&self, default: impl FnOnce() -> String) -> String {
     self.ts_arg_type.as_ref().cloned().unwrap_or_else(default)
   }
+
+  /// The element type of a `Rest<T>` argument, which collects the remaining arguments
+  pub fn rest_elem(&self) -> Option<&syn::Type> {
+    let path = match &self.kind {
+      NapiFnArgKind::PatType(pat) => match pat.ty.as_ref() {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return None,
+      },
+      NapiFnArgKind::Callback(_) => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Rest" {
+      return None;
+    }
+    match &segment.arguments {
+      syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -83,10 +117,23 @@ pub struct NapiStruct {
   pub kind: NapiStructKind,
   pub object_from_js: bool,
   pub object_to_js: bool,
+  /// Seal the objects converted to JavaScript, their fields stay writable but no property can be added or removed
+  pub seal: bool,
+  /// Don't reject a `js_name` which is not a valid JavaScript identifier when the module is loaded
+  pub any_js_name: bool,
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub implement_iterator: bool,
+  pub implement_async_iterator: bool,
   pub use_custom_finalize: bool,
+  /// Keep the Rust value of the instances in an external property instead of `napi_wrap`
+  pub external_state: bool,
+  /// Called once per env when the constructor reference of the class is deleted at env cleanup
+  pub class_finalizer: Option<syn::Path>,
+  /// `new` throws in JavaScript, instances are only created by returning the struct from Rust
+  pub private_constructor: bool,
+  /// Define the class when it's first accessed instead of when the module is loaded
+  pub lazy: bool,
   pub register_name: Ident,
 }
 
@@ -95,6 +142,7 @@ pub enum NapiStructKind {
   None,
   Constructor,
   Object,
+  Opaque,
 }
 
 #[derive(Debug, Clone)]
@@ -110,6 +158,7 @@ pub struct NapiStructField {
   pub comments: Vec<String>,
   pub skip_typescript: bool,
   pub ts_type: Option<String>,
+  pub flatten: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -121,6 +170,8 @@ pub struct NapiImpl {
   pub iterator_yield_type: Option<Type>,
   pub iterator_next_type: Option<Type>,
   pub iterator_return_type: Option<Type>,
+  /// `iterator_*_type` come from an `AsyncGenerator` impl instead of a `Generator` impl
+  pub iterator_is_async: bool,
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub register_name: Ident,
@@ -134,9 +185,26 @@ pub struct NapiEnum {
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub skip_typescript: bool,
+  /// Only emit a TS `const enum`, without registering a runtime object.
+  /// Consumers compiled with `isolatedModules` (esbuild, swc, Babel) can't inline it and must use the numeric values instead.
+  pub const_enum: bool,
+  /// `#[napi(string_enum, ts_union)]`, only emit a TS union of the string values, without registering a runtime object.
+  pub ts_union: bool,
+  /// `#[napi(discriminant = "...")]`, the enum is converted from and to an object with the
+  /// variant name in this field and the fields of the variant next to it, like a TS discriminated union.
+  pub discriminant: Option<String>,
   pub register_name: Ident,
 }
 
+impl NapiEnum {
+  pub fn is_string_enum(&self) -> bool {
+    self
+      .variants
+      .iter()
+      .any(|v| matches!(v.val, NapiEnumValue::String(_)))
+  }
+}
+
 #[derive(Debug, Clone)]
 pub enum NapiEnumValue {
   String(String),
@@ -157,6 +225,15 @@ pub struct NapiEnumVariant {
   pub name: Ident,
   pub val: NapiEnumValue,
   pub comments: Vec<String>,
+  /// Named fields of the variant, only with `#[napi(discriminant)]`
+  pub fields: Vec<NapiEnumVariantField>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NapiEnumVariantField {
+  pub name: Ident,
+  pub js_name: String,
+  pub ty: syn::Type,
 }
 
 #[derive(Debug, Clone)]
@@ -168,6 +245,13 @@ pub struct NapiConst {
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub skip_typescript: bool,
+  /// Only export the const if this environment variable is truthy at load time
+  pub enabled_if_env: Option<String>,
+  /// Deeply freeze the value once it's converted, see `#[napi(frozen)]`
+  pub frozen: bool,
+  /// The value is a JSON `&str` exported as the parsed value, see `#[napi(json)]`
+  pub json: bool,
+  pub ts_type: Option<String>,
   pub register_name: Ident,
 }
 
@@ -23,6 +23,19 @@ pub trait TryToTokens {
   }
 }
 
+/// Whether `name` can be used as a JavaScript identifier, e.g. `exports.name` or `new Name()`.
+///
+/// Reserved words are allowed, they are still valid property names.
+pub fn is_js_identifier(name: &str) -> bool {
+  let mut chars = name.chars();
+  match chars.next() {
+    Some(c) if c == '$' || c == '_' || c.is_alphabetic() => {
+      chars.all(|c| c == '$' || c == '_' || c.is_alphanumeric())
+    }
+    _ => false,
+  }
+}
+
 fn get_intermediate_ident(name: &str) -> Ident {
   let new_name = format!("__napi__{}", name);
   Ident::new(&new_name, Span::call_site())
@@ -26,18 +26,37 @@ impl NapiConst {
       self.name.span(),
     );
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let env_gate = self.enabled_if_env.as_ref().map(|env_var| {
+      quote! { napi::bindgen_prelude::register_export_env_gate(#cb_name, #env_var); }
+    });
+
+    let to_napi_value = if self.json {
+      quote! { napi::bindgen_prelude::parse_json(env, #name_ident) }
+    } else {
+      quote! { <#type_name as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #name_ident) }
+    };
+    let value = if self.frozen {
+      quote! { napi::bindgen_prelude::deep_freeze(env, #to_napi_value?) }
+    } else {
+      to_napi_value
+    };
+    let require_napi8 = self.frozen.then(|| {
+      quote! { const _: () = napi::bindgen_prelude::require_napi8_for_frozen(); }
+    });
 
     quote! {
+      #require_napi8
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
       unsafe fn #cb_name(env: napi::sys::napi_env) -> napi::Result<napi::sys::napi_value> {
-        <#type_name as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #name_ident)
+        #value
       }
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
       #[cfg(all(not(test), not(feature = "noop"), not(target_family = "wasm")))]
       #[napi::bindgen_prelude::ctor]
       fn #register_name() {
+        #env_gate
         napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name_lit, #cb_name);
       }
 
@@ -46,6 +65,7 @@ impl NapiConst {
       #[cfg(all(not(test), not(feature = "noop"), target_family = "wasm"))]
       #[no_mangle]
       unsafe extern "C" fn #register_name() {
+        #env_gate
         napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name_lit, #cb_name);
       }
     }
@@ -1,11 +1,21 @@
 use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
-use crate::{codegen::js_mod_to_token_stream, BindgenResult, NapiEnum, TryToTokens};
+use crate::{codegen::js_mod_to_token_stream, BindgenResult, NapiEnum, NapiEnumValue, TryToTokens};
 
 impl TryToTokens for NapiEnum {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
-    let register = self.gen_module_register();
+    if let Some(discriminant) = &self.discriminant {
+      self
+        .gen_discriminated_union_impl(discriminant)
+        .to_tokens(tokens);
+      return Ok(());
+    }
+    let register = if self.const_enum || self.ts_union {
+      quote! {}
+    } else {
+      self.gen_module_register()
+    };
     let napi_value_conversion = self.gen_napi_value_map_impl();
 
     (quote! {
@@ -24,13 +34,29 @@ impl NapiEnum {
     let name_str = self.name.to_string();
     let mut from_napi_branches = vec![];
     let mut to_napi_branches = vec![];
+    let mut variants = vec![];
+    let mut key_branches = vec![];
+    let mut index_branches = vec![];
+
+    let value_type = if self.is_string_enum() {
+      quote! { napi::bindgen_prelude::ValueType::String }
+    } else {
+      quote! { napi::bindgen_prelude::ValueType::Number }
+    };
 
-    self.variants.iter().for_each(|v| {
+    self.variants.iter().enumerate().for_each(|(index, v)| {
       let val: Literal = (&v.val).into();
+      let key = match &v.val {
+        NapiEnumValue::String(string) => string.clone(),
+        NapiEnumValue::Number(number) => number.to_string(),
+      };
       let v_name = &v.name;
 
       from_napi_branches.push(quote! { #val => Ok(#name::#v_name) });
       to_napi_branches.push(quote! { #name::#v_name => #val });
+      variants.push(quote! { #name::#v_name });
+      key_branches.push(quote! { #name::#v_name => #key });
+      index_branches.push(quote! { #name::#v_name => #index });
     });
 
     quote! {
@@ -49,7 +75,7 @@ impl NapiEnum {
           env: napi::bindgen_prelude::sys::napi_env,
           napi_val: napi::bindgen_prelude::sys::napi_value
         ) -> napi::bindgen_prelude::Result<napi::sys::napi_value> {
-          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::Number)?;
+          napi::bindgen_prelude::assert_type_of!(env, napi_val, #value_type)?;
           Ok(std::ptr::null_mut())
         }
       }
@@ -94,6 +120,163 @@ impl NapiEnum {
           napi::bindgen_prelude::ToNapiValue::to_napi_value(env, val)
         }
       }
+
+      impl napi::bindgen_prelude::EnumKey for #name {
+        const VARIANTS: &'static [Self] = &[#(#variants),*];
+
+        fn key(&self) -> &'static str {
+          match *self {
+            #(#key_branches,)*
+          }
+        }
+
+        fn index(&self) -> usize {
+          match *self {
+            #(#index_branches,)*
+          }
+        }
+      }
+    }
+  }
+
+  /// Convert from and to an object with the variant name in the `discriminant` field,
+  /// next to the fields of the variant. Nothing is registered on the exports.
+  fn gen_discriminated_union_impl(&self, discriminant: &str) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let mut to_napi_branches = vec![];
+    let mut from_napi_branches = vec![];
+    let mut expected = vec![];
+
+    for v in self.variants.iter() {
+      let v_name = &v.name;
+      let val: Literal = (&v.val).into();
+      let val_str = match &v.val {
+        NapiEnumValue::String(string) => string.clone(),
+        NapiEnumValue::Number(number) => number.to_string(),
+      };
+      expected.push(format!("'{}'", val_str));
+      let field_names = v.fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+
+      let field_setters = v.fields.iter().map(|f| {
+        let field_name = &f.name;
+        let js_name = &f.js_name;
+        if is_option(&f.ty) {
+          quote! {
+            if #field_name.is_some() {
+              obj.set(#js_name, #field_name)?;
+            }
+          }
+        } else {
+          quote! { obj.set(#js_name, #field_name)?; }
+        }
+      });
+      to_napi_branches.push(quote! {
+        #name::#v_name { #(#field_names),* } => {
+          obj.set(#discriminant, #val)?;
+          #(#field_setters)*
+        }
+      });
+
+      let field_getters = v.fields.iter().map(|f| {
+        let field_name = &f.name;
+        let js_name = &f.js_name;
+        let ty = &f.ty;
+        let get = quote! {
+          obj.get::<_, #ty>(#js_name).map_err(|e| {
+            napi::bindgen_prelude::error!(
+              e.status,
+              "Failed to convert field `{}` of {} `{}` of `{}`. {}",
+              #js_name,
+              #discriminant,
+              #val_str,
+              #name_str,
+              e.reason,
+            )
+          })?
+        };
+        if is_option(ty) {
+          quote! { let #field_name: #ty = #get.flatten(); }
+        } else {
+          quote! {
+            let #field_name: #ty = #get.ok_or_else(|| {
+              napi::bindgen_prelude::error!(
+                napi::bindgen_prelude::Status::InvalidArg,
+                "Missing field `{}` of {} `{}` of `{}`",
+                #js_name,
+                #discriminant,
+                #val_str,
+                #name_str,
+              )
+            })?;
+          }
+        }
+      });
+      from_napi_branches.push(quote! {
+        #val => {
+          #(#field_getters)*
+          Ok(#name::#v_name { #(#field_names),* })
+        }
+      });
+    }
+    let expected = expected.join(", ");
+
+    quote! {
+      impl napi::bindgen_prelude::TypeName for #name {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #name {}
+
+      impl napi::bindgen_prelude::FromNapiValue for #name {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<Self> {
+          let _nesting_guard = napi::bindgen_prelude::NestingGuard::enter()?;
+          let obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
+          let tag: String = obj.get(#discriminant)?.ok_or_else(|| {
+            napi::bindgen_prelude::error!(
+              napi::bindgen_prelude::Status::InvalidArg,
+              "Missing field `{}` of `{}`, expected one of {}",
+              #discriminant,
+              #name_str,
+              #expected,
+            )
+          })?;
+
+          match tag.as_str() {
+            #(#from_napi_branches,)*
+            _ => Err(napi::bindgen_prelude::error!(
+              napi::bindgen_prelude::Status::InvalidArg,
+              "{} `{}` does not match any variant of `{}`, expected one of {}",
+              #discriminant,
+              tag,
+              #name_str,
+              #expected,
+            )),
+          }
+        }
+      }
+
+      impl napi::bindgen_prelude::ToNapiValue for #name {
+        unsafe fn to_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          val: Self
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          let mut obj = napi::bindgen_prelude::Env::from(env).create_object()?;
+          match val {
+            #(#to_napi_branches,)*
+          }
+          napi::bindgen_prelude::Object::to_napi_value(env, obj)
+        }
+      }
     }
   }
 
@@ -166,3 +349,11 @@ impl NapiEnum {
     }
   }
 }
+
+fn is_option(ty: &syn::Type) -> bool {
+  matches!(
+    ty,
+    syn::Type::Path(syn::TypePath { path, .. })
+      if path.segments.last().map_or(false, |segment| segment.ident == "Option")
+  )
+}
@@ -3,12 +3,17 @@ use quote::ToTokens;
 use syn::spanned::Spanned;
 
 use crate::{
-  codegen::{get_intermediate_ident, js_mod_to_token_stream},
+  codegen::{get_intermediate_ident, is_js_identifier, js_mod_to_token_stream},
   BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, NapiFn, NapiFnArgKind, TryToTokens,
 };
 
 impl TryToTokens for NapiFn {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    if self.module_init {
+      tokens.extend(self.gen_module_init_register());
+      return Ok(());
+    }
+
     let name_str = self.name.to_string();
     let intermediate_ident = get_intermediate_ident(&name_str);
     let args_len = self.args.len();
@@ -119,10 +124,30 @@ impl TryToTokens for NapiFn {
       quote! { false }
     };
 
+    let arg_conversions = if self.strict_numbers {
+      quote! {
+        let _strict_numbers = napi::bindgen_prelude::StrictNumbersGuard::enter();
+        #(#arg_conversions)*
+        drop(_strict_numbers);
+      }
+    } else {
+      quote! { #(#arg_conversions)* }
+    };
+
+    // a class called as a function, without `new`, throws before its arguments are converted
+    let new_target_check = match (&self.kind, &self.parent) {
+      (FnKind::Constructor, Some(parent)) => {
+        let parent_str = parent.to_string();
+        quote! { cb.ensure_new_target(#parent_str)?; }
+      }
+      _ => quote! {},
+    };
+
     let function_call_inner = quote! {
       napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None, #use_after_async).and_then(|mut cb| {
+          #new_target_check
           #build_ref_container
-          #(#arg_conversions)*
+          #arg_conversions
           #native_call
         })
     };
@@ -155,19 +180,9 @@ impl TryToTokens for NapiFn {
     let function_call = if self.catch_unwind {
       quote! {
         {
+          napi::__private::reset_panic_location();
           std::panic::catch_unwind(|| { #function_call })
-            .map_err(|e| {
-              let message = {
-                if let Some(string) = e.downcast_ref::<String>() {
-                  string.clone()
-                } else if let Some(string) = e.downcast_ref::<&str>() {
-                  string.to_string()
-                } else {
-                  format!("panic from Rust code: {:?}", e)
-                }
-              };
-              napi::Error::new(napi::Status::GenericFailure, message)
-            })
+            .map_err(|e| napi::__private::panic_to_error(env, e))
             .and_then(|r| r)
         }
       }
@@ -235,6 +250,11 @@ impl NapiFn {
             let this: &mut #parent = Box::leak(Box::from_raw(this_ptr));
           });
         }
+        Some(FnSelf::Value) => {
+          arg_conversions.push(quote! {
+            let this: #parent = unsafe { cb.unwrap_owned::<#parent>()? };
+          });
+        }
         _ => {}
       };
     }
@@ -344,6 +364,16 @@ impl NapiFn {
                 }
               }
             }
+            if arg.rest_elem().is_some() {
+              if i + skipped_arg_count != self.args.len() - 1 {
+                bail_span!(path.ty, "`Rest` must be the last argument");
+              }
+              arg_conversions.push(quote! {
+                let #ident = napi::bindgen_prelude::Rest::from_callback_info(env, &cb, #i)?;
+              });
+              args.push(quote! { #ident });
+              continue;
+            }
             let (arg_conversion, arg_type) = self.gen_ty_arg_conversion(&ident, i, path);
             if NapiArgType::MutRef == arg_type {
               mut_ref_spans.push(path.ty.span());
@@ -489,11 +519,7 @@ impl NapiFn {
     let name = &self.name;
 
     match self.fn_self {
-      Some(FnSelf::Value) => {
-        // impossible, panic! in parser
-        unreachable!();
-      }
-      Some(FnSelf::Ref) | Some(FnSelf::MutRef) => quote! { this.#name },
+      Some(FnSelf::Value) | Some(FnSelf::Ref) | Some(FnSelf::MutRef) => quote! { this.#name },
       None => match &self.parent {
         Some(class) => quote! { #class::#name },
         None => quote! { #name },
@@ -508,26 +534,31 @@ impl NapiFn {
       let ty_string = ty.into_token_stream().to_string();
       let is_return_self = ty_string == "& Self" || ty_string == "&mut Self";
       if self.kind == FnKind::Constructor {
-        if self.is_ret_result {
-          if self.parent_is_generator {
-            quote! { cb.construct_generator(#js_name, #ret?) }
-          } else {
-            quote! { cb.construct(#js_name, #ret?) }
-          }
+        let construct = if self.parent_is_async_generator {
+          quote! { construct_async_generator }
         } else if self.parent_is_generator {
-          quote! { cb.construct_generator(#js_name, #ret) }
+          quote! { construct_generator }
+        } else {
+          quote! { construct }
+        };
+        if self.is_ret_result {
+          quote! { cb.#construct(#js_name, #ret?) }
         } else {
-          quote! { cb.construct(#js_name, #ret) }
+          quote! { cb.#construct(#js_name, #ret) }
         }
       } else if self.kind == FnKind::Factory {
         if self.is_ret_result {
-          if self.parent_is_generator {
+          if self.parent_is_async_generator {
+            quote! { cb.async_generator_factory(#js_name, #ret?) }
+          } else if self.parent_is_generator {
             quote! { cb.generator_factory(#js_name, #ret?) }
           } else if self.is_async {
             quote! { cb.factory(#js_name, #ret) }
           } else {
             quote! { cb.factory(#js_name, #ret?) }
           }
+        } else if self.parent_is_async_generator {
+          quote! { cb.async_generator_factory(#js_name, #ret) }
         } else if self.parent_is_generator {
           quote! { cb.generator_factory(#js_name, #ret) }
         } else {
@@ -565,6 +596,39 @@ impl NapiFn {
     }
   }
 
+  fn gen_module_init_register(&self) -> TokenStream {
+    let name = &self.name;
+    let module_register_name = &self.register_name;
+    let init = if self.is_ret_result {
+      quote! { #name }
+    } else {
+      quote! {
+        || {
+          #name();
+          Ok(())
+        }
+      }
+    };
+
+    quote! {
+      #[allow(clippy::all)]
+      #[allow(non_snake_case)]
+      #[cfg(all(not(test), not(feature = "noop"), not(target_family = "wasm")))]
+      #[napi::bindgen_prelude::ctor]
+      fn #module_register_name() {
+        napi::bindgen_prelude::register_module_init(#init);
+      }
+
+      #[allow(clippy::all)]
+      #[allow(non_snake_case)]
+      #[cfg(all(not(test), not(feature = "noop"), target_family = "wasm"))]
+      #[no_mangle]
+      extern "C" fn #module_register_name() {
+        napi::bindgen_prelude::register_module_init(#init);
+      }
+    }
+  }
+
   fn gen_fn_register(&self) -> TokenStream {
     if self.parent.is_some() {
       quote! {}
@@ -576,6 +640,20 @@ impl NapiFn {
       let intermediate_ident = get_intermediate_ident(&name_str);
       let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
       let cb_name = Ident::new(&format!("{}_js_function", name_str), Span::call_site());
+      let env_gate = self.enabled_if_env.as_ref().map(|env_var| {
+        quote! { napi::bindgen_prelude::register_export_env_gate(#cb_name, #env_var); }
+      });
+      // the main function is returned as the module, its name is not used
+      let rejected_js_name = (!self.any_js_name && !self.main && !is_js_identifier(&self.js_name))
+        .then(|| {
+          quote! { napi::bindgen_prelude::reject_export_name(#cb_name, #name_str); }
+        });
+      let class_references = self.gen_class_references(&js_name);
+      let register = if self.main {
+        quote! { napi::bindgen_prelude::register_module_main(#js_name, #cb_name); }
+      } else {
+        quote! { napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name, #cb_name); }
+      };
 
       quote! {
         #[allow(non_snake_case)]
@@ -604,7 +682,10 @@ impl NapiFn {
         #[cfg(all(not(test), not(feature = "noop"), not(target_family = "wasm")))]
         #[napi::bindgen_prelude::ctor]
         fn #module_register_name() {
-          napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name, #cb_name);
+          #env_gate
+          #rejected_js_name
+          #class_references
+          #register
         }
 
         #[allow(clippy::all)]
@@ -612,10 +693,85 @@ impl NapiFn {
         #[cfg(all(not(test), not(feature = "noop"), target_family = "wasm"))]
         #[no_mangle]
         extern "C" fn #module_register_name() {
-          napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name, #cb_name);
+          #env_gate
+          #rejected_js_name
+          #class_references
+          #register
+        }
+      }
+    }
+  }
+
+  /// Register the classes among the argument and return types, checked against the registered classes
+  /// while the module is loading
+  fn gen_class_references(&self, js_name: &str) -> TokenStream {
+    let mut candidates = Vec::new();
+    for arg in &self.args {
+      if let NapiFnArgKind::PatType(pat) = &arg.kind {
+        collect_class_candidates(&pat.ty, &mut candidates);
+      }
+    }
+    if let Some(ret) = &self.ret {
+      collect_class_candidates(ret, &mut candidates);
+    }
+    let mut seen = std::collections::HashSet::new();
+    candidates.retain(|ty| seen.insert(ty.to_token_stream().to_string()));
+    if candidates.is_empty() {
+      return quote! {};
+    }
+    let references = candidates.iter().map(|ty| {
+      quote! {
+        if let Some(class) = (&napi::bindgen_prelude::ClassReferenceProbe::<#ty>(std::marker::PhantomData)).class_js_name() {
+          napi::bindgen_prelude::register_class_reference(#js_name, class);
         }
       }
+    });
+    quote! {
+      {
+        use napi::bindgen_prelude::{ClassReference as _, NotClassReference as _};
+        #(#references)*
+      }
+    }
+  }
+}
+
+/// The types without generic arguments in `ty`, any of them may be a class
+fn collect_class_candidates<'a>(ty: &'a syn::Type, candidates: &mut Vec<&'a syn::Type>) {
+  match ty {
+    syn::Type::Reference(syn::TypeReference { elem, .. })
+    | syn::Type::Paren(syn::TypeParen { elem, .. })
+    | syn::Type::Group(syn::TypeGroup { elem, .. })
+    | syn::Type::Array(syn::TypeArray { elem, .. })
+    | syn::Type::Slice(syn::TypeSlice { elem, .. }) => collect_class_candidates(elem, candidates),
+    syn::Type::Tuple(tuple) => {
+      for elem in &tuple.elems {
+        collect_class_candidates(elem, candidates);
+      }
+    }
+    syn::Type::Path(syn::TypePath { qself: None, path }) => {
+      if path.is_ident("Self") {
+        return;
+      }
+      match path.segments.last().map(|segment| &segment.arguments) {
+        Some(syn::PathArguments::None)
+          if path
+            .segments
+            .iter()
+            .all(|segment| segment.arguments.is_none()) =>
+        {
+          candidates.push(ty)
+        }
+        Some(syn::PathArguments::AngleBracketed(args)) => {
+          for arg in &args.args {
+            if let syn::GenericArgument::Type(ty) = arg {
+              collect_class_candidates(ty, candidates);
+            }
+          }
+        }
+        _ => {}
+      }
     }
+    _ => {}
   }
 }
 
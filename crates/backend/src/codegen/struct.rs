@@ -1,11 +1,10 @@
-use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
 use crate::{
-  codegen::{get_intermediate_ident, js_mod_to_token_stream},
+  codegen::{get_intermediate_ident, is_js_identifier, js_mod_to_token_stream},
   BindgenResult, FnKind, NapiImpl, NapiStruct, NapiStructKind, TryToTokens,
 };
 
@@ -99,11 +98,15 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
       ) -> napi::bindgen_prelude::Result<&'static Self> {
         let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
 
-        napi::bindgen_prelude::check_status!(
-          napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
-          "Failed to recover `{}` type from napi value",
-          #name_str,
-        )?;
+        if <#name as napi::bindgen_prelude::ClassStorage>::EXTERNAL_STATE {
+          wrapped_val = napi::bindgen_prelude::unwrap_external_state::<#name>(env, napi_val)?.cast();
+        } else {
+          napi::bindgen_prelude::check_status!(
+            napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
+            "Failed to recover `{}` type from napi value",
+            #name_str,
+          )?;
+        }
 
         Ok(&*(wrapped_val as *const #name))
       }
@@ -116,11 +119,15 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
       ) -> napi::bindgen_prelude::Result<&'static mut Self> {
         let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
 
-        napi::bindgen_prelude::check_status!(
-          napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
-          "Failed to recover `{}` type from napi value",
-          #name_str,
-        )?;
+        if <#name as napi::bindgen_prelude::ClassStorage>::EXTERNAL_STATE {
+          wrapped_val = napi::bindgen_prelude::unwrap_external_state::<#name>(env, napi_val)?.cast();
+        } else {
+          napi::bindgen_prelude::check_status!(
+            napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
+            "Failed to recover `{}` type from napi value",
+            #name_str,
+          )?;
+        }
 
         Ok(&mut *(wrapped_val as *mut #name))
       }
@@ -206,6 +213,7 @@ impl NapiStruct {
 
   fn gen_default_ctor(&self) -> TokenStream {
     let name = &self.name;
+    let name_str = name.to_string();
     let js_name_str = &self.js_name;
     let fields_len = self.fields.len();
     let mut fields = vec![];
@@ -227,7 +235,9 @@ impl NapiStruct {
       quote! { #name {#(#fields),*} }
     };
 
-    let constructor = if self.implement_iterator {
+    let constructor = if self.implement_async_iterator {
+      quote! { unsafe { cb.construct_async_generator(#js_name_str, #construct) } }
+    } else if self.implement_iterator {
       quote! { unsafe { cb.construct_generator(#js_name_str, #construct) } }
     } else {
       quote! { unsafe { cb.construct(#js_name_str, #construct) } }
@@ -239,7 +249,10 @@ impl NapiStruct {
         cb: napi::bindgen_prelude::sys::napi_callback_info
       ) -> napi::bindgen_prelude::sys::napi_value {
         napi::bindgen_prelude::CallbackInfo::<#fields_len>::new(env, cb, None, false)
-          .and_then(|cb| #constructor)
+          .and_then(|cb| {
+            cb.ensure_new_target(#name_str)?;
+            #constructor
+          })
           .unwrap_or_else(|e| {
             unsafe { napi::bindgen_prelude::JsError::from(e).throw_into(env) };
             std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
@@ -250,7 +263,7 @@ impl NapiStruct {
 
   fn gen_napi_value_map_impl(&self) -> TokenStream {
     match self.kind {
-      NapiStructKind::None => gen_napi_value_map_impl(
+      NapiStructKind::None | NapiStructKind::Opaque => gen_napi_value_map_impl(
         &self.name,
         self.gen_to_napi_value_ctor_impl_for_non_default_constructor_struct(),
       ),
@@ -271,6 +284,14 @@ impl NapiStruct {
     } else {
       quote! { impl napi::bindgen_prelude::ObjectFinalize for #name {} }
     };
+    let external_state = self.external_state;
+    let class_js_name = &self.js_name;
+    let storage_trait = quote! {
+      impl napi::bindgen_prelude::ClassStorage for #name {
+        const EXTERNAL_STATE: bool = #external_state;
+        const JS_NAME: Option<&'static str> = Some(#class_js_name);
+      }
+    };
     let instance_of_impl = self.gen_instance_of_impl(name, &js_name_str);
     quote! {
       impl napi::bindgen_prelude::ToNapiValue for #name {
@@ -292,6 +313,7 @@ impl NapiStruct {
       }
 
       #finalize_trait
+      #storage_trait
       #instance_of_impl
       impl #name {
         pub fn into_reference(val: #name, env: napi::Env) -> napi::Result<napi::bindgen_prelude::Reference<#name>> {
@@ -347,6 +369,10 @@ impl NapiStruct {
             #js_name_raw
           )?;
           napi::__private::___CALL_FROM_FACTORY.with(|inner| inner.store(false, std::sync::atomic::Ordering::Relaxed));
+          if <#name as napi::bindgen_prelude::ClassStorage>::EXTERNAL_STATE {
+            napi::bindgen_prelude::attach_external_state(env, result, wrapped_value as *mut #name, #js_name_raw)?;
+            return Ok(result);
+          }
           let mut object_ref = std::ptr::null_mut();
           let initial_finalize: Box<dyn FnOnce()> = Box::new(|| {});
           let finalize_callbacks_ptr = std::rc::Rc::into_raw(std::rc::Rc::new(std::cell::Cell::new(Box::into_raw(initial_finalize))));
@@ -370,6 +396,11 @@ impl NapiStruct {
   }
 
   fn gen_iterator_property(&self, name: &Ident) -> TokenStream {
+    if self.implement_async_iterator {
+      return quote! {
+        napi::__private::create_async_iterator::<#name>(env, instance_value, wrapped_value);
+      };
+    }
     if !self.implement_iterator {
       return quote! {};
     }
@@ -423,6 +454,14 @@ impl NapiStruct {
     } else {
       quote! { impl napi::bindgen_prelude::ObjectFinalize for #name {} }
     };
+    let external_state = self.external_state;
+    let class_js_name = &self.js_name;
+    let storage_trait = quote! {
+      impl napi::bindgen_prelude::ClassStorage for #name {
+        const EXTERNAL_STATE: bool = #external_state;
+        const JS_NAME: Option<&'static str> = Some(#class_js_name);
+      }
+    };
 
     quote! {
       impl napi::bindgen_prelude::ToNapiValue for #name {
@@ -459,6 +498,7 @@ impl NapiStruct {
       }
       #instance_of_impl
       #finalize_trait
+      #storage_trait
     }
   }
 
@@ -473,20 +513,37 @@ impl NapiStruct {
     for field in self.fields.iter() {
       let field_js_name = &field.js_name;
       let ty = &field.ty;
-      let is_optional_field = if let syn::Type::Path(syn::TypePath {
+      let last_path_ident = if let syn::Type::Path(syn::TypePath {
         path: syn::Path { segments, .. },
         ..
       }) = &ty
       {
-        if let Some(last_path) = segments.last() {
-          last_path.ident == "Option"
-        } else {
-          false
-        }
+        segments.last().map(|last_path| &last_path.ident)
       } else {
-        false
+        None
       };
+      let is_optional_field = last_path_ident.map_or(false, |ident| ident == "Option");
+      let is_lazy_field = last_path_ident.map_or(false, |ident| ident == "LazyField");
       match &field.name {
+        syn::Member::Named(ident) if field.flatten => {
+          let alias_ident = format_ident!("{}_", ident);
+          field_destructions.push(quote! { #ident: #alias_ident });
+          // properties of the flattened struct live on the same object
+          obj_field_setters.push(quote! {
+            let flattened = napi::bindgen_prelude::Object::from_napi_value(
+              env,
+              <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #alias_ident)?,
+            )?;
+            for key in napi::bindgen_prelude::Object::keys(&flattened)? {
+              if let Some(value) = flattened.get::<_, napi::bindgen_prelude::Unknown>(&key)? {
+                obj.set(&key, value)?;
+              }
+            }
+          });
+          obj_field_getters.push(quote! {
+            let #alias_ident: #ty = <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?;
+          });
+        }
         syn::Member::Named(ident) => {
           let alias_ident = format_ident!("{}_", ident);
           field_destructions.push(quote! { #ident: #alias_ident });
@@ -496,6 +553,10 @@ impl NapiStruct {
                 obj.set(#field_js_name, #alias_ident)?;
               }
             });
+          } else if is_lazy_field {
+            obj_field_setters.push(quote! {
+              napi::bindgen_prelude::LazyField::define_on(#alias_ident, &mut obj, #field_js_name)?;
+            });
           } else {
             obj_field_setters.push(quote! { obj.set(#field_js_name, #alias_ident)?; });
           }
@@ -518,6 +579,10 @@ impl NapiStruct {
                 obj.set(#field_js_name, arg #i)?;
               }
             });
+          } else if is_lazy_field {
+            obj_field_setters.push(quote! {
+              napi::bindgen_prelude::LazyField::define_on(arg #i, &mut obj, #field_js_name)?;
+            });
           } else {
             obj_field_setters.push(quote! { obj.set(#field_js_name, arg #1)?; });
           }
@@ -545,8 +610,18 @@ impl NapiStruct {
       }
     };
 
+    let seal = if self.seal {
+      quote! { napi::bindgen_prelude::seal_object(&mut obj)?; }
+    } else {
+      quote! {}
+    };
+    let require_napi8 = self.seal.then(|| {
+      quote! { const _: () = napi::bindgen_prelude::require_napi8_for_seal(); }
+    });
+
     let to_napi_value = if self.object_to_js {
       quote! {
+        #require_napi8
         impl napi::bindgen_prelude::ToNapiValue for #name {
           unsafe fn to_napi_value(env: napi::bindgen_prelude::sys::napi_env, val: #name) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
             let env_wrapper = napi::bindgen_prelude::Env::from(env);
@@ -554,6 +629,7 @@ impl NapiStruct {
 
             let #destructed_fields = val;
             #(#obj_field_setters)*
+            #seal
 
             napi::bindgen_prelude::Object::to_napi_value(env, obj)
           }
@@ -570,6 +646,7 @@ impl NapiStruct {
             env: napi::bindgen_prelude::sys::napi_env,
             napi_val: napi::bindgen_prelude::sys::napi_value
           ) -> napi::bindgen_prelude::Result<Self> {
+            let _nesting_guard = napi::bindgen_prelude::NestingGuard::enter()?;
             let env_wrapper = napi::bindgen_prelude::Env::from(env);
             let mut obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
 
@@ -711,6 +788,8 @@ impl NapiStruct {
 
     if self.kind == NapiStructKind::Constructor {
       props.push(quote! { napi::bindgen_prelude::Property::new("constructor").unwrap().with_ctor(constructor) });
+    } else if self.private_constructor {
+      props.push(quote! { napi::bindgen_prelude::Property::new("constructor").unwrap().with_ctor(napi::bindgen_prelude::private_constructor) });
     }
 
     for field in self.fields.iter() {
@@ -760,13 +839,25 @@ impl NapiStruct {
       props.push(prop);
     }
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let rejected_js_name = (!self.any_js_name && !is_js_identifier(&self.js_name)).then(|| {
+      quote! { napi::bindgen_prelude::reject_class_name(#name_str, #js_mod_ident); }
+    });
+    let lazy = self.lazy.then(|| {
+      quote! { napi::bindgen_prelude::define_class_lazily(#name_str, #js_mod_ident); }
+    });
+    let class_finalizer = match &self.class_finalizer {
+      Some(class_finalizer) => quote! { Some(#class_finalizer) },
+      None => quote! { None },
+    };
     quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
       #[cfg(all(not(test), not(feature = "noop"), not(target_family = "wasm")))]
       #[napi::bindgen_prelude::ctor]
       fn #struct_register_name() {
-        napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+        #rejected_js_name
+        #lazy
+        napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*], #class_finalizer, None);
       }
 
       #[allow(non_snake_case)]
@@ -774,7 +865,9 @@ impl NapiStruct {
       #[cfg(all(not(test), not(feature = "noop"), target_family = "wasm"))]
       #[no_mangle]
       extern "C" fn #struct_register_name() {
-        napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+        #rejected_js_name
+        #lazy
+        napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*], #class_finalizer, None);
       }
     }
   }
@@ -830,7 +923,8 @@ impl NapiImpl {
     let register_name = &self.register_name;
 
     let mut methods = vec![];
-    let mut props = HashMap::new();
+    // in declaration order, a getter and a setter of the same name share the property
+    let mut props: Vec<(&String, TokenStream)> = vec![];
 
     for item in self.items.iter() {
       let js_name = Literal::string(&item.js_name);
@@ -849,18 +943,28 @@ impl NapiImpl {
         attribute |= super::PROPERTY_ATTRIBUTE_CONFIGURABLE;
       }
 
-      let prop = props.entry(&item.js_name).or_insert_with(|| {
-        quote! {
-          napi::bindgen_prelude::Property::new(#js_name).unwrap().with_property_attributes(napi::bindgen_prelude::PropertyAttributes::from_bits(#attribute).unwrap())
+      let index = match props.iter().position(|(name, _)| *name == &item.js_name) {
+        Some(index) => index,
+        None => {
+          props.push((
+            &item.js_name,
+            quote! {
+              napi::bindgen_prelude::Property::new(#js_name).unwrap().with_property_attributes(napi::bindgen_prelude::PropertyAttributes::from_bits(#attribute).unwrap())
+            },
+          ));
+          props.len() - 1
         }
-      });
+      };
+      let prop = &mut props[index].1;
 
       let appendix = match item.kind {
         FnKind::Constructor => quote! { .with_ctor(#intermediate_name) },
         FnKind::Getter => quote! { .with_getter(#intermediate_name) },
         FnKind::Setter => quote! { .with_setter(#intermediate_name) },
         _ => {
-          if item.fn_self.is_some() {
+          if item.to_primitive {
+            quote! { .with_method(#intermediate_name).with_to_primitive() }
+          } else if item.fn_self.is_some() {
             quote! { .with_method(#intermediate_name) }
           } else {
             quote! { .with_method(#intermediate_name).with_property_attributes(napi::bindgen_prelude::PropertyAttributes::Static) }
@@ -871,8 +975,6 @@ impl NapiImpl {
       appendix.to_tokens(prop);
     }
 
-    let mut props: Vec<_> = props.into_iter().collect();
-    props.sort_by_key(|(_, prop)| prop.to_string());
     let props = props.into_iter().map(|(_, prop)| prop);
     let props_wasm = props.clone();
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
@@ -886,13 +988,13 @@ impl NapiImpl {
         #[cfg(all(not(test), not(feature = "noop"), not(target_family = "wasm")))]
         #[napi::bindgen_prelude::ctor]
         fn #register_name() {
-          napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+          napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*], None, None);
         }
 
         #[cfg(all(not(test), not(feature = "noop"), target_family = "wasm"))]
         #[no_mangle]
         extern "C" fn #register_name() {
-          napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props_wasm),*]);
+          napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props_wasm),*], None, None);
         }
       }
     })
@@ -95,6 +95,7 @@ pub(crate) static PRIMITIVE_TYPES: &[(&str, (&str, bool, bool))] = &[
   ("str", ("string", false, false)),
   ("Latin1String", ("string", false, false)),
   ("Utf16String", ("string", false, false)),
+  ("RawUtf16String", ("string", false, false)),
   ("char", ("string", false, false)),
   ("Null", ("null", false, false)),
   ("JsNull", ("null", false, false)),
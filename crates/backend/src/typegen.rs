@@ -21,9 +21,13 @@ pub struct TypeDef {
   pub kind: String,
   pub name: String,
   pub original_name: Option<String>,
+  /// Interfaces extended by a `kind: "interface"` def
+  pub extends: Option<String>,
   pub def: String,
   pub js_mod: Option<String>,
   pub js_doc: String,
+  /// A `#[napi(lazy)]` class, the CLI re-exports it with a getter so it's not defined while the binding is loaded
+  pub lazy: bool,
 }
 
 thread_local! {
@@ -111,6 +115,12 @@ impl ToString for TypeDef {
     } else {
       "".to_owned()
     };
+    let extends = if let Some(extends) = &self.extends {
+      format!(", \"extends\": \"{}\"", extends)
+    } else {
+      "".to_owned()
+    };
+    let lazy = if self.lazy { ", \"lazy\": true" } else { "" };
     // TODO: remove this in v3
     // This is a workaround for lower version of @napi-rs/cli
     // See https://github.com/napi-rs/napi-rs/pull/1531
@@ -120,18 +130,35 @@ impl ToString for TypeDef {
       "".to_string()
     };
     format!(
-      r#"{}{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}}}"#,
+      r#"{}{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}{}{}}}"#,
       prefix,
       self.kind,
       self.name,
       escape_json(&self.js_doc),
       escape_json(&self.def),
       original_name,
+      extends,
       js_mod,
+      lazy,
     )
   }
 }
 
+/// The def of a `#[napi(ts_import = "...")]` statement, the CLI adds every distinct statement
+/// to the top of the type def file.
+pub fn ts_import_type_def(statement: &str) -> TypeDef {
+  TypeDef {
+    kind: "import".to_owned(),
+    name: escape_json(statement),
+    original_name: None,
+    extends: None,
+    def: statement.to_owned(),
+    js_mod: None,
+    lazy: false,
+    js_doc: "".to_owned(),
+  }
+}
+
 pub trait ToTypeDef {
   fn to_type_def(&self) -> Option<TypeDef>;
 }
@@ -142,11 +169,15 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, (&'static str, bool, bool)>> = La
   map.extend(crate::PRIMITIVE_TYPES.iter().cloned());
   map.extend([
     ("JsObject", ("object", false, false)),
+    ("Scaled", ("number", false, false)),
     ("Object", ("object", false, false)),
     ("Array", ("unknown[]", false, false)),
     ("Value", ("any", false, false)),
     ("Map", ("Record<string, any>", false, false)),
     ("HashMap", ("Record<{}, {}>", false, false)),
+    ("EnumMap", ("Record<{}, {}>", false, false)),
+    ("JsMap", ("Map<{}, {}>", false, false)),
+    ("LazyField", ("{}", false, false)),
     ("ArrayBuffer", ("ArrayBuffer", false, false)),
     ("JsArrayBuffer", ("ArrayBuffer", false, false)),
     ("Int8Array", ("Int8Array", false, false)),
@@ -161,10 +192,14 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, (&'static str, bool, bool)>> = La
     ("BigInt64Array", ("BigInt64Array", false, false)),
     ("BigUint64Array", ("BigUint64Array", false, false)),
     ("DataView", ("DataView", false, false)),
+    ("JsDataView", ("DataView", false, false)),
     ("DateTime", ("Date", false, false)),
     ("NaiveDateTime", ("Date", false ,false)),
     ("Date", ("Date", false, false)),
     ("JsDate", ("Date", false, false)),
+    ("SystemTime", ("Date", false, false)),
+    ("Duration", ("number", false, false)),
+    ("PreciseDuration", ("{ secs: number, nanos: number }", false, false)),
     ("JsBuffer", ("Buffer", false, false)),
     ("Buffer", ("Buffer", false, false)),
     ("Vec", ("Array<{}>", false, false)),
@@ -176,6 +211,7 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, (&'static str, bool, bool)>> = La
     ("ClassInstance", ("{}", false, false)),
     ("Function", ("({}) => {}", true, false)),
     ("FunctionRef", ("({}) => {}", true, false)),
+    ("NodeCallback", ("(err: Error | null, result: {}) => void", true, false)),
     ("Either", ("{} | {}", false, true)),
     ("Either3", ("{} | {} | {}", false, true)),
     ("Either4", ("{} | {} | {} | {}", false, true)),
@@ -206,12 +242,14 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, (&'static str, bool, bool)>> = La
     ("AbortSignal", ("AbortSignal", false, false)),
     ("JsGlobal", ("typeof global", false, false)),
     ("External", ("ExternalObject<{}>", false, false)),
+    ("RcHandle", ("ExternalObject<{}>", false, false)),
     ("unknown", ("unknown", false, false)),
     ("Unknown", ("unknown", false, false)),
     ("JsUnknown", ("unknown", false, false)),
     ("This", ("this", false, false)),
     ("Rc", ("{}", false, false)),
     ("Arc", ("{}", false, false)),
+    ("Cow", ("{}", false, false)),
     ("Mutex", ("{}", false, false)),
   ]);
 
@@ -268,6 +306,27 @@ fn is_ts_function_type_notation(ty: &Type) -> bool {
   }
 }
 
+fn typed_array_of_slice(elem: &Type) -> Option<&'static str> {
+  let path = match elem {
+    Type::Path(syn::TypePath { qself: None, path }) => path,
+    _ => return None,
+  };
+  let typed_array = match path.get_ident()?.to_string().as_str() {
+    "i8" => "Int8Array",
+    "u8" => "Uint8Array",
+    "i16" => "Int16Array",
+    "u16" => "Uint16Array",
+    "i32" => "Int32Array",
+    "u32" => "Uint32Array",
+    "f32" => "Float32Array",
+    "f64" => "Float64Array",
+    "i64" => "BigInt64Array",
+    "u64" => "BigUint64Array",
+    _ => return None,
+  };
+  Some(typed_array)
+}
+
 // return (type, is_optional)
 pub fn ty_to_ts_type(
   ty: &Type,
@@ -445,6 +504,15 @@ pub fn ty_to_ts_type(
         ty_to_ts_type(&p.elem, is_return_ty, is_struct_field, false);
       (element_type, is_optional)
     }
+    // a slice argument borrows the backing store of a typed array, a returned slice is copied into an array
+    Type::Slice(s) => match (is_return_ty, typed_array_of_slice(&s.elem)) {
+      (false, Some(typed_array)) => (typed_array.to_owned(), false),
+      _ => {
+        let (element_type, is_optional) =
+          ty_to_ts_type(&s.elem, is_return_ty, is_struct_field, false);
+        (format!("{}[]", element_type), is_optional)
+      }
+    },
     _ => ("any".to_owned(), false),
   }
 }
@@ -14,12 +14,18 @@ impl ToTypeDef for NapiConst {
       kind: "const".to_owned(),
       name: self.js_name.to_owned(),
       original_name: Some(self.name.to_string()),
+      extends: None,
       def: format!(
         "export const {}: {}",
         &self.js_name,
-        ty_to_ts_type(&self.type_name, false, false, false).0
+        self.ts_type.clone().unwrap_or_else(|| if self.json {
+          "any".to_owned()
+        } else {
+          ty_to_ts_type(&self.type_name, false, false, false).0
+        })
       ),
       js_mod: self.js_mod.to_owned(),
+      lazy: false,
       js_doc: js_doc_from_comments(&self.comments),
     })
   }
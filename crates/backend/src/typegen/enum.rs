@@ -1,5 +1,5 @@
 use super::{add_alias, ToTypeDef, TypeDef};
-use crate::{js_doc_from_comments, NapiEnum, NapiEnumValue};
+use crate::{js_doc_from_comments, ty_to_ts_type, NapiEnum, NapiEnumValue};
 
 impl ToTypeDef for NapiEnum {
   fn to_type_def(&self) -> Option<TypeDef> {
@@ -9,13 +9,54 @@ impl ToTypeDef for NapiEnum {
 
     add_alias(self.name.to_string(), self.js_name.to_string());
 
+    if let Some(discriminant) = &self.discriminant {
+      return Some(TypeDef {
+        kind: "type".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        extends: None,
+        def: format!(
+          "export type {} = {}",
+          self.js_name,
+          self.gen_ts_discriminated_union(discriminant)
+        ),
+        js_doc: js_doc_from_comments(&self.comments),
+        js_mod: self.js_mod.to_owned(),
+        lazy: false,
+      });
+    }
+
+    if self.ts_union {
+      return Some(TypeDef {
+        kind: "type".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        extends: None,
+        def: format!(
+          "export type {} = {}",
+          self.js_name,
+          self.gen_ts_string_literals()
+        ),
+        js_doc: js_doc_from_comments(&self.comments),
+        js_mod: self.js_mod.to_owned(),
+        lazy: false,
+      });
+    }
+
     Some(TypeDef {
-      kind: "enum".to_owned(),
+      kind: if self.const_enum {
+        "const_enum"
+      } else {
+        "enum"
+      }
+      .to_owned(),
       name: self.js_name.to_owned(),
       original_name: Some(self.name.to_string()),
+      extends: None,
       def: self.gen_ts_variants(),
       js_doc: js_doc_from_comments(&self.comments),
       js_mod: self.js_mod.to_owned(),
+      lazy: false,
     })
   }
 }
@@ -35,4 +76,39 @@ impl NapiEnum {
       .collect::<Vec<_>>()
       .join(",\n ")
   }
+
+  fn gen_ts_discriminated_union(&self, discriminant: &str) -> String {
+    self
+      .variants
+      .iter()
+      .map(|v| {
+        let val = match &v.val {
+          NapiEnumValue::Number(num) => format!("{}", num),
+          NapiEnumValue::String(string) => format!("'{}'", string),
+        };
+        let fields = std::iter::once(format!("{}: {}", discriminant, val))
+          .chain(v.fields.iter().map(|f| {
+            let (ts_type, is_optional) = ty_to_ts_type(&f.ty, false, true, false);
+            let sep = if is_optional { "?" } else { "" };
+            format!("{}{}: {}", f.js_name, sep, ts_type)
+          }))
+          .collect::<Vec<_>>()
+          .join("; ");
+        format!("{{ {} }}", fields)
+      })
+      .collect::<Vec<_>>()
+      .join(" | ")
+  }
+
+  fn gen_ts_string_literals(&self) -> String {
+    self
+      .variants
+      .iter()
+      .filter_map(|v| match &v.val {
+        NapiEnumValue::String(string) => Some(format!("'{}'", string)),
+        NapiEnumValue::Number(_) => None,
+      })
+      .collect::<Vec<_>>()
+      .join(" | ")
+  }
 }
@@ -31,7 +31,7 @@ impl Display for FnArgList {
         && self
           .last_required
           .map_or(true, |last_required| i > last_required);
-      if is_optional {
+      if is_optional && !arg.arg.starts_with("...") {
         write!(f, "{}?: {}", arg.arg, arg.ts_type)?;
       } else {
         write!(f, "{}: {}", arg.arg, arg.ts_type)?;
@@ -67,7 +67,11 @@ impl FromIterator<FnArg> for FnArgList {
 
 impl ToTypeDef for NapiFn {
   fn to_type_def(&self) -> Option<TypeDef> {
-    if self.skip_typescript {
+    if self.skip_typescript || self.module_init {
+      return None;
+    }
+    // can't be declared in TypeScript, only accessed with brackets
+    if !crate::is_js_identifier(&self.js_name) {
       return None;
     }
 
@@ -95,8 +99,10 @@ impl ToTypeDef for NapiFn {
       kind: "fn".to_owned(),
       name: self.js_name.clone(),
       original_name: None,
+      extends: None,
       def,
       js_mod: self.js_mod.to_owned(),
+      lazy: false,
       js_doc: js_doc_from_comments(&self.comments),
     })
   }
@@ -178,9 +184,22 @@ impl NapiFn {
               i.mutability = None;
             }
 
+            let name = path.pat.to_token_stream().to_string().to_case(Case::Camel);
+            if let Some(elem) = arg.rest_elem() {
+              let ts_type = arg.use_overridden_type_or(|| {
+                format!("{}[]", ty_to_ts_type(elem, false, false, false).0)
+              });
+              // a rest parameter doesn't make the preceding ones required
+              return Some(FnArg {
+                arg: format!("...{}", name),
+                ts_type,
+                is_optional: true,
+              });
+            }
+
             let (ts_type, is_optional) = ty_to_ts_type(&path.ty, false, false, false);
             let ts_type = arg.use_overridden_type_or(|| ts_type);
-            let arg = path.pat.to_token_stream().to_string().to_case(Case::Camel);
+            let arg = name;
 
             Some(FnArg {
               arg,
@@ -215,6 +234,8 @@ impl NapiFn {
         crate::FnKind::Getter => "get",
         crate::FnKind::Setter => "set",
       }
+    } else if self.main {
+      "export default function"
     } else {
       "export function"
     }
@@ -16,6 +16,10 @@ impl ToTypeDef for NapiStruct {
         .insert(self.name.to_string(), self.js_name.clone());
     });
     add_alias(self.name.to_string(), self.js_name.to_string());
+    // can't be declared in TypeScript, only accessed with brackets
+    if !crate::is_js_identifier(&self.js_name) {
+      return None;
+    }
 
     Some(TypeDef {
       kind: String::from(if self.kind == NapiStructKind::Object {
@@ -25,8 +29,10 @@ impl ToTypeDef for NapiStruct {
       }),
       name: self.js_name.to_owned(),
       original_name: Some(self.name.to_string()),
+      extends: self.gen_ts_extends(),
       def: self.gen_ts_class(),
       js_mod: self.js_mod.to_owned(),
+      lazy: self.lazy,
       js_doc: js_doc_from_comments(&self.comments),
     })
   }
@@ -57,7 +63,7 @@ impl ToTypeDef for NapiImpl {
         "void".to_owned()
       };
       let return_type = if let Some(ref ty) = self.iterator_return_type {
-        ty_to_ts_type(ty, false, false, false).0
+        ty_to_ts_type(ty, false, self.iterator_is_async, false).0
       } else {
         "void".to_owned()
       };
@@ -65,13 +71,24 @@ impl ToTypeDef for NapiImpl {
         kind: "impl".to_owned(),
         name: self.js_name.to_owned(),
         original_name: None,
-        def: format!(
-          "[Symbol.iterator](): Iterator<{}, {}, {}>",
-          ty_to_ts_type(output_type, false, true, false).0,
-          return_type,
-          next_type,
-        ),
+        extends: None,
+        def: if self.iterator_is_async {
+          format!(
+            "[Symbol.asyncIterator](): AsyncGenerator<{}, {}, {}>",
+            ty_to_ts_type(output_type, false, true, false).0,
+            return_type,
+            next_type,
+          )
+        } else {
+          format!(
+            "[Symbol.iterator](): Iterator<{}, {}, {}>",
+            ty_to_ts_type(output_type, false, true, false).0,
+            return_type,
+            next_type,
+          )
+        },
         js_mod: self.js_mod.to_owned(),
+        lazy: false,
         js_doc: "".to_string(),
       })
     } else {
@@ -79,6 +96,7 @@ impl ToTypeDef for NapiImpl {
         kind: "impl".to_owned(),
         name: self.js_name.to_owned(),
         original_name: None,
+        extends: None,
         def: self
           .items
           .iter()
@@ -97,6 +115,7 @@ impl ToTypeDef for NapiImpl {
           .collect::<Vec<_>>()
           .join("\\n"),
         js_mod: self.js_mod.to_owned(),
+        lazy: false,
         js_doc: "".to_string(),
       })
     }
@@ -104,12 +123,27 @@ impl ToTypeDef for NapiImpl {
 }
 
 impl NapiStruct {
+  /// Flattened fields of `#[napi(object)]` become interfaces the generated interface extends
+  fn gen_ts_extends(&self) -> Option<String> {
+    let extends = self
+      .fields
+      .iter()
+      .filter(|f| f.flatten)
+      .map(|f| ty_to_ts_type(&f.ty, false, false, false).0)
+      .collect::<Vec<_>>();
+    if extends.is_empty() {
+      None
+    } else {
+      Some(extends.join(", "))
+    }
+  }
+
   fn gen_ts_class(&self) -> String {
     let mut ctor_args = vec![];
     let def = self
       .fields
       .iter()
-      .filter(|f| f.getter)
+      .filter(|f| f.getter && !f.flatten)
       .filter_map(|f| {
         if f.skip_typescript {
           return None;
@@ -142,6 +176,14 @@ impl NapiStruct {
 
     if self.kind == NapiStructKind::Constructor {
       format!("{}\\nconstructor({})", def, ctor_args.join(", "))
+    } else if self.kind == NapiStructKind::Opaque {
+      "private constructor()".to_owned()
+    } else if self.private_constructor {
+      if def.is_empty() {
+        "private constructor()".to_owned()
+      } else {
+        format!("{}\\nprivate constructor()", def)
+      }
     } else {
       def
     }
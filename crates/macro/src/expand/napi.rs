@@ -7,7 +7,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use crate::parser::{attrs::BindgenAttrs, ParseNapi};
 #[cfg(feature = "type-def")]
 use napi_derive_backend::ToTypeDef;
-use napi_derive_backend::{BindgenResult, Napi, TryToTokens};
+use napi_derive_backend::{BindgenResult, Diagnostic, Napi, TryToTokens};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{Attribute, Item};
@@ -80,11 +80,13 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStrea
           },
         ) {
           let napi = item.parse_napi(&mut tokens, &item_opts)?;
+          #[cfg_attr(not(feature = "type-def"), allow(unused_variables))]
+          let ts_import = parse_ts_import(&item_opts)?;
           item_opts.check_used()?;
           napi.try_to_tokens(&mut tokens)?;
 
           #[cfg(feature = "type-def")]
-          output_type_def(&napi);
+          output_type_def(&napi, ts_import.as_deref());
           output_wasi_register_def(&napi);
         } else {
           item.to_tokens(&mut tokens);
@@ -105,16 +107,35 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStrea
     Ok(mod_tokens)
   } else {
     let napi = item.parse_napi(&mut tokens, &opts)?;
+    #[cfg_attr(not(feature = "type-def"), allow(unused_variables))]
+    let ts_import = parse_ts_import(&opts)?;
     opts.check_used()?;
     napi.try_to_tokens(&mut tokens)?;
 
     #[cfg(feature = "type-def")]
-    output_type_def(&napi);
+    output_type_def(&napi, ts_import.as_deref());
     output_wasi_register_def(&napi);
     Ok(tokens)
   }
 }
 
+/// The statement of `#[napi(ts_import = "import type { Readable } from 'node:stream'")]`,
+/// which is added to the type def file once for every item using it.
+fn parse_ts_import(opts: &BindgenAttrs) -> BindgenResult<Option<String>> {
+  match opts.ts_import() {
+    Some((statement, span)) => {
+      if !statement.trim_start().starts_with("import ") {
+        return Err(Diagnostic::span_error(
+          span,
+          "#[napi(ts_import)] must be an `import` statement, like `import type { Readable } from 'node:stream'`",
+        ));
+      }
+      Ok(Some(statement.trim().to_owned()))
+    }
+    None => Ok(None),
+  }
+}
+
 fn output_wasi_register_def(napi: &Napi) {
   if let Ok(wasi_register_file) = env::var("WASI_REGISTER_TMP_PATH") {
     fs::OpenOptions::new()
@@ -134,22 +155,31 @@ fn output_wasi_register_def(napi: &Napi) {
 }
 
 #[cfg(feature = "type-def")]
-fn output_type_def(napi: &Napi) {
+fn output_type_def(napi: &Napi, ts_import: Option<&str>) {
   if let Ok(type_def_file) = env::var("TYPE_DEF_TMP_PATH") {
-    if let Some(type_def) = napi.to_type_def() {
-      fs::OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(type_def_file)
-        .and_then(|file| {
-          let mut writer = BufWriter::<fs::File>::new(file);
-          writer.write_all(type_def.to_string().as_bytes())?;
-          writer.write_all("\n".as_bytes())
-        })
-        .unwrap_or_else(|e| {
-          println!("Failed to write type def file: {:?}", e);
-        });
+    let type_defs = ts_import
+      .map(napi_derive_backend::ts_import_type_def)
+      .into_iter()
+      .chain(napi.to_type_def())
+      .collect::<Vec<_>>();
+    if type_defs.is_empty() {
+      return;
     }
+    fs::OpenOptions::new()
+      .append(true)
+      .create(true)
+      .open(type_def_file)
+      .and_then(|file| {
+        let mut writer = BufWriter::<fs::File>::new(file);
+        for type_def in type_defs {
+          writer.write_all(type_def.to_string().as_bytes())?;
+          writer.write_all("\n".as_bytes())?;
+        }
+        Ok(())
+      })
+      .unwrap_or_else(|e| {
+        println!("Failed to write type def file: {:?}", e);
+      });
   }
 }
 
@@ -49,7 +49,10 @@ macro_rules! attrgen {
   ($mac:ident) => {
     $mac! {
       (catch_unwind, CatchUnwind(Span)),
+      (module_init, ModuleInit(Span)),
+      (main, Main(Span)),
       (js_name, JsName(Span, String, Span)),
+      (any_js_name, AnyJsName(Span)),
       (constructor, Constructor(Span)),
       (factory, Factory(Span)),
       (getter, Getter(Span, Option<Ident>)),
@@ -60,18 +63,36 @@ macro_rules! attrgen {
       (configurable, Configurable(Span, Option<bool>)),
       (skip, Skip(Span)),
       (strict, Strict(Span)),
+      (strict_numbers, StrictNumbers(Span)),
       (return_if_invalid, ReturnIfInvalid(Span)),
       (object, Object(Span)),
+      (opaque, Opaque(Span)),
+      (private_constructor, PrivateConstructor(Span)),
+      (lazy, Lazy(Span)),
       (object_from_js, ObjectFromJs(Span, Option<bool>)),
       (object_to_js, ObjectToJs(Span, Option<bool>)),
       (custom_finalize, CustomFinalize(Span)),
+      (external_state, ExternalState(Span)),
+      (class_finalizer, ClassFinalizer(Span, String, Span)),
       (namespace, Namespace(Span, String, Span)),
       (iterator, Iterator(Span)),
+      (async_iterator, AsyncIterator(Span)),
       (ts_args_type, TsArgsType(Span, String, Span)),
       (ts_return_type, TsReturnType(Span, String, Span)),
       (ts_type, TsType(Span, String, Span)),
+      (ts_import, TsImport(Span, String, Span)),
       (ts_generic_types, TsGenericTypes(Span, String, Span)),
       (string_enum, StringEnum(Span)),
+      (const_enum, ConstEnum(Span)),
+      (ts_union, TsUnion(Span)),
+      (discriminant, Discriminant(Span, String, Span)),
+      (rename_all, RenameAll(Span, String, Span)),
+      (rename, Rename(Span, String, Span)),
+      (enabled_if_env, EnabledIfEnv(Span, String, Span)),
+      (to_primitive, ToPrimitive(Span)),
+      (frozen, Frozen(Span)),
+      (seal, Seal(Span)),
+      (json, Json(Span)),
 
       // impl later
       // (inspectable, Inspectable(Span)),
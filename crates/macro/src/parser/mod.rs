@@ -11,8 +11,8 @@ use attrs::BindgenAttrs;
 use convert_case::{Case, Casing};
 use napi_derive_backend::{
   BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, Napi, NapiConst, NapiEnum, NapiEnumValue,
-  NapiEnumVariant, NapiFn, NapiFnArg, NapiFnArgKind, NapiImpl, NapiItem, NapiStruct,
-  NapiStructField, NapiStructKind,
+  NapiEnumVariant, NapiEnumVariantField, NapiFn, NapiFnArg, NapiFnArgKind, NapiImpl, NapiItem,
+  NapiStruct, NapiStructField, NapiStructKind,
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
@@ -25,6 +25,7 @@ use crate::parser::attrs::{check_recorded_struct_for_impl, record_struct};
 
 thread_local! {
   static GENERATOR_STRUCT: RefCell<HashMap<String, bool>> = Default::default();
+  static ASYNC_GENERATOR_STRUCT: RefCell<HashMap<String, bool>> = Default::default();
 }
 
 static REGISTER_INDEX: AtomicUsize = AtomicUsize::new(0);
@@ -487,6 +488,28 @@ fn napi_fn_from_decl(
     ..
   } = sig.clone();
 
+  if opts.module_init().is_some() {
+    if parent.is_some() {
+      bail_span!(
+        sig.ident,
+        "#[napi(module_init)] can only be applied to a function, not a method."
+      );
+    }
+    if asyncness.is_some() || !sig.inputs.is_empty() {
+      bail_span!(
+        sig.ident,
+        "#[napi(module_init)] function must be a non-async function without arguments."
+      );
+    }
+  }
+
+  if opts.enabled_if_env().is_some() && parent.is_some() {
+    bail_span!(
+      sig.ident,
+      "#[napi(enabled_if_env)] can only be applied to a function, not a method."
+    );
+  }
+
   let mut fn_self = None;
   let callback_traits = extract_fn_closure_generics(&generics)?;
 
@@ -530,10 +553,7 @@ fn napi_fn_from_decl(
         if parent.is_some() {
           assert!(fn_self.is_none());
           if r.reference.is_none() {
-            errors.push(err_span!(
-              r,
-              "The native methods can't move values from napi. Try `&self` or `&mut self` instead."
-            ));
+            fn_self = Some(FnSelf::Value);
           } else if r.mutability.is_some() {
             fn_self = Some(FnSelf::MutRef);
           } else {
@@ -559,6 +579,25 @@ fn napi_fn_from_decl(
     }
   };
 
+  if opts.to_primitive().is_some()
+    && (!matches!(fn_self, Some(FnSelf::Ref) | Some(FnSelf::MutRef))
+      || fn_kind(opts) != FnKind::Normal)
+  {
+    errors.push(err_span!(
+      sig.ident,
+      "#[napi(to_primitive)] can only be applied to a method taking `&self` or `&mut self`."
+    ));
+  }
+
+  if opts.main().is_some()
+    && (parent.is_some() || opts.namespace().is_some() || opts.module_init().is_some())
+  {
+    errors.push(err_span!(
+      sig.ident,
+      "#[napi(main)] can only be applied to a free function, not a method, a `module_init` function or a function in a `namespace`."
+    ));
+  }
+
   Diagnostic::from_vec(errors).map(|_| {
     let js_name = if let Some(prop_name) = opts.getter() {
       opts.js_name().map_or_else(
@@ -598,18 +637,18 @@ fn napi_fn_from_decl(
     };
 
     let namespace = opts.namespace().map(|(m, _)| m.to_owned());
-    let parent_is_generator = if let Some(p) = parent {
-      GENERATOR_STRUCT.with(|inner| {
-        let inner = inner.borrow();
-        let key = namespace
-          .as_ref()
-          .map(|n| format!("{}::{}", n, p))
-          .unwrap_or_else(|| p.to_string());
-        *inner.get(&key).unwrap_or(&false)
-      })
-    } else {
-      false
-    };
+    let parent_key = parent.map(|p| {
+      namespace
+        .as_ref()
+        .map(|n| format!("{}::{}", n, p))
+        .unwrap_or_else(|| p.to_string())
+    });
+    let parent_is_generator = parent_key.as_ref().map_or(false, |key| {
+      GENERATOR_STRUCT.with(|inner| *inner.borrow().get(key).unwrap_or(&false))
+    });
+    let parent_is_async_generator = parent_key.as_ref().map_or(false, |key| {
+      ASYNC_GENERATOR_STRUCT.with(|inner| *inner.borrow().get(key).unwrap_or(&false))
+    });
 
     NapiFn {
       name: ident.clone(),
@@ -632,11 +671,18 @@ fn napi_fn_from_decl(
       ts_return_type: opts.ts_return_type().map(|(m, _)| m.to_owned()),
       skip_typescript: opts.skip_typescript().is_some(),
       parent_is_generator,
+      parent_is_async_generator,
       writable: opts.writable(),
       enumerable: opts.enumerable(),
       configurable: opts.configurable(),
       catch_unwind: opts.catch_unwind().is_some(),
+      strict_numbers: opts.strict_numbers().is_some(),
       unsafe_: sig.unsafety.is_some(),
+      module_init: opts.module_init().is_some(),
+      enabled_if_env: opts.enabled_if_env().map(|(m, _)| m.to_owned()),
+      to_primitive: opts.to_primitive().is_some(),
+      any_js_name: opts.any_js_name().is_some(),
+      main: opts.main().is_some(),
       register_name: get_register_ident(ident.to_string().as_str()),
     }
   })
@@ -702,9 +748,27 @@ impl ParseNapi for syn::ItemStruct {
         "#[napi(catch_unwind)] can only be applied to a function or method."
       );
     }
+    if opts.strict_numbers().is_some() {
+      bail_span!(
+        self,
+        "#[napi(strict_numbers)] can only be applied to a function or method."
+      );
+    }
     if opts.object().is_some() && opts.custom_finalize().is_some() {
       bail_span!(self, "Custom finalize is not supported for #[napi(object)]");
     }
+    if opts.object().is_some() && opts.external_state().is_some() {
+      bail_span!(
+        self,
+        "#[napi(external_state)] is only supported for a class, not for #[napi(object)]"
+      );
+    }
+    if opts.object().is_some() && opts.class_finalizer().is_some() {
+      bail_span!(
+        self,
+        "#[napi(class_finalizer)] is only supported for a class, not for #[napi(object)]"
+      );
+    }
     let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
 
@@ -737,6 +801,12 @@ impl ParseNapi for syn::ItemImpl {
         "#[napi(catch_unwind)] can only be applied to a function or method."
       );
     }
+    if opts.strict_numbers().is_some() {
+      bail_span!(
+        self,
+        "#[napi(strict_numbers)] can only be applied to a function or method."
+      );
+    }
     // #[napi] macro will be remove from impl items after converted to ast
     let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
@@ -769,6 +839,12 @@ impl ParseNapi for syn::ItemEnum {
         "#[napi(catch_unwind)] can only be applied to a function or method."
       );
     }
+    if opts.strict_numbers().is_some() {
+      bail_span!(
+        self,
+        "#[napi(strict_numbers)] can only be applied to a function or method."
+      );
+    }
     let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
 
@@ -779,12 +855,17 @@ impl ParseNapi for syn::ItemConst {
   fn parse_napi(&mut self, tokens: &mut TokenStream, opts: &BindgenAttrs) -> BindgenResult<Napi> {
     if opts.ts_args_type().is_some()
       || opts.ts_return_type().is_some()
-      || opts.ts_type().is_some()
       || opts.custom_finalize().is_some()
     {
       bail_span!(
         self,
-        "#[napi] can't be applied to a const with #[napi(ts_args_type)], #[napi(ts_return_type)] or #[napi(ts_type)] or #[napi(custom_finalize)]"
+        "#[napi] can't be applied to a const with #[napi(ts_args_type)], #[napi(ts_return_type)] or #[napi(custom_finalize)]"
+      );
+    }
+    if opts.ts_type().is_some() && opts.json().is_none() {
+      bail_span!(
+        self,
+        "#[napi(ts_type)] can only be applied to a const with #[napi(json)]"
       );
     }
     if opts.return_if_invalid().is_some() {
@@ -799,6 +880,12 @@ impl ParseNapi for syn::ItemConst {
         "#[napi(catch_unwind)] can only be applied to a function or method."
       );
     }
+    if opts.strict_numbers().is_some() {
+      bail_span!(
+        self,
+        "#[napi(strict_numbers)] can only be applied to a function or method."
+      );
+    }
     let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
     napi
@@ -843,6 +930,39 @@ impl ConvertToAST for syn::ItemFn {
   }
 }
 
+#[cfg(feature = "serde-compat")]
+fn is_option_type(ty: &Type) -> bool {
+  matches!(ty, Type::Path(syn::TypePath { path, .. }) if path.segments.last().map_or(false, |s| s.ident == "Option"))
+}
+
+#[cfg(feature = "serde-compat")]
+/// Read `rename` and `flatten` from the `#[serde(...)]` attributes of a field.
+fn parse_serde_field_attrs(attrs: &[Attribute]) -> BindgenResult<(Option<String>, bool)> {
+  let mut rename = None;
+  let mut flatten = false;
+  for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("rename") {
+        if !meta.input.peek(Token![=]) {
+          return Err(meta.error(
+            "#[napi(object)] fields can only have one name, use #[serde(rename = \"...\")]",
+          ));
+        }
+        rename = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+      } else if meta.path.is_ident("flatten") {
+        flatten = true;
+      } else if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+      } else if meta.input.peek(syn::token::Paren) {
+        let _content;
+        syn::parenthesized!(_content in meta.input);
+      }
+      Ok(())
+    })?;
+  }
+  Ok((rename, flatten))
+}
+
 impl ConvertToAST for syn::ItemStruct {
   fn convert_to_ast(&mut self, opts: &BindgenAttrs) -> BindgenResult<Napi> {
     let mut errors = vec![];
@@ -859,11 +979,63 @@ impl ConvertToAST for syn::ItemStruct {
       NapiStructKind::Constructor
     } else if opts.object().is_some() {
       NapiStructKind::Object
+    } else if opts.opaque().is_some() {
+      NapiStructKind::Opaque
     } else {
       NapiStructKind::None
     };
 
+    if struct_kind == NapiStructKind::Opaque
+      && (opts.constructor().is_some() || opts.object().is_some())
+    {
+      errors.push(err_span!(
+        self,
+        "#[napi(opaque)] can not be combined with `constructor` or `object`"
+      ));
+    }
+
+    if opts.lazy().is_some() && struct_kind == NapiStructKind::Object {
+      errors.push(err_span!(
+        self,
+        "#[napi(lazy)] can not be combined with `object`, only a class can be defined lazily"
+      ));
+    }
+
+    if opts.private_constructor().is_some() && struct_kind != NapiStructKind::None {
+      errors.push(err_span!(
+        self,
+        "#[napi(private_constructor)] can not be combined with `constructor`, `object` or `opaque`, an opaque class has a private constructor already"
+      ));
+    }
+
+    if let Some(span) = opts.seal() {
+      if struct_kind != NapiStructKind::Object {
+        errors.push(Diagnostic::span_error(
+          *span,
+          "#[napi(seal)] is only supported on #[napi(object)] structs",
+        ));
+      }
+      for field in self.fields.iter() {
+        if let syn::Type::Path(syn::TypePath { path, .. }) = &field.ty {
+          if path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "LazyField")
+          {
+            errors.push(err_span!(
+              field,
+              "`LazyField` is not supported in a #[napi(object, seal)] struct, a sealed object can't replace its getters"
+            ));
+          }
+        }
+      }
+    }
+
     for (i, field) in self.fields.iter_mut().enumerate() {
+      // opaque handles never expose their fields to JavaScript
+      if struct_kind == NapiStructKind::Opaque {
+        continue;
+      }
       match field.vis {
         syn::Visibility::Public(..) => {}
         _ => {
@@ -879,10 +1051,33 @@ impl ConvertToAST for syn::ItemStruct {
 
       let field_opts = BindgenAttrs::find(&mut field.attrs)?;
 
+      #[cfg(feature = "serde-compat")]
+      let (serde_rename, flatten) = if struct_kind == NapiStructKind::Object {
+        let (serde_rename, flatten) = parse_serde_field_attrs(&field.attrs)?;
+        if flatten && field_opts.js_name().is_some() {
+          errors.push(err_span!(
+            field,
+            "#[napi(js_name)] can not be used on a #[serde(flatten)] field"
+          ));
+        }
+        if flatten && is_option_type(&field.ty) {
+          errors.push(err_span!(
+            field,
+            "#[serde(flatten)] is not supported on optional fields of #[napi(object)]"
+          ));
+        }
+        (serde_rename, flatten)
+      } else {
+        (None, false)
+      };
+      #[cfg(not(feature = "serde-compat"))]
+      let (serde_rename, flatten) = (None::<String>, false);
+
       let (js_name, name) = match &field.ident {
+        // `#[napi(js_name)]` takes precedence over `#[serde(rename)]`
         Some(ident) => (
           field_opts.js_name().map_or_else(
-            || ident.unraw().to_string().to_case(Case::Camel),
+            || serde_rename.unwrap_or_else(|| ident.unraw().to_string().to_case(Case::Camel)),
             |(js_name, _)| js_name.to_owned(),
           ),
           syn::Member::Named(ident.clone()),
@@ -913,19 +1108,37 @@ impl ConvertToAST for syn::ItemStruct {
         comments: extract_doc_comments(&field.attrs),
         skip_typescript,
         ts_type,
+        flatten,
       })
     }
 
+    let class_finalizer = match opts.class_finalizer() {
+      Some((path, span)) => match syn::parse_str::<syn::Path>(path) {
+        Ok(path) => Some(path),
+        Err(_) => {
+          errors.push(Diagnostic::span_error(
+            span,
+            "#[napi(class_finalizer)] expects the path of a `fn()`, e.g. `class_finalizer = \"release_pool\"`",
+          ));
+          None
+        }
+      },
+      None => None,
+    };
+
     record_struct(&struct_name, js_name.clone(), opts);
     let namespace = opts.namespace().map(|(m, _)| m.to_owned());
     let implement_iterator = opts.iterator().is_some();
+    let implement_async_iterator = opts.async_iterator().is_some();
+    let key = namespace
+      .as_ref()
+      .map(|n| format!("{}::{}", n, struct_name))
+      .unwrap_or_else(|| struct_name.to_string());
     GENERATOR_STRUCT.with(|inner| {
-      let mut inner = inner.borrow_mut();
-      let key = namespace
-        .as_ref()
-        .map(|n| format!("{}::{}", n, struct_name))
-        .unwrap_or_else(|| struct_name.to_string());
-      inner.insert(key, implement_iterator);
+      inner.borrow_mut().insert(key.clone(), implement_iterator);
+    });
+    ASYNC_GENERATOR_STRUCT.with(|inner| {
+      inner.borrow_mut().insert(key, implement_async_iterator);
     });
 
     Diagnostic::from_vec(errors).map(|()| Napi {
@@ -938,10 +1151,17 @@ impl ConvertToAST for syn::ItemStruct {
         kind: struct_kind,
         object_from_js: opts.object_from_js(),
         object_to_js: opts.object_to_js(),
+        seal: opts.seal().is_some(),
+        any_js_name: opts.any_js_name().is_some(),
         js_mod: namespace,
         comments: extract_doc_comments(&self.attrs),
         implement_iterator,
+        implement_async_iterator,
         use_custom_finalize: opts.custom_finalize().is_some(),
+        external_state: opts.external_state().is_some(),
+        class_finalizer,
+        private_constructor: opts.private_constructor().is_some(),
+        lazy: opts.lazy().is_some(),
         register_name: get_register_ident(format!("{struct_name}_struct").as_str()),
       }),
     })
@@ -968,6 +1188,7 @@ impl ConvertToAST for syn::ItemImpl {
     let mut iterator_yield_type = None;
     let mut iterator_next_type = None;
     let mut iterator_return_type = None;
+    let mut iterator_is_async = false;
     for item in self.items.iter_mut() {
       if let Some(method) = match item {
         syn::ImplItem::Fn(m) => Some(m),
@@ -976,7 +1197,8 @@ impl ConvertToAST for syn::ItemImpl {
             if let Some(PathSegment { ident, .. }) = t.segments.last() {
               if ident == "Task" && m.ident == "JsValue" {
                 task_output_type = Some(m.ty.clone());
-              } else if ident == "Generator" {
+              } else if ident == "Generator" || ident == "AsyncGenerator" {
+                iterator_is_async = ident == "AsyncGenerator";
                 if let Type::Path(_) = &m.ty {
                   if m.ident == "Yield" {
                     iterator_yield_type = Some(m.ty.clone());
@@ -1038,6 +1260,7 @@ impl ConvertToAST for syn::ItemImpl {
         iterator_yield_type,
         iterator_next_type,
         iterator_return_type,
+        iterator_is_async,
         js_mod: namespace,
         comments: extract_doc_comments(&self.attrs),
         register_name: get_register_ident(format!("{struct_name}_impl").as_str()),
@@ -1046,6 +1269,21 @@ impl ConvertToAST for syn::ItemImpl {
   }
 }
 
+/// Maps the `rename_all` values supported by serde to their `convert_case` equivalent.
+fn rename_all_to_case(rename_all: &str) -> Option<Case> {
+  Some(match rename_all {
+    "lowercase" => Case::Flat,
+    "UPPERCASE" => Case::UpperFlat,
+    "PascalCase" => Case::Pascal,
+    "camelCase" => Case::Camel,
+    "snake_case" => Case::Snake,
+    "SCREAMING_SNAKE_CASE" => Case::UpperSnake,
+    "kebab-case" => Case::Kebab,
+    "SCREAMING-KEBAB-CASE" => Case::Cobol,
+    _ => return None,
+  })
+}
+
 impl ConvertToAST for syn::ItemEnum {
   fn convert_to_ast(&mut self, opts: &BindgenAttrs) -> BindgenResult<Napi> {
     match self.vis {
@@ -1053,16 +1291,117 @@ impl ConvertToAST for syn::ItemEnum {
       _ => bail_span!(self, "only public enum allowed"),
     }
 
-    self.attrs.push(parse_quote!(#[derive(Copy, Clone)]));
+    let discriminant = opts.discriminant().map(|(field, _)| field.to_owned());
+    // variants with data can't be `Copy`
+    if discriminant.is_none() {
+      self.attrs.push(parse_quote!(#[derive(Copy, Clone)]));
+    }
 
     let js_name = opts
       .js_name()
       .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string());
 
-    let variants = match opts.string_enum() {
-      Some(_) => self
+    if opts.const_enum().is_some() && opts.string_enum().is_some() {
+      bail_span!(
+        self,
+        "#[napi(const_enum)] can not be used with #[napi(string_enum)]"
+      );
+    }
+
+    if opts.ts_union().is_some() && opts.string_enum().is_none() {
+      bail_span!(
+        self,
+        "#[napi(ts_union)] can only be used with #[napi(string_enum)]"
+      );
+    }
+
+    if discriminant.is_some() && (opts.const_enum().is_some() || opts.string_enum().is_some()) {
+      bail_span!(
+        self,
+        "#[napi(discriminant)] can not be used with #[napi(string_enum)] or #[napi(const_enum)]"
+      );
+    }
+
+    let rename_all = match opts.rename_all() {
+      Some((rename_all, span)) => {
+        if opts.string_enum().is_none() && discriminant.is_none() {
+          bail_span!(
+            self,
+            "#[napi(rename_all)] can only be used with #[napi(string_enum)] or #[napi(discriminant)]"
+          );
+        }
+        match rename_all_to_case(rename_all) {
+          Some(case) => Some(case),
+          None => {
+            return Err(Diagnostic::span_error(
+              span,
+              format!("unsupported #[napi(rename_all = \"{}\")]", rename_all),
+            ))
+          }
+        }
+      }
+      None => None,
+    };
+
+    let variants = match (&discriminant, opts.string_enum()) {
+      (Some(discriminant), _) => self
+        .variants
+        .iter_mut()
+        .map(|v| {
+          if matches!(&v.discriminant, Some((_, _))) {
+            bail_span!(
+              v,
+              "Literal values are not supported with #[napi(discriminant)]"
+            )
+          }
+          let variant_opts = BindgenAttrs::find(&mut v.attrs)?;
+          let val = variant_opts.rename().map_or_else(
+            || {
+              rename_all.map_or_else(
+                || v.ident.to_string(),
+                |case| v.ident.to_string().to_case(case),
+              )
+            },
+            |(rename, _)| rename.to_owned(),
+          );
+          let fields = match &v.fields {
+            syn::Fields::Unit => vec![],
+            syn::Fields::Named(fields) => fields
+              .named
+              .iter()
+              .map(|field| {
+                let name = field.ident.clone().expect("named field");
+                let js_name = name.unraw().to_string().to_case(Case::Camel);
+                if &js_name == discriminant {
+                  bail_span!(
+                    field,
+                    "Field `{}` collides with the discriminant of #[napi(discriminant)]",
+                    js_name
+                  )
+                }
+                Ok(NapiEnumVariantField {
+                  name,
+                  js_name,
+                  ty: field.ty.clone(),
+                })
+              })
+              .collect::<BindgenResult<Vec<_>>>()?,
+            syn::Fields::Unnamed(_) => bail_span!(
+              v.fields,
+              "Tuple variants are not supported with #[napi(discriminant)], use named fields"
+            ),
+          };
+          Ok(NapiEnumVariant {
+            name: v.ident.clone(),
+            val: NapiEnumValue::String(val),
+            comments: extract_doc_comments(&v.attrs),
+            fields,
+          })
+        })
+        .collect::<BindgenResult<Vec<NapiEnumVariant>>>()?,
+      (None, Some(_)) => self
         .variants
-        .iter()
+        .iter_mut()
         .map(|v| {
           if !matches!(v.fields, syn::Fields::Unit) {
             bail_span!(v.fields, "Structured enum is not supported in #[napi]")
@@ -1073,23 +1412,41 @@ impl ConvertToAST for syn::ItemEnum {
               "Literal values are not supported with string enum in #[napi]"
             )
           }
+          let variant_opts = BindgenAttrs::find(&mut v.attrs)?;
+          // `#[napi(rename)]` on the variant takes precedence over `#[napi(rename_all)]`
+          let val = variant_opts.rename().map_or_else(
+            || {
+              rename_all.map_or_else(
+                || v.ident.to_string(),
+                |case| v.ident.to_string().to_case(case),
+              )
+            },
+            |(rename, _)| rename.to_owned(),
+          );
           Ok(NapiEnumVariant {
             name: v.ident.clone(),
-            val: NapiEnumValue::String(v.ident.to_string()),
+            val: NapiEnumValue::String(val),
             comments: extract_doc_comments(&v.attrs),
+            fields: vec![],
           })
         })
         .collect::<BindgenResult<Vec<NapiEnumVariant>>>()?,
-      None => {
+      (None, None) => {
         let mut last_variant_val: i32 = -1;
 
         self
           .variants
-          .iter()
+          .iter_mut()
           .map(|v| {
             if !matches!(v.fields, syn::Fields::Unit) {
               bail_span!(v.fields, "Structured enum is not supported in #[napi]")
             }
+            if BindgenAttrs::find(&mut v.attrs)?.rename().is_some() {
+              bail_span!(
+                v,
+                "#[napi(rename)] on enum variants can only be used with #[napi(string_enum)]"
+              )
+            }
 
             let val = match &v.discriminant {
               Some((_, expr)) => {
@@ -1135,6 +1492,7 @@ impl ConvertToAST for syn::ItemEnum {
               name: v.ident.clone(),
               val: NapiEnumValue::Number(val),
               comments: extract_doc_comments(&v.attrs),
+              fields: vec![],
             })
           })
           .collect::<BindgenResult<Vec<NapiEnumVariant>>>()?
@@ -1149,6 +1507,9 @@ impl ConvertToAST for syn::ItemEnum {
         js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
         comments: extract_doc_comments(&self.attrs),
         skip_typescript: opts.skip_typescript().is_some(),
+        const_enum: opts.const_enum().is_some(),
+        ts_union: opts.ts_union().is_some(),
+        discriminant,
         register_name: get_register_ident(self.ident.to_string().as_str()),
       }),
     })
@@ -1169,6 +1530,10 @@ impl ConvertToAST for syn::ItemConst {
           js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
           comments: extract_doc_comments(&self.attrs),
           skip_typescript: opts.skip_typescript().is_some(),
+          enabled_if_env: opts.enabled_if_env().map(|(m, _)| m.to_owned()),
+          frozen: opts.frozen().is_some(),
+          json: opts.json().is_some(),
+          ts_type: opts.ts_type().map(|(m, _)| m.to_owned()),
           register_name: get_register_ident(self.ident.to_string().as_str()),
         }),
       }),
@@ -0,0 +1,524 @@
+use std::ffi::c_void;
+use std::ptr;
+
+use crate::{
+  bindgen_runtime::Unknown, check_status, sys, Env, JsError, JsObject, JsSymbol, NapiRaw,
+  NapiValue, Property, PropertyAttributes, Result, ValueType,
+};
+
+use super::{FromNapiValue, ToNapiValue};
+
+const ASYNC_GENERATOR_STATE_KEY: &str = "[[AsyncGeneratorState]]";
+
+/// One step of an [`AsyncGenerator`].
+pub enum GeneratorStep<Y, R> {
+  /// Resolves the pending `next()` with `{ value, done: false }`.
+  Yield(Y),
+  /// Finishes the generator and resolves the pending `next()` with `{ value, done: true }`,
+  /// which is the result of `yield*` in JavaScript.
+  Return(R),
+}
+
+/// Implement the async generator protocol for the JavaScript Class, via `#[napi(async_iterator)]`.
+///
+/// The steps are produced synchronously on the JavaScript thread and every `next`, `return` and `throw`
+/// call returns an already settled Promise, so the instance works with `for await` and `yield*`.
+/// Unlike [`Generator`](super::Generator), the generator can finish with a return value.
+/// This feature is an experimental feature and is not yet stable.
+pub trait AsyncGenerator {
+  type Yield: ToNapiValue;
+  type Next: FromNapiValue;
+  type Return: ToNapiValue;
+
+  /// Handle the `AsyncGenerator.next()`, an `Err` rejects the returned Promise and finishes the generator.
+  /// <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/AsyncGenerator/next>
+  fn next(&mut self, value: Option<Self::Next>)
+    -> Result<GeneratorStep<Self::Yield, Self::Return>>;
+
+  /// Implement complete to clean up when the generator is finished early by `AsyncGenerator.return()`.
+  /// <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/AsyncGenerator/return>
+  fn complete(&mut self) {}
+
+  #[allow(unused_variables)]
+  /// Implement catch to handle the `AsyncGenerator.throw()`, an `Err` rejects the returned Promise with it.
+  /// <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/AsyncGenerator/throw>
+  fn catch(
+    &mut self,
+    env: Env,
+    value: Unknown,
+  ) -> std::result::Result<GeneratorStep<Self::Yield, Self::Return>, Unknown> {
+    Err(value)
+  }
+}
+
+/// The settled state of a call: `Ok((value, done))` resolves, `Err(reason)` rejects.
+type Settlement = std::result::Result<(sys::napi_value, bool), sys::napi_value>;
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn create_async_iterator<T: AsyncGenerator>(
+  env: sys::napi_env,
+  instance: sys::napi_value,
+  generator_ptr: *mut T,
+) {
+  if let Err(e) = unsafe { define_async_iterator(env, instance, generator_ptr) } {
+    unsafe { JsError::from(e).throw_into(env) };
+  }
+}
+
+unsafe fn define_async_iterator<T: AsyncGenerator>(
+  env: sys::napi_env,
+  instance: sys::napi_value,
+  generator_ptr: *mut T,
+) -> Result<()> {
+  let async_iterator_symbol = unsafe { Env::from_raw(env) }
+    .get_global()?
+    .get_named_property_unchecked::<JsObject>("Symbol")?
+    .get_named_property_unchecked::<JsSymbol>("asyncIterator")?;
+  let async_iterator_function = unsafe {
+    create_function(
+      env,
+      "AsyncIterator",
+      symbol_async_iterator::<T>,
+      generator_ptr.cast(),
+    )
+  }?;
+  check_status!(
+    unsafe {
+      sys::napi_set_property(
+        env,
+        instance,
+        async_iterator_symbol.raw(),
+        async_iterator_function,
+      )
+    },
+    "Failed to set Symbol.asyncIterator on class instance",
+  )
+}
+
+unsafe fn create_function(
+  env: sys::napi_env,
+  name: &str,
+  callback: unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value,
+  data: *mut c_void,
+) -> Result<sys::napi_value> {
+  let mut function = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_function(
+        env,
+        name.as_ptr().cast(),
+        name.len(),
+        Some(callback),
+        data,
+        &mut function,
+      )
+    },
+    "Create {} function failed",
+    name
+  )?;
+  Ok(function)
+}
+
+unsafe extern "C" fn symbol_async_iterator<T: AsyncGenerator>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let create_generator_object = || -> Result<sys::napi_value> {
+    let (_, _, generator_ptr) = unsafe { get_call_info(env, info) }?;
+    let env = unsafe { Env::from_raw(env) };
+    let mut generator_object = env.create_object()?;
+    for (name, callback) in [
+      ("next", async_generator_next::<T> as _),
+      ("return", async_generator_return::<T> as _),
+      ("throw", async_generator_throw::<T> as _),
+    ] {
+      let function = unsafe { create_function(env.raw(), name, callback, generator_ptr) }?;
+      generator_object.set_named_property(name, unsafe {
+        Unknown::from_raw_unchecked(env.raw(), function)
+      })?;
+    }
+    generator_object.define_properties(&[Property::new(ASYNC_GENERATOR_STATE_KEY)?
+      .with_value(&env.get_boolean(false)?)
+      .with_property_attributes(PropertyAttributes::Writable)])?;
+    Ok(unsafe { generator_object.raw() })
+  };
+  create_generator_object().unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
+
+unsafe extern "C" fn async_generator_next<T: AsyncGenerator>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  settle(env, info, |this, arg, generator: &mut T| {
+    if unsafe { is_completed(env, this) }? {
+      return Ok(Ok((unsafe { get_undefined(env) }?, true)));
+    }
+    let value = match arg {
+      Some(arg) if crate::type_of!(env, arg)? != ValueType::Undefined => {
+        Some(unsafe { T::Next::from_napi_value(env, arg) }?)
+      }
+      _ => None,
+    };
+    match generator.next(value) {
+      Ok(step) => unsafe { step_to_settlement(env, step) },
+      Err(e) => Ok(Err(unsafe { JsError::from(e).into_value(env) })),
+    }
+  })
+}
+
+unsafe extern "C" fn async_generator_return<T: AsyncGenerator>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  settle(env, info, |this, arg, generator: &mut T| {
+    if !unsafe { is_completed(env, this) }? {
+      generator.complete();
+    }
+    let value = match arg {
+      Some(arg) => arg,
+      None => unsafe { get_undefined(env) }?,
+    };
+    Ok(Ok((value, true)))
+  })
+}
+
+unsafe extern "C" fn async_generator_throw<T: AsyncGenerator>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  settle(env, info, |this, arg, generator: &mut T| {
+    let error = match arg {
+      Some(arg) => arg,
+      None => unsafe { get_undefined(env) }?,
+    };
+    if unsafe { is_completed(env, this) }? {
+      return Ok(Err(error));
+    }
+    match generator.catch(Env(env), unsafe { Unknown::from_raw_unchecked(env, error) }) {
+      Ok(step) => unsafe { step_to_settlement(env, step) },
+      Err(e) => Ok(Err(unsafe { e.raw() })),
+    }
+  })
+}
+
+/// Run `f` and return a Promise settled with its outcome, recording the generator as completed once it is done.
+fn settle<T, F>(env: sys::napi_env, info: sys::napi_callback_info, f: F) -> sys::napi_value
+where
+  F: FnOnce(sys::napi_value, Option<sys::napi_value>, &mut T) -> Result<Settlement>,
+{
+  let run = || -> Result<sys::napi_value> {
+    let (this, arg, generator_ptr) = unsafe { get_call_info(env, info) }?;
+    let generator = unsafe { &mut *generator_ptr.cast::<T>() };
+    let settlement =
+      f(this, arg, generator).unwrap_or_else(|e| Err(unsafe { JsError::from(e).into_value(env) }));
+    if settlement.as_ref().map_or(true, |(_, done)| *done) {
+      let mut this = unsafe { JsObject::from_raw_unchecked(env, this) };
+      this.set_named_property(
+        ASYNC_GENERATOR_STATE_KEY,
+        unsafe { Env::from_raw(env) }.get_boolean(true)?,
+      )?;
+    }
+    unsafe { settled_promise(env, settlement) }
+  };
+  run().unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
+
+/// Create a Promise already resolved with `{ value, done }` or rejected with the reason.
+unsafe fn settled_promise(env: sys::napi_env, settlement: Settlement) -> Result<sys::napi_value> {
+  let mut deferred = ptr::null_mut();
+  let mut promise = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) },
+    "Failed to create async generator result Promise",
+  )?;
+  match settlement {
+    Ok((value, done)) => {
+      let result = unsafe { create_iterator_result(env, value, done) }?;
+      check_status!(
+        unsafe { sys::napi_resolve_deferred(env, deferred, result) },
+        "Failed to resolve async generator result",
+      )?;
+    }
+    Err(reason) => {
+      check_status!(
+        unsafe { sys::napi_reject_deferred(env, deferred, reason) },
+        "Failed to reject async generator result",
+      )?;
+    }
+  }
+  Ok(promise)
+}
+
+unsafe fn create_iterator_result(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  done: bool,
+) -> Result<sys::napi_value> {
+  let env = unsafe { Env::from_raw(env) };
+  let mut result = env.create_object()?;
+  result.set_named_property("value", unsafe {
+    Unknown::from_raw_unchecked(env.raw(), value)
+  })?;
+  result.set_named_property("done", env.get_boolean(done)?)?;
+  Ok(unsafe { result.raw() })
+}
+
+unsafe fn step_to_settlement<Y: ToNapiValue, R: ToNapiValue>(
+  env: sys::napi_env,
+  step: GeneratorStep<Y, R>,
+) -> Result<Settlement> {
+  Ok(Ok(match step {
+    GeneratorStep::Yield(value) => (unsafe { Y::to_napi_value(env, value) }?, false),
+    GeneratorStep::Return(value) => (unsafe { R::to_napi_value(env, value) }?, true),
+  }))
+}
+
+unsafe fn get_call_info(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> Result<(sys::napi_value, Option<sys::napi_value>, *mut c_void)> {
+  let mut this = ptr::null_mut();
+  let mut argv: [sys::napi_value; 1] = [ptr::null_mut()];
+  let mut argc = 1;
+  let mut generator_ptr = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        info,
+        &mut argc,
+        argv.as_mut_ptr(),
+        &mut this,
+        &mut generator_ptr,
+      )
+    },
+    "Get callback info from async generator function failed"
+  )?;
+  Ok((this, (argc > 0).then_some(argv[0]), generator_ptr))
+}
+
+unsafe fn is_completed(env: sys::napi_env, this: sys::napi_value) -> Result<bool> {
+  let this = unsafe { JsObject::from_raw_unchecked(env, this) };
+  this.get_named_property_unchecked::<bool>(ASYNC_GENERATOR_STATE_KEY)
+}
+
+unsafe fn get_undefined(env: sys::napi_env) -> Result<sys::napi_value> {
+  let mut undefined = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_undefined(env, &mut undefined) },
+    "Get undefined failed"
+  )?;
+  Ok(undefined)
+}
+
+/// The value of a `next()` call on an async iterable created by [`Env::create_async_iterable`],
+/// `None` once the channel is disconnected.
+#[cfg(feature = "napi4")]
+pub(crate) struct ChannelStep<T>(pub(crate) Option<T>);
+
+#[cfg(feature = "napi4")]
+impl<T: ToNapiValue> ToNapiValue for ChannelStep<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let done = val.0.is_none();
+    let value = match val.0 {
+      Some(value) => unsafe { T::to_napi_value(env, value) }?,
+      None => unsafe { get_undefined(env) }?,
+    };
+    unsafe { create_iterator_result(env, value, done) }
+  }
+}
+
+#[cfg(feature = "napi4")]
+pub(crate) type ChannelStepResolver<T> = Box<dyn FnOnce(Env) -> Result<ChannelStep<T>>>;
+
+#[cfg(feature = "napi4")]
+type ChannelStepDeferred<T> = crate::JsDeferred<ChannelStep<T>, ChannelStepResolver<T>>;
+
+/// Wrapped into the object returned by [`Env::create_async_iterable`].
+///
+/// The pending `next()` calls are queued to a thread which receives one value from the channel
+/// for each of them, and resolves the Promise of the call through the threadsafe function of its
+/// [`JsDeferred`](crate::JsDeferred).
+#[cfg(feature = "napi4")]
+struct ChannelIterator<T: ToNapiValue> {
+  /// `None` once the iterable is finished by `return()` or `throw()`
+  requests: Option<std::sync::mpsc::Sender<ChannelStepDeferred<T>>>,
+}
+
+#[cfg(feature = "napi4")]
+pub(crate) fn create_channel_iterable<T: ToNapiValue + Send + 'static>(
+  env: &Env,
+  receiver: std::sync::mpsc::Receiver<T>,
+) -> Result<JsObject> {
+  let (requests, pending) = std::sync::mpsc::channel::<ChannelStepDeferred<T>>();
+  std::thread::Builder::new()
+    .name("napi-async-iterable".to_owned())
+    .spawn(move || {
+      // Ends once the iterable is finished or garbage collected, dropping `receiver`
+      // makes `send` fail on the producer side.
+      for deferred in pending {
+        let value = receiver.recv().ok();
+        deferred.resolve(Box::new(move |_| Ok(ChannelStep(value))));
+      }
+    })
+    .map_err(|e| crate::Error::from_reason(format!("Spawn async iterable thread failed: {}", e)))?;
+
+  let mut iterable = env.create_object()?;
+  let async_iterator_symbol = env
+    .get_global()?
+    .get_named_property_unchecked::<JsObject>("Symbol")?
+    .get_named_property_unchecked::<JsSymbol>("asyncIterator")?;
+  let symbol_async_iterator = unsafe {
+    create_function(
+      env.raw(),
+      "AsyncIterator",
+      channel_iterator_self,
+      ptr::null_mut(),
+    )
+  }?;
+  check_status!(
+    unsafe {
+      sys::napi_set_property(
+        env.raw(),
+        iterable.raw(),
+        async_iterator_symbol.raw(),
+        symbol_async_iterator,
+      )
+    },
+    "Failed to set Symbol.asyncIterator on async iterable",
+  )?;
+  for (name, callback) in [
+    ("next", channel_iterator_next::<T> as _),
+    ("return", channel_iterator_return::<T> as _),
+    ("throw", channel_iterator_throw::<T> as _),
+  ] {
+    let function = unsafe { create_function(env.raw(), name, callback, ptr::null_mut()) }?;
+    iterable.set_named_property(name, unsafe {
+      Unknown::from_raw_unchecked(env.raw(), function)
+    })?;
+  }
+  let state = Box::new(ChannelIterator {
+    requests: Some(requests),
+  });
+  check_status!(
+    unsafe {
+      sys::napi_wrap(
+        env.raw(),
+        iterable.raw(),
+        Box::into_raw(state).cast(),
+        Some(finalize_channel_iterator::<T>),
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    },
+    "Failed to wrap async iterable state",
+  )?;
+  Ok(iterable)
+}
+
+/// `[Symbol.asyncIterator]()` of the async iterable, which is its own iterator.
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn channel_iterator_self(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  match unsafe { get_call_info(env, info) } {
+    Ok((this, _, _)) => this,
+    Err(e) => {
+      unsafe { JsError::from(e).throw_into(env) };
+      ptr::null_mut()
+    }
+  }
+}
+
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn channel_iterator_next<T: ToNapiValue + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  with_channel_iterator(env, info, |env, iterator: &mut ChannelIterator<T>, _| {
+    let (deferred, promise) = env.create_deferred::<ChannelStep<T>, ChannelStepResolver<T>>()?;
+    match &iterator.requests {
+      Some(requests) => {
+        if let Err(std::sync::mpsc::SendError(deferred)) = requests.send(deferred) {
+          deferred.resolve(Box::new(|_| Ok(ChannelStep(None))));
+        }
+      }
+      None => deferred.resolve(Box::new(|_| Ok(ChannelStep(None)))),
+    }
+    Ok(unsafe { promise.raw() })
+  })
+}
+
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn channel_iterator_return<T: ToNapiValue + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  with_channel_iterator(env, info, |env, iterator: &mut ChannelIterator<T>, arg| {
+    iterator.requests.take();
+    let value = match arg {
+      Some(arg) => arg,
+      None => unsafe { get_undefined(env.raw()) }?,
+    };
+    unsafe { settled_promise(env.raw(), Ok((value, true))) }
+  })
+}
+
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn channel_iterator_throw<T: ToNapiValue + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  with_channel_iterator(env, info, |env, iterator: &mut ChannelIterator<T>, arg| {
+    iterator.requests.take();
+    let reason = match arg {
+      Some(arg) => arg,
+      None => unsafe { get_undefined(env.raw()) }?,
+    };
+    unsafe { settled_promise(env.raw(), Err(reason)) }
+  })
+}
+
+#[cfg(feature = "napi4")]
+fn with_channel_iterator<T: ToNapiValue, F>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+  f: F,
+) -> sys::napi_value
+where
+  F: FnOnce(Env, &mut ChannelIterator<T>, Option<sys::napi_value>) -> Result<sys::napi_value>,
+{
+  let run = || -> Result<sys::napi_value> {
+    let (this, arg, _) = unsafe { get_call_info(env, info) }?;
+    let mut iterator = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_unwrap(env, this, &mut iterator) },
+      "`this` of the async iterable method is not an async iterable",
+    )?;
+    f(
+      unsafe { Env::from_raw(env) },
+      unsafe { &mut *iterator.cast::<ChannelIterator<T>>() },
+      arg,
+    )
+  };
+  run().unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
+
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn finalize_channel_iterator<T: ToNapiValue>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_data.cast::<ChannelIterator<T>>()) });
+}
@@ -1,10 +1,13 @@
 use std::cell::Cell;
 use std::ffi::c_void;
+use std::mem::ManuallyDrop;
 use std::ptr;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::{bindgen_prelude::*, check_status, sys, Result};
+use super::{release_reference, ClassData};
+use crate::env_cache::{self, CacheKey};
+use crate::{bindgen_prelude::*, check_status, sys, JsTypeError, NapiValue, Result};
 
 thread_local! {
   #[doc(hidden)]
@@ -12,11 +15,19 @@ thread_local! {
   pub static ___CALL_FROM_FACTORY: AtomicBool = AtomicBool::new(false);
 }
 
+/// The arguments of a call, the first `N` of them are fetched into `args`.
+///
+/// `N` is the number of parameters of the Rust function, missing arguments are `undefined`.
+/// Arguments beyond them aren't fetched until asked for with [`CallbackInfo::get_args_from`],
+/// which is how a [`Rest`] parameter collects any number of them.
 pub struct CallbackInfo<const N: usize> {
   env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
   pub this: sys::napi_value,
   pub args: [sys::napi_value; N],
+  argc: usize,
   this_reference: sys::napi_ref,
+  data: *mut c_void,
 }
 
 impl<const N: usize> CallbackInfo<N> {
@@ -32,6 +43,7 @@ impl<const N: usize> CallbackInfo<N> {
     let mut this = ptr::null_mut();
     let mut args = [ptr::null_mut(); N];
     let mut argc = N;
+    let mut data = ptr::null_mut();
 
     unsafe {
       check_status!(
@@ -41,7 +53,7 @@ impl<const N: usize> CallbackInfo<N> {
           &mut argc,
           args.as_mut_ptr(),
           &mut this,
-          ptr::null_mut(),
+          &mut data,
         ),
         "Failed to initialize napi function call."
       )?;
@@ -70,21 +82,99 @@ impl<const N: usize> CallbackInfo<N> {
 
     Ok(Self {
       env,
+      callback_info,
       this,
       args,
+      argc,
       this_reference,
+      data,
     })
   }
 
+  /// Fail with a `TypeError` like a JavaScript class does, if the constructor of the class
+  /// generated by the struct `rust_name` is called as a function, without `new`.
+  pub fn ensure_new_target(&self, rust_name: &str) -> Result<()> {
+    let mut new_target = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_new_target(self.env, self.callback_info, &mut new_target) },
+      "Failed to get `new.target` in the constructor of class `{}`",
+      rust_name,
+    )?;
+    if !new_target.is_null() {
+      return Ok(());
+    }
+    let js_name = super::get_class_js_name(rust_name).unwrap_or(rust_name);
+    let error = JsTypeError::from(Error::new(
+      Status::InvalidArg,
+      format!(
+        "Class constructor {} cannot be invoked without 'new'",
+        js_name
+      ),
+    ));
+    let type_error = unsafe { error.into_value(self.env) };
+    Err(Error::from(unsafe {
+      Unknown::from_raw_unchecked(self.env, type_error)
+    }))
+  }
+
   pub fn get_arg(&self, index: usize) -> sys::napi_value {
     self.args[index]
   }
 
+  /// The number of arguments passed, `arguments.length` in JavaScript
+  pub fn args_len(&self) -> usize {
+    self.argc
+  }
+
+  /// Fetch the arguments passed from `start` on, which may be more than `N`.
+  ///
+  /// The buffer is allocated with exactly the number of arguments passed, so any arity is supported.
+  pub fn get_args_from(&self, start: usize) -> Result<Vec<sys::napi_value>> {
+    if start >= self.argc {
+      return Ok(Vec::new());
+    }
+    if self.argc <= N {
+      return Ok(self.args[start..self.argc].to_vec());
+    }
+    let mut argc = self.argc;
+    let mut args = vec![ptr::null_mut(); argc];
+    check_status!(
+      unsafe {
+        sys::napi_get_cb_info(
+          self.env,
+          self.callback_info,
+          &mut argc,
+          args.as_mut_ptr(),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        )
+      },
+      "Failed to get the arguments of napi function call."
+    )?;
+    args.truncate(argc.min(self.argc));
+    Ok(args.split_off(start))
+  }
+
   pub fn this(&self) -> sys::napi_value {
     self.this
   }
 
-  fn _construct<T: ObjectFinalize + 'static>(
+  /// Get the `class_data` passed to `register_class`, `None` if it is not a `T`.
+  ///
+  /// # Safety
+  ///
+  /// Only call this in the constructor of a class, the `data` of other callbacks is not class data.
+  pub unsafe fn class_data<T: 'static>(&self) -> Option<&T> {
+    if self.data.is_null() {
+      return None;
+    }
+    unsafe { &*self.data.cast::<ClassData>() }
+      .data
+      .as_ref()?
+      .downcast_ref::<T>()
+  }
+
+  fn _construct<T: ObjectFinalize + ClassStorage + 'static>(
     &self,
     js_name: &str,
     obj: T,
@@ -92,6 +182,10 @@ impl<const N: usize> CallbackInfo<N> {
     let obj = Box::new(obj);
     let this = self.this();
     let value_ref = Box::into_raw(obj);
+    if T::EXTERNAL_STATE {
+      unsafe { attach_external_state(self.env, this, value_ref, js_name) }?;
+      return Ok((this, value_ref));
+    }
     let mut object_ref = ptr::null_mut();
     let initial_finalize: Box<dyn FnOnce()> = Box::new(|| {});
     let finalize_callbacks_ptr = Rc::into_raw(Rc::new(Cell::new(Box::into_raw(initial_finalize))));
@@ -118,7 +212,7 @@ impl<const N: usize> CallbackInfo<N> {
     Ok((this, value_ref))
   }
 
-  pub fn construct<T: ObjectFinalize + 'static>(
+  pub fn construct<T: ObjectFinalize + ClassStorage + 'static>(
     &self,
     js_name: &str,
     obj: T,
@@ -126,7 +220,7 @@ impl<const N: usize> CallbackInfo<N> {
     self._construct(js_name, obj).map(|(v, _)| v)
   }
 
-  pub fn construct_generator<T: Generator + ObjectFinalize + 'static>(
+  pub fn construct_generator<T: Generator + ObjectFinalize + ClassStorage + 'static>(
     &self,
     js_name: &str,
     obj: T,
@@ -136,7 +230,17 @@ impl<const N: usize> CallbackInfo<N> {
     Ok(instance)
   }
 
-  pub fn factory<T: ObjectFinalize + 'static>(
+  pub fn construct_async_generator<T: AsyncGenerator + ObjectFinalize + ClassStorage + 'static>(
+    &self,
+    js_name: &str,
+    obj: T,
+  ) -> Result<sys::napi_value> {
+    let (instance, generator_ptr) = self._construct(js_name, obj)?;
+    crate::__private::create_async_iterator(self.env, instance, generator_ptr);
+    Ok(instance)
+  }
+
+  pub fn factory<T: ObjectFinalize + ClassStorage + 'static>(
     &self,
     js_name: &str,
     obj: T,
@@ -144,7 +248,7 @@ impl<const N: usize> CallbackInfo<N> {
     self._factory(js_name, obj).map(|(value, _)| value)
   }
 
-  pub fn generator_factory<T: ObjectFinalize + Generator + 'static>(
+  pub fn generator_factory<T: ObjectFinalize + ClassStorage + Generator + 'static>(
     &self,
     js_name: &str,
     obj: T,
@@ -154,7 +258,17 @@ impl<const N: usize> CallbackInfo<N> {
     Ok(instance)
   }
 
-  fn _factory<T: ObjectFinalize + 'static>(
+  pub fn async_generator_factory<T: ObjectFinalize + ClassStorage + AsyncGenerator + 'static>(
+    &self,
+    js_name: &str,
+    obj: T,
+  ) -> Result<sys::napi_value> {
+    let (instance, generator_ptr) = self._factory(js_name, obj)?;
+    crate::__private::create_async_iterator(self.env, instance, generator_ptr);
+    Ok(instance)
+  }
+
+  fn _factory<T: ObjectFinalize + ClassStorage + 'static>(
     &self,
     js_name: &str,
     obj: T,
@@ -184,10 +298,14 @@ impl<const N: usize> CallbackInfo<N> {
     }
     check_status!(status, "Failed to create instance of class `{}`", js_name)?;
     let obj = Box::new(obj);
+    let value_ref = Box::into_raw(obj);
+    if T::EXTERNAL_STATE {
+      unsafe { attach_external_state(self.env, instance, value_ref, js_name) }?;
+      return Ok((instance, value_ref));
+    }
     let initial_finalize: Box<dyn FnOnce()> = Box::new(|| {});
     let finalize_callbacks_ptr = Rc::into_raw(Rc::new(Cell::new(Box::into_raw(initial_finalize))));
     let mut object_ref = ptr::null_mut();
-    let value_ref = Box::into_raw(obj);
     check_status!(
       unsafe {
         sys::napi_wrap(
@@ -213,14 +331,14 @@ impl<const N: usize> CallbackInfo<N> {
 
   pub fn unwrap_borrow_mut<T>(&mut self) -> Result<&'static mut T>
   where
-    T: FromNapiMutRef + TypeName,
+    T: FromNapiMutRef + TypeName + ClassStorage + 'static,
   {
     unsafe { self.unwrap_raw::<T>() }.map(|raw| Box::leak(unsafe { Box::from_raw(raw) }))
   }
 
   pub fn unwrap_borrow<T>(&mut self) -> Result<&'static T>
   where
-    T: FromNapiRef + TypeName,
+    T: FromNapiRef + TypeName + ClassStorage + 'static,
   {
     unsafe { self.unwrap_raw::<T>() }
       .map(|raw| Box::leak(unsafe { Box::from_raw(raw) }) as &'static T)
@@ -230,18 +348,184 @@ impl<const N: usize> CallbackInfo<N> {
   #[inline]
   pub unsafe fn unwrap_raw<T>(&mut self) -> Result<*mut T>
   where
-    T: TypeName,
+    T: TypeName + ClassStorage + 'static,
   {
+    if T::EXTERNAL_STATE {
+      return unsafe { unwrap_external_state::<T>(self.env, self.this) };
+    }
     let mut wrapped_val: *mut c_void = std::ptr::null_mut();
 
-    unsafe {
-      check_status!(
-        sys::napi_unwrap(self.env, self.this, &mut wrapped_val),
-        "Failed to unwrap exclusive reference of `{}` type from napi value",
-        T::type_name(),
-      )?;
+    let status = unsafe { sys::napi_unwrap(self.env, self.this, &mut wrapped_val) };
+    if status != sys::Status::napi_ok && self.is_consumed() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("`{}` instance is already consumed", T::type_name()),
+      ));
+    }
+    check_status!(
+      status,
+      "Failed to unwrap exclusive reference of `{}` type from napi value",
+      T::type_name(),
+    )?;
+
+    Ok(wrapped_val.cast())
+  }
 
-      Ok(wrapped_val.cast())
+  #[doc(hidden)]
+  /// Take the value out of `this` for a method taking `self` by value.
+  ///
+  /// The wrap is removed from the object, so the value is dropped by the method instead of the
+  /// finalizer of the class, and later method calls on the object fail as it is consumed.
+  pub unsafe fn unwrap_owned<T>(&mut self) -> Result<T>
+  where
+    T: TypeName + ClassStorage + 'static,
+  {
+    if T::EXTERNAL_STATE {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "`{}` keeps its value in an external state, it can't be consumed by a method taking `self`",
+          T::type_name()
+        ),
+      ));
+    }
+    let wrapped_val = unsafe { self.unwrap_raw::<T>() }?.cast::<c_void>();
+    let is_referenced = REFERENCE_MAP.with(|map| {
+      map
+        .borrow()
+        .get(&wrapped_val)
+        .map_or(false, |(_, _, finalize_callbacks_ptr)| {
+          let finalize_callbacks =
+            ManuallyDrop::new(unsafe { Rc::from_raw(*finalize_callbacks_ptr) });
+          Rc::strong_count(&finalize_callbacks) > 1
+        })
+    });
+    if is_referenced {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "`{}` instance can't be consumed while a `Reference` to it is alive",
+          T::type_name()
+        ),
+      ));
     }
+    check_status!(
+      unsafe { sys::napi_remove_wrap(self.env, self.this, &mut ptr::null_mut()) },
+      "Failed to remove wrap of `{}` instance",
+      T::type_name(),
+    )?;
+    unsafe { release_reference(self.env, wrapped_val) };
+    let mut consumed = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_boolean(self.env, true, &mut consumed) })?;
+    let descriptor = sys::napi_property_descriptor {
+      utf8name: ptr::null(),
+      name: get_consumed_symbol(self.env)?,
+      method: None,
+      getter: None,
+      setter: None,
+      value: consumed,
+      attributes: PropertyAttributes::Default.into(),
+      data: ptr::null_mut(),
+    };
+    check_status!(
+      unsafe { sys::napi_define_properties(self.env, self.this, 1, &descriptor) },
+      "Failed to mark `{}` instance as consumed",
+      T::type_name(),
+    )?;
+    Ok(*unsafe { Box::from_raw(wrapped_val.cast::<T>()) })
+  }
+
+  fn is_consumed(&self) -> bool {
+    let Ok(key) = get_consumed_symbol(self.env) else {
+      return false;
+    };
+    let mut is_consumed = false;
+    let status = unsafe { sys::napi_has_own_property(self.env, self.this, key, &mut is_consumed) };
+    status == sys::Status::napi_ok && is_consumed
+  }
+}
+
+/// The symbol marking an instance whose value is taken by a method taking `self` by value,
+/// one for each env so JavaScript can't read or forge the mark by its name
+fn get_consumed_symbol(env: sys::napi_env) -> Result<sys::napi_value> {
+  let key = CacheKey::Named("napi-rs consumed");
+  if let Some(symbol) = env_cache::get(env, &key)? {
+    return Ok(symbol);
+  }
+  let mut symbol = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_symbol(env, ptr::null_mut(), &mut symbol) },
+    "Failed to create the consumed symbol"
+  )?;
+  env_cache::insert(env, key, symbol)?;
+  Ok(symbol)
+}
+
+/// The arguments passed after the other parameters, `...name: T[]` in TypeScript.
+///
+/// It must be the last parameter of a `#[napi]` function:
+///
+/// ```rust,ignore
+/// #[napi]
+/// fn sum(first: f64, rest: Rest<f64>) -> f64 {
+///   first + rest.iter().sum::<f64>()
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Rest<T>(pub Vec<T>);
+
+impl<T: FromNapiValue> Rest<T> {
+  /// Convert the arguments from the `start`th on
+  ///
+  /// # Safety
+  ///
+  /// `env` must be the env of the call described by `cb`
+  pub unsafe fn from_callback_info<const N: usize>(
+    env: sys::napi_env,
+    cb: &CallbackInfo<N>,
+    start: usize,
+  ) -> Result<Self> {
+    cb.get_args_from(start)?
+      .into_iter()
+      .enumerate()
+      .map(|(i, arg)| {
+        unsafe { T::from_napi_value(env, arg) }.map_err(|e| {
+          Error::new(
+            e.status,
+            format!("Failed to convert rest argument {}. {}", i, e.reason),
+          )
+        })
+      })
+      .collect::<Result<Vec<_>>>()
+      .map(Self)
+  }
+}
+
+impl<T> Rest<T> {
+  pub fn into_inner(self) -> Vec<T> {
+    self.0
+  }
+}
+
+impl<T> std::ops::Deref for Rest<T> {
+  type Target = Vec<T>;
+
+  fn deref(&self) -> &Vec<T> {
+    &self.0
+  }
+}
+
+impl<T> std::ops::DerefMut for Rest<T> {
+  fn deref_mut(&mut self) -> &mut Vec<T> {
+    &mut self.0
+  }
+}
+
+impl<T> IntoIterator for Rest<T> {
+  type Item = T;
+  type IntoIter = std::vec::IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.0.into_iter()
   }
 }
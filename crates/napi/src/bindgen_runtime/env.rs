@@ -7,6 +7,8 @@ use super::Array;
 pub use crate::Env;
 
 impl Env {
+  /// Create an `Array` with `len` empty slots, to fill it with [`Array::set_elements`]
+  /// without growing it for every element.
   pub fn create_array(&self, len: u32) -> Result<Array> {
     Array::new(self.0, len)
   }
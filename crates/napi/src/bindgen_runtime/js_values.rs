@@ -16,19 +16,26 @@ mod class;
 #[cfg(all(feature = "chrono_date", feature = "napi5"))]
 mod date;
 mod either;
+mod enum_map;
 mod external;
 mod function;
+mod js_map;
+#[cfg(feature = "napi5")]
+mod lazy_field;
 mod map;
 mod nil;
 mod number;
 mod object;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 mod promise;
+mod rc_handle;
+mod scaled;
 #[cfg(feature = "serde-json")]
 mod serde;
 mod string;
 mod symbol;
 mod task;
+mod time;
 mod value_ref;
 
 pub use crate::js_values::JsUnknown as Unknown;
@@ -41,15 +48,22 @@ pub use bigint::*;
 pub use buffer::*;
 pub use class::*;
 pub use either::*;
+pub use enum_map::*;
 pub use external::*;
 pub use function::*;
+pub use js_map::*;
+#[cfg(feature = "napi5")]
+pub use lazy_field::*;
 pub use nil::*;
 pub use object::*;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 pub use promise::*;
+pub use rc_handle::*;
+pub use scaled::*;
 pub use string::*;
 pub use symbol::*;
 pub use task::*;
+pub use time::*;
 pub use value_ref::*;
 
 #[cfg(feature = "latin1")]
@@ -60,6 +60,36 @@ impl Array {
     }
   }
 
+  /// Set the `items` as the elements from index `start` on, the array grows if they don't fit.
+  ///
+  /// Creating the array with its final length, e.g. by [`Env::create_array`], and setting all
+  /// the elements at once is faster than [`Array::insert`] for every element.
+  pub fn set_elements<T, I>(&mut self, start: u32, items: I) -> Result<()>
+  where
+    T: ToNapiValue,
+    I: IntoIterator<Item = T>,
+  {
+    let mut index = start;
+    for item in items {
+      let napi_val = unsafe { T::to_napi_value(self.env, item) }?;
+      check_status!(
+        unsafe { sys::napi_set_element(self.env, self.inner, index, napi_val) },
+        "Failed to set element with index `{}`",
+        index,
+      )?;
+      index = index.checked_add(1).ok_or_else(|| {
+        Error::new(
+          Status::InvalidArg,
+          "Array length exceeds the maximum of `u32::MAX`".to_owned(),
+        )
+      })?;
+    }
+    if index > self.len {
+      self.len = index;
+    }
+    Ok(())
+  }
+
   pub fn insert<T: ToNapiValue>(&mut self, val: T) -> Result<()> {
     self.set(self.len(), val)?;
     Ok(())
@@ -134,20 +164,14 @@ impl Array {
     T: ToNapiValue,
   {
     let mut arr = Array::new(env.0, value.len() as u32)?;
-    value.into_iter().enumerate().try_for_each(|(index, val)| {
-      arr.set(index as u32, val)?;
-      Ok::<(), Error>(())
-    })?;
+    arr.set_elements(0, value)?;
     Ok(arr)
   }
 
   /// Create `Array` from `&Vec<String>`
   pub fn from_ref_vec_string(env: &Env, value: &[String]) -> Result<Self> {
     let mut arr = Array::new(env.0, value.len() as u32)?;
-    value.iter().enumerate().try_for_each(|(index, val)| {
-      arr.set(index as u32, val.as_str())?;
-      Ok::<(), Error>(())
-    })?;
+    arr.set_elements(0, value.iter().map(String::as_str))?;
     Ok(arr)
   }
 
@@ -157,10 +181,7 @@ impl Array {
     T: ToNapiValue + Copy,
   {
     let mut arr = Array::new(env.0, value.len() as u32)?;
-    value.iter().enumerate().try_for_each(|(index, val)| {
-      arr.set(index as u32, *val)?;
-      Ok::<(), Error>(())
-    })?;
+    arr.set_elements(0, value.iter().copied())?;
     Ok(arr)
   }
 }
@@ -12,7 +12,7 @@ use crate::bindgen_prelude::{CUSTOM_GC_TSFN, CUSTOM_GC_TSFN_DESTROYED, THREADS_C
 pub use crate::js_values::TypedArrayType;
 use crate::{check_status, sys, Error, Result, Status};
 
-use super::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use super::{FromNapiMutRef, FromNapiRef, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
 
 #[cfg(target_family = "wasm")]
 extern "C" {
@@ -338,8 +338,16 @@ macro_rules! impl_typed_array {
             format!("Expected $name, got {}", typed_array_type),
           ));
         }
+        // `napi_get_typedarray_info` may return a null `data` pointer for an empty TypedArray.
+        // `slice::from_raw_parts` requires a non-null pointer even if the length is 0, so an empty
+        // TypedArray is kept as `Some(empty)` instead of ending up as an invalid slice.
+        let data = if data.is_null() {
+          ptr::NonNull::<$rust_type>::dangling().as_ptr()
+        } else {
+          data as *mut $rust_type
+        };
         Ok($name {
-          data: data as *mut $rust_type,
+          data,
           length,
           byte_offset,
           raw: Some((ref_, env)),
@@ -507,6 +515,165 @@ impl_typed_array!(BigInt64Array, i64, TypedArrayType::BigInt64);
 #[cfg(feature = "napi6")]
 impl_typed_array!(BigUint64Array, u64, TypedArrayType::BigUint64);
 
+/// Borrow the backing store of a `TypedArray` passed from JavaScript as a slice, e.g. `&mut [f64]` from a `Float64Array`.
+///
+/// This is how a `#[napi]` function writes its results into an output array allocated by the caller,
+/// so a pipeline calling it repeatedly can reuse the same array without allocating:
+///
+/// ```rust,ignore
+/// #[napi]
+/// fn scale_into(input: &[f64], factor: f64, output: &mut [f64]) -> u32 {
+///   let count = input.len().min(output.len());
+///   for (out, value) in output.iter_mut().zip(input) {
+///     *out = value * factor;
+///   }
+///   count as u32
+/// }
+/// ```
+///
+/// The slice points into the `ArrayBuffer` of the array, so these aliasing rules apply:
+///
+/// - The slice is only valid during the call, it must not be stored anywhere. `async` functions
+///   taking a `&mut` slice must be `unsafe` like the ones taking any other `&mut` argument.
+/// - The same array, or views overlapping the same `ArrayBuffer`, must not be passed for two slice arguments
+///   if one of them is `&mut`, that would be two Rust references to the same memory.
+/// - JavaScript must not read or write the array while the slice is borrowed, e.g. from a callback called with it,
+///   use the owned typed array types like [`Float64Array`] in that case.
+///
+/// Only the matching typed array is accepted, `Uint8Array` for `[u8]` which includes a `Buffer`.
+macro_rules! impl_typed_array_slice {
+  ($rust_type:ident, $typed_array_type:expr, $js_name:literal) => {
+    impl FromNapiMutRef for [$rust_type] {
+      unsafe fn from_napi_mut_ref(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+      ) -> Result<&'static mut Self> {
+        let mut is_typed_array = false;
+        check_status!(
+          unsafe { sys::napi_is_typedarray(env, napi_val, &mut is_typed_array) },
+          "Failed to check if value is typed array"
+        )?;
+        if !is_typed_array {
+          return Err(Error::new(
+            Status::InvalidArg,
+            concat!("Expected a ", $js_name).to_owned(),
+          ));
+        }
+        let mut typed_array_type = 0;
+        let mut length = 0;
+        let mut data = ptr::null_mut();
+        let mut array_buffer = ptr::null_mut();
+        let mut byte_offset = 0;
+        check_status!(
+          unsafe {
+            sys::napi_get_typedarray_info(
+              env,
+              napi_val,
+              &mut typed_array_type,
+              &mut length,
+              &mut data,
+              &mut array_buffer,
+              &mut byte_offset,
+            )
+          },
+          "Get TypedArray info failed"
+        )?;
+        if typed_array_type != $typed_array_type as i32 {
+          return Err(Error::new(
+            Status::InvalidArg,
+            format!(
+              concat!("Expected a ", $js_name, ", got TypedArray of type {}"),
+              typed_array_type
+            ),
+          ));
+        }
+        // the data pointer of an empty TypedArray may be null, which is not allowed for a slice
+        let data = if data.is_null() {
+          ptr::NonNull::<$rust_type>::dangling().as_ptr()
+        } else {
+          data as *mut $rust_type
+        };
+        Ok(unsafe { std::slice::from_raw_parts_mut(data, length) })
+      }
+    }
+
+    impl FromNapiRef for [$rust_type] {
+      unsafe fn from_napi_ref(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+      ) -> Result<&'static Self> {
+        Ok(unsafe { <[$rust_type] as FromNapiMutRef>::from_napi_mut_ref(env, napi_val)? })
+      }
+    }
+
+    impl TypeName for &[$rust_type] {
+      fn type_name() -> &'static str {
+        $js_name
+      }
+
+      fn value_type() -> crate::ValueType {
+        crate::ValueType::Object
+      }
+    }
+
+    impl TypeName for &mut [$rust_type] {
+      fn type_name() -> &'static str {
+        $js_name
+      }
+
+      fn value_type() -> crate::ValueType {
+        crate::ValueType::Object
+      }
+    }
+
+    impl FromNapiValue for &[$rust_type] {
+      unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        unsafe { FromNapiRef::from_napi_ref(env, napi_val) }
+      }
+    }
+
+    impl FromNapiValue for &mut [$rust_type] {
+      unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        unsafe { FromNapiMutRef::from_napi_mut_ref(env, napi_val) }
+      }
+    }
+
+    impl ValidateNapiValue for &[$rust_type] {
+      unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+        unsafe { <[$rust_type] as FromNapiRef>::from_napi_ref(env, napi_val)? };
+        Ok(ptr::null_mut())
+      }
+    }
+
+    impl ValidateNapiValue for &mut [$rust_type] {
+      unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+        unsafe { <[$rust_type] as FromNapiRef>::from_napi_ref(env, napi_val)? };
+        Ok(ptr::null_mut())
+      }
+    }
+  };
+}
+
+impl_typed_array_slice!(i8, TypedArrayType::Int8, "Int8Array");
+impl_typed_array_slice!(u8, TypedArrayType::Uint8, "Uint8Array");
+impl_typed_array_slice!(i16, TypedArrayType::Int16, "Int16Array");
+impl_typed_array_slice!(u16, TypedArrayType::Uint16, "Uint16Array");
+impl_typed_array_slice!(i32, TypedArrayType::Int32, "Int32Array");
+impl_typed_array_slice!(u32, TypedArrayType::Uint32, "Uint32Array");
+impl_typed_array_slice!(f32, TypedArrayType::Float32, "Float32Array");
+impl_typed_array_slice!(f64, TypedArrayType::Float64, "Float64Array");
+#[cfg(feature = "napi6")]
+impl_typed_array_slice!(i64, TypedArrayType::BigInt64, "BigInt64Array");
+#[cfg(feature = "napi6")]
+impl_typed_array_slice!(u64, TypedArrayType::BigUint64, "BigUint64Array");
+
+/// Return bytes as a plain `Uint8Array`, e.g. `Uint8Array::from(vec)`, for runtimes without `Buffer` like Deno and browsers.
+///
+/// Unlike [`Buffer`](crate::bindgen_prelude::Buffer), which is a Node.js `Buffer` created with `napi_create_external_buffer`,
+/// the `Uint8Array` is created with `napi_create_typedarray` over an `ArrayBuffer` that takes ownership of the `Vec`.
+/// The bytes are copied into an `ArrayBuffer` allocated by the VM instead where external buffers are not allowed,
+/// e.g. in Electron with the V8 memory cage.
+/// Either way `Buffer` methods like `toString('hex')` are not available on it.
 impl<T: Into<Vec<u8>>> From<T> for Uint8Array {
   fn from(data: T) -> Self {
     Uint8Array::new(data.into())
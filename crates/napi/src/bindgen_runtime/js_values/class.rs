@@ -1,13 +1,26 @@
 use std::any::type_name;
+use std::cell::Cell;
+use std::ffi::c_void;
 use std::ops::{Deref, DerefMut};
 use std::ptr;
+use std::rc::Rc;
 
 use super::Object;
 use crate::{
-  bindgen_runtime::{FromNapiValue, TypeName, ValidateNapiValue},
-  check_status, sys, Env, NapiRaw, NapiValue, ValueType,
+  bindgen_runtime::{
+    raw_finalize_unchecked, ClassStorage, FromNapiValue, ObjectFinalize, Reference, TypeName,
+    ValidateNapiValue,
+  },
+  check_status,
+  env_cache::{self, CacheKey},
+  sys, tag_external, Env, Error, NapiRaw, NapiValue, PropertyAttributes, Result, Status,
+  TaggedObject, ValueType,
 };
 
+/// The value of a `#[napi(external_state)]` class instance, its `TypeId` differs from the one of `T`,
+/// so the external can't be read as a `T` by `External<T>`
+struct ExternalState<T>(*mut T);
+
 pub type This<T = Object> = T;
 
 pub struct ClassInstance<T: 'static> {
@@ -45,7 +58,7 @@ where
   }
 }
 
-impl<T: 'static> ValidateNapiValue for ClassInstance<T>
+impl<T: ClassStorage + 'static> ValidateNapiValue for ClassInstance<T>
 where
   &'static T: ValidateNapiValue,
 {
@@ -57,14 +70,18 @@ where
   }
 }
 
-impl<T: 'static> FromNapiValue for ClassInstance<T> {
+impl<T: ClassStorage + 'static> FromNapiValue for ClassInstance<T> {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> crate::Result<Self> {
     let mut value = ptr::null_mut();
-    check_status!(
-      unsafe { sys::napi_unwrap(env, napi_val, &mut value) },
-      "Unwrap value [{}] from class failed",
-      type_name::<T>(),
-    )?;
+    if T::EXTERNAL_STATE {
+      value = unsafe { unwrap_external_state::<T>(env, napi_val) }?.cast();
+    } else {
+      check_status!(
+        unsafe { sys::napi_unwrap(env, napi_val, &mut value) },
+        "Unwrap value [{}] from class failed",
+        type_name::<T>(),
+      )?;
+    }
     let value = unsafe { Box::from_raw(value as *mut T) };
     Ok(Self {
       value: napi_val,
@@ -98,3 +115,120 @@ impl<T: 'static> AsMut<T> for ClassInstance<T> {
     self.inner
   }
 }
+
+#[doc(hidden)]
+/// Attach the value of a `#[napi(external_state)]` class to `instance`, the counterpart of `napi_wrap`.
+///
+/// # Safety
+///
+/// `value` must be created by `Box::into_raw`, it's owned by the external afterwards.
+pub unsafe fn attach_external_state<T: ObjectFinalize + 'static>(
+  env: sys::napi_env,
+  instance: sys::napi_value,
+  value: *mut T,
+  js_name: &str,
+) -> Result<()> {
+  let mut external = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_external(
+        env,
+        Box::into_raw(Box::new(TaggedObject::new(ExternalState(value)))).cast(),
+        Some(finalize_external_state::<T>),
+        ptr::null_mut(),
+        &mut external,
+      )
+    },
+    "Failed to create external state of class `{}`",
+    js_name,
+  )?;
+  unsafe { tag_external(env, external) }?;
+  let descriptor = sys::napi_property_descriptor {
+    utf8name: ptr::null(),
+    name: unsafe { get_external_state_symbol(env) }?,
+    method: None,
+    getter: None,
+    setter: None,
+    value: external,
+    attributes: PropertyAttributes::Enumerable.into(),
+    data: ptr::null_mut(),
+  };
+  check_status!(
+    unsafe { sys::napi_define_properties(env, instance, 1, &descriptor) },
+    "Failed to initialize class `{}`",
+    js_name,
+  )?;
+  // A weak reference like the one `napi_wrap` returns, for `Reference<T>`
+  let mut object_ref = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_reference(env, instance, 0, &mut object_ref) },
+    "Failed to create reference of class `{}` instance",
+    js_name,
+  )?;
+  let initial_finalize: Box<dyn FnOnce()> = Box::new(|| {});
+  let finalize_callbacks_ptr = Rc::into_raw(Rc::new(Cell::new(Box::into_raw(initial_finalize))));
+  Reference::<T>::add_ref(
+    env,
+    value.cast(),
+    (value.cast(), object_ref, finalize_callbacks_ptr),
+  );
+  Ok(())
+}
+
+#[doc(hidden)]
+/// Get the value of a `#[napi(external_state)]` class from `object`, an instance or a copy of its properties.
+///
+/// # Safety
+///
+/// `env` and `object` must be valid.
+pub unsafe fn unwrap_external_state<T: 'static>(
+  env: sys::napi_env,
+  object: sys::napi_value,
+) -> Result<*mut T> {
+  let key = unsafe { get_external_state_symbol(env) }?;
+  let mut external = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_property(env, object, key, &mut external) },
+    "Failed to recover `{}` type from napi value",
+    type_name::<T>(),
+  )?;
+  let mut value_type = 0;
+  check_status!(unsafe { sys::napi_typeof(env, external, &mut value_type) })?;
+  if value_type != sys::ValueType::napi_external {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "Value has no external state of `{}`, it's not an instance or a copy of one",
+        type_name::<T>()
+      ),
+    ));
+  }
+  unsafe { crate::env::external_as_mut::<ExternalState<T>>(env, external) }.map(|state| state.0)
+}
+
+unsafe extern "C" fn finalize_external_state<T: ObjectFinalize + 'static>(
+  env: sys::napi_env,
+  finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  let mut state: Box<TaggedObject<ExternalState<T>>> =
+    unsafe { Box::from_raw(finalize_data.cast()) };
+  if let Some(ExternalState(value)) = state.object.take() {
+    unsafe { raw_finalize_unchecked::<T>(env, value.cast(), finalize_hint) };
+  }
+}
+
+unsafe fn get_external_state_symbol(env: sys::napi_env) -> Result<sys::napi_value> {
+  // the symbol the value of a `#[napi(external_state)]` class is keyed by, one for each env
+  let key = CacheKey::Named("napi-rs external state");
+  if let Some(symbol) = env_cache::get(env, &key)? {
+    return Ok(symbol);
+  }
+  let symbol = unsafe {
+    Env::from_raw(env)
+      .create_symbol(Some("napi-rs external state"))?
+      .raw()
+  };
+  env_cache::insert(env, key, symbol)?;
+  Ok(symbol)
+}
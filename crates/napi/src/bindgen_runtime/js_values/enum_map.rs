@@ -0,0 +1,186 @@
+use std::ffi::CString;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::ptr;
+
+use crate::bindgen_prelude::{
+  FromNapiValue, Result, ToNapiValue, TypeName, ValidateNapiValue, ValueType,
+};
+use crate::{check_status, sys, Error};
+
+/// A fieldless enum whose variants are the keys of a JavaScript object, see [`EnumMap`].
+///
+/// It's implemented for every `#[napi]` enum, the key of a variant is its value in JavaScript:
+/// the string of a `string_enum` or the number of a numeric enum.
+pub trait EnumKey: Sized + 'static {
+  /// All the variants, in declaration order
+  const VARIANTS: &'static [Self];
+
+  /// The JavaScript object key of the variant
+  fn key(&self) -> &'static str;
+
+  /// The position of the variant in [`EnumKey::VARIANTS`]
+  fn index(&self) -> usize;
+}
+
+/// A map with a value for every variant of the enum `K`, e.g. counts per category.
+///
+/// It's an object with one property per variant in JavaScript, `Record<K, V>` in TypeScript.
+/// Converting from JavaScript reads every key, a missing key is `undefined` which only converts to an `Option`.
+///
+/// ```rust,ignore
+/// #[napi(string_enum)]
+/// pub enum Category {
+///   Fruit,
+///   Vegetable,
+/// }
+///
+/// #[napi]
+/// fn count_categories(items: Vec<Category>) -> EnumMap<Category, u32> {
+///   let mut counts = EnumMap::default();
+///   for item in items {
+///     counts[item] += 1;
+///   }
+///   counts
+/// }
+/// ```
+pub struct EnumMap<K, V> {
+  values: Vec<V>,
+  _key: PhantomData<fn() -> K>,
+}
+
+impl<K: EnumKey, V> EnumMap<K, V> {
+  /// Create the map with the value `f` returns for every variant
+  pub fn from_fn<F: FnMut(&K) -> V>(f: F) -> Self {
+    Self {
+      values: K::VARIANTS.iter().map(f).collect(),
+      _key: PhantomData,
+    }
+  }
+
+  pub fn get(&self, key: &K) -> &V {
+    &self.values[key.index()]
+  }
+
+  pub fn get_mut(&mut self, key: &K) -> &mut V {
+    &mut self.values[key.index()]
+  }
+
+  /// Replace the value of `key`, returning the previous one
+  pub fn insert(&mut self, key: K, value: V) -> V {
+    std::mem::replace(self.get_mut(&key), value)
+  }
+
+  /// Iterate the entries in the declaration order of the variants
+  pub fn iter(&self) -> impl Iterator<Item = (&'static K, &V)> {
+    K::VARIANTS.iter().zip(self.values.iter())
+  }
+}
+
+impl<K: EnumKey, V: Default> Default for EnumMap<K, V> {
+  fn default() -> Self {
+    Self::from_fn(|_| V::default())
+  }
+}
+
+impl<K: EnumKey, V> Index<K> for EnumMap<K, V> {
+  type Output = V;
+
+  fn index(&self, key: K) -> &V {
+    self.get(&key)
+  }
+}
+
+impl<K: EnumKey, V> IndexMut<K> for EnumMap<K, V> {
+  fn index_mut(&mut self, key: K) -> &mut V {
+    self.get_mut(&key)
+  }
+}
+
+impl<K, V: Clone> Clone for EnumMap<K, V> {
+  fn clone(&self) -> Self {
+    Self {
+      values: self.values.clone(),
+      _key: PhantomData,
+    }
+  }
+}
+
+impl<K, V: PartialEq> PartialEq for EnumMap<K, V> {
+  fn eq(&self, other: &Self) -> bool {
+    self.values == other.values
+  }
+}
+
+impl<K: EnumKey, V: fmt::Debug> fmt::Debug for EnumMap<K, V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map()
+      .entries(self.iter().map(|(k, v)| (k.key(), v)))
+      .finish()
+  }
+}
+
+impl<K, V> TypeName for EnumMap<K, V> {
+  fn type_name() -> &'static str {
+    "EnumMap"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<K: EnumKey, V: FromNapiValue> ValidateNapiValue for EnumMap<K, V> {}
+
+impl<K: EnumKey, V: ToNapiValue> ToNapiValue for EnumMap<K, V> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut obj = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(env, &mut obj) },
+      "Failed to create object for `EnumMap`"
+    )?;
+    for (key, value) in K::VARIANTS.iter().zip(val.values) {
+      let key = key.key();
+      let c_key = CString::new(key)?;
+      let value = unsafe { V::to_napi_value(env, value)? };
+      check_status!(
+        unsafe { sys::napi_set_named_property(env, obj, c_key.as_ptr(), value) },
+        "Failed to set property `{}` of `EnumMap`",
+        key
+      )?;
+    }
+    Ok(obj)
+  }
+}
+
+impl<K: EnumKey, V: FromNapiValue> FromNapiValue for EnumMap<K, V> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let values = K::VARIANTS
+      .iter()
+      .map(|key| {
+        let key = key.key();
+        let c_key = CString::new(key)?;
+        let mut value = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_get_named_property(env, napi_val, c_key.as_ptr(), &mut value) },
+          "Failed to get property `{}` of `EnumMap`",
+          key
+        )?;
+        unsafe { V::from_napi_value(env, value) }.map_err(|e| {
+          Error::new(
+            e.status,
+            format!(
+              "Failed to convert property `{}` of `EnumMap`. {}",
+              key, e.reason
+            ),
+          )
+        })
+      })
+      .collect::<Result<Vec<_>>>()?;
+    Ok(Self {
+      values,
+      _key: PhantomData,
+    })
+  }
+}
@@ -4,7 +4,9 @@ use std::{
 };
 
 use super::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
-use crate::{check_status, sys, Error, Status, TaggedObject};
+use crate::{
+  check_status, get_tagged_object_from_external, sys, tag_external, Error, Status, TaggedObject,
+};
 
 pub struct External<T: 'static> {
   obj: *mut TaggedObject<T>,
@@ -58,11 +60,8 @@ impl<T: 'static> External<T> {
 
 impl<T: 'static> FromNapiValue for External<T> {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> crate::Result<Self> {
-    let mut unknown_tagged_object = std::ptr::null_mut();
-    check_status!(
-      unsafe { sys::napi_get_value_external(env, napi_val, &mut unknown_tagged_object) },
-      "Failed to get external value"
-    )?;
+    let unknown_tagged_object =
+      unsafe { get_tagged_object_from_external(env, napi_val, std::any::type_name::<T>()) }?;
 
     let type_id = unknown_tagged_object as *const TypeId;
     if unsafe { *type_id } == TypeId::of::<T>() {
@@ -122,6 +121,7 @@ impl<T: 'static> ToNapiValue for External<T> {
       },
       "Create external value failed"
     )?;
+    unsafe { tag_external(env, napi_value) }?;
 
     let mut adjusted_external_memory_size = std::mem::MaybeUninit::new(0);
 
@@ -1,9 +1,14 @@
 use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use super::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use crate::bindgen_runtime::env_alive_flag;
 
 pub use crate::JsFunction;
-use crate::{check_pending_exception, check_status, sys, Env, NapiRaw, Result, ValueType};
+use crate::{
+  check_pending_exception, check_status, sys, Env, Error, NapiRaw, Result, Status, ValueType,
+};
 
 impl ValidateNapiValue for JsFunction {}
 
@@ -124,6 +129,7 @@ impl<'scope, Args: JsValuesTupleIntoVec, Return: FromNapiValue> Function<'scope,
     Ok(FunctionRef {
       inner: reference,
       env: self.env,
+      env_alive: env_alive_flag(self.env),
       _args: std::marker::PhantomData,
       _return: std::marker::PhantomData,
     })
@@ -131,10 +137,20 @@ impl<'scope, Args: JsValuesTupleIntoVec, Return: FromNapiValue> Function<'scope,
 }
 
 /// A reference to a JavaScript function.
-/// It can be used to outlive the scope of the function.
+/// It can be used to outlive the scope of the function, e.g. to keep an event handler passed to a `#[napi]` function.
+///
+/// The reference is bound to the JavaScript thread of the env it was created in:
+/// it must only be called and dropped on that thread, while the env is still alive.
+/// Dropping it deletes the reference, so the function can be garbage collected again.
+/// Use a `ThreadsafeFunction` to call a JavaScript function from other threads.
+///
+/// Once the env is torn down, e.g. when the worker it belongs to exits, `borrow_back` and the calls return
+/// a `Closing` error instead of touching the freed reference, and dropping it doesn't delete the reference.
+/// This only guards against calls after the teardown: a call racing with it from another thread is still unsound.
 pub struct FunctionRef<Args: JsValuesTupleIntoVec, Return: FromNapiValue> {
   pub(crate) inner: sys::napi_ref,
   pub(crate) env: sys::napi_env,
+  env_alive: Arc<AtomicBool>,
   _args: std::marker::PhantomData<Args>,
   _return: std::marker::PhantomData<Return>,
 }
@@ -143,6 +159,12 @@ unsafe impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> Sync for Function
 
 impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> FunctionRef<Args, Return> {
   pub fn borrow_back<'scope>(&self, env: &'scope Env) -> Result<Function<'scope, Args, Return>> {
+    if !self.env_alive.load(Ordering::Acquire) {
+      return Err(Error::new(
+        Status::Closing,
+        "The env of the FunctionRef has been torn down".to_owned(),
+      ));
+    }
     let mut value = ptr::null_mut();
     check_status!(
       unsafe { sys::napi_get_reference_value(env.0, self.inner, &mut value) },
@@ -156,10 +178,45 @@ impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> FunctionRef<Args, Return
       _scope: std::marker::PhantomData,
     })
   }
+
+  /// Call the referenced JavaScript function with `this` set to `undefined`.
+  ///
+  /// Shorthand for `borrow_back(env)?.call(args)`.
+  pub fn call(&self, env: &Env, args: Args) -> Result<Return> {
+    self.borrow_back(env)?.call(args)
+  }
+
+  /// Call the referenced JavaScript function with the provided `this`.
+  pub fn apply<Context: ToNapiValue>(
+    &self,
+    env: &Env,
+    this: Context,
+    args: Args,
+  ) -> Result<Return> {
+    self.borrow_back(env)?.apply(this, args)
+  }
+
+  /// Call the referenced JavaScript function once for every item of `arg_sets`, e.g. to replay queued events.
+  ///
+  /// The reference is resolved only once and all calls run in the current handle scope.
+  /// Stops at the first call that throws and returns its error; the results of the earlier calls are dropped.
+  pub fn call_many<I>(&self, env: &Env, arg_sets: I) -> Result<Vec<Return>>
+  where
+    I: IntoIterator<Item = Args>,
+  {
+    let function = self.borrow_back(env)?;
+    arg_sets
+      .into_iter()
+      .map(|args| function.call(args))
+      .collect()
+  }
 }
 
 impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> Drop for FunctionRef<Args, Return> {
   fn drop(&mut self) {
+    if !self.env_alive.load(Ordering::Acquire) {
+      return;
+    }
     let status = unsafe { sys::napi_delete_reference(self.env, self.inner) };
     debug_assert_eq!(status, sys::Status::napi_ok, "Drop FunctionRef failed");
   }
@@ -187,6 +244,7 @@ impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> FromNapiValue
     Ok(FunctionRef {
       inner: reference,
       env,
+      env_alive: env_alive_flag(env),
       _args: std::marker::PhantomData,
       _return: std::marker::PhantomData,
     })
@@ -198,6 +256,105 @@ impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> ValidateNapiValue
 {
 }
 
+/// A Node.js style error-first callback, `(err: Error | null, result: T) => void` in TypeScript.
+///
+/// It's for interop with callback based Node.js code, it's called with `(null, result)` on success
+/// and with `(err)` on failure. Like [`Function`] it can only be called in the scope of the function call,
+/// use a `ThreadsafeFunction` with the default `CalleeHandled` error strategy to call back later or from other threads,
+/// it passes the arguments the same way.
+///
+/// ```rust,ignore
+/// #[napi]
+/// fn read_config(path: String, callback: NodeCallback<String>) -> Result<()> {
+///   callback.call_result(std::fs::read_to_string(path).map_err(Error::from))
+/// }
+/// ```
+pub struct NodeCallback<'scope, T: ToNapiValue> {
+  env: sys::napi_env,
+  value: sys::napi_value,
+  _result: std::marker::PhantomData<T>,
+  _scope: std::marker::PhantomData<&'scope ()>,
+}
+
+impl<'scope, T: ToNapiValue> TypeName for NodeCallback<'scope, T> {
+  fn type_name() -> &'static str {
+    "NodeCallback"
+  }
+
+  fn value_type() -> crate::ValueType {
+    ValueType::Function
+  }
+}
+
+impl<'scope, T: ToNapiValue> NapiRaw for NodeCallback<'scope, T> {
+  unsafe fn raw(&self) -> sys::napi_value {
+    self.value
+  }
+}
+
+impl<'scope, T: ToNapiValue> FromNapiValue for NodeCallback<'scope, T> {
+  unsafe fn from_napi_value(env: sys::napi_env, value: sys::napi_value) -> Result<Self> {
+    Ok(NodeCallback {
+      env,
+      value,
+      _result: std::marker::PhantomData,
+      _scope: std::marker::PhantomData,
+    })
+  }
+}
+
+impl<'scope, T: ToNapiValue> ValidateNapiValue for NodeCallback<'scope, T> {}
+
+impl<'scope, T: ToNapiValue> NodeCallback<'scope, T> {
+  /// Call back with `(null, result)`
+  pub fn call_ok(&self, result: T) -> Result<()> {
+    let mut null = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_null(self.env, &mut null) },
+      "Get null value failed"
+    )?;
+    let result = unsafe { T::to_napi_value(self.env, result) }?;
+    self.call_raw(&[null, result])
+  }
+
+  /// Call back with `(err)`, `err` is converted to a JavaScript `Error`
+  pub fn call_err(&self, err: Error) -> Result<()> {
+    let err = unsafe { crate::JsError::from(err).into_value(self.env) };
+    self.call_raw(&[err])
+  }
+
+  /// Call back with the error or the result of `result`
+  pub fn call_result(&self, result: Result<T>) -> Result<()> {
+    match result {
+      Ok(result) => self.call_ok(result),
+      Err(err) => self.call_err(err),
+    }
+  }
+
+  fn call_raw(&self, args: &[sys::napi_value]) -> Result<()> {
+    let mut raw_this = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_undefined(self.env, &mut raw_this) },
+      "Get undefined value failed"
+    )?;
+    let mut raw_return = ptr::null_mut();
+    check_pending_exception!(
+      self.env,
+      unsafe {
+        sys::napi_call_function(
+          self.env,
+          raw_this,
+          self.value,
+          args.len(),
+          args.as_ptr(),
+          &mut raw_return,
+        )
+      },
+      "Call NodeCallback failed"
+    )
+  }
+}
+
 macro_rules! impl_call_apply {
   ($fn_call_name:ident, $fn_apply_name:ident, $($ident:ident),*) => {
     #[allow(non_snake_case, clippy::too_many_arguments)]
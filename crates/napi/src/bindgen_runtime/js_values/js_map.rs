@@ -0,0 +1,217 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+
+use crate::bindgen_prelude::{
+  FromNapiValue, Result, ToNapiValue, TypeName, ValidateNapiValue, ValueType,
+};
+use crate::{check_status, sys, Error, Status};
+
+/// A `HashMap` converted from and to a JavaScript `Map`, `Map<K, V>` in TypeScript.
+///
+/// Unlike a `HashMap`, which is converted from and to an object with string keys,
+/// the keys can be of any type, e.g. numbers. Converting from JavaScript only accepts a `Map`.
+///
+/// ```rust,ignore
+/// #[napi]
+/// fn count_by_length(words: Vec<String>) -> JsMap<u32, u32> {
+///   let mut counts = JsMap::default();
+///   for word in words {
+///     *counts.entry(word.len() as u32).or_default() += 1;
+///   }
+///   counts
+/// }
+/// ```
+pub struct JsMap<K, V, S = RandomState>(pub HashMap<K, V, S>);
+
+impl<K, V, S> JsMap<K, V, S> {
+  pub fn into_inner(self) -> HashMap<K, V, S> {
+    self.0
+  }
+}
+
+impl<K, V, S: Default> Default for JsMap<K, V, S> {
+  fn default() -> Self {
+    Self(HashMap::default())
+  }
+}
+
+impl<K, V, S> From<HashMap<K, V, S>> for JsMap<K, V, S> {
+  fn from(map: HashMap<K, V, S>) -> Self {
+    Self(map)
+  }
+}
+
+impl<K, V, S> Deref for JsMap<K, V, S> {
+  type Target = HashMap<K, V, S>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<K, V, S> DerefMut for JsMap<K, V, S> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for JsMap<K, V, S> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.0.iter()).finish()
+  }
+}
+
+impl<K, V, S> TypeName for JsMap<K, V, S> {
+  fn type_name() -> &'static str {
+    "Map"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<K, V, S> ValidateNapiValue for JsMap<K, V, S>
+where
+  K: FromNapiValue + Eq + Hash,
+  V: FromNapiValue,
+  S: Default + BuildHasher,
+{
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    if !unsafe { is_map(env, napi_val) }? {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Value is not a Map".to_owned(),
+      ));
+    }
+    Ok(ptr::null_mut())
+  }
+}
+
+impl<K, V, S> ToNapiValue for JsMap<K, V, S>
+where
+  K: ToNapiValue,
+  V: ToNapiValue,
+{
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let map_constructor = unsafe { get_map_constructor(env) }?;
+    let mut map = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_new_instance(env, map_constructor, 0, ptr::null(), &mut map) },
+      "Failed to create Map"
+    )?;
+    let set = unsafe { get_named_property(env, map, "set\0") }?;
+    for (key, value) in val.0 {
+      let args = [unsafe { K::to_napi_value(env, key) }?, unsafe {
+        V::to_napi_value(env, value)
+      }?];
+      let mut ret = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_call_function(env, map, set, 2, args.as_ptr(), &mut ret) },
+        "Failed to set entry of Map"
+      )?;
+    }
+    Ok(map)
+  }
+}
+
+impl<K, V, S> FromNapiValue for JsMap<K, V, S>
+where
+  K: FromNapiValue + Eq + Hash,
+  V: FromNapiValue,
+  S: Default + BuildHasher,
+{
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    if !unsafe { is_map(env, napi_val) }? {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Value is not a Map".to_owned(),
+      ));
+    }
+    let size = unsafe { get_named_property(env, napi_val, "size\0") }?;
+    let size = unsafe { u32::from_napi_value(env, size) }?;
+    let mut map = HashMap::with_capacity_and_hasher(size as usize, S::default());
+
+    let entries = unsafe { get_named_property(env, napi_val, "entries\0") }?;
+    let mut iterator = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_call_function(env, napi_val, entries, 0, ptr::null(), &mut iterator) },
+      "Failed to get entries of Map"
+    )?;
+    let next = unsafe { get_named_property(env, iterator, "next\0") }?;
+    loop {
+      let mut step = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_call_function(env, iterator, next, 0, ptr::null(), &mut step) },
+        "Failed to get next entry of Map"
+      )?;
+      let done = unsafe { get_named_property(env, step, "done\0") }?;
+      if unsafe { bool::from_napi_value(env, done) }? {
+        break;
+      }
+      let entry = unsafe { get_named_property(env, step, "value\0") }?;
+      let mut key = ptr::null_mut();
+      let mut value = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_element(env, entry, 0, &mut key) },
+        "Failed to get key of Map entry"
+      )?;
+      check_status!(
+        unsafe { sys::napi_get_element(env, entry, 1, &mut value) },
+        "Failed to get value of Map entry"
+      )?;
+      let key = unsafe { K::from_napi_value(env, key) }.map_err(|e| {
+        Error::new(
+          e.status,
+          format!("Failed to convert key of Map. {}", e.reason),
+        )
+      })?;
+      let value = unsafe { V::from_napi_value(env, value) }.map_err(|e| {
+        Error::new(
+          e.status,
+          format!("Failed to convert value of Map. {}", e.reason),
+        )
+      })?;
+      map.insert(key, value);
+    }
+    Ok(Self(map))
+  }
+}
+
+unsafe fn get_map_constructor(env: sys::napi_env) -> Result<sys::napi_value> {
+  let mut global = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_global(env, &mut global) },
+    "Failed to get global object"
+  )?;
+  unsafe { get_named_property(env, global, "Map\0") }
+}
+
+unsafe fn is_map(env: sys::napi_env, value: sys::napi_value) -> Result<bool> {
+  let map_constructor = unsafe { get_map_constructor(env) }?;
+  let mut is_map = false;
+  check_status!(
+    unsafe { sys::napi_instanceof(env, value, map_constructor, &mut is_map) },
+    "Failed to check whether the value is a Map"
+  )?;
+  Ok(is_map)
+}
+
+unsafe fn get_named_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  // nul terminated
+  name: &str,
+) -> Result<sys::napi_value> {
+  let mut value = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, object, name.as_ptr().cast(), &mut value) },
+    "Failed to get property `{}` for Map",
+    name.trim_end_matches('\0')
+  )?;
+  Ok(value)
+}
@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+
+use super::{FromNapiValue, ToNapiValue, TypeName, Unknown, ValidateNapiValue};
+use crate::{sys, Error, JsObject, NapiValue, Property, Result, Status, ValueType};
+
+/// A field of a `#[napi(object)]` struct which is only computed when it's read from JavaScript.
+///
+/// The field is defined as a getter, which runs the closure on first access and replaces itself with
+/// the value, so later reads don't call into Rust. The TypeScript type is the type of `T`,
+/// the same as a plain field. Reading every field, like `JSON.stringify` or an object spread does,
+/// evaluates all of them.
+///
+/// Not supported in a `#[napi(object, seal)]` struct, a sealed object can't replace its getters.
+///
+/// ```rust
+/// #[napi(object)]
+/// pub struct Config {
+///   pub name: String,
+///   pub plugins: LazyField<Vec<String>>,
+/// }
+///
+/// #[napi]
+/// fn load_config() -> Config {
+///   Config {
+///     name: "app".to_owned(),
+///     plugins: LazyField::new(|| scan_plugins()),
+///   }
+/// }
+/// ```
+pub struct LazyField<T>(Box<dyn FnOnce() -> Result<T>>);
+
+impl<T: 'static> LazyField<T> {
+  pub fn new<F>(f: F) -> Self
+  where
+    F: 'static + FnOnce() -> Result<T>,
+  {
+    Self(Box::new(f))
+  }
+
+  /// Run the closure, or get the value converted from JavaScript
+  pub fn evaluate(self) -> Result<T> {
+    (self.0)()
+  }
+}
+
+impl<T: ToNapiValue + 'static> LazyField<T> {
+  #[doc(hidden)]
+  /// Define `name` as a getter on `obj` which evaluates the field on first access.
+  pub fn define_on(self, obj: &mut JsObject, name: &str) -> Result<()> {
+    let field_name = name.to_owned();
+    let pending = RefCell::new(Some(self));
+    obj.define_properties(&[
+      Property::new(name)?.with_getter_closure(move |env, mut this| {
+        let lazy = pending.borrow_mut().take().ok_or_else(|| {
+          Error::new(
+            Status::GenericFailure,
+            format!(
+              "Lazy field `{}` was already evaluated for another object",
+              field_name
+            ),
+          )
+        })?;
+        let value = unsafe { T::to_napi_value(env.raw(), lazy.evaluate()?) }?;
+        let value = unsafe { Unknown::from_raw_unchecked(env.raw(), value) };
+        this.define_properties(&[Property::new(&field_name)?.with_value(&value)])?;
+        Ok(value)
+      }),
+    ])
+  }
+}
+
+impl<T: TypeName> TypeName for LazyField<T> {
+  fn type_name() -> &'static str {
+    T::type_name()
+  }
+
+  fn value_type() -> ValueType {
+    T::value_type()
+  }
+}
+
+impl<T: ValidateNapiValue + FromNapiValue + 'static> ValidateNapiValue for LazyField<T> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { T::validate(env, napi_val) }
+  }
+}
+
+/// Evaluates the field, a `LazyField` is only lazy as a field of a `#[napi(object)]` struct
+impl<T: ToNapiValue + 'static> ToNapiValue for LazyField<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { T::to_napi_value(env, val.evaluate()?) }
+  }
+}
+
+/// The value is converted eagerly, [`LazyField::evaluate`] returns it
+impl<T: FromNapiValue + 'static> FromNapiValue for LazyField<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let value = unsafe { T::from_napi_value(env, napi_val) }?;
+    Ok(Self::new(move || Ok(value)))
+  }
+}
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use std::hash::{BuildHasher, Hash};
+use std::ptr;
 
-use crate::bindgen_prelude::{Env, Result, ToNapiValue, *};
+use crate::bindgen_prelude::{Result, ToNapiValue, *};
+use crate::check_status;
 
 impl<K, V, S> TypeName for HashMap<K, V, S> {
   fn type_name() -> &'static str {
@@ -15,19 +17,49 @@ impl<K, V, S> TypeName for HashMap<K, V, S> {
 
 impl<K: From<String> + Eq + Hash, V: FromNapiValue> ValidateNapiValue for HashMap<K, V> {}
 
+/// Converted to a plain object with a property for each entry.
+///
+/// The keys are created as internalized strings, which the engine looks up in its string table:
+/// a key repeated within a map or across calls is the same string as long as it's alive.
+/// napi-rs keeps no cache of key strings of its own, it would cost a reference lookup per key
+/// and keep the strings alive with the env.
 impl<K, V, S> ToNapiValue for HashMap<K, V, S>
 where
   K: AsRef<str>,
   V: ToNapiValue,
 {
   unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let env = Env::from(raw_env);
-    let mut obj = env.create_object()?;
+    let mut obj = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(raw_env, &mut obj) },
+      "Failed to create object for `HashMap`"
+    )?;
+    // `napi_set_named_property` creates the key as an internalized string. Reuse one nul terminated
+    // buffer for all the keys instead of allocating a `CString` per entry.
+    // Keys with interior nul fall back to `napi_set_property`.
+    let mut key_buf = Vec::new();
     for (k, v) in val.into_iter() {
-      obj.set(k.as_ref(), v)?;
+      let key = k.as_ref();
+      let value = unsafe { V::to_napi_value(raw_env, v)? };
+      if key.as_bytes().contains(&0) {
+        let key = unsafe { <&str>::to_napi_value(raw_env, key)? };
+        check_status!(
+          unsafe { sys::napi_set_property(raw_env, obj, key, value) },
+          "Failed to set property of `HashMap`"
+        )?;
+        continue;
+      }
+      key_buf.clear();
+      key_buf.extend_from_slice(key.as_bytes());
+      key_buf.push(0);
+      check_status!(
+        unsafe { sys::napi_set_named_property(raw_env, obj, key_buf.as_ptr().cast(), value) },
+        "Failed to set property `{}` of `HashMap`",
+        key
+      )?;
     }
 
-    unsafe { Object::to_napi_value(raw_env, obj) }
+    Ok(obj)
   }
 }
 
@@ -1,6 +1,34 @@
 use super::{check_status, sys};
+use crate::bindgen_runtime::strict_numbers::get_strict_integer;
 use crate::{bindgen_prelude::ToNapiValue, type_of, Error, Result};
 
+/// The numbers accepted in strict mode, see [`set_strict_numbers`](crate::bindgen_prelude::set_strict_numbers)
+trait StrictRange {
+  const STRICT_RANGE: Option<(f64, f64)>;
+}
+
+macro_rules! impl_strict_range {
+  ( $( $t:ty ),* ) => {
+    $(
+      impl StrictRange for $t {
+        const STRICT_RANGE: Option<(f64, f64)> = Some((<$t>::MIN as f64, <$t>::MAX as f64));
+      }
+    )*
+  };
+}
+
+impl_strict_range!(u8, i8, u16, i16, u32, i32);
+
+impl StrictRange for i64 {
+  // larger numbers may already be rounded, so only the safe integers are accepted
+  const STRICT_RANGE: Option<(f64, f64)> =
+    Some((-9_007_199_254_740_991.0, 9_007_199_254_740_991.0));
+}
+
+impl StrictRange for f64 {
+  const STRICT_RANGE: Option<(f64, f64)> = None;
+}
+
 macro_rules! impl_number_conversions {
   ( $( ($name:literal, $t:ty as $st:ty, $get:ident, $create:ident) ,)* ) => {
     $(
@@ -33,6 +61,13 @@ macro_rules! impl_number_conversions {
 
       impl $crate::bindgen_prelude::FromNapiValue for $t {
         unsafe fn from_napi_value(env: $crate::sys::napi_env, napi_val: $crate::sys::napi_value) -> Result<Self> {
+          if let Some((min, max)) = <$t as StrictRange>::STRICT_RANGE {
+            if $crate::bindgen_prelude::strict_numbers() {
+              let value = unsafe { get_strict_integer(env, napi_val, $name, min, max)? };
+              return Ok(value as $t);
+            }
+          }
+
           let mut ret = 0 as $st;
 
           check_status!(
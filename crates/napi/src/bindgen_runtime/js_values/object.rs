@@ -90,3 +90,92 @@ impl TypeName for Object {
 }
 
 impl ValidateNapiValue for JsObject {}
+
+#[doc(hidden)]
+/// Fails the build of a `#[napi(frozen)]` const without the `napi8` feature, `napi_object_freeze` is Node-API 8.
+pub const fn require_napi8_for_frozen() {
+  if !cfg!(feature = "napi8") {
+    panic!("#[napi(frozen)] needs the `napi8` feature of napi");
+  }
+}
+
+#[doc(hidden)]
+/// Fails the build of a `#[napi(object, seal)]` struct without the `napi8` feature, `napi_object_seal` is Node-API 8.
+pub const fn require_napi8_for_seal() {
+  if !cfg!(feature = "napi8") {
+    panic!("#[napi(seal)] needs the `napi8` feature of napi");
+  }
+}
+
+#[doc(hidden)]
+/// Seal the object of a `#[napi(object, seal)]` struct.
+pub fn seal_object(obj: &mut JsObject) -> Result<()> {
+  #[cfg(feature = "napi8")]
+  {
+    obj.seal()
+  }
+  #[cfg(not(feature = "napi8"))]
+  {
+    let _ = obj;
+    unreachable!("`require_napi8_for_seal` fails the build without `napi8`")
+  }
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "napi8"))]
+pub unsafe fn deep_freeze(_env: sys::napi_env, _value: sys::napi_value) -> Result<sys::napi_value> {
+  unreachable!("`require_napi8_for_frozen` fails the build without `napi8`")
+}
+
+#[doc(hidden)]
+#[cfg(feature = "napi8")]
+/// Freeze `value` and every object reachable through its own properties, for `#[napi(frozen)]` consts.
+///
+/// The value must not contain cycles, which is always true for converted Rust values and parsed JSON.
+/// Typed arrays with elements can't be frozen, freezing a value containing one returns an error.
+pub unsafe fn deep_freeze(env: sys::napi_env, value: sys::napi_value) -> Result<sys::napi_value> {
+  let value_type = type_of!(env, value)?;
+  if value_type != ValueType::Object && value_type != ValueType::Function {
+    return Ok(value);
+  }
+  check_status!(
+    unsafe { sys::napi_object_freeze(env, value) },
+    "Failed to freeze object"
+  )?;
+  let mut names = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_all_property_names(
+        env,
+        value,
+        sys::KeyCollectionMode::own_only,
+        sys::KeyFilter::all_properties,
+        sys::KeyConversion::numbers_to_strings,
+        &mut names,
+      )
+    },
+    "Failed to get property names of frozen object"
+  )?;
+  let mut len = 0;
+  check_status!(unsafe { sys::napi_get_array_length(env, names, &mut len) })?;
+  for i in 0..len {
+    let mut name = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_element(env, names, i, &mut name) })?;
+    let mut property = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_property(env, value, name, &mut property) })?;
+    unsafe { deep_freeze(env, property)? };
+  }
+  Ok(value)
+}
+
+#[doc(hidden)]
+/// Parse `json` with `JSON.parse`, for `#[napi(json)]` consts.
+pub unsafe fn parse_json(env: sys::napi_env, json: &str) -> Result<sys::napi_value> {
+  let env = unsafe { crate::Env::from_raw(env) };
+  let parse = env
+    .get_global()?
+    .get_named_property_unchecked::<JsObject>("JSON")?
+    .get_named_property_unchecked::<crate::JsFunction>("parse")?;
+  let parsed = parse.call(None, &[env.create_string(json)?])?;
+  Ok(parsed.0.value)
+}
@@ -0,0 +1,105 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use super::{External, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use crate::sys;
+
+/// A reference-counted resource shared between Rust and any number of JavaScript values.
+///
+/// Every JavaScript value it's converted to is an `External` owning one reference to the resource,
+/// which is released when the value is garbage collected.
+/// Converting it back from JavaScript takes another reference, so it can be kept after the call returns.
+/// The resource is dropped once the last reference, in Rust or JavaScript, is gone.
+///
+/// For a handle doing its own ref counting, like a GObject, use a `T` whose `Drop` releases the handle.
+///
+/// ```rust,ignore
+/// #[napi]
+/// fn open_connection(url: String) -> RcHandle<Connection> {
+///   RcHandle::new(Connection::open(url))
+/// }
+///
+/// #[napi]
+/// fn query(conn: RcHandle<Connection>, sql: String) -> Vec<String> {
+///   conn.query(sql)
+/// }
+/// ```
+pub struct RcHandle<T: 'static>(Arc<T>);
+
+impl<T: 'static> RcHandle<T> {
+  pub fn new(value: T) -> Self {
+    Self(Arc::new(value))
+  }
+
+  /// The number of references to the resource, including the ones held by JavaScript values
+  pub fn strong_count(this: &Self) -> usize {
+    Arc::strong_count(&this.0)
+  }
+
+  /// Whether both handles refer to the same resource
+  pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+    Arc::ptr_eq(&this.0, &other.0)
+  }
+
+  pub fn into_arc(this: Self) -> Arc<T> {
+    this.0
+  }
+}
+
+impl<T: 'static> Clone for RcHandle<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+impl<T: 'static> From<Arc<T>> for RcHandle<T> {
+  fn from(value: Arc<T>) -> Self {
+    Self(value)
+  }
+}
+
+impl<T: 'static> From<T> for RcHandle<T> {
+  fn from(value: T) -> Self {
+    Self::new(value)
+  }
+}
+
+impl<T: 'static> Deref for RcHandle<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T: 'static> AsRef<T> for RcHandle<T> {
+  fn as_ref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T: 'static> TypeName for RcHandle<T> {
+  fn type_name() -> &'static str {
+    "RcHandle"
+  }
+
+  fn value_type() -> crate::ValueType {
+    crate::ValueType::External
+  }
+}
+
+impl<T: 'static> ValidateNapiValue for RcHandle<T> {}
+
+impl<T: 'static> ToNapiValue for RcHandle<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> crate::Result<sys::napi_value> {
+    // the external owns the reference moved into it, it's released by the finalizer
+    unsafe { External::to_napi_value(env, External::new(val.0)) }
+  }
+}
+
+impl<T: 'static> FromNapiValue for RcHandle<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> crate::Result<Self> {
+    let external = unsafe { External::<Arc<T>>::from_napi_value(env, napi_val)? };
+    Ok(Self(Arc::clone(&external)))
+  }
+}
@@ -0,0 +1,158 @@
+use super::{check_status, sys};
+use crate::{
+  bindgen_prelude::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue},
+  type_of, Error, Result, Status, ValueType,
+};
+
+/// Largest scaled integer whose `value / SCALE` still round trips through a `f64` for any `SCALE`.
+/// Above it, the `f64` spacing gets close to `1 / SCALE` and neighbouring values may collapse.
+const MAX_EXACT: i64 = 1 << 52;
+
+/// How [`Scaled::from_f64`] rounds a value that is not a multiple of `1 / SCALE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+  /// Round to the nearest value, ties away from zero: `0.125` is `0.13` and `-0.125` is `-0.13` with a scale of `100`
+  #[default]
+  HalfAwayFromZero,
+  /// Round to the nearest value, ties to the even one: `0.125` is `0.12` and `0.135` is `0.14` with a scale of `100`
+  HalfEven,
+  /// Round towards negative infinity
+  Floor,
+  /// Round towards positive infinity
+  Ceil,
+  /// Drop the digits beyond the scale
+  TowardZero,
+}
+
+/// A fixed-point number stored as an integer scaled by `SCALE`, e.g. an amount of cents with `Scaled<100>`.
+///
+/// It's passed to and from JavaScript as a `number`, `Scaled::<100>(1234)` is `12.34` in JavaScript,
+/// so the arithmetic on the Rust side stays exact.
+///
+/// A `number` is converted with [`Rounding::HalfAwayFromZero`], use [`Scaled::from_f64`] to pick another rounding.
+/// The decimal written in JavaScript is what gets rounded, not its binary approximation:
+/// `1.005` is a tie with a scale of `100` and becomes `101`, although `1.005 * 100` is `100.49999999999999`.
+///
+/// Only the scaled integers up to `2^52` in magnitude round trip exactly through a `number`,
+/// converting a value out of that range in either direction returns an error instead of losing precision.
+/// That's `±45_035_996_273_704.96` with a scale of `100`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Scaled<const SCALE: u32>(pub i64);
+
+impl<const SCALE: u32> Scaled<SCALE> {
+  const FACTOR: f64 = {
+    assert!(SCALE > 0, "SCALE of Scaled must not be 0");
+    SCALE as f64
+  };
+
+  /// Scale `value` and round it with `rounding`.
+  ///
+  /// Returns an error if `value` is not finite or out of the exactly representable range.
+  pub fn from_f64(value: f64, rounding: Rounding) -> Result<Self> {
+    if !value.is_finite() {
+      return Err(Error::new(
+        Status::NumberExpected,
+        format!("Can not convert {} to Scaled<{}>", value, SCALE),
+      ));
+    }
+    let scaled = value * Self::FACTOR;
+    if scaled.abs() > MAX_EXACT as f64 {
+      return Err(Self::out_of_range(value));
+    }
+    let mut low = scaled.floor() as i64;
+    // `value * SCALE` is rounded, so it may be on the wrong side of an integer
+    if Self::unscale(low) > value {
+      low -= 1;
+    } else if Self::unscale(low + 1) <= value {
+      low += 1;
+    }
+    let high = low + 1;
+    let result = if Self::unscale(low) == value {
+      low
+    } else {
+      // the nearest `f64` of the middle, so a tie written in JavaScript compares equal
+      let middle = (2 * low + 1) as f64 / (2.0 * Self::FACTOR);
+      match rounding {
+        Rounding::Floor => low,
+        Rounding::Ceil => high,
+        Rounding::TowardZero if value < 0.0 => high,
+        Rounding::TowardZero => low,
+        _ if value < middle => low,
+        _ if value > middle => high,
+        Rounding::HalfAwayFromZero if value < 0.0 => low,
+        Rounding::HalfAwayFromZero => high,
+        _ if low % 2 == 0 => low,
+        _ => high,
+      }
+    };
+    if result.abs() > MAX_EXACT {
+      return Err(Self::out_of_range(value));
+    }
+    Ok(Self(result))
+  }
+
+  /// The value as a `f64`, the nearest `f64` of `self.0 / SCALE`.
+  ///
+  /// Returns an error if `self.0` is out of the exactly representable range.
+  pub fn to_f64(self) -> Result<f64> {
+    if self.0.abs() > MAX_EXACT {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Scaled<{}>({}) is out of the exactly representable range",
+          SCALE, self.0
+        ),
+      ));
+    }
+    Ok(Self::unscale(self.0))
+  }
+
+  fn unscale(value: i64) -> f64 {
+    value as f64 / Self::FACTOR
+  }
+
+  fn out_of_range(value: f64) -> Error {
+    Error::new(
+      Status::InvalidArg,
+      format!(
+        "{} scaled by {} is out of the exactly representable range",
+        value, SCALE
+      ),
+    )
+  }
+}
+
+impl<const SCALE: u32> TypeName for Scaled<SCALE> {
+  fn type_name() -> &'static str {
+    "Scaled"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Number
+  }
+}
+
+impl<const SCALE: u32> ValidateNapiValue for Scaled<SCALE> {}
+
+impl<const SCALE: u32> ToNapiValue for Scaled<SCALE> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut ptr = std::ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_double(env, val.to_f64()?, &mut ptr) },
+      "Failed to convert rust type `Scaled` into napi value",
+    )?;
+    Ok(ptr)
+  }
+}
+
+impl<const SCALE: u32> FromNapiValue for Scaled<SCALE> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut value = 0f64;
+    check_status!(
+      unsafe { sys::napi_get_value_double(env, napi_val, &mut value) },
+      "Failed to convert napi value {:?} into rust type `Scaled`",
+      type_of!(env, napi_val)?,
+    )?;
+    Self::from_f64(value, Rounding::default())
+  }
+}
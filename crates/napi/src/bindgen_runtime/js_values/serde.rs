@@ -1,13 +1,17 @@
 use serde_json::{Map, Number, Value};
 
 use crate::{
-  bindgen_runtime::Null, check_status, sys, type_of, Error, JsObject, Result, Status, ValueType,
+  bindgen_runtime::{NestingGuard, Null},
+  check_status, sys, type_of, Error, JsObject, JsUnknown, NapiRaw, Result, Status, ValueType,
 };
 
 #[cfg(feature = "napi6")]
 use super::BigInt;
 use super::{FromNapiValue, Object, ToNapiValue};
 
+/// `Number.MAX_SAFE_INTEGER`, larger integers are converted into `BigInt`s with the `napi6` feature
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
 impl ToNapiValue for Value {
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
     match val {
@@ -29,6 +33,7 @@ impl FromNapiValue for Value {
       ValueType::Number => Value::Number(unsafe { Number::from_napi_value(env, napi_val)? }),
       ValueType::String => Value::String(unsafe { String::from_napi_value(env, napi_val)? }),
       ValueType::Object => {
+        let _nesting_guard = NestingGuard::enter()?;
         let mut is_arr = false;
         check_status!(
           unsafe { sys::napi_is_array(env, napi_val, &mut is_arr) },
@@ -42,7 +47,9 @@ impl FromNapiValue for Value {
         }
       }
       #[cfg(feature = "napi6")]
-      ValueType::BigInt => todo!(),
+      ValueType::BigInt => Value::Number(bigint_to_number(unsafe {
+        BigInt::from_napi_value(env, napi_val)?
+      })?),
       ValueType::Null => Value::Null,
       ValueType::Function => {
         return Err(Error::new(
@@ -102,8 +109,10 @@ impl FromNapiValue for Map<String, Value> {
 
     let mut map = Map::new();
     for key in Object::keys(&obj)?.into_iter() {
-      if let Some(val) = obj.get(&key)? {
-        map.insert(key, val);
+      let val = obj.get_named_property_unchecked::<JsUnknown>(&key)?;
+      // skipped like `JSON.stringify` does, but `null` is kept
+      if val.get_type()? != ValueType::Undefined {
+        map.insert(key, unsafe { Value::from_napi_value(env, val.raw())? });
       }
     }
 
@@ -113,13 +122,12 @@ impl FromNapiValue for Map<String, Value> {
 
 impl ToNapiValue for Number {
   unsafe fn to_napi_value(env: sys::napi_env, n: Self) -> Result<sys::napi_value> {
-    #[cfg(feature = "napi6")]
-    const MAX_SAFE_INT: i64 = 9007199254740991i64; // 2 ^ 53 - 1
     if n.is_i64() {
       let n = n.as_i64().unwrap();
       #[cfg(feature = "napi6")]
       {
-        if !(-MAX_SAFE_INT..=MAX_SAFE_INT).contains(&n) {
+        let max_safe_integer = MAX_SAFE_INTEGER as i64;
+        if !(-max_safe_integer..=max_safe_integer).contains(&n) {
           return unsafe { BigInt::to_napi_value(env, BigInt::from(n)) };
         }
       }
@@ -129,16 +137,18 @@ impl ToNapiValue for Number {
       unsafe { f64::to_napi_value(env, n.as_f64().unwrap()) }
     } else {
       let n = n.as_u64().unwrap();
-      if n > u32::MAX as u64 {
+      if n <= MAX_SAFE_INTEGER {
+        unsafe { i64::to_napi_value(env, n as i64) }
+      } else {
         #[cfg(feature = "napi6")]
         {
-          return unsafe { BigInt::to_napi_value(env, BigInt::from(n)) };
+          unsafe { BigInt::to_napi_value(env, BigInt::from(n)) }
         }
 
         #[cfg(not(feature = "napi6"))]
-        return unsafe { String::to_napi_value(env, n.to_string()) };
-      } else {
-        unsafe { u32::to_napi_value(env, n as u32) }
+        {
+          unsafe { String::to_napi_value(env, n.to_string()) }
+        }
       }
     }
   }
@@ -147,16 +157,12 @@ impl ToNapiValue for Number {
 impl FromNapiValue for Number {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
     let n = unsafe { f64::from_napi_value(env, napi_val)? };
-    // Try to auto-convert to integers
-    let n = if n.trunc() == n {
-      if n >= 0.0f64 && n <= u32::MAX as f64 {
-        // This can be represented as u32
-        Some(Number::from(n as u32))
-      } else if n < 0.0f64 && n >= i32::MIN as f64 {
-        Some(Number::from(n as i32))
+    // Keep integers as integers, unless they may already be rounded
+    let n = if n.trunc() == n && n.abs() <= MAX_SAFE_INTEGER as f64 {
+      if n >= 0.0f64 {
+        Some(Number::from(n as u64))
       } else {
-        // must be a float
-        Number::from_f64(n)
+        Some(Number::from(n as i64))
       }
     } else {
       // must be a float
@@ -173,3 +179,20 @@ impl FromNapiValue for Number {
     Ok(n)
   }
 }
+
+#[cfg(feature = "napi6")]
+fn bigint_to_number(bigint: BigInt) -> Result<Number> {
+  let out_of_range = || {
+    Error::new(
+      Status::InvalidArg,
+      "BigInt is out of the range of serde_json::Number".to_owned(),
+    )
+  };
+  match bigint.words.as_slice() {
+    [word] if !bigint.sign_bit => Ok(Number::from(*word)),
+    [word] => i64::try_from(-(*word as i128))
+      .map(Number::from)
+      .map_err(|_| out_of_range()),
+    _ => Err(out_of_range()),
+  }
+}
@@ -1,10 +1,13 @@
 use crate::{bindgen_prelude::*, check_status, check_status_and_type, sys, Error, Result, Status};
 
+use std::borrow::Cow;
 use std::ffi::{c_void, CStr};
 use std::fmt::Display;
 use std::mem;
 use std::ops::Deref;
 use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
 
 impl TypeName for String {
   fn type_name() -> &'static str {
@@ -19,15 +22,9 @@ impl TypeName for String {
 impl ValidateNapiValue for String {}
 
 impl ToNapiValue for &String {
+  #[inline]
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let mut ptr = ptr::null_mut();
-
-    check_status!(
-      unsafe { sys::napi_create_string_utf8(env, val.as_ptr() as *const _, val.len(), &mut ptr) },
-      "Failed to convert rust `String` into napi `string`"
-    )?;
-
-    Ok(ptr)
+    unsafe { ToNapiValue::to_napi_value(env, val.as_str()) }
   }
 }
 
@@ -149,7 +146,65 @@ impl FromNapiValue for &str {
 
 impl ToNapiValue for &str {
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    unsafe { String::to_napi_value(env, val.to_owned()) }
+    let mut ptr = ptr::null_mut();
+
+    check_status!(
+      unsafe { sys::napi_create_string_utf8(env, val.as_ptr() as *const _, val.len(), &mut ptr) },
+      "Failed to convert rust `&str` into napi `string`"
+    )?;
+
+    Ok(ptr)
+  }
+}
+
+impl TypeName for Cow<'_, str> {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ToNapiValue for Cow<'_, str> {
+  #[inline]
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, val.as_ref()) }
+  }
+}
+
+impl TypeName for Rc<str> {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ToNapiValue for Rc<str> {
+  #[inline]
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, &*val) }
+  }
+}
+
+impl TypeName for Arc<str> {
+  fn type_name() -> &'static str {
+    "String"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl ToNapiValue for Arc<str> {
+  #[inline]
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { ToNapiValue::to_napi_value(env, &*val) }
   }
 }
 
@@ -190,34 +245,9 @@ impl TypeName for Utf16String {
 
 impl FromNapiValue for Utf16String {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
-    let mut len = 0;
-
-    check_status!(
-      unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) },
-      "Failed to convert napi `utf16 string` into rust type `String`",
-    )?;
-
-    // end char len in C
-    len += 1;
-    let mut ret = vec![0; len];
-    let mut written_char_count = 0;
-
-    check_status!(
-      unsafe {
-        sys::napi_get_value_string_utf16(
-          env,
-          napi_val,
-          ret.as_mut_ptr(),
-          len,
-          &mut written_char_count,
-        )
-      },
-      "Failed to convert napi `utf16 string` into rust type `String`",
-    )?;
+    let units = unsafe { read_utf16(env, napi_val) }?;
 
-    let (_, ret) = ret.split_last().unwrap_or((&0, &[]));
-
-    match String::from_utf16(ret) {
+    match String::from_utf16(&units) {
       Err(e) => Err(Error::new(
         Status::InvalidArg,
         format!("Failed to read utf16 string, {}", e),
@@ -244,6 +274,118 @@ impl ToNapiValue for Utf16String {
   }
 }
 
+/// The UTF-16 code units of a JavaScript string, as is.
+///
+/// JavaScript strings may contain unpaired surrogates, which `String` replaces with `U+FFFD` and `Utf16String` rejects.
+/// `RawUtf16String` keeps them, so the caller decides how to handle them:
+/// with [`to_utf8`](RawUtf16String::to_utf8), [`to_utf8_lossy`](RawUtf16String::to_utf8_lossy),
+/// or by working on the code units directly.
+/// Converting it back to JavaScript restores the exact same string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawUtf16String(Vec<u16>);
+
+impl RawUtf16String {
+  /// Decode into a `String`, failing with `InvalidArg` on the first unpaired surrogate.
+  pub fn to_utf8(&self) -> Result<String> {
+    String::from_utf16(&self.0).map_err(|e| {
+      Error::new(
+        Status::InvalidArg,
+        format!("Failed to read utf16 string, {}", e),
+      )
+    })
+  }
+
+  /// Decode into a `String`, replacing unpaired surrogates with `U+FFFD`.
+  pub fn to_utf8_lossy(&self) -> String {
+    String::from_utf16_lossy(&self.0)
+  }
+
+  pub fn into_inner(self) -> Vec<u16> {
+    self.0
+  }
+}
+
+impl ValidateNapiValue for RawUtf16String {}
+
+impl From<Vec<u16>> for RawUtf16String {
+  fn from(units: Vec<u16>) -> Self {
+    RawUtf16String(units)
+  }
+}
+
+impl From<&str> for RawUtf16String {
+  fn from(s: &str) -> Self {
+    RawUtf16String(s.encode_utf16().collect())
+  }
+}
+
+impl Deref for RawUtf16String {
+  type Target = [u16];
+
+  fn deref(&self) -> &Self::Target {
+    self.0.as_slice()
+  }
+}
+
+impl TypeName for RawUtf16String {
+  fn type_name() -> &'static str {
+    "String(utf16)"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::String
+  }
+}
+
+impl FromNapiValue for RawUtf16String {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(RawUtf16String(unsafe { read_utf16(env, napi_val) }?))
+  }
+}
+
+impl ToNapiValue for RawUtf16String {
+  unsafe fn to_napi_value(env: sys::napi_env, val: RawUtf16String) -> Result<sys::napi_value> {
+    let mut ptr = ptr::null_mut();
+
+    check_status!(
+      unsafe { sys::napi_create_string_utf16(env, val.0.as_ptr(), val.0.len(), &mut ptr) },
+      "Failed to convert rust type `RawUtf16String` into napi `string`"
+    )?;
+
+    Ok(ptr)
+  }
+}
+
+unsafe fn read_utf16(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Vec<u16>> {
+  let mut len = 0;
+
+  check_status!(
+    unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) },
+    "Failed to convert napi `utf16 string` into rust type `String`",
+  )?;
+
+  // end char len in C
+  let len = crate::js_values::nul_terminated_len::<u16>(len)?;
+  let mut ret = vec![0; len];
+  let mut written_char_count = 0;
+
+  check_status!(
+    unsafe {
+      sys::napi_get_value_string_utf16(
+        env,
+        napi_val,
+        ret.as_mut_ptr(),
+        len,
+        &mut written_char_count,
+      )
+    },
+    "Failed to convert napi `utf16 string` into rust type `String`",
+  )?;
+
+  ret.truncate(written_char_count);
+  Ok(ret)
+}
+
 #[cfg(feature = "latin1")]
 pub mod latin1_string {
   use super::*;
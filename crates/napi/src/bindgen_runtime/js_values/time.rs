@@ -0,0 +1,243 @@
+use std::ptr;
+use std::time::Duration;
+#[cfg(feature = "napi5")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{bindgen_prelude::*, check_status, sys, type_of, ValueType};
+
+/// `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+#[cfg(feature = "napi5")]
+/// The largest distance from the epoch in milliseconds a `Date` can represent
+const MAX_DATE_MILLIS: f64 = 8.64e15;
+
+fn duration_to_millis(duration: Duration) -> f64 {
+  duration.as_secs() as f64 * 1000.0 + f64::from(duration.subsec_nanos()) / 1_000_000.0
+}
+
+fn millis_to_duration(millis: f64) -> Result<Duration> {
+  if !(0.0..=MAX_SAFE_INTEGER).contains(&millis) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "Can not convert {} milliseconds to `Duration`, it must be a non-negative finite number",
+        millis
+      ),
+    ));
+  }
+  let whole = millis.trunc();
+  let nanos = ((millis - whole) * 1_000_000.0).round() as u64;
+  Ok(Duration::from_millis(whole as u64) + Duration::from_nanos(nanos))
+}
+
+/// A `Duration` is a `number` of milliseconds in JavaScript, like the delay of `setTimeout`.
+///
+/// The sub-millisecond part is kept as the fraction, rounded to the precision of a `f64`:
+/// it's exact to the nanosecond up to about 104 days, and to the microsecond up to about 285 years.
+/// Use [`PreciseDuration`] to pass the seconds and nanoseconds separately.
+/// Converting a negative, `NaN` or infinite `number` returns an error, a `Duration` can't be negative.
+impl TypeName for Duration {
+  fn type_name() -> &'static str {
+    "Duration"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Number
+  }
+}
+
+impl ValidateNapiValue for Duration {}
+
+impl ToNapiValue for Duration {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Duration) -> Result<sys::napi_value> {
+    let mut ptr = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_double(env, duration_to_millis(val), &mut ptr) },
+      "Failed to convert rust type `Duration` into napi value",
+    )?;
+    Ok(ptr)
+  }
+}
+
+impl FromNapiValue for Duration {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut millis = 0f64;
+    check_status!(
+      unsafe { sys::napi_get_value_double(env, napi_val, &mut millis) },
+      "Failed to convert napi value {:?} into rust type `Duration`",
+      type_of!(env, napi_val)?,
+    )?;
+    millis_to_duration(millis)
+  }
+}
+
+/// A `Duration` passed as `{ secs: number, nanos: number }`, without the precision loss of a `number` of milliseconds.
+///
+/// `secs` must be a non-negative integer up to `Number.MAX_SAFE_INTEGER` and `nanos` an integer below `1_000_000_000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PreciseDuration(pub Duration);
+
+impl From<Duration> for PreciseDuration {
+  fn from(duration: Duration) -> Self {
+    Self(duration)
+  }
+}
+
+impl From<PreciseDuration> for Duration {
+  fn from(duration: PreciseDuration) -> Self {
+    duration.0
+  }
+}
+
+impl TypeName for PreciseDuration {
+  fn type_name() -> &'static str {
+    "PreciseDuration"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for PreciseDuration {}
+
+impl ToNapiValue for PreciseDuration {
+  unsafe fn to_napi_value(env: sys::napi_env, val: PreciseDuration) -> Result<sys::napi_value> {
+    let secs = val.0.as_secs() as f64;
+    if secs > MAX_SAFE_INTEGER {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Seconds of `PreciseDuration` {} are out of the range of a safe integer",
+          val.0.as_secs()
+        ),
+      ));
+    }
+    let mut ptr = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(env, &mut ptr) },
+      "Failed to create object for `PreciseDuration`"
+    )?;
+    let mut obj = unsafe { Object::from_napi_value(env, ptr)? };
+    obj.set("secs", secs)?;
+    obj.set("nanos", val.0.subsec_nanos())?;
+    Ok(ptr)
+  }
+}
+
+impl FromNapiValue for PreciseDuration {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let obj = unsafe { Object::from_napi_value(env, napi_val)? };
+    let secs: f64 = obj.get("secs")?.unwrap_or(0.0);
+    let nanos: f64 = obj.get("nanos")?.unwrap_or(0.0);
+    if !(0.0..=MAX_SAFE_INTEGER).contains(&secs) || secs.fract() != 0.0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "`secs` of `PreciseDuration` must be a non-negative safe integer, got {}",
+          secs
+        ),
+      ));
+    }
+    if !(0.0..1_000_000_000.0).contains(&nanos) || nanos.fract() != 0.0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "`nanos` of `PreciseDuration` must be an integer in 0..1000000000, got {}",
+          nanos
+        ),
+      ));
+    }
+    Ok(Self(Duration::new(secs as u64, nanos as u32)))
+  }
+}
+
+#[cfg(feature = "napi5")]
+/// A `SystemTime` is a `Date` in JavaScript.
+///
+/// A `Date` only has millisecond precision, the sub-millisecond part of a `SystemTime` is rounded down,
+/// towards the past for times before the epoch as well.
+/// Converting a time out of the range of a `Date`, ±8.64e15 milliseconds from the epoch, or an invalid `Date` returns an error.
+impl TypeName for SystemTime {
+  fn type_name() -> &'static str {
+    "SystemTime"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl ValidateNapiValue for SystemTime {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    let mut is_date = false;
+    check_status!(unsafe { sys::napi_is_date(env, napi_val, &mut is_date) })?;
+    if !is_date {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Expected a Date object".to_owned(),
+      ));
+    }
+
+    Ok(ptr::null_mut())
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl ToNapiValue for SystemTime {
+  unsafe fn to_napi_value(env: sys::napi_env, val: SystemTime) -> Result<sys::napi_value> {
+    let millis = match val.duration_since(UNIX_EPOCH) {
+      Ok(since_epoch) => duration_to_millis(since_epoch).floor(),
+      Err(e) => (-duration_to_millis(e.duration())).floor(),
+    };
+    if millis.abs() > MAX_DATE_MILLIS {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "`SystemTime` {} milliseconds from the epoch is out of the range of a Date",
+          millis
+        ),
+      ));
+    }
+    let mut ptr = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_date(env, millis, &mut ptr) },
+      "Failed to convert rust type `SystemTime` into napi value",
+    )?;
+    Ok(ptr)
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl FromNapiValue for SystemTime {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut millis = 0f64;
+    check_status!(
+      unsafe { sys::napi_get_date_value(env, napi_val, &mut millis) },
+      "Failed to convert napi value {:?} into rust type `SystemTime`",
+      type_of!(env, napi_val)?,
+    )?;
+    if millis.is_nan() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Can not convert an invalid Date to `SystemTime`".to_owned(),
+      ));
+    }
+    let since_epoch = Duration::from_millis(millis.abs() as u64);
+    if millis >= 0.0 {
+      UNIX_EPOCH.checked_add(since_epoch)
+    } else {
+      UNIX_EPOCH.checked_sub(since_epoch)
+    }
+    .ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        format!(
+          "Date {} milliseconds from the epoch is out of the range of `SystemTime`",
+          millis
+        ),
+      )
+    })
+  }
+}
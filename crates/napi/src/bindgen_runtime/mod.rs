@@ -1,22 +1,31 @@
 use std::ffi::c_void;
 use std::rc::Rc;
 
+pub use async_iterator::{AsyncGenerator, GeneratorStep};
 pub use callback_info::*;
 pub use ctor::ctor;
 pub use env::*;
 pub use iterator::Generator;
 pub use js_values::*;
 pub use module_register::*;
+pub use nesting::*;
+pub use strict_numbers::*;
 
 use super::sys;
 use crate::{JsError, Result, Status};
 
+pub mod async_iterator;
 mod callback_info;
 mod env;
 mod error;
 pub mod iterator;
 mod js_values;
 mod module_register;
+mod nesting;
+pub(crate) mod panic;
+pub(crate) mod strict_numbers;
+#[cfg(feature = "web_stream")]
+pub(crate) mod web_stream;
 
 pub trait ObjectFinalize: Sized {
   #[allow(unused)]
@@ -25,6 +34,59 @@ pub trait ObjectFinalize: Sized {
   }
 }
 
+/// Where the Rust value of a class instance is kept, implemented by `#[napi]` for every class.
+/// A type used with `CallbackInfo::construct`, `CallbackInfo::factory`, the `CallbackInfo::unwrap_*` methods
+/// or `ClassInstance` without the macro needs an empty `impl ClassStorage for T {}` to keep using `napi_wrap`.
+///
+/// By default the value is attached to the instance with `napi_wrap`. A `#[napi(external_state)]` class
+/// keeps it in an external instead, stored under an enumerable symbol property of the instance.
+/// Prefer it when instances pass through code which copies their own properties to a new object,
+/// like an object spread or `Object.assign`: the copy carries the external, so it can still be
+/// passed to a method argument of the class type, while a wrap stays with the original object only.
+/// The value is dropped once the instance and all copies are garbage collected.
+///
+/// The property is read on every method call, so `napi_wrap` is faster,
+/// and a method taking `self` by value is not supported on an external state class.
+///
+/// ```rust,ignore
+/// #[napi(external_state)]
+/// pub struct Session {
+///   token: String,
+/// }
+/// ```
+pub trait ClassStorage {
+  /// `true` for a `#[napi(external_state)]` class
+  const EXTERNAL_STATE: bool = false;
+  /// The JavaScript name of a `#[napi]` class, the exports taking or returning it reference the class by it
+  const JS_NAME: Option<&'static str> = None;
+}
+
+#[doc(hidden)]
+/// The type of an argument or the return value of an export, `#[napi]` checks if it's a class with
+/// `(&ClassReferenceProbe::<T>(PhantomData)).class_js_name()`, which resolves to [`ClassReference`]
+/// for a class and to [`NotClassReference`] for every other type.
+pub struct ClassReferenceProbe<T: ?Sized>(pub std::marker::PhantomData<T>);
+
+#[doc(hidden)]
+pub trait ClassReference {
+  fn class_js_name(&self) -> Option<&'static str>;
+}
+
+impl<T: ClassStorage + ?Sized> ClassReference for ClassReferenceProbe<T> {
+  fn class_js_name(&self) -> Option<&'static str> {
+    T::JS_NAME
+  }
+}
+
+#[doc(hidden)]
+pub trait NotClassReference {
+  fn class_js_name(&self) -> Option<&'static str> {
+    None
+  }
+}
+
+impl<T: ?Sized> NotClassReference for &ClassReferenceProbe<T> {}
+
 /// # Safety
 ///
 /// called when node wrapper objects destroyed
@@ -40,8 +102,14 @@ pub unsafe extern "C" fn raw_finalize_unchecked<T: ObjectFinalize>(
     unsafe { e.throw_into(env) };
     return;
   }
+  unsafe { release_reference(env, finalize_data) };
+}
+
+/// Forget the `Reference` information of a wrapped value which is finalized or taken out of its object,
+/// run the finalize callbacks of it and delete the reference to the object.
+pub(crate) unsafe fn release_reference(env: sys::napi_env, wrapped_value: *mut c_void) {
   if let Some((_, ref_val, finalize_callbacks_ptr)) =
-    REFERENCE_MAP.with(|reference_map| reference_map.borrow_mut().remove(&finalize_data))
+    REFERENCE_MAP.with(|reference_map| reference_map.borrow_mut().remove(&wrapped_value))
   {
     let finalize_callbacks_rc = unsafe { Rc::from_raw(finalize_callbacks_ptr) };
 
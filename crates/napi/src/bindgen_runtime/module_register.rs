@@ -1,30 +1,59 @@
+use std::any::Any;
 use std::collections::HashMap;
-#[cfg(not(feature = "noop"))]
-use std::collections::HashSet;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::ptr;
 #[cfg(all(feature = "napi4", not(target_family = "wasm")))]
 use std::sync::atomic::AtomicPtr;
-#[cfg(all(
-  not(any(target_os = "macos", target_family = "wasm")),
-  feature = "napi4",
-  feature = "tokio_rt"
-))]
-use std::sync::atomic::AtomicUsize;
-#[cfg(not(feature = "noop"))]
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use std::thread::ThreadId;
 
 use once_cell::sync::Lazy;
 
-use crate::{check_status, sys, Env, JsFunction, Property, Result, Value, ValueType};
 #[cfg(not(feature = "noop"))]
-use crate::{check_status_or_throw, JsError};
+use crate::{
+  bindgen_runtime::Object, check_status_or_throw, Error, JsError, JsObject, JsSymbol, JsUnknown,
+  NapiRaw, NapiValue, PropertyAttributes, Status,
+};
+use crate::{check_status, sys, Env, JsFunction, Property, Result, Value, ValueType};
 
 pub type ExportRegisterCallback = unsafe fn(sys::napi_env) -> Result<sys::napi_value>;
 pub type ModuleExportsCallback =
   unsafe fn(env: sys::napi_env, exports: sys::napi_value) -> Result<()>;
+pub type ModuleInitCallback = fn() -> Result<()>;
+pub type ClassDataFactory = fn() -> Box<dyn Any>;
+
+/// The `data` of `napi_define_class`, which is passed to the constructor of the class
+pub(crate) struct ClassData {
+  /// The JavaScript name of the class, without the nul terminator
+  pub(crate) js_name: &'static str,
+  /// The value created by the `class_data` of [`register_class`]
+  pub(crate) data: Option<Box<dyn Any>>,
+}
+
+impl ClassData {
+  /// The class data of the class whose constructor is called by `info`, `None` outside of a constructor.
+  unsafe fn of_constructor<'a>(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+  ) -> Option<&'a ClassData> {
+    let mut data = ptr::null_mut();
+    let status = unsafe {
+      sys::napi_get_cb_info(
+        env,
+        info,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        ptr::null_mut(),
+        &mut data,
+      )
+    };
+    if status != sys::Status::napi_ok || data.is_null() {
+      return None;
+    }
+    Some(unsafe { &*data.cast::<ClassData>() })
+  }
+}
 
 #[repr(transparent)]
 pub(crate) struct PersistedPerInstanceHashMap<K, V>(RwLock<HashMap<K, V>>);
@@ -53,10 +82,54 @@ impl<K, V> Default for PersistedPerInstanceHashMap<K, V> {
 
 type ModuleRegisterCallback =
   RwLock<Vec<(Option<&'static str>, (&'static str, ExportRegisterCallback))>>;
+/// `(js_mod, name, callback)` of the exports registered with `register_module_export_dynamic`,
+/// the names are nul terminated like the static ones
+type ModuleDynamicExports = RwLock<
+  Vec<(
+    Option<String>,
+    String,
+    ExportRegisterCallback,
+    /* doc */ Option<String>,
+  )>,
+>;
+type ModuleRawExports = RwLock<Vec<(Option<&'static str>, &'static str, crate::Callback)>>;
+
+#[cfg(feature = "napi8")]
+type ModuleEnum = RwLock<
+  Vec<(
+    Option<&'static str>,
+    &'static str,
+    &'static [(&'static str, i64)],
+  )>,
+>;
+
+type ModuleAccessor = RwLock<
+  Vec<(
+    Option<&'static str>,
+    &'static str,
+    Option<crate::Callback>,
+    Option<crate::Callback>,
+  )>,
+>;
+
+type ModuleClassReference = RwLock<
+  Vec<(
+    /* referrer */ &'static str,
+    /* js_name */ &'static str,
+  )>,
+>;
 
 type ModuleClassProperty = PersistedPerInstanceHashMap<
   &'static str,
-  HashMap<Option<&'static str>, (&'static str, Vec<Property>)>,
+  HashMap<
+    Option<&'static str>,
+    (
+      &'static str,
+      /* props of each register_class call */ Vec<Vec<Property>>,
+      Option<fn()>,
+      Option<ClassDataFactory>,
+    ),
+  >,
 >;
 
 unsafe impl<K, V> Send for PersistedPerInstanceHashMap<K, V> {}
@@ -65,15 +138,73 @@ unsafe impl<K, V> Sync for PersistedPerInstanceHashMap<K, V> {}
 type FnRegisterMap =
   PersistedPerInstanceHashMap<ExportRegisterCallback, (sys::napi_callback, &'static str)>;
 type RegisteredClassesMap = PersistedPerInstanceHashMap<ThreadId, RegisteredClasses>;
+/// `(rust_name, js_mod)` of a class, a struct may be exported as a class in several namespaces
+type ClassKey = (&'static str, Option<&'static str>);
+#[cfg(not(feature = "noop"))]
+/// `(rust_name, js_mod) -> (env, js_name)` of the `#[napi(lazy)]` classes which are not defined yet
+type PendingLazyClassesMap =
+  PersistedPerInstanceHashMap<ThreadId, HashMap<ClassKey, (sys::napi_env, &'static str)>>;
+type ExportEnvGates = PersistedPerInstanceHashMap<ExportRegisterCallback, &'static str>;
+/// `(js_mod, name) -> bool`, see [`set_export_filter`]
+type ExportFilter = fn(Option<&str>, &str) -> bool;
+type EnvAliveFlags = PersistedPerInstanceHashMap</* env */ usize, Arc<AtomicBool>>;
+type ExportReferences =
+  PersistedPerInstanceHashMap<ExportRegisterCallback, HashMap</* env */ usize, sys::napi_ref>>;
+/// `(js_name, module, export)`, the class `js_name` extends `export` of the parent module `module`
+type ClassParents = RwLock<Vec<(&'static str, &'static str, &'static str)>>;
+/// `callback -> rust_name` of the exports named by a `js_name` which is not a valid JavaScript identifier
+type RejectedExportNames = PersistedPerInstanceHashMap<ExportRegisterCallback, &'static str>;
+/// `(rust_name, js_mod)` of the classes named by a `js_name` which is not a valid JavaScript identifier
+type RejectedClassNames = RwLock<Vec<(&'static str, Option<&'static str>)>>;
+/// `env -> module -> exports` of the parent modules set with [`set_parent_module`]
+type ParentModules =
+  PersistedPerInstanceHashMap</* env */ usize, HashMap<&'static str, sys::napi_ref>>;
 
 static MODULE_REGISTER_CALLBACK: Lazy<ModuleRegisterCallback> = Lazy::new(Default::default);
+static MODULE_DYNAMIC_EXPORTS: Lazy<ModuleDynamicExports> = Lazy::new(Default::default);
+static MODULE_RAW_EXPORTS: Lazy<ModuleRawExports> = Lazy::new(Default::default);
+static MODULE_GLOBALS: Lazy<RwLock<Vec<(&'static str, ExportRegisterCallback)>>> =
+  Lazy::new(Default::default);
+static MODULE_MAIN: Lazy<RwLock<Vec<(&'static str, ExportRegisterCallback)>>> =
+  Lazy::new(Default::default);
 static MODULE_CLASS_PROPERTIES: Lazy<ModuleClassProperty> = Lazy::new(Default::default);
+static MODULE_CLASS_REFERENCES: Lazy<ModuleClassReference> = Lazy::new(Default::default);
+static REJECTED_EXPORT_NAMES: Lazy<RejectedExportNames> = Lazy::new(Default::default);
+static REJECTED_CLASS_NAMES: Lazy<RejectedClassNames> = Lazy::new(Default::default);
+static LAZY_CLASSES: Lazy<RwLock<Vec<ClassKey>>> = Lazy::new(Default::default);
+/// `(js_mod, name)` of an export, or `(None, js_name)` of a class
+type OrderKey = (Option<&'static str>, &'static str);
+/// `(export, anchor)`, the export is installed after the export `anchor`
+static EXPORTS_AFTER: Lazy<RwLock<Vec<(OrderKey, OrderKey)>>> = Lazy::new(Default::default);
+/// `(js_name, anchor)`, the class `js_name` is defined after the class `anchor`
+static CLASSES_AFTER: Lazy<RwLock<Vec<(&'static str, &'static str)>>> = Lazy::new(Default::default);
+static MODULE_ACCESSORS: Lazy<ModuleAccessor> = Lazy::new(Default::default);
+static MODULE_INIT: Lazy<RwLock<Vec<ModuleInitCallback>>> = Lazy::new(Default::default);
+static MODULE_PRE_INIT: Lazy<RwLock<Vec<ModuleExportsCallback>>> = Lazy::new(Default::default);
+static FLATTEN_NAMESPACES_SEPARATOR: Lazy<RwLock<Option<&'static str>>> =
+  Lazy::new(Default::default);
+static ES_MODULE_MARKER: AtomicBool = AtomicBool::new(false);
+static EXPORT_LIMIT: AtomicUsize = AtomicUsize::new(usize::MAX);
+static EXPORT_FILTER: Lazy<RwLock<Option<ExportFilter>>> = Lazy::new(Default::default);
+#[cfg(feature = "napi8")]
+static MODULE_ENUMS: Lazy<ModuleEnum> = Lazy::new(Default::default);
 #[cfg(not(feature = "noop"))]
 static IS_FIRST_MODULE: AtomicBool = AtomicBool::new(true);
 #[cfg(not(feature = "noop"))]
 static FIRST_MODULE_REGISTERED: AtomicBool = AtomicBool::new(false);
+#[cfg(not(feature = "noop"))]
+// Thread registering the first module, cleared once the registration is done.
+static FIRST_MODULE_REGISTERING_THREAD: Lazy<RwLock<Option<ThreadId>>> =
+  Lazy::new(Default::default);
 static REGISTERED_CLASSES: Lazy<RegisteredClassesMap> = Lazy::new(Default::default);
+#[cfg(not(feature = "noop"))]
+static PENDING_LAZY_CLASSES: Lazy<PendingLazyClassesMap> = Lazy::new(Default::default);
 static FN_REGISTER_MAP: Lazy<FnRegisterMap> = Lazy::new(Default::default);
+static EXPORT_ENV_GATES: Lazy<ExportEnvGates> = Lazy::new(Default::default);
+static ENV_ALIVE_FLAGS: Lazy<EnvAliveFlags> = Lazy::new(Default::default);
+static EXPORT_REFERENCES: Lazy<ExportReferences> = Lazy::new(Default::default);
+static CLASS_PARENTS: Lazy<ClassParents> = Lazy::new(Default::default);
+static PARENT_MODULES: Lazy<ParentModules> = Lazy::new(Default::default);
 #[cfg(all(feature = "napi4", not(feature = "noop"), not(target_family = "wasm")))]
 pub(crate) static CUSTOM_GC_TSFN: AtomicPtr<sys::napi_threadsafe_function__> =
   AtomicPtr::new(ptr::null_mut());
@@ -93,15 +224,121 @@ type RegisteredClasses =
 static MODULE_EXPORTS: Lazy<RwLock<Vec<ModuleExportsCallback>>> = Lazy::new(Default::default);
 
 #[cfg(not(feature = "noop"))]
-#[inline]
-fn wait_first_thread_registered() {
+/// Wait until the first module is registered.
+///
+/// Returns an error instead of waiting forever if the current thread is the one registering it,
+/// e.g. when called from an export callback.
+fn wait_first_thread_registered() -> Result<()> {
+  const SPIN_LIMIT: u32 = 64;
+
+  if FIRST_MODULE_REGISTERED.load(Ordering::SeqCst) {
+    return Ok(());
+  }
+  let current_id = std::thread::current().id();
+  if *FIRST_MODULE_REGISTERING_THREAD
+    .read()
+    .expect("Read FIRST_MODULE_REGISTERING_THREAD failed")
+    == Some(current_id)
+  {
+    return Err(Error::new(
+      Status::GenericFailure,
+      "The module is still being registered on the current thread".to_owned(),
+    ));
+  }
+  let mut spins = 0;
   while !FIRST_MODULE_REGISTERED.load(Ordering::SeqCst) {
-    std::hint::spin_loop();
+    if spins < SPIN_LIMIT {
+      spins += 1;
+      std::hint::spin_loop();
+    } else {
+      // let the registering thread run on single core or contended systems
+      std::thread::yield_now();
+    }
+  }
+  Ok(())
+}
+
+#[cfg(not(feature = "noop"))]
+fn finish_first_module_registration() {
+  FIRST_MODULE_REGISTERING_THREAD
+    .write()
+    .expect("Write FIRST_MODULE_REGISTERING_THREAD failed")
+    .take();
+  FIRST_MODULE_REGISTERED.store(true, Ordering::SeqCst);
+}
+
+#[cfg(not(feature = "noop"))]
+/// Run the `#[napi(module_init)]` functions once per process.
+///
+/// Later calls, e.g. from worker threads loading the module, return the result of the first run.
+fn run_module_init() -> Result<()> {
+  static MODULE_INIT_RESULT: once_cell::sync::OnceCell<std::result::Result<(), (Status, String)>> =
+    once_cell::sync::OnceCell::new();
+  MODULE_INIT_RESULT
+    .get_or_init(|| {
+      let module_init = MODULE_INIT.read().expect("Read MODULE_INIT failed");
+      module_init
+        .iter()
+        .try_for_each(|init| init())
+        .map_err(|e| (e.status, e.reason))
+    })
+    .clone()
+    .map_err(|(status, reason)| Error::new(status, reason))
+}
+
+/// Flag that is cleared by a cleanup hook once `env` is torn down.
+///
+/// Envs the module was not registered in are never flagged as torn down.
+pub(crate) fn env_alive_flag(env: sys::napi_env) -> Arc<AtomicBool> {
+  ENV_ALIVE_FLAGS.borrow_mut(|flags| {
+    flags
+      .get(&(env as usize))
+      .cloned()
+      .unwrap_or_else(|| Arc::new(AtomicBool::new(true)))
+  })
+}
+
+#[cfg(all(not(feature = "noop"), feature = "napi3"))]
+unsafe extern "C" fn mark_env_torn_down(env: *mut std::ffi::c_void) {
+  if let Some(flag) = ENV_ALIVE_FLAGS.borrow_mut(|flags| flags.remove(&(env as usize))) {
+    flag.store(false, Ordering::Release);
   }
+  // the references are freed along with the env
+  EXPORT_REFERENCES.borrow_mut(|inner| {
+    for references in inner.values_mut() {
+      references.remove(&(env as usize));
+    }
+  });
+  PARENT_MODULES.borrow_mut(|inner| inner.remove(&(env as usize)));
+  PENDING_LAZY_CLASSES.borrow_mut(|inner| {
+    for classes in inner.values_mut() {
+      classes.retain(|_, (class_env, ..)| class_env.cast() != env);
+    }
+  });
+}
+
+/// The JavaScript name of the class generated by the struct `rust_name`, without the nul terminator.
+pub(crate) fn get_class_js_name(rust_name: &str) -> Option<&'static str> {
+  MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
+    inner
+      .get(rust_name)
+      .and_then(|js_mods| js_mods.values().next())
+      .map(|(js_name, ..)| js_name.trim_end_matches('\0'))
+  })
 }
 
 #[doc(hidden)]
-pub fn get_class_constructor(js_name: &'static str) -> Option<sys::napi_ref> {
+/// The constructor of the class `js_name` registered in the current thread.
+///
+/// A `#[napi(lazy)]` class is defined by the first call, if an error is thrown it returns `None`.
+pub fn get_class_constructor(js_name: &str) -> Option<sys::napi_ref> {
+  let ctor_ref = get_defined_class_constructor(js_name);
+  #[cfg(not(feature = "noop"))]
+  let ctor_ref = ctor_ref.or_else(|| unsafe { define_pending_lazy_class(js_name) });
+  ctor_ref
+}
+
+fn get_defined_class_constructor(js_name: &str) -> Option<sys::napi_ref> {
   let current_id = std::thread::current().id();
   REGISTERED_CLASSES.borrow_mut(|map| {
     map
@@ -132,320 +369,1338 @@ pub fn register_module_export(
     .push((js_mod, (name, cb)));
 }
 
-#[doc(hidden)]
-pub fn register_js_function(
+/// Register an export like `#[napi]` does, installed after the export `anchor` instead of in registration order.
+///
+/// `anchor` is the namespace and JavaScript name of another export registered by `#[napi]` or this function,
+/// `(None, name)` for an export of the module itself.
+/// Unlike `register_module_export`, `name` is taken without the trailing nul, like `anchor`.
+/// The exports are sorted once the pre init callbacks have run, loading the module fails if `anchor`
+/// is not registered or if the exports registered after each other form a cycle.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   // `exports.plugins` is created after `exports.registry`, which it looks up
+///   register_module_export_after((None, "registry"), None, "plugins", create_plugins);
+/// }
+/// ```
+pub fn register_module_export_after(
+  anchor: (Option<&'static str>, &'static str),
+  js_mod: Option<&'static str>,
   name: &'static str,
   cb: ExportRegisterCallback,
-  c_fn: sys::napi_callback,
 ) {
-  FN_REGISTER_MAP.borrow_mut(|inner| {
-    inner.insert(cb, (c_fn, name));
-  });
+  let name = name.trim_end_matches('\0');
+  // installing the export needs the nul terminated name, the order is keyed by the bare name
+  let nul_terminated: &'static str = Box::leak(format!("{}\0", name).into_boxed_str());
+  register_module_export(js_mod, nul_terminated, cb);
+  EXPORTS_AFTER
+    .write()
+    .expect("Register export order failed")
+    .push(((js_mod, name), anchor));
+}
+
+/// Define the class `js_name` after the class `anchor`, e.g. a subclass after its parent class.
+///
+/// Classes are otherwise defined in no particular order, after every other export.
+/// Both names are the JavaScript names of the classes, without a trailing nul.
+/// Loading the module fails if `anchor` is not registered or if the classes form a cycle.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   register_class_after("Dog", "Animal");
+/// }
+/// ```
+pub fn register_class_after(js_name: &'static str, anchor: &'static str) {
+  CLASSES_AFTER
+    .write()
+    .expect("Register class order failed")
+    .push((js_name, anchor));
+}
+
+#[cfg(not(feature = "noop"))]
+/// Sort `items` so every item comes after the items keyed by its anchors in `after`, `(key, anchor)` pairs,
+/// the items keep their order otherwise.
+///
+/// `kind` names the items in the errors, for a missing anchor or a cycle.
+fn sort_after<T>(
+  items: Vec<T>,
+  key_of: impl Fn(&T) -> OrderKey,
+  after: &[(OrderKey, OrderKey)],
+  kind: &str,
+) -> Result<Vec<T>> {
+  if after.is_empty() {
+    return Ok(items);
+  }
+  let trim = |(js_mod, name): OrderKey| (js_mod, name.trim_end_matches('\0'));
+  let display = |(js_mod, name): (Option<&str>, &str)| match js_mod {
+    Some(js_mod) => format!("{}.{}", js_mod, name),
+    None => name.to_owned(),
+  };
+  let keys = items
+    .iter()
+    .map(|item| trim(key_of(item)))
+    .collect::<Vec<_>>();
+  let mut indices_by_key: HashMap<(Option<&str>, &str), Vec<usize>> = HashMap::new();
+  for (index, key) in keys.iter().enumerate() {
+    indices_by_key.entry(*key).or_default().push(index);
+  }
+  // the indices of the items each item comes after
+  let mut anchors: Vec<Vec<usize>> = vec![Vec::new(); items.len()];
+  for (key, anchor) in after {
+    let (key, anchor) = (trim(*key), trim(*anchor));
+    let Some(indices) = indices_by_key.get(&key) else {
+      continue;
+    };
+    let anchor_indices = indices_by_key.get(&anchor).ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        format!(
+          "The {} `{}` is registered after `{}`, which is not registered",
+          kind,
+          display(key),
+          display(anchor)
+        ),
+      )
+    })?;
+    for index in indices {
+      anchors[*index].extend(anchor_indices);
+    }
+  }
+
+  // Kahn's algorithm, taking the first registered item whose anchors are all sorted
+  let mut dependents = vec![Vec::new(); items.len()];
+  let mut pending_anchors = anchors.iter().map(Vec::len).collect::<Vec<_>>();
+  for (index, item_anchors) in anchors.iter().enumerate() {
+    for anchor in item_anchors {
+      dependents[*anchor].push(index);
+    }
+  }
+  let mut ready = (0..items.len())
+    .filter(|index| pending_anchors[*index] == 0)
+    .map(std::cmp::Reverse)
+    .collect::<std::collections::BinaryHeap<_>>();
+  let mut order = Vec::with_capacity(items.len());
+  while let Some(std::cmp::Reverse(index)) = ready.pop() {
+    order.push(index);
+    for dependent in &dependents[index] {
+      pending_anchors[*dependent] -= 1;
+      if pending_anchors[*dependent] == 0 {
+        ready.push(std::cmp::Reverse(*dependent));
+      }
+    }
+  }
+
+  if order.len() < items.len() {
+    // an item left unsorted always has an unsorted anchor, following them leads into a cycle
+    let mut path = Vec::new();
+    let mut current = (0..items.len())
+      .find(|index| pending_anchors[*index] > 0)
+      .unwrap();
+    let cycle_start = loop {
+      if let Some(position) = path.iter().position(|index| *index == current) {
+        break position;
+      }
+      path.push(current);
+      current = *anchors[current]
+        .iter()
+        .find(|anchor| pending_anchors[**anchor] > 0)
+        .unwrap();
+    };
+    let cycle = path[cycle_start..]
+      .iter()
+      .chain(std::iter::once(&current))
+      .map(|index| format!("`{}`", display(keys[*index])))
+      .collect::<Vec<_>>();
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "The {}s registered after each other form a cycle: {}",
+        kind,
+        cycle.join(" -> ")
+      ),
+    ));
+  }
+
+  let mut items = items.into_iter().map(Some).collect::<Vec<_>>();
+  Ok(
+    order
+      .into_iter()
+      .map(|index| items[index].take().unwrap())
+      .collect(),
+  )
 }
 
 #[doc(hidden)]
-pub fn register_class(
-  rust_name: &'static str,
-  js_mod: Option<&'static str>,
-  js_name: &'static str,
-  props: Vec<Property>,
-) {
-  MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
-    let val = inner.entry(rust_name).or_default();
-    let val = val.entry(js_mod).or_default();
-    val.0 = js_name;
-    val.1.extend(props);
-  });
+/// Register the `#[napi(main)]` function, which is returned as the module instead of `exports`.
+pub fn register_module_main(name: &'static str, cb: ExportRegisterCallback) {
+  MODULE_MAIN
+    .write()
+    .expect("Register module main failed")
+    .push((name, cb));
 }
 
-#[inline]
-/// Get `JsFunction` from defined Rust `fn`
-/// ```rust
-/// #[napi]
-/// fn some_fn() -> u32 {
-///     1
+/// Register an export under a name computed at runtime, e.g. read from a config file in a `#[napi::module_init]` function.
+///
+/// It's installed by `napi_register_module_v1` after the exports of `#[napi]` items, as `exports[name]`
+/// or `exports[js_mod][name]`. The names are kept for the lifetime of the addon, so every env the addon
+/// is loaded into gets the same exports. Registering after the module is loaded only affects the envs loaded later.
+///
+/// Returns an error if a name contains a nul byte.
+///
+/// ```rust,ignore
+/// unsafe fn create_greeting(env: sys::napi_env) -> Result<sys::napi_value> {
+///   unsafe { String::to_napi_value(env, "hello".to_owned()) }
 /// }
 ///
-/// #[napi]
-/// fn return_some_fn() -> Result<JsFunction> {
-///     get_js_function(some_fn_js_function)
+/// #[napi::module_init]
+/// fn init() {
+///   for name in read_export_names() {
+///     register_module_export_dynamic(None, name, create_greeting).unwrap();
+///   }
 /// }
 /// ```
+pub fn register_module_export_dynamic(
+  js_mod: Option<String>,
+  name: String,
+  cb: ExportRegisterCallback,
+) -> Result<()> {
+  register_dynamic_export(js_mod, name, cb, None)
+}
+
+/// [`register_module_export_dynamic`] with a doc comment, for tooling which emits the typings of the exports at runtime.
 ///
-/// ```js
-/// returnSomeFn()(); // 1
+/// The doc comment is only metadata, read it back with [`export_doc`], it doesn't change the export.
+/// It's the text of the comment without the `/** */` delimiters.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   for (name, description) in read_exports() {
+///     register_module_export_dynamic_with_doc(None, name, description, create_greeting).unwrap();
+///   }
+/// }
 /// ```
+pub fn register_module_export_dynamic_with_doc(
+  js_mod: Option<String>,
+  name: String,
+  doc: String,
+  cb: ExportRegisterCallback,
+) -> Result<()> {
+  register_dynamic_export(js_mod, name, cb, Some(doc))
+}
+
+/// The doc comment of the export `name` registered by [`register_module_export_dynamic_with_doc`],
+/// `None` if there is no such export or it was registered without a doc comment.
 ///
-pub fn get_js_function(env: &Env, raw_fn: ExportRegisterCallback) -> Result<JsFunction> {
-  FN_REGISTER_MAP.borrow_mut(|inner| {
-    inner
-      .get(&raw_fn)
-      .and_then(|(cb, name)| {
-        let mut function = ptr::null_mut();
-        let name_len = name.len() - 1;
-        let fn_name = unsafe { CStr::from_bytes_with_nul_unchecked(name.as_bytes()) };
-        check_status!(unsafe {
-          sys::napi_create_function(
-            env.0,
-            fn_name.as_ptr(),
-            name_len,
-            *cb,
-            ptr::null_mut(),
-            &mut function,
-          )
-        })
-        .ok()?;
-        Some(JsFunction(Value {
-          env: env.0,
-          value: function,
-          value_type: ValueType::Function,
-        }))
-      })
-      .ok_or_else(|| {
-        crate::Error::new(
-          crate::Status::InvalidArg,
-          "JavaScript function does not exist".to_owned(),
-        )
-      })
-  })
+/// `js_mod` is the namespace the export was registered in.
+pub fn export_doc(js_mod: Option<&str>, name: &str) -> Option<String> {
+  MODULE_DYNAMIC_EXPORTS
+    .read()
+    .expect("Read MODULE_DYNAMIC_EXPORTS failed")
+    .iter()
+    .rev()
+    .find(|(export_js_mod, export_name, _, _)| {
+      export_js_mod
+        .as_deref()
+        .map(|js_mod| js_mod.trim_end_matches('\0'))
+        == js_mod
+        && export_name.trim_end_matches('\0') == name
+    })
+    .and_then(|(_, _, _, doc)| doc.clone())
 }
 
-/// Get `C Callback` from defined Rust `fn`
-/// ```rust
-/// #[napi]
-/// fn some_fn() -> u32 {
-///     1
+fn register_dynamic_export(
+  js_mod: Option<String>,
+  name: String,
+  cb: ExportRegisterCallback,
+  doc: Option<String>,
+) -> Result<()> {
+  let nul_terminated = |s: String| {
+    if s.contains('\0') {
+      Err(crate::Error::new(
+        crate::Status::InvalidArg,
+        format!("Export name {:?} contains a nul byte", s),
+      ))
+    } else {
+      Ok(format!("{}\0", s))
+    }
+  };
+  let js_mod = js_mod.map(nul_terminated).transpose()?;
+  let name = nul_terminated(name)?;
+  MODULE_DYNAMIC_EXPORTS
+    .write()
+    .expect("Register dynamic module export failed")
+    .push((js_mod, name, cb, doc));
+  Ok(())
+}
+
+/// Export the `napi_callback` `cb` as the function `name`, created with `napi_create_function`
+/// by `napi_register_module_v1` after the exports of `#[napi]` items.
+///
+/// This skips the trampoline `#[napi]` generates around a function, so the arguments are not
+/// converted with `FromNapiValue` and the return value is not converted with `ToNapiValue`.
+/// It's meant for a few hot functions where that overhead matters, prefer `#[napi]` otherwise.
+///
+/// # Safety
+///
+/// `cb` is called by the JavaScript engine as is, it must follow the N-API calling convention:
+///
+/// - read the arguments and `this` with `napi_get_cb_info` from the `napi_callback_info`,
+///   which is only valid during the call. The callback data is always null.
+/// - return a `napi_value` created in the `napi_env` of the call, or null for `undefined`.
+/// - never unwind, a panic escaping an `extern "C"` function aborts the process.
+///   Report errors with `napi_throw_*` and return null instead.
+/// - a pending exception must not be overwritten, check the status of every N-API call.
+///
+/// ```rust,ignore
+/// unsafe extern "C" fn add_one(env: sys::napi_env, info: sys::napi_callback_info) -> sys::napi_value {
+///   let mut argc = 1;
+///   let mut arg = ptr::null_mut();
+///   let mut value = 0.0;
+///   let mut ret = ptr::null_mut();
+///   unsafe {
+///     sys::napi_get_cb_info(env, info, &mut argc, &mut arg, ptr::null_mut(), ptr::null_mut());
+///     if sys::napi_get_value_double(env, arg, &mut value) != sys::Status::napi_ok {
+///       sys::napi_throw_type_error(env, ptr::null(), "Expected a number\0".as_ptr().cast());
+///       return ptr::null_mut();
+///     }
+///     sys::napi_create_double(env, value + 1.0, &mut ret);
+///   }
+///   ret
 /// }
 ///
-/// #[napi]
-/// fn create_obj(env: Env) -> Result<JsObject> {
-///     let mut obj = env.create_object()?;
-///     obj.define_property(&[Property::new("getter")?.with_getter(get_c_callback(some_fn_js_function)?)])?;
-///     Ok(obj)
+/// #[napi::module_init]
+/// fn init() {
+///   unsafe { register_module_export_raw(None, "addOne", add_one) };
 /// }
 /// ```
+pub unsafe fn register_module_export_raw(
+  js_mod: Option<&'static str>,
+  name: &'static str,
+  cb: crate::Callback,
+) {
+  MODULE_RAW_EXPORTS
+    .write()
+    .expect("Register raw module export failed")
+    .push((js_mod, name, cb));
+}
+
+/// Install the value created by `cb` as `globalThis[name]` instead of on the exports, e.g. for a polyfill.
 ///
-/// ```js
-/// console.log(createObj().getter) // 1
-/// ```
+/// It's installed by `napi_register_module_v1` after the exports, in every env the addon is loaded into.
+/// Like the builtins, the property is writable and configurable but not enumerable.
+/// If `globalThis` already has an own property `name`, for example installed by another addon,
+/// loading the module throws instead of replacing it.
 ///
-pub fn get_c_callback(raw_fn: ExportRegisterCallback) -> Result<crate::Callback> {
-  FN_REGISTER_MAP.borrow_mut(|inner| {
-    inner
-      .get(&raw_fn)
-      .and_then(|(cb, _name)| *cb)
-      .ok_or_else(|| {
-        crate::Error::new(
-          crate::Status::InvalidArg,
-          "JavaScript function does not exist".to_owned(),
-        )
-      })
-  })
+/// ```rust,ignore
+/// unsafe fn create_text_codec(env: sys::napi_env) -> Result<sys::napi_value> {
+///   let env = unsafe { Env::from_raw(env) };
+///   // ...
+/// }
+///
+/// #[napi::module_init]
+/// fn init() {
+///   register_global("TextCodec", create_text_codec);
+/// }
+/// ```
+pub fn register_global(name: &'static str, cb: ExportRegisterCallback) {
+  MODULE_GLOBALS
+    .write()
+    .expect("Register global failed")
+    .push((name, cb));
 }
 
-#[cfg(all(windows, not(feature = "noop")))]
-#[ctor::ctor]
-fn load_host() {
-  unsafe {
-    sys::setup();
-  }
+#[doc(hidden)]
+/// Only install the export registered with `cb` if the environment variable `env_var` is truthy
+/// when the module is loaded, see `#[napi(enabled_if_env = "...")]`.
+///
+/// The variable is read from `process.env`, or from the process environment if there is no `process`.
+/// It's truthy if it's set and not empty, `"0"` or `"false"` (case insensitive).
+/// This is a runtime gate, the export is still compiled into the addon.
+pub fn register_export_env_gate(cb: ExportRegisterCallback, env_var: &'static str) {
+  EXPORT_ENV_GATES.borrow_mut(|inner| {
+    inner.insert(cb, env_var);
+  });
 }
 
-#[cfg(all(target_family = "wasm", not(feature = "noop")))]
-#[no_mangle]
-unsafe extern "C" fn napi_register_wasm_v1(
-  env: sys::napi_env,
-  exports: sys::napi_value,
-) -> sys::napi_value {
-  unsafe { napi_register_module_v1(env, exports) }
+#[doc(hidden)]
+/// Define the class generated by the struct `rust_name` in the namespace `js_mod` on first use
+/// instead of while the module is loaded, see `#[napi(lazy)]`.
+///
+/// The class is defined by a getter on the exports, or by the first [`get_class_constructor`] call,
+/// e.g. when an instance is returned from Rust before the class is accessed.
+/// The `index.js` generated by the CLI re-exports the class with a getter too,
+/// a binding which copies the value of every export defines it when it's loaded.
+pub fn define_class_lazily(rust_name: &'static str, js_mod: Option<&'static str>) {
+  LAZY_CLASSES
+    .write()
+    .expect("Register lazy class failed")
+    .push((rust_name, js_mod));
 }
 
 #[cfg(not(feature = "noop"))]
-#[no_mangle]
-/// Register the n-api module exports.
-///
-/// # Safety
-/// This method is meant to be called by Node.js while importing the n-api module.
-/// Only call this method if the current module is **not** imported by a node-like runtime.
+/// The index of the class in `LAZY_CLASSES` if it's a `#[napi(lazy)]` class.
+fn lazy_class_index(key: ClassKey) -> Option<usize> {
+  LAZY_CLASSES
+    .read()
+    .expect("Read LAZY_CLASSES failed")
+    .iter()
+    .position(|lazy_class| *lazy_class == key)
+}
+
+#[doc(hidden)]
+/// Throw while the module is loading instead of installing the export created by `callback`,
+/// the function `rust_name` has a `js_name` which is not a valid JavaScript identifier.
 ///
-/// Arguments `env` and `exports` must **not** be null.
-pub unsafe extern "C" fn napi_register_module_v1(
-  env: sys::napi_env,
-  exports: sys::napi_value,
-) -> sys::napi_value {
-  if IS_FIRST_MODULE.load(Ordering::SeqCst) {
-    IS_FIRST_MODULE.store(false, Ordering::SeqCst);
-  } else {
-    wait_first_thread_registered();
-  }
-  let mut exports_objects: HashSet<String> = HashSet::default();
+/// `#[napi]` checks the name when it's expanded, `#[napi(any_js_name)]` skips the check.
+pub fn reject_export_name(callback: ExportRegisterCallback, rust_name: &'static str) {
+  REJECTED_EXPORT_NAMES.borrow_mut(|inner| {
+    inner.insert(callback, rust_name);
+  });
+}
+
+#[doc(hidden)]
+/// Throw while the module is loading instead of defining the class of the struct `rust_name` in `js_mod`,
+/// its `js_name` is not a valid JavaScript identifier, see [`reject_export_name`].
+pub fn reject_class_name(rust_name: &'static str, js_mod: Option<&'static str>) {
+  REJECTED_CLASS_NAMES
+    .write()
+    .expect("Register rejected class name failed")
+    .push((rust_name, js_mod));
+}
+
+#[cfg(feature = "napi8")]
+/// Export a frozen object shaped like a TypeScript numeric `enum`.
+///
+/// Every variant is exported both as `name -> value` and as `value -> name`:
+///
+/// ```rust
+/// #[napi::module_init]
+/// fn init() {
+///   register_enum(None, "Direction", &[("Up", 0), ("Down", 1)]);
+/// }
+/// ```
+///
+/// ```js
+/// Direction.Up // 0
+/// Direction[0] // 'Up'
+/// Object.isFrozen(Direction) // true
+/// ```
+pub fn register_enum(
+  js_mod: Option<&'static str>,
+  name: &'static str,
+  variants: &'static [(&'static str, i64)],
+) {
+  MODULE_ENUMS
+    .write()
+    .expect("Register enum failed")
+    .push((js_mod, name, variants));
+}
+
+#[doc(hidden)]
+/// Register a `#[napi(module_init)]` function, run before any export is installed.
+pub fn register_module_init(init: ModuleInitCallback) {
+  MODULE_INIT
+    .write()
+    .expect("Write MODULE_INIT failed")
+    .push(init);
+}
+
+/// Register a callback receiving the raw `exports` object before napi-rs installs any export.
+///
+/// Callbacks run in registration order, once per env. If one of them fails, the error is thrown
+/// and the module registration is aborted.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   register_pre_init(|env, exports| {
+///     // set up `exports` here
+///     Ok(())
+///   });
+/// }
+/// ```
+pub fn register_pre_init(callback: ModuleExportsCallback) {
+  MODULE_PRE_INIT
+    .write()
+    .expect("Write MODULE_PRE_INIT failed")
+    .push(callback);
+}
+
+/// Additionally export everything in a namespace at the top level as `{namespace}{separator}{name}`.
+///
+/// `exports.ns.foo` stays as is and the same value is also exported as `exports.ns_foo` with a `"_"` separator,
+/// so an ES module wrapper can re-export namespaced items as static named exports.
+/// The flattened names are listed, sorted, in the non-enumerable `exports.__napiFlattenedExports` array,
+/// which can be used to generate such a wrapper.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   flatten_namespaces("_");
+/// }
+/// ```
+pub fn flatten_namespaces(separator: &'static str) {
+  *FLATTEN_NAMESPACES_SEPARATOR
+    .write()
+    .expect("Write FLATTEN_NAMESPACES_SEPARATOR failed") = Some(separator);
+}
+
+/// Set `exports.__esModule` to `true` once all other exports are attached,
+/// so Babel and TypeScript interop treat the addon as an ES module in `import addon from './addon.node'`.
+///
+/// The marker is defined like Babel does, non-enumerable and read-only.
+/// If the module already exports its own `__esModule`, that export is kept as is.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   mark_es_module();
+/// }
+/// ```
+pub fn mark_es_module() {
+  ES_MODULE_MARKER.store(true, Ordering::Relaxed);
+}
+
+/// Refuse to install anything on the exports if the module has more than `limit` exports and classes.
+///
+/// This is a safety valve for sandboxed embeddings, to catch runaway code generation before it
+/// bloats every `exports` object, not something a normal addon needs.
+/// Exports, dynamic exports, enums, accessors and classes are counted, one for each namespace they are registered in.
+/// Over the limit, loading the module throws and `exports` is left empty.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   set_export_limit(1000);
+/// }
+/// ```
+pub fn set_export_limit(limit: usize) {
+  EXPORT_LIMIT.store(limit, Ordering::Relaxed);
+}
+
+/// Only attach the exports and classes for which `filter(js_mod, name)` returns `true`, e.g. to turn
+/// features of a single binary on and off with a config read while the module is loaded.
+///
+/// `js_mod` is the namespace of the export, `None` at the top level. Exports, dynamic exports,
+/// enums, accessors and classes are filtered, globals and the `#[napi(main)]` function are not.
+/// Unlike `#[napi(enabled_if_env = "...")]`, the filter applies to every export.
+/// Filtering out a class which is referenced by an export that is kept fails loading the module.
+///
+/// The filter is read once the pre init callbacks have run, set it from a `#[napi::module_init]`
+/// function or a [`register_pre_init`] callback. Setting it again replaces the previous filter.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   register_pre_init(|_env, _exports| {
+///     set_export_filter(|_js_mod, name| name != "experimentalApi");
+///     Ok(())
+///   });
+/// }
+/// ```
+pub fn set_export_filter(filter: ExportFilter) {
+  *EXPORT_FILTER.write().expect("Write EXPORT_FILTER failed") = Some(filter);
+}
+
+/// Whether the export `name` of `js_mod` passes the filter set by [`set_export_filter`].
+#[cfg(not(feature = "noop"))]
+fn is_export_enabled(filter: Option<ExportFilter>, js_mod: Option<&str>, name: &str) -> bool {
+  filter.map_or(true, |filter| {
+    filter(
+      js_mod.map(|js_mod| js_mod.trim_end_matches('\0')),
+      name.trim_end_matches('\0'),
+    )
+  })
+}
+
+/// The number of exports and classes checked against [`set_export_limit`].
+#[cfg(not(feature = "noop"))]
+fn count_exports() -> usize {
+  let exports = MODULE_REGISTER_CALLBACK
+    .read()
+    .expect("Read MODULE_REGISTER_CALLBACK failed")
+    .len();
+  let dynamic_exports = MODULE_DYNAMIC_EXPORTS
+    .read()
+    .expect("Read MODULE_DYNAMIC_EXPORTS failed")
+    .len();
+  let raw_exports = MODULE_RAW_EXPORTS
+    .read()
+    .expect("Read MODULE_RAW_EXPORTS failed")
+    .len();
+  #[cfg(feature = "napi8")]
+  let enums = MODULE_ENUMS.read().expect("Read MODULE_ENUMS failed").len();
+  #[cfg(not(feature = "napi8"))]
+  let enums = 0;
+  let accessors = MODULE_ACCESSORS
+    .read()
+    .expect("Read MODULE_ACCESSORS failed")
+    .len();
+  let classes: usize =
+    MODULE_CLASS_PROPERTIES.borrow_mut(|inner| inner.values().map(|js_mods| js_mods.len()).sum());
+  exports + dynamic_exports + raw_exports + enums + accessors + classes
+}
+
+/// Define an accessor property `name` on the exports, or on the `js_mod` namespace of the exports.
+///
+/// The `getter` runs every time the property is read, e.g. to compute a value lazily,
+/// and the `setter` every time it's assigned. Without a `setter` the property is read-only.
+/// The property is enumerable and configurable.
+///
+/// ```rust,ignore
+/// unsafe extern "C" fn get_answer(
+///   env: sys::napi_env,
+///   _info: sys::napi_callback_info,
+/// ) -> sys::napi_value {
+///   let mut answer = ptr::null_mut();
+///   unsafe { sys::napi_create_uint32(env, 42, &mut answer) };
+///   answer
+/// }
+///
+/// #[napi::module_init]
+/// fn init() {
+///   register_module_accessor(None, "answer", Some(get_answer), None);
+/// }
+/// ```
+///
+/// ```js
+/// addon.answer // 42
+/// ```
+pub fn register_module_accessor(
+  js_mod: Option<&'static str>,
+  name: &'static str,
+  getter: Option<crate::Callback>,
+  setter: Option<crate::Callback>,
+) {
+  MODULE_ACCESSORS
+    .write()
+    .expect("Register module accessor failed")
+    .push((js_mod, name, getter, setter));
+}
+
+#[doc(hidden)]
+pub fn register_js_function(
+  name: &'static str,
+  cb: ExportRegisterCallback,
+  c_fn: sys::napi_callback,
+) {
+  FN_REGISTER_MAP.borrow_mut(|inner| {
+    inner.insert(cb, (c_fn, name));
+  });
+}
+
+/// Keep a reference to the value the export of `raw_fn` is installed with in every env,
+/// so [`get_exported_js_function`] can return it.
+///
+/// Call it before the module is registered, e.g. from a `#[napi::module_init]` function.
+pub fn keep_export_reference(raw_fn: ExportRegisterCallback) {
+  EXPORT_REFERENCES.borrow_mut(|inner| {
+    inner.entry(raw_fn).or_default();
+  });
+}
+
+/// Get the `JsFunction` the export of `raw_fn` is installed with in the env, rather than a new function
+/// like [`get_js_function`] creates, so a function can return itself or compare against itself.
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///     keep_export_reference(chain_js_function);
+/// }
+///
+/// #[napi]
+/// fn chain(env: Env) -> Result<JsFunction> {
+///     get_exported_js_function(&env, chain_js_function)
+/// }
+/// ```
+///
+/// ```js
+/// chain()()() === chain // true
+/// ```
+///
+/// The reference is kept for the env while the export is registered, it's not shared between envs,
+/// so this never waits for the first module registration like [`get_js_function`] does.
+/// That makes it safe to call on the registering thread: from the callbacks of the exports registered after
+/// `raw_fn` it returns the function, before `raw_fn` is registered it returns an error instead of waiting
+/// for a registration that can't finish until the caller returns.
+/// It also returns an error if [`keep_export_reference`] was not called for `raw_fn`.
+pub fn get_exported_js_function(env: &Env, raw_fn: ExportRegisterCallback) -> Result<JsFunction> {
+  let reference = EXPORT_REFERENCES.borrow_mut(|inner| {
+    inner
+      .get(&raw_fn)
+      .ok_or_else(|| {
+        crate::Error::new(
+          crate::Status::InvalidArg,
+          "The reference of the export is not kept, call `keep_export_reference` first".to_owned(),
+        )
+      })
+      .and_then(|references| {
+        references.get(&(env.0 as usize)).copied().ok_or_else(|| {
+          crate::Error::new(
+            crate::Status::InvalidArg,
+            "The export is not registered in the env yet".to_owned(),
+          )
+        })
+      })
+  })?;
+  let mut function = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_reference_value(env.0, reference, &mut function) },
+    "Failed to get the value of the export reference"
+  )?;
+  if crate::type_of!(env.0, function)? != ValueType::Function {
+    return Err(crate::Error::new(
+      crate::Status::FunctionExpected,
+      "The export is not a function".to_owned(),
+    ));
+  }
+  Ok(JsFunction(Value {
+    env: env.0,
+    value: function,
+    value_type: ValueType::Function,
+  }))
+}
+
+#[doc(hidden)]
+/// Properties from all calls for the same class are merged. They keep their order within each call,
+/// and the calls are ordered by the name of their first property, so the property order of the class
+/// does not depend on the order of the calls.
+///
+/// `class_finalizer` is called once per env when the constructor reference of the class is
+/// deleted during env cleanup, e.g. when a worker thread which loaded the module exits.
+/// `#[napi(class_finalizer = "path")]` on the struct passes the function at `path`.
+///
+/// `class_data` creates the value passed as `data` to `napi_define_class` in every env, the
+/// constructor reads it with [`CallbackInfo::class_data`]. The value is dropped at env cleanup.
+pub fn register_class(
+  rust_name: &'static str,
+  js_mod: Option<&'static str>,
+  js_name: &'static str,
+  props: Vec<Property>,
+  class_finalizer: Option<fn()>,
+  class_data: Option<ClassDataFactory>,
+) {
+  MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
+    let val = inner.entry(rust_name).or_default();
+    let val = val.entry(js_mod).or_default();
+    val.0 = js_name;
+    if let Some(key) = first_property_name(&props) {
+      let at = val
+        .1
+        .partition_point(|call_props| first_property_name(call_props) <= Some(key));
+      val.1.insert(at, props);
+    }
+    if class_finalizer.is_some() {
+      val.2 = class_finalizer;
+    }
+    if class_data.is_some() {
+      val.3 = class_data;
+    }
+  });
+}
+
+/// The name of the first property which is not the constructor, the constructor is defined by
+/// `napi_define_class` itself instead of as a property.
+fn first_property_name(props: &[Property]) -> Option<&CStr> {
+  props
+    .iter()
+    .find(|prop| !prop.is_ctor)
+    .or_else(|| props.first())
+    .map(|prop| prop.name.as_c_str())
+}
+
+/// Make the class `js_name` extend the export `export` of another module, e.g. a class of another addon.
+///
+/// `module` names the parent module, its exports are supplied as a JavaScript value when the addon is loaded
+/// by calling [`set_parent_module`] from a [`register_pre_init`] hook. The prototype chains of the class and its
+/// prototype are linked to the parent's, so static and prototype members are inherited and instances are
+/// `instanceof` the parent.
+///
+/// The parent constructor is not called when an instance is created. Members of a parent which is itself
+/// a native class expect the native value created by its own constructor and throw on instances of the subclass.
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   register_class_parent("JsonPlugin", "plugin-host", "Plugin");
+///   register_pre_init(|env, _exports| {
+///     // e.g. put on `globalThis` by the JavaScript entry of the addon: `globalThis.pluginHost = require('plugin-host')`
+///     let env = unsafe { Env::from_raw(env) };
+///     let host = env.get_global()?.get_named_property_unchecked::<JsObject>("pluginHost")?;
+///     unsafe { set_parent_module(env.raw(), "plugin-host", host.raw()) }
+///   });
+/// }
+/// ```
+pub fn register_class_parent(js_name: &'static str, module: &'static str, export: &'static str) {
+  CLASS_PARENTS
+    .write()
+    .expect("Register class parent failed")
+    .push((js_name, module, export));
+}
+
+/// Supply the exports of the parent module `module` of [`register_class_parent`] for the env `env`.
+///
+/// Call it from a [`register_pre_init`] hook, the classes are defined after the hooks have run.
+/// Loading the module fails if a parent class is registered for a module which is not supplied.
+///
+/// # Safety
+///
+/// `env` and `exports` must be valid, `exports` a value created in `env`.
+pub unsafe fn set_parent_module(
+  env: sys::napi_env,
+  module: &'static str,
+  exports: sys::napi_value,
+) -> Result<()> {
+  let mut exports_ref = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_reference(env, exports, 1, &mut exports_ref) },
+    "Failed to create reference of parent module `{}`",
+    module
+  )?;
+  let previous = PARENT_MODULES.borrow_mut(|inner| {
+    inner
+      .entry(env as usize)
+      .or_default()
+      .insert(module, exports_ref)
+  });
+  if let Some(previous) = previous {
+    check_status!(
+      unsafe { sys::napi_delete_reference(env, previous) },
+      "Failed to delete reference of parent module `{}`",
+      module
+    )?;
+  }
+  Ok(())
+}
+
+#[cfg(not(feature = "noop"))]
+/// Link the class `js_name` to its parent class registered with [`register_class_parent`], if any.
+unsafe fn inherit_parent_class(
+  env: sys::napi_env,
+  js_name: &str,
+  class: sys::napi_value,
+) -> Result<()> {
+  let js_name = js_name.trim_end_matches('\0');
+  let Some((_, module, export)) = CLASS_PARENTS
+    .read()
+    .expect("Read CLASS_PARENTS failed")
+    .iter()
+    .find(|(name, ..)| name.trim_end_matches('\0') == js_name)
+    .copied()
+  else {
+    return Ok(());
+  };
+  let exports_ref = PARENT_MODULES
+    .borrow_mut(|inner| inner.get(&(env as usize))?.get(module).copied())
+    .ok_or_else(|| {
+      crate::Error::new(
+        crate::Status::InvalidArg,
+        format!(
+          "Class `{}` extends `{}` of the module `{}`, which is not set, call `set_parent_module` in a pre init hook",
+          js_name, export, module
+        ),
+      )
+    })?;
+  let mut exports = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_reference_value(env, exports_ref, &mut exports) },
+    "Failed to get the exports of parent module `{}`",
+    module
+  )?;
+  let exports = unsafe { JsObject::from_raw_unchecked(env, exports) };
+  let parent = exports.get_named_property_unchecked::<JsUnknown>(export)?;
+  if parent.get_type()? != ValueType::Function {
+    return Err(crate::Error::new(
+      crate::Status::FunctionExpected,
+      format!(
+        "The parent `{}` of class `{}` exported by the module `{}` is not a class",
+        export, js_name, module
+      ),
+    ));
+  }
+  let parent = unsafe { parent.cast::<JsObject>() };
+  let mut class = unsafe { JsObject::from_raw_unchecked(env, class) };
+  class
+    .get_named_property_unchecked::<JsObject>("prototype")?
+    .set_prototype(parent.get_named_property_unchecked::<JsObject>("prototype")?)?;
+  class.set_prototype(parent)
+}
+
+/// The JS-visible property names of the class generated by the Rust struct `rust_name`, sorted
+/// by name.
+///
+/// Instance and static members are both listed, the constructor is not. Returns `None` if no
+/// class is registered for `rust_name`.
+pub fn class_property_names(rust_name: &str) -> Option<Vec<String>> {
+  MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
+    let js_mods = inner.get(rust_name)?;
+    let mut names = js_mods
+      .values()
+      .flat_map(|(_, props, _, _)| props.iter().flatten())
+      .filter(|prop| !prop.is_ctor)
+      .map(|prop| prop.name.to_string_lossy().into_owned())
+      .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    Some(names)
+  })
+}
+
+#[doc(hidden)]
+/// Record that the export `referrer` accepts or returns instances of the class `js_name`,
+/// `#[napi]` records the classes among the argument and return types of every function.
+///
+/// The reference is checked against the registered classes while the module is loading,
+/// before any export is installed, see [`validate_class_references`].
+pub fn register_class_reference(referrer: &'static str, js_name: &'static str) {
+  MODULE_CLASS_REFERENCES
+    .write()
+    .expect("Register class reference failed")
+    .push((referrer, js_name));
+}
+
+/// Cross-reference the classes recorded by `register_class_reference` against the classes
+/// registered to be defined by the module, leaving out the classes rejected by the export filter.
+///
+/// Returns an `InvalidArg` error listing every missing class and the exports referencing it.
+#[cfg(not(feature = "noop"))]
+pub fn validate_class_references() -> Result<()> {
+  let references = MODULE_CLASS_REFERENCES
+    .read()
+    .expect("Read MODULE_CLASS_REFERENCES failed");
+  if references.is_empty() {
+    return Ok(());
+  }
+  let filter = *EXPORT_FILTER.read().expect("Read EXPORT_FILTER failed");
+  let registered = MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
+    inner
+      .values()
+      .flat_map(|js_mods| js_mods.iter())
+      .filter(|(js_mod, (js_name, ..))| is_export_enabled(filter, **js_mod, js_name))
+      .map(|(_, (js_name, ..))| js_name.trim_end_matches('\0'))
+      .collect::<std::collections::HashSet<_>>()
+  });
+  let mut missing = references
+    .iter()
+    .filter(|(_, js_name)| !registered.contains(js_name.trim_end_matches('\0')))
+    .map(|(referrer, js_name)| {
+      format!(
+        "`{}` (referenced by `{}`)",
+        js_name.trim_end_matches('\0'),
+        referrer.trim_end_matches('\0')
+      )
+    })
+    .collect::<Vec<_>>();
+  missing.dedup();
+  if missing.is_empty() {
+    return Ok(());
+  }
+  Err(crate::Error::new(
+    crate::Status::InvalidArg,
+    format!(
+      "Classes referenced by exports are not registered: {}",
+      missing.join(", ")
+    ),
+  ))
+}
+
+#[inline]
+/// Get `JsFunction` from defined Rust `fn`
+/// ```rust
+/// #[napi]
+/// fn some_fn() -> u32 {
+///     1
+/// }
+///
+/// #[napi]
+/// fn return_some_fn() -> Result<JsFunction> {
+///     get_js_function(some_fn_js_function)
+/// }
+/// ```
+///
+/// ```js
+/// returnSomeFn()(); // 1
+/// ```
+///
+/// Calling it while the module is being registered on the same thread, e.g. from an export callback,
+/// returns an error.
+pub fn get_js_function(env: &Env, raw_fn: ExportRegisterCallback) -> Result<JsFunction> {
+  #[cfg(not(feature = "noop"))]
+  wait_first_thread_registered()?;
+  FN_REGISTER_MAP.borrow_mut(|inner| {
+    inner
+      .get(&raw_fn)
+      .and_then(|(cb, name)| {
+        let mut function = ptr::null_mut();
+        let name_len = name.len() - 1;
+        let fn_name = unsafe { CStr::from_bytes_with_nul_unchecked(name.as_bytes()) };
+        check_status!(unsafe {
+          sys::napi_create_function(
+            env.0,
+            fn_name.as_ptr(),
+            name_len,
+            *cb,
+            ptr::null_mut(),
+            &mut function,
+          )
+        })
+        .ok()?;
+        Some(JsFunction(Value {
+          env: env.0,
+          value: function,
+          value_type: ValueType::Function,
+        }))
+      })
+      .ok_or_else(|| {
+        crate::Error::new(
+          crate::Status::InvalidArg,
+          "JavaScript function does not exist".to_owned(),
+        )
+      })
+  })
+}
+
+/// Get `C Callback` from defined Rust `fn`
+/// ```rust
+/// #[napi]
+/// fn some_fn() -> u32 {
+///     1
+/// }
+///
+/// #[napi]
+/// fn create_obj(env: Env) -> Result<JsObject> {
+///     let mut obj = env.create_object()?;
+///     obj.define_property(&[Property::new("getter")?.with_getter(get_c_callback(some_fn_js_function)?)])?;
+///     Ok(obj)
+/// }
+/// ```
+///
+/// ```js
+/// console.log(createObj().getter) // 1
+/// ```
+///
+pub fn get_c_callback(raw_fn: ExportRegisterCallback) -> Result<crate::Callback> {
+  get_c_callback_with_name(raw_fn).map(|(cb, _name)| cb)
+}
+
+/// Get `C Callback` and the JavaScript name from defined Rust `fn`
+/// ```rust,ignore
+/// #[napi]
+/// fn some_fn() -> u32 {
+///     1
+/// }
+///
+/// #[napi]
+/// fn create_obj(env: Env) -> Result<JsObject> {
+///     let mut obj = env.create_object()?;
+///     let (cb, name) = get_c_callback_with_name(some_fn_js_function)?;
+///     obj.define_properties(&[Property::new(name)?.with_getter(cb)])?;
+///     Ok(obj)
+/// }
+/// ```
+///
+/// ```js
+/// console.log(createObj().someFn) // 1
+/// ```
+///
+pub fn get_c_callback_with_name(
+  raw_fn: ExportRegisterCallback,
+) -> Result<(crate::Callback, &'static str)> {
+  FN_REGISTER_MAP.borrow_mut(|inner| {
+    inner
+      .get(&raw_fn)
+      .and_then(|(cb, name)| cb.map(|cb| (cb, name.trim_end_matches('\0'))))
+      .ok_or_else(|| {
+        crate::Error::new(
+          crate::Status::InvalidArg,
+          "JavaScript function does not exist".to_owned(),
+        )
+      })
+  })
+}
+
+#[cfg(all(windows, not(feature = "noop")))]
+#[ctor::ctor]
+fn load_host() {
+  unsafe {
+    sys::setup();
+  }
+}
+
+#[cfg(all(target_family = "wasm", not(feature = "noop")))]
+#[no_mangle]
+unsafe extern "C" fn napi_register_wasm_v1(
+  env: sys::napi_env,
+  exports: sys::napi_value,
+) -> sys::napi_value {
+  unsafe { napi_register_module_v1(env, exports) }
+}
+
+#[cfg(not(feature = "noop"))]
+#[no_mangle]
+/// Register the n-api module exports.
+///
+/// # Safety
+/// This method is meant to be called by Node.js while importing the n-api module.
+/// Only call this method if the current module is **not** imported by a node-like runtime.
+///
+/// Arguments `env` and `exports` must **not** be null.
+pub unsafe extern "C" fn napi_register_module_v1(
+  env: sys::napi_env,
+  exports: sys::napi_value,
+) -> sys::napi_value {
+  if IS_FIRST_MODULE.load(Ordering::SeqCst) {
+    *FIRST_MODULE_REGISTERING_THREAD
+      .write()
+      .expect("Write FIRST_MODULE_REGISTERING_THREAD failed") = Some(std::thread::current().id());
+    IS_FIRST_MODULE.store(false, Ordering::SeqCst);
+  } else if let Err(e) = wait_first_thread_registered() {
+    unsafe { JsError::from(e).throw_into(env) };
+    return exports;
+  }
+
+  super::panic::install_panic_location_hook();
+
+  if let Err(e) = run_module_init().and_then(|_| {
+    let pre_init = MODULE_PRE_INIT.read().expect("Read MODULE_PRE_INIT failed");
+    pre_init
+      .iter()
+      .try_for_each(|callback| unsafe { callback(env, exports) })
+  }) {
+    unsafe { JsError::from(e).throw_into(env) };
+    finish_first_module_registration();
+    return exports;
+  }
+
+  let export_limit = EXPORT_LIMIT.load(Ordering::Relaxed);
+  let export_count = count_exports();
+  if export_count > export_limit {
+    let e = crate::Error::new(
+      crate::Status::GenericFailure,
+      format!(
+        "Module has {} exports and classes, more than the limit of {} set by `set_export_limit`",
+        export_count, export_limit
+      ),
+    );
+    unsafe { JsError::from(e).throw_into(env) };
+    finish_first_module_registration();
+    return exports;
+  }
+
+  let register_callback = MODULE_REGISTER_CALLBACK
+    .read()
+    .expect("Read MODULE_REGISTER_CALLBACK in napi_register_module_v1 failed")
+    .clone();
+  let class_keys = MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
+    inner
+      .iter()
+      .flat_map(|(rust_name, js_mods)| {
+        js_mods
+          .iter()
+          .map(|(js_mod, (js_name, ..))| (*rust_name, *js_mod, *js_name))
+      })
+      .collect::<Vec<_>>()
+  });
+  let sorted = sort_after(
+    register_callback,
+    |(js_mod, (name, _))| (*js_mod, *name),
+    &EXPORTS_AFTER.read().expect("Read EXPORTS_AFTER failed"),
+    "export",
+  )
+  .and_then(|register_callback| {
+    let class_keys = sort_after(
+      class_keys,
+      |(_, _, js_name)| (None, *js_name),
+      &CLASSES_AFTER
+        .read()
+        .expect("Read CLASSES_AFTER failed")
+        .iter()
+        .map(|(js_name, anchor)| ((None, *js_name), (None, *anchor)))
+        .collect::<Vec<_>>(),
+      "class",
+    )?;
+    validate_class_references()?;
+    Ok((register_callback, class_keys))
+  });
+  let (register_callback, class_keys) = match sorted {
+    Ok(sorted) => sorted,
+    Err(e) => {
+      unsafe { JsError::from(e).throw_into(env) };
+      finish_first_module_registration();
+      return exports;
+    }
+  };
+
+  let filter = *EXPORT_FILTER.read().expect("Read EXPORT_FILTER failed");
+
+  // namespace objects created so far, so every export of a namespace reuses the same object
+  // instead of reading it back from `exports`
+  let mut exports_objects: HashMap<String, sys::napi_value> = HashMap::default();
+
+  // in registration order across the namespaces, so an export comes after its anchor,
+  // an export without a namespace is installed on `exports` directly
+  for (js_mod, (name, callback)) in register_callback.iter() {
+    if !is_export_enabled(filter, *js_mod, name) {
+      continue;
+    }
+    let exported_object =
+      unsafe { get_or_create_js_mod(env, exports, *js_mod, &mut exports_objects) };
+    unsafe { install_named_export(env, exported_object, name, *callback) };
+  }
+
+  {
+    let dynamic_exports = MODULE_DYNAMIC_EXPORTS
+      .read()
+      .expect("Read MODULE_DYNAMIC_EXPORTS failed");
+    for (js_mod, name, callback, _) in dynamic_exports.iter() {
+      if !is_export_enabled(filter, js_mod.as_deref(), name) {
+        continue;
+      }
+      unsafe {
+        let exported_object =
+          get_or_create_js_mod(env, exports, js_mod.as_deref(), &mut exports_objects);
+        install_export(env, exported_object, name, *callback);
+      }
+    }
+  }
+
+  {
+    let raw_exports = MODULE_RAW_EXPORTS
+      .read()
+      .expect("Read MODULE_RAW_EXPORTS failed");
+    for (js_mod, name, callback) in raw_exports.iter() {
+      if !is_export_enabled(filter, *js_mod, name) {
+        continue;
+      }
+      let js_mod = js_mod.map(|js_mod| format!("{}\0", js_mod.trim_end_matches('\0')));
+      unsafe {
+        let exported_object =
+          get_or_create_js_mod(env, exports, js_mod.as_deref(), &mut exports_objects);
+        if let Err(e) = define_raw_export(env, exported_object, name, *callback) {
+          JsError::from(e).throw_into(env)
+        }
+      }
+    }
+  }
 
   {
-    let mut register_callback = MODULE_REGISTER_CALLBACK
-      .write()
-      .expect("Write MODULE_REGISTER_CALLBACK in napi_register_module_v1 failed");
-    register_callback
-      .iter_mut()
-      .fold(
-        HashMap::<Option<&'static str>, Vec<(&'static str, ExportRegisterCallback)>>::new(),
-        |mut acc, (js_mod, item)| {
-          if let Some(k) = acc.get_mut(js_mod) {
-            k.push(*item);
-          } else {
-            acc.insert(*js_mod, vec![*item]);
-          }
-          acc
-        },
-      )
-      .iter()
-      .for_each(|(js_mod, items)| {
-        let mut exports_js_mod = ptr::null_mut();
-        if let Some(js_mod_str) = js_mod {
-          let mod_name_c_str =
-            unsafe { CStr::from_bytes_with_nul_unchecked(js_mod_str.as_bytes()) };
-          if exports_objects.contains(*js_mod_str) {
-            check_status_or_throw!(
-              env,
-              unsafe {
-                sys::napi_get_named_property(
-                  env,
-                  exports,
-                  mod_name_c_str.as_ptr(),
-                  &mut exports_js_mod,
-                )
-              },
-              "Get mod {} from exports failed",
-              js_mod_str,
-            );
-          } else {
-            check_status_or_throw!(
-              env,
-              unsafe { sys::napi_create_object(env, &mut exports_js_mod) },
-              "Create export JavaScript Object [{}] failed",
-              js_mod_str
-            );
-            check_status_or_throw!(
-              env,
-              unsafe {
-                sys::napi_set_named_property(env, exports, mod_name_c_str.as_ptr(), exports_js_mod)
-              },
-              "Set exports Object [{}] into exports object failed",
-              js_mod_str
-            );
-            exports_objects.insert(js_mod_str.to_string());
-          }
+    let globals = MODULE_GLOBALS.read().expect("Read MODULE_GLOBALS failed");
+    for (name, callback) in globals.iter() {
+      if let Err(e) = unsafe { install_global(env, name, *callback) } {
+        unsafe { JsError::from(e).throw_into(env) }
+      }
+    }
+  }
+
+  #[cfg(feature = "napi8")]
+  {
+    let enums = MODULE_ENUMS.read().expect("Read MODULE_ENUMS failed");
+    for (js_mod, name, variants) in enums.iter() {
+      if !is_export_enabled(filter, *js_mod, name) {
+        continue;
+      }
+      let js_mod = js_mod.map(|js_mod| format!("{}\0", js_mod.trim_end_matches('\0')));
+      unsafe {
+        let exported_object =
+          get_or_create_js_mod(env, exports, js_mod.as_deref(), &mut exports_objects);
+        if let Err(e) = create_enum_object(env, variants).and_then(|enum_object| {
+          let js_name = std::ffi::CString::new(name.trim_end_matches('\0'))?;
+          check_status!(
+            sys::napi_set_named_property(env, exported_object, js_name.as_ptr(), enum_object),
+            "Failed to register enum `{}`",
+            name,
+          )
+        }) {
+          JsError::from(e).throw_into(env)
         }
-        for (name, callback) in items {
-          unsafe {
-            let js_name = CStr::from_bytes_with_nul_unchecked(name.as_bytes());
-            if let Err(e) = callback(env).and_then(|v| {
-              let exported_object = if exports_js_mod.is_null() {
-                exports
-              } else {
-                exports_js_mod
-              };
-              check_status!(
-                sys::napi_set_named_property(env, exported_object, js_name.as_ptr(), v),
-                "Failed to register export `{}`",
-                name,
-              )
-            }) {
-              JsError::from(e).throw_into(env)
-            }
-          }
+      }
+    }
+  }
+
+  {
+    let accessors = MODULE_ACCESSORS
+      .read()
+      .expect("Read MODULE_ACCESSORS failed");
+    for (js_mod, name, getter, setter) in accessors.iter() {
+      if !is_export_enabled(filter, *js_mod, name) {
+        continue;
+      }
+      let js_mod = js_mod.map(|js_mod| format!("{}\0", js_mod.trim_end_matches('\0')));
+      unsafe {
+        let exported_object =
+          get_or_create_js_mod(env, exports, js_mod.as_deref(), &mut exports_objects);
+        if let Err(e) = define_module_accessor(env, exported_object, name, *getter, *setter) {
+          JsError::from(e).throw_into(env)
         }
-      });
+      }
+    }
   }
 
   let mut registered_classes = HashMap::new();
+  let mut pending_lazy_classes = HashMap::new();
 
   MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
-    inner.iter().for_each(|(rust_name, js_mods)| {
-      for (js_mod, (js_name, props)) in js_mods {
-        let mut exports_js_mod = ptr::null_mut();
+    for (rust_name, js_mod, _) in class_keys.iter() {
+      let Some((js_name, props, class_finalizer, class_data)) =
+        inner.get(rust_name).and_then(|js_mods| js_mods.get(js_mod))
+      else {
+        continue;
+      };
+      if !is_export_enabled(filter, *js_mod, js_name) {
+        continue;
+      }
+      let is_rejected_name = REJECTED_CLASS_NAMES
+        .read()
+        .expect("Read REJECTED_CLASS_NAMES failed")
+        .contains(&(*rust_name, *js_mod));
+      if is_rejected_name {
+        let e = crate::Error::new(
+          crate::Status::InvalidArg,
+          format!(
+            "Class name `{}` of struct `{}` is not a valid JavaScript identifier, \
+             add `#[napi(any_js_name)]` to register it anyway",
+            js_name.trim_end_matches('\0'),
+            rust_name
+          ),
+        );
+        unsafe { JsError::from(e).throw_into(env) };
+        continue;
+      }
+      let exported_object =
+        unsafe { get_or_create_js_mod(env, exports, *js_mod, &mut exports_objects) };
+      let result = if let Some(index) = lazy_class_index((*rust_name, *js_mod)) {
+        pending_lazy_classes.insert((*rust_name, *js_mod), (env, *js_name));
+        unsafe { define_lazy_class_getter(env, exported_object, js_name, index) }
+      } else {
         unsafe {
-          if let Some(js_mod_str) = js_mod {
-            let mod_name_c_str = CStr::from_bytes_with_nul_unchecked(js_mod_str.as_bytes());
-            if exports_objects.contains(*js_mod_str) {
-              check_status_or_throw!(
-                env,
-                sys::napi_get_named_property(
-                  env,
-                  exports,
-                  mod_name_c_str.as_ptr(),
-                  &mut exports_js_mod,
-                ),
-                "Get mod {} from exports failed",
-                js_mod_str,
-              );
-            } else {
-              check_status_or_throw!(
-                env,
-                sys::napi_create_object(env, &mut exports_js_mod),
-                "Create export JavaScript Object [{}] failed",
-                js_mod_str
-              );
-              check_status_or_throw!(
-                env,
-                sys::napi_set_named_property(env, exports, mod_name_c_str.as_ptr(), exports_js_mod),
-                "Set exports Object [{}] into exports object failed",
-                js_mod_str
-              );
-              exports_objects.insert(js_mod_str.to_string());
-            }
-          }
-          let (ctor, props): (Vec<_>, Vec<_>) = props.iter().partition(|prop| prop.is_ctor);
-
-          let ctor = ctor
-            .first()
-            .map(|c| c.raw().method.unwrap())
-            .unwrap_or(noop);
-          let raw_props: Vec<_> = props.iter().map(|prop| prop.raw()).collect();
-
-          let js_class_name = CStr::from_bytes_with_nul_unchecked(js_name.as_bytes());
-          let mut class_ptr = ptr::null_mut();
-
-          check_status_or_throw!(
+          define_class(
             env,
-            sys::napi_define_class(
-              env,
-              js_class_name.as_ptr(),
-              js_name.len() - 1,
-              Some(ctor),
-              ptr::null_mut(),
-              raw_props.len(),
-              raw_props.as_ptr(),
-              &mut class_ptr,
-            ),
-            "Failed to register class `{}` generate by struct `{}`",
-            &js_name,
-            &rust_name
-          );
-
-          let mut ctor_ref = ptr::null_mut();
-          sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref);
-
+            rust_name,
+            js_name,
+            props,
+            *class_finalizer,
+            *class_data,
+          )
+        }
+        .and_then(|(class, ctor_ref)| {
           registered_classes.insert(js_name.to_string(), ctor_ref);
-
-          check_status_or_throw!(
-            env,
-            sys::napi_set_named_property(
-              env,
-              if exports_js_mod.is_null() {
-                exports
-              } else {
-                exports_js_mod
-              },
-              js_class_name.as_ptr(),
-              class_ptr
-            ),
+          let js_class_name = unsafe { CStr::from_bytes_with_nul_unchecked(js_name.as_bytes()) };
+          check_status!(
+            unsafe {
+              sys::napi_set_named_property(env, exported_object, js_class_name.as_ptr(), class)
+            },
             "Failed to register class `{}` generate by struct `{}`",
             &js_name,
             &rust_name
-          );
-        }
+          )
+        })
+      };
+      if let Err(e) = result {
+        unsafe { JsError::from(e).throw_into(env) };
       }
-    });
+    }
 
     REGISTERED_CLASSES.borrow_mut(|map| {
       map.insert(
@@ -455,6 +1710,19 @@ pub unsafe extern "C" fn napi_register_module_v1(
     });
   });
 
+  PENDING_LAZY_CLASSES.borrow_mut(|map| {
+    map.insert(std::thread::current().id(), pending_lazy_classes);
+  });
+
+  let separator = *FLATTEN_NAMESPACES_SEPARATOR
+    .read()
+    .expect("Read FLATTEN_NAMESPACES_SEPARATOR failed");
+  if let Some(separator) = separator {
+    if let Err(e) = unsafe { flatten_js_mods(env, exports, &exports_objects, separator) } {
+      unsafe { JsError::from(e).throw_into(env) };
+    }
+  }
+
   #[cfg(feature = "compat-mode")]
   {
     let module_exports = MODULE_EXPORTS.read().expect("Read MODULE_EXPORTS failed");
@@ -465,6 +1733,20 @@ pub unsafe extern "C" fn napi_register_module_v1(
     })
   }
 
+  if ES_MODULE_MARKER.load(Ordering::Relaxed) {
+    if let Err(e) = unsafe { define_es_module_marker(env, exports) } {
+      unsafe { JsError::from(e).throw_into(env) };
+    }
+  }
+
+  let exports = match unsafe { create_module_main(env, exports) } {
+    Ok(module) => module,
+    Err(e) => {
+      unsafe { JsError::from(e).throw_into(env) };
+      exports
+    }
+  };
+
   #[cfg(all(
     not(any(target_os = "macos", target_family = "wasm")),
     feature = "napi4",
@@ -485,18 +1767,777 @@ pub unsafe extern "C" fn napi_register_module_v1(
       )
     };
   }
+  ENV_ALIVE_FLAGS.borrow_mut(|flags| {
+    flags.insert(env as usize, Arc::new(AtomicBool::new(true)));
+  });
+  #[cfg(feature = "napi3")]
+  check_status_or_throw!(
+    env,
+    unsafe { sys::napi_add_env_cleanup_hook(env, Some(mark_env_torn_down), env.cast()) },
+    "Failed to add env teardown cleanup hook"
+  );
+
   #[cfg(all(feature = "napi4", not(target_family = "wasm")))]
   create_custom_gc(env);
-  FIRST_MODULE_REGISTERED.store(true, Ordering::SeqCst);
+  finish_first_module_registration();
   exports
 }
 
+#[cfg(not(feature = "noop"))]
+/// Define the class `js_name` generated by the struct `rust_name`, returns the class and a reference to it.
+unsafe fn define_class(
+  env: sys::napi_env,
+  rust_name: &str,
+  js_name: &'static str,
+  props: &[Vec<Property>],
+  class_finalizer: Option<fn()>,
+  class_data: Option<ClassDataFactory>,
+) -> Result<(sys::napi_value, sys::napi_ref)> {
+  // in the order `register_class` keeps the calls in, see its docs
+  let (ctor, props): (Vec<_>, Vec<_>) = props.iter().flatten().partition(|prop| prop.is_ctor);
+
+  let ctor = ctor
+    .first()
+    .map(|c| c.raw().method.unwrap())
+    .unwrap_or(noop);
+  let raw_props: Vec<_> = props.iter().map(|prop| prop.raw()).collect();
+
+  let js_class_name = unsafe { CStr::from_bytes_with_nul_unchecked(js_name.as_bytes()) };
+  let mut class_ptr = ptr::null_mut();
+  let class_data = Box::into_raw(Box::new(ClassData {
+    js_name: js_name.trim_end_matches('\0'),
+    data: class_data.map(|create| create()),
+  }));
+
+  let status = unsafe {
+    sys::napi_define_class(
+      env,
+      js_class_name.as_ptr(),
+      js_name.len() - 1,
+      Some(ctor),
+      class_data.cast(),
+      raw_props.len(),
+      raw_props.as_ptr(),
+      &mut class_ptr,
+    )
+  };
+  if status != sys::Status::napi_ok {
+    drop(unsafe { Box::from_raw(class_data) });
+  }
+  check_status!(
+    status,
+    "Failed to register class `{}` generate by struct `{}`",
+    &js_name,
+    &rust_name
+  )?;
+
+  // freed at env cleanup from here on, also when one of the steps below fails.
+  // Without env cleanup hooks the class data lives until the process exits.
+  #[cfg(feature = "napi3")]
+  {
+    let status =
+      unsafe { sys::napi_add_env_cleanup_hook(env, Some(drop_class_data), class_data.cast()) };
+    if status != sys::Status::napi_ok {
+      // the class is not exported yet, its constructor can't be called anymore
+      drop(unsafe { Box::from_raw(class_data) });
+    }
+    check_status!(
+      status,
+      "Failed to add class data cleanup hook of class `{}`",
+      &js_name
+    )?;
+  }
+
+  if let Some(to_primitive) = props.iter().find(|prop| prop.is_to_primitive) {
+    unsafe { define_to_primitive(env, class_ptr, to_primitive) }?;
+  }
+  unsafe { inherit_parent_class(env, js_name, class_ptr) }?;
+
+  let mut ctor_ref = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref) },
+    "Failed to create reference of class `{}`",
+    &js_name
+  )?;
+
+  #[cfg(feature = "napi3")]
+  if let Some(class_finalizer) = class_finalizer {
+    let finalize_data = Box::into_raw(Box::new((env, js_name, ctor_ref, class_finalizer)));
+    let status =
+      unsafe { sys::napi_add_env_cleanup_hook(env, Some(finalize_class), finalize_data.cast()) };
+    if status != sys::Status::napi_ok {
+      drop(unsafe { Box::from_raw(finalize_data) });
+    }
+    check_status!(
+      status,
+      "Failed to add class finalizer of class `{}`",
+      &js_name
+    )?;
+  }
+  #[cfg(not(feature = "napi3"))]
+  let _ = class_finalizer;
+
+  Ok((class_ptr, ctor_ref))
+}
+
+#[cfg(not(feature = "noop"))]
+/// Define the pending `#[napi(lazy)]` class `js_name` of the current thread and register its constructor.
+///
+/// Returns `None` if there is no such class, or after throwing the error if it can't be defined.
+unsafe fn define_pending_lazy_class(js_name: &str) -> Option<sys::napi_ref> {
+  let key = PENDING_LAZY_CLASSES.borrow_mut(|map| {
+    map
+      .get(&std::thread::current().id())?
+      .iter()
+      .find(|(_, (_, pending_js_name))| *pending_js_name == js_name)
+      .map(|(key, _)| *key)
+  })?;
+  unsafe { define_pending_lazy_class_by_key(key) }
+}
+
+#[cfg(not(feature = "noop"))]
+/// Define the pending `#[napi(lazy)]` class of the struct `rust_name` in the namespace `js_mod`
+/// of the current thread and register its constructor, see [`define_pending_lazy_class`].
+unsafe fn define_pending_lazy_class_by_key((rust_name, js_mod): ClassKey) -> Option<sys::napi_ref> {
+  let current_id = std::thread::current().id();
+  let (env, js_name) = PENDING_LAZY_CLASSES.borrow_mut(|map| {
+    map
+      .get_mut(&current_id)
+      .and_then(|classes| classes.remove(&(rust_name, js_mod)))
+  })?;
+  let class = MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
+    inner
+      .get(rust_name)
+      .and_then(|js_mods| js_mods.get(&js_mod))
+      .map(|(_, props, class_finalizer, class_data)| unsafe {
+        define_class(
+          env,
+          rust_name,
+          js_name,
+          props,
+          *class_finalizer,
+          *class_data,
+        )
+      })
+  })?;
+  match class {
+    Ok((_, ctor_ref)) => {
+      REGISTERED_CLASSES.borrow_mut(|map| {
+        map
+          .entry(current_id)
+          .or_default()
+          .borrow_mut(|classes| classes.insert(js_name.to_string(), ctor_ref));
+      });
+      Some(ctor_ref)
+    }
+    Err(e) => {
+      unsafe { JsError::from(e).throw_into(env) };
+      None
+    }
+  }
+}
+
+#[cfg(not(feature = "noop"))]
+/// Define `object[js_name]` as a getter which defines the `#[napi(lazy)]` class on first access
+/// and replaces itself with the class, `index` is the index of the class in `LAZY_CLASSES`.
+unsafe fn define_lazy_class_getter(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  js_name: &'static str,
+  index: usize,
+) -> Result<()> {
+  let descriptor = sys::napi_property_descriptor {
+    utf8name: js_name.as_ptr().cast(),
+    name: ptr::null_mut(),
+    method: None,
+    getter: Some(get_lazy_class),
+    setter: None,
+    value: ptr::null_mut(),
+    attributes: (PropertyAttributes::Enumerable | PropertyAttributes::Configurable).into(),
+    // `LAZY_CLASSES` is only pushed to while the addon is loaded, the index stays valid
+    data: index as *mut std::ffi::c_void,
+  };
+  check_status!(
+    unsafe { sys::napi_define_properties(env, object, 1, &descriptor) },
+    "Failed to register lazy class `{}`",
+    js_name.trim_end_matches('\0'),
+  )
+}
+
+#[cfg(not(feature = "noop"))]
+unsafe extern "C" fn get_lazy_class(
+  env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+) -> sys::napi_value {
+  match unsafe { define_lazy_class_on_access(env, callback_info) } {
+    Ok(class) => class,
+    Err(e) => {
+      // keeps the error thrown while defining the class if there is one
+      unsafe { JsError::from(e).throw_into(env) };
+      ptr::null_mut()
+    }
+  }
+}
+
+#[cfg(not(feature = "noop"))]
+unsafe fn define_lazy_class_on_access(
+  env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+) -> Result<sys::napi_value> {
+  let mut this = ptr::null_mut();
+  let mut data = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        callback_info,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        &mut this,
+        &mut data,
+      )
+    },
+    "Failed to get the callback info of a lazy class"
+  )?;
+  // `data` is the index of the class in `LAZY_CLASSES`, see `define_lazy_class_getter`
+  let (rust_name, js_mod) = LAZY_CLASSES.read().expect("Read LAZY_CLASSES failed")[data as usize];
+  let js_name = MODULE_CLASS_PROPERTIES
+    .borrow_mut(|inner| {
+      inner
+        .get(rust_name)
+        .and_then(|js_mods| js_mods.get(&js_mod))
+        .map(|(js_name, ..)| *js_name)
+    })
+    .ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Class of struct `{}` is not registered", rust_name),
+      )
+    })?;
+  // defined by `get_class_constructor` already if an instance was created before the access
+  let ctor_ref = unsafe { define_pending_lazy_class_by_key((rust_name, js_mod)) }
+    .or_else(|| get_defined_class_constructor(js_name))
+    .ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        format!(
+          "Failed to get constructor of class `{}`",
+          js_name.trim_end_matches('\0')
+        ),
+      )
+    })?;
+  let mut class = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_reference_value(env, ctor_ref, &mut class) },
+    "Failed to get constructor of class `{}`",
+    js_name.trim_end_matches('\0')
+  )?;
+  let mut value_type = 0;
+  check_status!(unsafe { sys::napi_typeof(env, this, &mut value_type) })?;
+  if value_type == sys::ValueType::napi_object || value_type == sys::ValueType::napi_function {
+    // replace the getter, later reads get the class as a plain property like an eager class
+    let descriptor = sys::napi_property_descriptor {
+      utf8name: js_name.as_ptr().cast(),
+      name: ptr::null_mut(),
+      method: None,
+      getter: None,
+      setter: None,
+      value: class,
+      attributes: (PropertyAttributes::Writable
+        | PropertyAttributes::Enumerable
+        | PropertyAttributes::Configurable)
+        .into(),
+      data: ptr::null_mut(),
+    };
+    check_status!(
+      unsafe { sys::napi_define_properties(env, this, 1, &descriptor) },
+      "Failed to define lazy class `{}`",
+      js_name.trim_end_matches('\0')
+    )?;
+  }
+  Ok(class)
+}
+
+#[cfg(all(feature = "napi3", not(feature = "noop")))]
+unsafe extern "C" fn finalize_class(data: *mut std::ffi::c_void) {
+  let (env, js_name, ctor_ref, class_finalizer) =
+    *unsafe { Box::from_raw(data.cast::<(sys::napi_env, &'static str, sys::napi_ref, fn())>()) };
+  REGISTERED_CLASSES.borrow_mut(|map| {
+    if let Some(classes) = map.get(&std::thread::current().id()) {
+      classes.borrow_mut(|classes| classes.remove(js_name));
+    }
+  });
+  unsafe { sys::napi_delete_reference(env, ctor_ref) };
+  class_finalizer();
+}
+
+#[cfg(all(feature = "napi3", not(feature = "noop")))]
+unsafe extern "C" fn drop_class_data(data: *mut std::ffi::c_void) {
+  drop(unsafe { Box::from_raw(data.cast::<ClassData>()) });
+}
+
+#[cfg(not(feature = "noop"))]
+/// [`install_export`] for an export registered by `#[napi]`, whose name must be a valid JavaScript identifier
+/// unless it's registered with `#[napi(any_js_name)]`, see [`reject_export_name`].
+unsafe fn install_named_export(
+  env: sys::napi_env,
+  exported_object: sys::napi_value,
+  name: &str,
+  callback: ExportRegisterCallback,
+) {
+  let rejected_by = REJECTED_EXPORT_NAMES.borrow_mut(|inner| inner.get(&callback).copied());
+  if let Some(rust_name) = rejected_by {
+    let e = crate::Error::new(
+      crate::Status::InvalidArg,
+      format!(
+        "Export name `{}` of function `{}` is not a valid JavaScript identifier, \
+         add `#[napi(any_js_name)]` to export it anyway",
+        name.trim_end_matches('\0'),
+        rust_name
+      ),
+    );
+    unsafe { JsError::from(e).throw_into(env) };
+    return;
+  }
+  unsafe { install_export(env, exported_object, name, callback) };
+}
+
+#[cfg(not(feature = "noop"))]
+/// Install the export created by `callback` as `exported_object[name]`, `name` must be nul terminated.
+///
+/// Errors are thrown, the export is skipped if its env gate is not enabled.
+unsafe fn install_export(
+  env: sys::napi_env,
+  exported_object: sys::napi_value,
+  name: &str,
+  callback: ExportRegisterCallback,
+) {
+  let env_var = EXPORT_ENV_GATES.borrow_mut(|inner| inner.get(&callback).copied());
+  if let Some(env_var) = env_var {
+    match unsafe { is_env_var_enabled(env, env_var) } {
+      Ok(true) => {}
+      Ok(false) => return,
+      Err(e) => {
+        unsafe { JsError::from(e).throw_into(env) };
+        return;
+      }
+    }
+  }
+  unsafe {
+    let js_name = CStr::from_bytes_with_nul_unchecked(name.as_bytes());
+    if let Err(e) = catch_register_panic(env, name, || callback(env)).and_then(|v| {
+      keep_export_reference_in_env(env, callback, v)?;
+      check_status!(
+        sys::napi_set_named_property(env, exported_object, js_name.as_ptr(), v),
+        "Failed to register export `{}`",
+        name,
+      )
+    }) {
+      JsError::from(e).throw_into(env)
+    }
+  }
+}
+
+#[cfg(not(feature = "noop"))]
+unsafe fn install_global(
+  env: sys::napi_env,
+  name: &'static str,
+  callback: ExportRegisterCallback,
+) -> Result<()> {
+  let name = name.trim_end_matches('\0');
+  let mut global = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_global(env, &mut global) },
+    "Failed to get global object"
+  )?;
+  let mut key = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_string_utf8(env, name.as_ptr().cast(), name.len(), &mut key) },
+    "Failed to create the name of global `{}`",
+    name,
+  )?;
+  let mut exists = false;
+  check_status!(
+    unsafe { sys::napi_has_own_property(env, global, key, &mut exists) },
+    "Failed to check global `{}`",
+    name,
+  )?;
+  if exists {
+    return Err(Error::new(
+      Status::GenericFailure,
+      format!(
+        "Global `{}` is already defined, it may be installed by another addon",
+        name
+      ),
+    ));
+  }
+  let value = unsafe { catch_register_panic(env, name, || callback(env)) }?;
+  let descriptor = sys::napi_property_descriptor {
+    utf8name: ptr::null(),
+    name: key,
+    method: None,
+    getter: None,
+    setter: None,
+    value,
+    attributes: (PropertyAttributes::Writable | PropertyAttributes::Configurable).into(),
+    data: ptr::null_mut(),
+  };
+  check_status!(
+    unsafe { sys::napi_define_properties(env, global, 1, &descriptor) },
+    "Failed to install global `{}`",
+    name,
+  )
+}
+
+#[cfg(not(feature = "noop"))]
+/// Get the namespace object of `js_mod` from `exports`, creating it on first use.
+///
+/// Returns `exports` itself for the root namespace.
+unsafe fn get_or_create_js_mod(
+  env: sys::napi_env,
+  exports: sys::napi_value,
+  js_mod: Option<&str>,
+  exports_objects: &mut HashMap<String, sys::napi_value>,
+) -> sys::napi_value {
+  let Some(js_mod_str) = js_mod else {
+    return exports;
+  };
+  if let Some(exports_js_mod) = exports_objects.get(js_mod_str) {
+    return *exports_js_mod;
+  }
+  let mut exports_js_mod = ptr::null_mut();
+  let mod_name_c_str = unsafe { CStr::from_bytes_with_nul_unchecked(js_mod_str.as_bytes()) };
+  check_status_or_throw!(
+    env,
+    unsafe { sys::napi_create_object(env, &mut exports_js_mod) },
+    "Create export JavaScript Object [{}] failed",
+    js_mod_str
+  );
+  check_status_or_throw!(
+    env,
+    unsafe { sys::napi_set_named_property(env, exports, mod_name_c_str.as_ptr(), exports_js_mod) },
+    "Set exports Object [{}] into exports object failed",
+    js_mod_str
+  );
+  if exports_js_mod.is_null() {
+    return exports;
+  }
+  exports_objects.insert(js_mod_str.to_string(), exports_js_mod);
+  exports_js_mod
+}
+
+#[cfg(not(feature = "noop"))]
+/// Unwinding out of `napi_register_module_v1` is undefined behavior, so a panic in a registration
+/// callback is turned into an error naming the export instead.
+unsafe fn catch_register_panic<F>(env: sys::napi_env, name: &str, f: F) -> Result<sys::napi_value>
+where
+  F: FnOnce() -> Result<sys::napi_value>,
+{
+  super::panic::reset_panic_location();
+  std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+    let message = format!(
+      "Panic while registering export `{}`: {}",
+      name.trim_end_matches('\0'),
+      super::panic::panic_message(&*payload)
+    );
+    Err(unsafe { super::panic::panic_message_to_error(env, message) })
+  })
+}
+
+#[cfg(not(feature = "noop"))]
+unsafe fn flatten_js_mods(
+  env: sys::napi_env,
+  exports: sys::napi_value,
+  exports_objects: &HashMap<String, sys::napi_value>,
+  separator: &str,
+) -> Result<()> {
+  let mut exports = unsafe { Object::from_raw_unchecked(env, exports) };
+  let mut js_mods = exports_objects.iter().collect::<Vec<_>>();
+  js_mods.sort_by(|a, b| a.0.cmp(b.0));
+  let mut flattened_names = vec![];
+  for (js_mod, js_mod_object) in js_mods {
+    let js_mod = js_mod.trim_end_matches('\0');
+    let js_mod_object = unsafe { Object::from_raw_unchecked(env, *js_mod_object) };
+    let mut names = Object::keys(&js_mod_object)?;
+    names.sort();
+    for name in names {
+      let flattened_name = format!("{js_mod}{separator}{name}");
+      if exports.has_own_property(&flattened_name)? {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!(
+            "Flattened export `{}` conflicts with an existing export",
+            flattened_name
+          ),
+        ));
+      }
+      let value: JsUnknown = js_mod_object.get_named_property_unchecked(&name)?;
+      exports.set_named_property(&flattened_name, value)?;
+      flattened_names.push(flattened_name);
+    }
+  }
+  let env = unsafe { Env::from_raw(env) };
+  let mut manifest = env.create_array_with_length(flattened_names.len())?;
+  for (index, name) in flattened_names.iter().enumerate() {
+    manifest.set_element(index as u32, env.create_string(name)?)?;
+  }
+  exports.define_properties(&[Property::new("__napiFlattenedExports")?
+    .with_value(&manifest)
+    .with_property_attributes(PropertyAttributes::Default)])
+}
+
+#[cfg(not(feature = "noop"))]
+/// Define every own property of `exports` on `main`, with the same descriptor.
+/// `__esModule` is left out, Babel and TypeScript interop would resolve `import main from` to `main.default` otherwise.
+const ATTACH_EXPORTS_TO_MAIN: &str = r#"
+for (const key of Reflect.ownKeys(exports)) {
+  if (key === '__esModule') {
+    continue
+  }
+  const current = Object.getOwnPropertyDescriptor(main, key)
+  if (current && !current.configurable) {
+    throw new TypeError(`Export \`${String(key)}\` can't be defined on the main function, it has a property with the same name`)
+  }
+  Object.defineProperty(main, key, Object.getOwnPropertyDescriptor(exports, key))
+}
+"#;
+
+#[cfg(not(feature = "noop"))]
+/// Create the `#[napi(main)]` function with every export as a property, it's returned as the module.
+/// `exports` is returned as is if there is no main function, or if it's disabled by `enabled_if_env`.
+unsafe fn create_module_main(
+  env: sys::napi_env,
+  exports: sys::napi_value,
+) -> Result<sys::napi_value> {
+  let module_main = MODULE_MAIN.read().expect("Read MODULE_MAIN failed");
+  let (name, callback) = match module_main.as_slice() {
+    [] => return Ok(exports),
+    [main] => *main,
+    [first, second, ..] => {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Only one function can be `#[napi(main)]`, found `{}` and `{}`",
+          first.0.trim_end_matches('\0'),
+          second.0.trim_end_matches('\0')
+        ),
+      ))
+    }
+  };
+  let env_var = EXPORT_ENV_GATES.borrow_mut(|inner| inner.get(&callback).copied());
+  if let Some(env_var) = env_var {
+    if !unsafe { is_env_var_enabled(env, env_var) }? {
+      return Ok(exports);
+    }
+  }
+  let main = unsafe { catch_register_panic(env, name, || callback(env)) }?;
+  unsafe { keep_export_reference_in_env(env, callback, main) }?;
+  let env = unsafe { Env::from_raw(env) };
+  let attach = env.compile_function(ATTACH_EXPORTS_TO_MAIN, &["main", "exports"])?;
+  let args = [main, exports];
+  let mut result = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_call_function(
+        env.raw(),
+        exports,
+        attach.raw(),
+        args.len(),
+        args.as_ptr(),
+        &mut result,
+      )
+    },
+    "Failed to define the exports on the main function `{}`",
+    name.trim_end_matches('\0')
+  )?;
+  Ok(main)
+}
+
+#[cfg(not(feature = "noop"))]
+unsafe fn define_es_module_marker(env: sys::napi_env, exports: sys::napi_value) -> Result<()> {
+  let mut exports = unsafe { Object::from_raw_unchecked(env, exports) };
+  if exports.has_own_property("__esModule")? {
+    return Ok(());
+  }
+  let env = unsafe { Env::from_raw(env) };
+  exports.define_properties(&[Property::new("__esModule")?
+    .with_value(&env.get_boolean(true)?)
+    .with_property_attributes(PropertyAttributes::Default)])
+}
+
+#[cfg(not(feature = "noop"))]
+/// Create the reference for [`get_exported_js_function`] if the export of `cb` keeps one.
+unsafe fn keep_export_reference_in_env(
+  env: sys::napi_env,
+  cb: ExportRegisterCallback,
+  value: sys::napi_value,
+) -> Result<()> {
+  EXPORT_REFERENCES.borrow_mut(|inner| {
+    if let Some(references) = inner.get_mut(&cb) {
+      let mut reference = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_create_reference(env, value, 1, &mut reference) },
+        "Failed to create the reference of the export"
+      )?;
+      references.insert(env as usize, reference);
+    }
+    Ok(())
+  })
+}
+
+#[cfg(not(feature = "noop"))]
+unsafe fn is_env_var_enabled(env: sys::napi_env, env_var: &str) -> Result<bool> {
+  let global = unsafe { Env::from_raw(env) }.get_global()?;
+  let value = if global.has_named_property("process")? {
+    let process_env = global
+      .get_named_property_unchecked::<Object>("process")?
+      .get_named_property_unchecked::<Object>("env")?;
+    process_env.get::<_, String>(env_var)?
+  } else {
+    std::env::var(env_var).ok()
+  };
+  Ok(value.map_or(false, |value| {
+    !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false")
+  }))
+}
+
+#[cfg(all(feature = "napi8", not(feature = "noop")))]
+unsafe fn create_enum_object(
+  env: sys::napi_env,
+  variants: &[(&'static str, i64)],
+) -> Result<sys::napi_value> {
+  let mut enum_object = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_object(env, &mut enum_object) },
+    "Failed to create enum object"
+  )?;
+  for (variant, value) in variants {
+    let variant = variant.trim_end_matches('\0');
+    let mut js_variant = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_string_utf8(env, variant.as_ptr().cast(), variant.len(), &mut js_variant)
+      },
+      "Failed to create enum variant `{}`",
+      variant
+    )?;
+    let mut js_value = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_int64(env, *value, &mut js_value) },
+      "Failed to create value of enum variant `{}`",
+      variant
+    )?;
+    check_status!(
+      unsafe { sys::napi_set_property(env, enum_object, js_variant, js_value) },
+      "Failed to set enum variant `{}`",
+      variant
+    )?;
+    // Use the JavaScript number as key so the reverse mapping is keyed exactly like
+    // `String(value)`, the same way TypeScript emits numeric enums.
+    check_status!(
+      unsafe { sys::napi_set_property(env, enum_object, js_value, js_variant) },
+      "Failed to set reverse mapping of enum variant `{}`",
+      variant
+    )?;
+  }
+  check_status!(
+    unsafe { sys::napi_object_freeze(env, enum_object) },
+    "Failed to freeze enum object"
+  )?;
+  Ok(enum_object)
+}
+
+#[cfg(not(feature = "noop"))]
+unsafe fn define_raw_export(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  name: &str,
+  callback: crate::Callback,
+) -> Result<()> {
+  let name = name.trim_end_matches('\0');
+  let mut function = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_function(
+        env,
+        name.as_ptr().cast(),
+        name.len(),
+        Some(callback),
+        ptr::null_mut(),
+        &mut function,
+      )
+    },
+    "Failed to create raw export `{}`",
+    name,
+  )?;
+  let js_name = std::ffi::CString::new(name)?;
+  check_status!(
+    unsafe { sys::napi_set_named_property(env, object, js_name.as_ptr(), function) },
+    "Failed to register raw export `{}`",
+    name,
+  )
+}
+
+#[cfg(not(feature = "noop"))]
+unsafe fn define_module_accessor(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  name: &str,
+  getter: Option<crate::Callback>,
+  setter: Option<crate::Callback>,
+) -> Result<()> {
+  let mut prop = Property::new(name.trim_end_matches('\0'))?
+    .with_property_attributes(PropertyAttributes::Enumerable | PropertyAttributes::Configurable);
+  if let Some(getter) = getter {
+    prop = prop.with_getter(getter);
+  }
+  if let Some(setter) = setter {
+    prop = prop.with_setter(setter);
+  }
+  check_status!(
+    unsafe { sys::napi_define_properties(env, object, 1, &prop.raw()) },
+    "Failed to register accessor `{}`",
+    name.trim_end_matches('\0'),
+  )
+}
+
+/// Define the method of `prop` as `[Symbol.toPrimitive]` of the prototype of the class.
+#[cfg(not(feature = "noop"))]
+unsafe fn define_to_primitive(
+  env: sys::napi_env,
+  class: sys::napi_value,
+  prop: &Property,
+) -> Result<()> {
+  let env = unsafe { Env::from_raw(env) };
+  let prototype = unsafe { JsObject::from_raw_unchecked(env.raw(), class) }
+    .get_named_property_unchecked::<JsObject>("prototype")?;
+  let to_primitive_symbol = env
+    .get_global()?
+    .get_named_property_unchecked::<JsObject>("Symbol")?
+    .get_named_property_unchecked::<JsSymbol>("toPrimitive")?;
+  let descriptor = sys::napi_property_descriptor {
+    utf8name: ptr::null(),
+    name: unsafe { to_primitive_symbol.raw() },
+    attributes: (PropertyAttributes::Writable | PropertyAttributes::Configurable).into(),
+    ..prop.raw()
+  };
+  check_status!(
+    unsafe { sys::napi_define_properties(env.raw(), prototype.raw(), 1, &descriptor) },
+    "Failed to define `Symbol.toPrimitive` with `{}`",
+    prop.name.to_string_lossy(),
+  )
+}
+
 #[cfg(not(feature = "noop"))]
 pub(crate) unsafe extern "C" fn noop(
   env: sys::napi_env,
-  _info: sys::napi_callback_info,
+  info: sys::napi_callback_info,
 ) -> sys::napi_value {
-  if !crate::bindgen_runtime::___CALL_FROM_FACTORY.with(|s| s.load(Ordering::Relaxed)) {
+  let mut new_target = ptr::null_mut();
+  unsafe { sys::napi_get_new_target(env, info, &mut new_target) };
+  if new_target.is_null() {
+    unsafe { throw_called_without_new(env, info) };
+  } else if !crate::bindgen_runtime::___CALL_FROM_FACTORY.with(|s| s.load(Ordering::Relaxed)) {
     unsafe {
       sys::napi_throw_error(
         env,
@@ -509,6 +2550,49 @@ pub(crate) unsafe extern "C" fn noop(
   ptr::null_mut()
 }
 
+/// Throw the `TypeError` a JavaScript class throws when it's called as a function, without `new`.
+unsafe fn throw_called_without_new(env: sys::napi_env, info: sys::napi_callback_info) {
+  let js_name = unsafe { ClassData::of_constructor(env, info) }.map_or("", |data| data.js_name);
+  let message = CString::new(format!(
+    "Class constructor {} cannot be invoked without 'new'",
+    js_name
+  ))
+  .unwrap_or_default();
+  unsafe { sys::napi_throw_type_error(env, ptr::null_mut(), message.as_ptr()) };
+}
+
+#[doc(hidden)]
+/// The constructor of a `#[napi(private_constructor)]` class, instances are only created by
+/// returning the struct from Rust, e.g. from a factory function.
+pub unsafe extern "C" fn private_constructor(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut new_target = ptr::null_mut();
+  unsafe { sys::napi_get_new_target(env, info, &mut new_target) };
+  if new_target.is_null() {
+    unsafe { throw_called_without_new(env, info) };
+  } else if !crate::bindgen_runtime::___CALL_FROM_FACTORY.with(|s| s.load(Ordering::Relaxed)) {
+    let mut name = ptr::null_mut();
+    let class_name = match unsafe {
+      sys::napi_get_named_property(env, new_target, "name\0".as_ptr().cast(), &mut name)
+    } {
+      sys::Status::napi_ok => {
+        unsafe { <String as crate::bindgen_prelude::FromNapiValue>::from_napi_value(env, name) }
+          .unwrap_or_default()
+      }
+      _ => String::new(),
+    };
+    let message = CString::new(format!(
+      "Class `{}` has a private constructor, create it with its factory instead",
+      class_name
+    ))
+    .unwrap_or_default();
+    unsafe { sys::napi_throw_type_error(env, ptr::null_mut(), message.as_ptr()) };
+  }
+  ptr::null_mut()
+}
+
 #[cfg(all(feature = "napi4", not(target_family = "wasm"), not(feature = "noop")))]
 fn create_custom_gc(env: sys::napi_env) {
   use std::os::raw::c_char;
@@ -0,0 +1,50 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{Error, Result, Status};
+
+/// The default of [`max_nesting_depth`].
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 256;
+
+static MAX_NESTING_DEPTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_NESTING_DEPTH);
+
+thread_local! {
+  static NESTING_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Set how deep nested JavaScript objects and arrays may be when converting them into `#[napi(object)]` structs,
+/// `serde_json::Value` or types implementing `Deserialize`.
+///
+/// Deeper (or cyclic) input fails with `Status::InvalidArg` instead of overflowing the stack.
+pub fn set_max_nesting_depth(depth: usize) {
+  MAX_NESTING_DEPTH.store(depth, Ordering::Relaxed);
+}
+
+pub fn max_nesting_depth() -> usize {
+  MAX_NESTING_DEPTH.load(Ordering::Relaxed)
+}
+
+/// Counts one level of a nested conversion on the current thread while it's alive.
+#[doc(hidden)]
+pub struct NestingGuard(());
+
+impl NestingGuard {
+  pub fn enter() -> Result<Self> {
+    NESTING_DEPTH.with(|depth| {
+      if depth.get() >= max_nesting_depth() {
+        return Err(Error::new(
+          Status::InvalidArg,
+          "maximum nesting depth exceeded".to_owned(),
+        ));
+      }
+      depth.set(depth.get() + 1);
+      Ok(NestingGuard(()))
+    })
+  }
+}
+
+impl Drop for NestingGuard {
+  fn drop(&mut self) {
+    NESTING_DEPTH.with(|depth| depth.set(depth.get() - 1));
+  }
+}
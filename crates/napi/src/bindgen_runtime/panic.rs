@@ -0,0 +1,87 @@
+use std::any::Any;
+use std::cell::RefCell;
+#[cfg(not(feature = "noop"))]
+use std::sync::Once;
+
+use crate::{sys, Env, Error, JsError, JsObject, NapiValue, Status};
+
+thread_local! {
+  /// `file:line` of the last panic on this thread, recorded by the panic hook
+  static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[cfg(not(feature = "noop"))]
+/// Install a panic hook recording the location of panics for `#[napi(catch_unwind)]`.
+///
+/// The previous hook is still called, so the panic message is printed as before.
+pub(crate) fn install_panic_location_hook() {
+  static INSTALL: Once = Once::new();
+  INSTALL.call_once(|| {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+      if let Some(location) = info.location() {
+        let _ = PANIC_LOCATION.try_with(|l| {
+          if let Ok(mut l) = l.try_borrow_mut() {
+            *l = Some(format!("{}:{}", location.file(), location.line()));
+          }
+        });
+      }
+      previous_hook(info);
+    }));
+  });
+}
+
+#[doc(hidden)]
+/// Forget the location of a panic caught outside of the `catch_unwind` trampoline.
+pub fn reset_panic_location() {
+  let _ = PANIC_LOCATION.try_with(|l| l.borrow_mut().take());
+}
+
+#[doc(hidden)]
+/// Convert a panic caught by the `catch_unwind` trampoline into an `Error`.
+///
+/// The location recorded by the panic hook is exposed as `rustLocation` on the JavaScript error.
+///
+/// # Safety
+///
+/// `env` must be the env of the current call.
+pub unsafe fn panic_to_error(env: sys::napi_env, payload: Box<dyn Any + Send>) -> Error {
+  unsafe { panic_message_to_error(env, panic_message(&*payload)) }
+}
+
+/// The message passed to `panic!`.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+  if let Some(string) = payload.downcast_ref::<String>() {
+    string.clone()
+  } else if let Some(string) = payload.downcast_ref::<&str>() {
+    string.to_string()
+  } else {
+    format!("panic from Rust code: {:?}", payload)
+  }
+}
+
+/// Same as [`panic_to_error`], but with a custom message.
+///
+/// # Safety
+///
+/// `env` must be the env of the current call.
+pub(crate) unsafe fn panic_message_to_error(env: sys::napi_env, message: String) -> Error {
+  let error = Error::new(Status::GenericFailure, message);
+  let Some(location) = PANIC_LOCATION
+    .try_with(|l| l.borrow_mut().take())
+    .ok()
+    .flatten()
+  else {
+    return error;
+  };
+  let env = unsafe { Env::from_raw(env) };
+  let js_error = unsafe { JsError::from(error.clone()).into_value(env.raw()) };
+  let mut js_error = unsafe { JsObject::from_raw_unchecked(env.raw(), js_error) };
+  match env
+    .create_string(&location)
+    .and_then(|location| js_error.set_named_property("rustLocation", location))
+  {
+    Ok(()) => Error::from(js_error.into_unknown()),
+    Err(_) => error,
+  }
+}
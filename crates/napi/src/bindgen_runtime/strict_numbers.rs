@@ -0,0 +1,85 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{bindgen_prelude::Unknown, check_status, sys, type_of, Error, JsRangeError, NapiValue};
+use crate::{Result, Status};
+
+static STRICT_NUMBERS: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+  static STRICT_NUMBERS_SCOPE: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Reject JavaScript numbers which can't be converted into Rust integers losslessly, in every function of the addon.
+///
+/// The setting is global to the process, it applies to every env the addon is loaded in, including worker threads.
+///
+/// By default a number is truncated and wrapped like `napi_get_value_int32` does, so `2.5` becomes `2_u32` and `-1` becomes `u32::MAX`.
+/// In strict mode a `RangeError` is thrown instead for numbers which are not integers or out of the range of the Rust type.
+/// Because `f64` can't represent every `i64`, an `i64` only accepts safe integers, from `Number.MIN_SAFE_INTEGER` to `Number.MAX_SAFE_INTEGER`.
+///
+/// Use `#[napi(strict_numbers)]` to only enable it for the arguments of some functions.
+pub fn set_strict_numbers(enabled: bool) {
+  STRICT_NUMBERS.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether numbers are converted into Rust integers in strict mode, either for the whole process by [`set_strict_numbers`]
+/// or on the current thread while a `#[napi(strict_numbers)]` function converts its arguments.
+pub fn strict_numbers() -> bool {
+  STRICT_NUMBERS.load(Ordering::Relaxed) || STRICT_NUMBERS_SCOPE.with(|scope| scope.get() > 0)
+}
+
+/// Converts numbers in strict mode on the current thread while it's alive.
+#[doc(hidden)]
+pub struct StrictNumbersGuard(());
+
+impl StrictNumbersGuard {
+  pub fn enter() -> Self {
+    STRICT_NUMBERS_SCOPE.with(|scope| scope.set(scope.get() + 1));
+    StrictNumbersGuard(())
+  }
+}
+
+impl Drop for StrictNumbersGuard {
+  fn drop(&mut self) {
+    STRICT_NUMBERS_SCOPE.with(|scope| scope.set(scope.get() - 1));
+  }
+}
+
+/// Get a number which is an integer in `min..=max`, or fail with a `RangeError`.
+pub(crate) unsafe fn get_strict_integer(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  name: &str,
+  min: f64,
+  max: f64,
+) -> Result<f64> {
+  let mut value = 0_f64;
+  check_status!(
+    unsafe { sys::napi_get_value_double(env, napi_val, &mut value) },
+    "Failed to convert napi value {:?} into rust type `{}`",
+    type_of!(env, napi_val)?,
+    name,
+  )?;
+  let reason = if !value.is_finite() || value.trunc() != value {
+    format!(
+      "Number {} is not an integer, expected rust type `{}`",
+      value, name
+    )
+  } else if value < min || value > max {
+    format!(
+      "Number {} is out of the range [{}, {}] of rust type `{}`",
+      value, min, max, name
+    )
+  } else {
+    return Ok(value);
+  };
+  Err(unsafe { range_error(env, reason) })
+}
+
+/// An `Error` which is thrown as a `RangeError`
+pub(crate) unsafe fn range_error(env: sys::napi_env, reason: String) -> Error {
+  let error = JsRangeError::from(Error::new(Status::InvalidArg, reason));
+  let range_error = unsafe { error.into_value(env) };
+  Error::from(unsafe { Unknown::from_raw_unchecked(env, range_error) })
+}
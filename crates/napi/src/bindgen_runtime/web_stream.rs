@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+use std::future::poll_fn;
+use std::rc::Rc;
+
+use futures_core::Stream;
+use tokio::sync::mpsc;
+
+use crate::{Env, JsObject, JsUnknown, NapiRaw, NapiValue, Result};
+
+use super::async_iterator::{ChannelStep, ChannelStepResolver};
+use super::ToNapiValue;
+
+type StreamStepDeferred<T> = crate::JsDeferred<ChannelStep<T>, ChannelStepResolver<T>>;
+
+/// Wraps the underlying source into a `ReadableStream`, compiled once per env.
+///
+/// `highWaterMark: 0` makes the stream pull-based, the Rust stream is only polled for a pending `read()`.
+const READABLE_STREAM_ADAPTER: &str = r#"
+if (typeof ReadableStream !== 'function') {
+  throw new TypeError('ReadableStream is not available in this runtime, it is a global since Node.js 18')
+}
+return new ReadableStream(
+  {
+    async pull(controller) {
+      const { value, done } = await source.next()
+      if (done) {
+        controller.close()
+      } else {
+        controller.enqueue(value)
+      }
+    },
+    cancel() {
+      source.cancel()
+    },
+  },
+  { highWaterMark: 0 },
+)
+"#;
+
+pub(crate) fn create_readable_stream<T, S>(env: &Env, stream: S) -> Result<JsObject>
+where
+  T: ToNapiValue + Send + 'static,
+  S: Stream<Item = Result<T>> + Send + 'static,
+{
+  let (requests, mut pending) = mpsc::unbounded_channel::<StreamStepDeferred<T>>();
+  crate::tokio_runtime::spawn(async move {
+    let mut stream = Box::pin(stream);
+    let mut finished = false;
+    // Ends once the stream is cancelled or garbage collected, which drops `stream`.
+    // Every request is settled, an unsettled deferred would keep the event loop alive.
+    while let Some(deferred) = pending.recv().await {
+      let step = if finished {
+        None
+      } else {
+        poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+      };
+      match step {
+        Some(Ok(value)) => deferred.resolve(Box::new(move |_| Ok(ChannelStep(Some(value))))),
+        Some(Err(e)) => {
+          finished = true;
+          deferred.reject(e);
+        }
+        None => {
+          finished = true;
+          deferred.resolve(Box::new(|_| Ok(ChannelStep(None))));
+        }
+      }
+    }
+  });
+
+  // `None` once the stream is cancelled
+  let requests = Rc::new(RefCell::new(Some(requests)));
+  let mut source = env.create_object()?;
+  let next_requests = requests.clone();
+  source.set_named_property(
+    "next",
+    env.create_function_from_closure("next", move |ctx| {
+      let (deferred, promise) = ctx
+        .env
+        .create_deferred::<ChannelStep<T>, ChannelStepResolver<T>>()?;
+      match next_requests.borrow().as_ref() {
+        Some(requests) => {
+          if let Err(mpsc::error::SendError(deferred)) = requests.send(deferred) {
+            deferred.resolve(Box::new(|_| Ok(ChannelStep(None))));
+          }
+        }
+        None => deferred.resolve(Box::new(|_| Ok(ChannelStep(None)))),
+      }
+      Ok(promise)
+    })?,
+  )?;
+  source.set_named_property(
+    "cancel",
+    env.create_function_from_closure("cancel", move |_| {
+      requests.borrow_mut().take();
+      Ok(())
+    })?,
+  )?;
+
+  let adapter = env.compile_function(READABLE_STREAM_ADAPTER, &["source"])?;
+  let readable_stream: JsUnknown = adapter.call(None, &[source])?;
+  Ok(unsafe { JsObject::from_raw_unchecked(env.raw(), readable_stream.raw()) })
+}
@@ -1,4 +1,6 @@
 use std::any::{type_name, TypeId};
+#[cfg(feature = "napi5")]
+use std::cell::RefCell;
 use std::convert::TryInto;
 use std::ffi::CString;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
@@ -7,16 +9,18 @@ use std::mem;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
 
-use crate::bindgen_runtime::FromNapiValue;
 #[cfg(feature = "napi4")]
 use crate::bindgen_runtime::ToNapiValue;
+use crate::bindgen_runtime::{get_class_constructor, FromNapiValue, JsValuesTupleIntoVec};
 use crate::{
   async_work::{self, AsyncWorkPromise},
-  check_status,
+  check_pending_exception, check_status,
+  env_cache::{self, CacheKey},
   js_values::*,
   sys,
   task::Task,
-  Error, ExtendedErrorInfo, NodeVersion, Result, Status, ValueType,
+  unwrap_cache, Error, ExtendedErrorInfo, JsError, NodeVersion, Result, Status, UnwrapCache,
+  ValueType,
 };
 
 #[cfg(feature = "napi8")]
@@ -27,8 +31,8 @@ use crate::cleanup_env::{CleanupEnvHook, CleanupEnvHookData};
 use crate::js_values::{De, Ser};
 #[cfg(feature = "napi4")]
 use crate::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction};
-#[cfg(feature = "napi3")]
-use crate::JsError;
+#[cfg(feature = "napi5")]
+use crate::timer::TimerHandle;
 #[cfg(feature = "serde-json")]
 use serde::de::DeserializeOwned;
 #[cfg(feature = "serde-json")]
@@ -206,12 +210,14 @@ impl Env {
   }
 
   pub fn create_symbol(&self, description: Option<&str>) -> Result<JsSymbol> {
+    let description = description
+      .map(|desc| self.create_string(desc))
+      .transpose()?;
     let mut result = ptr::null_mut();
     check_status!(unsafe {
       sys::napi_create_symbol(
         self.0,
         description
-          .and_then(|desc| self.create_string(desc).ok())
           .map(|string| string.0.value)
           .unwrap_or(ptr::null_mut()),
         &mut result,
@@ -220,18 +226,47 @@ impl Env {
     Ok(unsafe { JsSymbol::from_raw_unchecked(self.0, result) })
   }
 
+  #[cfg(not(feature = "napi9"))]
+  /// Get the symbol for `description` from the global symbol registry, like `Symbol.for()`.
+  pub fn symbol_for(&self, description: &str) -> Result<JsSymbol> {
+    let symbol_for: JsFunction = self
+      .get_global()?
+      .get_named_property::<JsFunction>("Symbol")?
+      .coerce_to_object()?
+      .get_named_property("for")?;
+    let symbol = symbol_for.call(None, &[self.create_string(description)?])?;
+    Ok(unsafe { symbol.cast() })
+  }
+
   pub fn create_object(&self) -> Result<JsObject> {
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe { sys::napi_create_object(self.0, &mut raw_value) })?;
     Ok(unsafe { JsObject::from_raw_unchecked(self.0, raw_value) })
   }
 
+  /// Create an empty object whose prototype is `proto`, like `Object.create(proto)`.
+  ///
+  /// Unlike `new`, the constructor owning `proto` is not invoked.
+  pub fn create_object_with_prototype(&self, proto: &JsObject) -> Result<JsObject> {
+    let object_create: JsFunction = self
+      .get_global()?
+      .get_named_property_unchecked::<JsObject>("Object")?
+      .get_named_property_unchecked("create")?;
+    let object = object_create.call(None, &[proto])?;
+    Ok(unsafe { object.cast() })
+  }
+
   pub fn create_empty_array(&self) -> Result<JsObject> {
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe { sys::napi_create_array(self.0, &mut raw_value) })?;
     Ok(unsafe { JsObject::from_raw_unchecked(self.0, raw_value) })
   }
 
+  /// Create an array object with `length` empty slots.
+  ///
+  /// [`Env::create_array`] allocates the same way and returns an
+  /// [`Array`](crate::bindgen_prelude::Array), whose
+  /// [`set_elements`](crate::bindgen_prelude::Array::set_elements) fills it from an iterator.
   pub fn create_array_with_length(&self, length: usize) -> Result<JsObject> {
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe { sys::napi_create_array_with_length(self.0, length, &mut raw_value) })?;
@@ -481,6 +516,50 @@ impl Env {
     ))
   }
 
+  /// Create a `DataView` over `length` bytes of `arraybuffer` from `byte_offset`.
+  ///
+  /// Unlike [`JsArrayBuffer::into_dataview`], the range is validated before creating the view,
+  /// an `Err` is returned if it's out of the bounds of `arraybuffer` or if `arraybuffer` is detached.
+  pub fn create_dataview(
+    &self,
+    arraybuffer: &JsArrayBuffer,
+    byte_offset: usize,
+    length: usize,
+  ) -> Result<JsDataView> {
+    #[cfg(feature = "napi7")]
+    if arraybuffer.is_detached()? {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Cannot create a DataView on a detached ArrayBuffer".to_owned(),
+      ));
+    }
+    let mut data = ptr::null_mut();
+    let mut byte_length = 0;
+    check_status!(unsafe {
+      sys::napi_get_arraybuffer_info(self.0, arraybuffer.0.value, &mut data, &mut byte_length)
+    })?;
+    if byte_offset > byte_length || length > byte_length - byte_offset {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "DataView of {} bytes at offset {} is out of the bounds of the ArrayBuffer of {} bytes",
+          length, byte_offset, byte_length
+        ),
+      ));
+    }
+    let mut raw_value = ptr::null_mut();
+    check_status!(unsafe {
+      sys::napi_create_dataview(
+        self.0,
+        length,
+        arraybuffer.0.value,
+        byte_offset,
+        &mut raw_value,
+      )
+    })?;
+    Ok(unsafe { JsDataView::from_raw_unchecked(self.0, raw_value) })
+  }
+
   /// # Safety
   /// Mostly the same with `create_arraybuffer_with_data`
   ///
@@ -614,6 +693,78 @@ impl Env {
     Ok(unsafe { JsFunction::from_raw_unchecked(self.0, raw_result) })
   }
 
+  /// Call `callback` once after `delay` milliseconds with the global `setTimeout`, e.g. to retry a failed
+  /// operation with a backoff on the event loop.
+  ///
+  /// The closure is wrapped in a JavaScript function, which drops it when it's garbage collected,
+  /// after the timer has fired or has been cleared. An error returned from the closure is thrown
+  /// as an uncaught exception, like an error thrown from a JavaScript timer callback.
+  ///
+  /// ```rust
+  /// #[napi]
+  /// pub fn retry_later(env: Env, delay: u32) -> Result<()> {
+  ///   env.set_timeout(delay, |_env| {
+  ///     // run the operation again
+  ///     Ok(())
+  ///   })?;
+  ///   Ok(())
+  /// }
+  /// ```
+  #[cfg(feature = "napi5")]
+  pub fn set_timeout<F>(&self, delay: u32, callback: F) -> Result<TimerHandle>
+  where
+    F: 'static + FnOnce(Env) -> Result<()>,
+  {
+    let callback = RefCell::new(Some(callback));
+    let js_callback = self.create_function_from_closure("timeoutCallback", move |ctx| {
+      match callback.borrow_mut().take() {
+        Some(callback) => callback(*ctx.env),
+        None => Ok(()),
+      }
+    })?;
+    let set_timeout = self.get_timer_function("setTimeout\0")?;
+    let delay = self.create_uint32(delay)?;
+    let timer = set_timeout.call(None, &[js_callback.into_unknown(), delay.into_unknown()])?;
+    TimerHandle::new(self.0, timer.0.value)
+  }
+
+  /// Cancel a timer scheduled by [`set_timeout`](Env::set_timeout) with the global `clearTimeout`.
+  ///
+  /// Clearing a timer which has already fired does nothing.
+  #[cfg(feature = "napi5")]
+  pub fn clear_timeout(&self, handle: TimerHandle) -> Result<()> {
+    let mut timer = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(self.0, handle.timer, &mut timer) },
+      "Get timer from reference failed"
+    )?;
+    let timer = unsafe { JsUnknown::from_raw_unchecked(self.0, timer) };
+    let clear_timeout = self.get_timer_function("clearTimeout\0")?;
+    clear_timeout.call(None, &[timer])?;
+    Ok(())
+  }
+
+  #[cfg(feature = "napi5")]
+  fn get_timer_function(&self, name: &'static str) -> Result<JsFunction> {
+    // the global `setTimeout` and `clearTimeout` are looked up once for each env
+    let key = CacheKey::Named(name);
+    if let Some(function) = env_cache::get(self.0, &key)? {
+      return Ok(unsafe { JsFunction::from_raw_unchecked(self.0, function) });
+    }
+    let mut function = ptr::null_mut();
+    let global = self.get_global()?;
+    check_status!(
+      unsafe {
+        sys::napi_get_named_property(self.0, global.0.value, name.as_ptr().cast(), &mut function)
+      },
+      "Get global `{}` failed",
+      name.trim_end_matches('\0')
+    )?;
+    let function = unsafe { JsFunction::from_raw(self.0, function) }?;
+    env_cache::insert(self.0, key, function.0.value)?;
+    Ok(function)
+  }
+
   /// This API retrieves a napi_extended_error_info structure with information about the last error that occurred.
   ///
   /// The content of the napi_extended_error_info returned is only valid up until an n-api function is called on the same env.
@@ -627,11 +778,36 @@ impl Env {
     unsafe { ptr::read(raw_extended_error) }.try_into()
   }
 
-  /// Throw any JavaScript value
+  /// Throw any JavaScript value, e.g. an error created with [`Env::create_error`] or a value caught from a JavaScript call.
+  ///
+  /// The value becomes the pending exception of the env, it is thrown to JavaScript when the native call returns.
+  /// While it's pending, calling into JavaScript fails with `Status::PendingException` and an `Err` returned from a `#[napi]` function
+  /// is not thrown, so the thrown value is not replaced.
+  /// Throwing while an exception is already pending returns an error and keeps the pending one.
+  ///
+  /// ```rust,ignore
+  /// use napi::{CallContext, JsUndefined, JsUnknown, Result};
+  ///
+  /// #[js_function(1)]
+  /// pub fn rethrow(ctx: CallContext) -> Result<JsUndefined> {
+  ///     let value = ctx.get::<JsUnknown>(0)?;
+  ///     ctx.env.throw(value)?;
+  ///     ctx.env.get_undefined()
+  /// }
+  /// ```
   pub fn throw<T: NapiRaw>(&self, value: T) -> Result<()> {
     check_status!(unsafe { sys::napi_throw(self.0, value.raw()) })
   }
 
+  /// Whether an exception is pending, e.g. thrown with [`Env::throw`] or by a JavaScript call.
+  ///
+  /// This API can be called even if there is a pending JavaScript exception.
+  pub fn is_exception_pending(&self) -> Result<bool> {
+    let mut is_pending = false;
+    check_status!(unsafe { sys::napi_is_exception_pending(self.0, &mut is_pending) })?;
+    Ok(is_pending)
+  }
+
   /// This API throws a JavaScript Error with the text provided.
   pub fn throw_error(&self, msg: &str, code: Option<&str>) -> Result<()> {
     let code = code.and_then(|s| CString::new(s).ok());
@@ -645,6 +821,33 @@ impl Env {
     })
   }
 
+  /// Throw the JavaScript Error for a failed raw `sys` call, formatted like the errors of this crate.
+  ///
+  /// Nothing is thrown for `Status::Ok`, and for `Status::PendingException` the exception that is already pending is kept.
+  ///
+  /// ```rust,ignore
+  /// use napi::{sys, CallContext, JsUndefined, JsUnknown, NapiRaw, Result, Status};
+  ///
+  /// #[js_function(1)]
+  /// pub fn read_u32(ctx: CallContext) -> Result<JsUndefined> {
+  ///     let value = ctx.get::<JsUnknown>(0)?;
+  ///     let mut result = 0;
+  ///     let status = unsafe { sys::napi_get_value_uint32(ctx.env.raw(), value.raw(), &mut result) };
+  ///     ctx.env.throw_status_error(Status::from(status))?;
+  ///     ctx.env.get_undefined()
+  /// }
+  /// ```
+  pub fn throw_status_error(&self, status: Status) -> Result<()> {
+    match status {
+      Status::Ok | Status::PendingException => Ok(()),
+      _ => {
+        let js_error =
+          unsafe { JsError::from(Error::from_failed_status(status)).into_value(self.0) };
+        check_status!(unsafe { sys::napi_throw(self.0, js_error) })
+      }
+    }
+  }
+
   /// This API throws a JavaScript RangeError with the text provided.
   pub fn throw_range_error(&self, msg: &str, code: Option<&str>) -> Result<()> {
     let code = code.and_then(|s| CString::new(s).ok());
@@ -671,6 +874,52 @@ impl Env {
     })
   }
 
+  /// Throw an `AggregateError` with `message`, whose `errors` are the JavaScript errors of `errors`,
+  /// e.g. to report every invalid option at once.
+  ///
+  /// On engines without a global `AggregateError`, an `Error` is thrown instead,
+  /// with `message` followed by the message of every error.
+  ///
+  /// ```rust,ignore
+  /// #[napi]
+  /// pub fn validate(env: Env, port: u32, host: String) -> Result<()> {
+  ///   let mut errors = Vec::new();
+  ///   if port == 0 {
+  ///     errors.push(Error::new(Status::InvalidArg, "port must not be 0".to_owned()));
+  ///   }
+  ///   if host.is_empty() {
+  ///     errors.push(Error::new(Status::InvalidArg, "host must not be empty".to_owned()));
+  ///   }
+  ///   if !errors.is_empty() {
+  ///     env.throw_aggregate_error(errors, "Invalid options")?;
+  ///   }
+  ///   Ok(())
+  /// }
+  /// ```
+  pub fn throw_aggregate_error(&self, errors: Vec<Error>, message: &str) -> Result<()> {
+    let aggregate_error = self
+      .get_global()?
+      .get_named_property_unchecked::<JsUnknown>("AggregateError")?;
+    if aggregate_error.get_type()? != ValueType::Function {
+      let reasons = errors
+        .iter()
+        .map(|e| e.reason.as_str())
+        .collect::<Vec<_>>()
+        .join("; ");
+      return self.throw_error(&format!("{}: {}", message, reasons), None);
+    }
+    let mut js_errors = self.create_array_with_length(errors.len())?;
+    for (index, error) in errors.into_iter().enumerate() {
+      js_errors.set_element(index as u32, JsError::from(error).into_unknown(*self))?;
+    }
+    let aggregate_error: JsFunction = unsafe { aggregate_error.cast() };
+    let error = aggregate_error.new_instance(&[
+      js_errors.into_unknown(),
+      self.create_string(message)?.into_unknown(),
+    ])?;
+    self.throw(error)
+  }
+
   /// This API throws a JavaScript SyntaxError with the text provided.
   #[cfg(feature = "napi9")]
   pub fn throw_syntax_error<S: AsRef<str>, C: AsRef<str>>(&self, msg: S, code: Option<C>) {
@@ -764,6 +1013,7 @@ impl Env {
     })
   }
 
+  #[allow(clippy::mut_from_ref)]
   pub fn unwrap<T: 'static>(&self, js_object: &JsObject) -> Result<&mut T> {
     unsafe {
       let mut unknown_tagged_object: *mut c_void = ptr::null_mut();
@@ -773,27 +1023,21 @@ impl Env {
         &mut unknown_tagged_object,
       ))?;
 
-      let type_id = unknown_tagged_object as *const TypeId;
-      if *type_id == TypeId::of::<T>() {
-        let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
-        (*tagged_object).object.as_mut().ok_or_else(|| {
-          Error::new(
-            Status::InvalidArg,
-            "Invalid argument, nothing attach to js_object".to_owned(),
-          )
-        })
-      } else {
-        Err(Error::new(
-          Status::InvalidArg,
-          format!(
-            "Invalid argument, {} on unwrap is not the type of wrapped object",
-            type_name::<T>()
-          ),
-        ))
-      }
+      tagged_object_as_mut(unknown_tagged_object)
     }
   }
 
+  /// Run `f` with an [`UnwrapCache`], which memoizes [`unwrap`](Env::unwrap) for objects that are unwrapped many times,
+  /// e.g. `this` of a method that calls into other methods of the same object.
+  ///
+  /// The cache is dropped when `f` returns.
+  pub fn with_unwrap_cache<R, F>(&self, f: F) -> R
+  where
+    F: FnOnce(&UnwrapCache) -> R,
+  {
+    f(&UnwrapCache::new(*self))
+  }
+
   pub fn drop_wrapped<T: 'static>(&self, js_object: &JsObject) -> Result<()> {
     unsafe {
       let mut unknown_tagged_object = ptr::null_mut();
@@ -818,6 +1062,80 @@ impl Env {
     }
   }
 
+  /// Attach `data` to `js_object`, next to the native object of [`wrap`](Env::wrap).
+  ///
+  /// N-API allows only one wrap per object, and `#[napi]` classes already use it for the struct.
+  /// This is a second slot for per-instance bookkeeping that doesn't belong in the struct, e.g. a cached
+  /// `Ref` or a handle of a native resource. `data` is stored in an external under a symbol property,
+  /// which is created once per env, and the property is not enumerable, so it doesn't show up in `Object.keys`
+  /// or `JSON.stringify`. `data` is dropped when the object is garbage collected, or replaced by a later call.
+  ///
+  /// ```rust,ignore
+  /// #[napi]
+  /// impl Connection {
+  ///   #[napi]
+  ///   pub fn query(&self, env: Env, mut this: This) -> Result<u32> {
+  ///     if env.unwrap_private_data::<QueryCache>(&this)?.is_none() {
+  ///       env.wrap_private_data(&mut this, QueryCache::default())?;
+  ///     }
+  ///     let cache = env.unwrap_private_data::<QueryCache>(&this)?.unwrap();
+  ///     // ...
+  ///   }
+  /// }
+  /// ```
+  pub fn wrap_private_data<T: 'static>(&self, js_object: &mut JsObject, data: T) -> Result<()> {
+    let key = self.get_private_data_symbol()?;
+    let external = self.create_external(data, None)?;
+    let descriptor = sys::napi_property_descriptor {
+      utf8name: ptr::null(),
+      name: key,
+      method: None,
+      getter: None,
+      setter: None,
+      value: external.0.value,
+      attributes: PropertyAttributes::Configurable.into(),
+      data: ptr::null_mut(),
+    };
+    check_status!(
+      unsafe { sys::napi_define_properties(self.0, js_object.0.value, 1, &descriptor) },
+      "Failed to attach private data to object"
+    )
+  }
+
+  /// Get the data attached to `js_object` by [`wrap_private_data`](Env::wrap_private_data),
+  /// `None` if there is none.
+  ///
+  /// Returns an error if the data is not a `T`.
+  #[allow(clippy::mut_from_ref)]
+  pub fn unwrap_private_data<T: 'static>(&self, js_object: &JsObject) -> Result<Option<&mut T>> {
+    let key = self.get_private_data_symbol()?;
+    let mut has_data = false;
+    check_status!(
+      unsafe { sys::napi_has_own_property(self.0, js_object.0.value, key, &mut has_data) },
+      "Failed to check private data of object"
+    )?;
+    if !has_data {
+      return Ok(None);
+    }
+    let mut external = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_property(self.0, js_object.0.value, key, &mut external) },
+      "Failed to get private data of object"
+    )?;
+    unsafe { external_as_mut(self.0, external) }.map(Some)
+  }
+
+  fn get_private_data_symbol(&self) -> Result<sys::napi_value> {
+    // the symbol the private data of `Env::wrap_private_data` is keyed by, one for each env
+    let key = CacheKey::Named("napi-rs private data");
+    if let Some(symbol) = env_cache::get(self.0, &key)? {
+      return Ok(symbol);
+    }
+    let symbol = unsafe { self.create_symbol(Some("napi-rs private data"))?.raw() };
+    env_cache::insert(self.0, key, symbol)?;
+    Ok(symbol)
+  }
+
   /// This API create a new reference with the initial 1 ref count to the Object passed in.
   pub fn create_reference<T>(&self, value: T) -> Result<Ref<()>>
   where
@@ -903,6 +1221,7 @@ impl Env {
         &mut object_value,
       )
     })?;
+    unsafe { tag_external(self.0, object_value) }?;
     if let Some(changed) = size_hint {
       if changed != 0 {
         let mut adjusted_value = 0i64;
@@ -915,30 +1234,7 @@ impl Env {
   }
 
   pub fn get_value_external<T: 'static>(&self, js_external: &JsExternal) -> Result<&mut T> {
-    unsafe {
-      let mut unknown_tagged_object = ptr::null_mut();
-      check_status!(sys::napi_get_value_external(
-        self.0,
-        js_external.0.value,
-        &mut unknown_tagged_object,
-      ))?;
-
-      let type_id = unknown_tagged_object as *const TypeId;
-      if *type_id == TypeId::of::<T>() {
-        let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
-        (*tagged_object).object.as_mut().ok_or_else(|| {
-          Error::new(
-            Status::InvalidArg,
-            "nothing attach to js_external".to_owned(),
-          )
-        })
-      } else {
-        Err(Error::new(
-          Status::InvalidArg,
-          "T on get_value_external is not the type of wrapped object".to_owned(),
-        ))
-      }
-    }
+    unsafe { external_as_mut(self.0, js_external.0.value) }
   }
 
   pub fn create_error(&self, e: Error) -> Result<JsObject> {
@@ -966,6 +1262,7 @@ impl Env {
     let result = executor();
 
     check_status!(unsafe { sys::napi_close_handle_scope(self.0, handle_scope) })?;
+    unwrap_cache::handle_scope_closed();
     result
   }
 
@@ -981,6 +1278,144 @@ impl Env {
     unsafe { V::from_napi_value(self.0, raw_value) }
   }
 
+  /// Construct an instance of the `#[napi]` class exported as `class_js_name`, like `new ClassName(...args)` in JavaScript.
+  ///
+  /// An exception thrown by the constructor is returned as an `Err`, as well as a class that's not registered
+  /// in the current thread, e.g. the module was not loaded in this worker thread yet.
+  ///
+  /// ```rust,ignore
+  /// #[napi]
+  /// fn adopt_dog(env: Env, name: String) -> Result<JsObject> {
+  ///   env.new_instance("Animal", &[env.create_uint32(0)?.into_unknown(), env.create_string(&name)?.into_unknown()])
+  /// }
+  /// ```
+  pub fn new_instance<V>(&self, class_js_name: &str, args: &[V]) -> Result<JsObject>
+  where
+    V: NapiRaw,
+  {
+    let class_js_name = class_js_name.trim_end_matches('\0');
+    self.get_registered_class(class_js_name)?.new_instance(args)
+  }
+
+  /// Construct an instance of the `#[napi]` class exported as `js_name` and call its `method` on it,
+  /// like `new JsName(...ctor_args).method(...method_args)` in JavaScript.
+  ///
+  /// The arguments are a value or a tuple of values. Exceptions thrown by the constructor or the method are returned as an `Err`,
+  /// as well as a class that's not registered in the current thread or a `method` that's not a function.
+  ///
+  /// ```rust,ignore
+  /// let greeting: String = env.new_and_call("Animal", (Kind::Dog, "Doge"), "whoami", ())?;
+  /// ```
+  pub fn new_and_call<T, CtorArgs, MethodArgs>(
+    &self,
+    js_name: &str,
+    ctor_args: CtorArgs,
+    method: &str,
+    method_args: MethodArgs,
+  ) -> Result<T>
+  where
+    T: FromNapiValue,
+    CtorArgs: JsValuesTupleIntoVec,
+    MethodArgs: JsValuesTupleIntoVec,
+  {
+    let js_name = js_name.trim_end_matches('\0');
+    let ctor = self.get_registered_class(js_name)?;
+    let ctor_args = ctor_args.into_vec(self.0)?;
+    let mut instance = ptr::null_mut();
+    check_pending_exception!(
+      self.0,
+      unsafe {
+        sys::napi_new_instance(
+          self.0,
+          ctor.raw(),
+          ctor_args.len(),
+          ctor_args.as_ptr(),
+          &mut instance,
+        )
+      },
+      "Failed to construct class `{}`",
+      js_name
+    )?;
+    let instance = unsafe { JsObject::from_raw_unchecked(self.0, instance) };
+    let method_fn = instance.get_named_property_unchecked::<JsUnknown>(method)?;
+    if method_fn.get_type()? != ValueType::Function {
+      return Err(Error::new(
+        Status::FunctionExpected,
+        format!("`{}.prototype.{}` is not a function", js_name, method),
+      ));
+    }
+    let method_args = method_args.into_vec(self.0)?;
+    let mut return_value = ptr::null_mut();
+    check_pending_exception!(
+      self.0,
+      unsafe {
+        sys::napi_call_function(
+          self.0,
+          instance.0.value,
+          method_fn.0.value,
+          method_args.len(),
+          method_args.as_ptr(),
+          &mut return_value,
+        )
+      },
+      "Failed to call `{}.prototype.{}`",
+      js_name,
+      method
+    )?;
+    unsafe { T::from_napi_value(self.0, return_value) }
+  }
+
+  /// The constructor of the `#[napi]` class exported as `js_name`, which must not be nul terminated
+  fn get_registered_class(&self, js_name: &str) -> Result<JsFunction> {
+    let ctor_ref = get_class_constructor(&format!("{}\0", js_name)).ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        format!(
+          "Class `{}` is not registered in the current thread",
+          js_name
+        ),
+      )
+    })?;
+    let mut ctor = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(self.0, ctor_ref, &mut ctor) },
+      "Failed to get constructor reference of class `{}`",
+      js_name
+    )?;
+    Ok(unsafe { JsFunction::from_raw_unchecked(self.0, ctor) })
+  }
+
+  /// Create a function with the `param_names` and the `source` as body, like `new Function(...param_names, source)`.
+  ///
+  /// The source is only compiled by the first call with the same `source` and `param_names` in this env,
+  /// later calls return the same function from a cached reference. The cache is dropped with the env and keeps
+  /// at most 256 functions, releasing the oldest one first. A syntax error in the source is returned as an `Err`.
+  ///
+  /// ```rust,ignore
+  /// let add = env.compile_function("return a + b", &["a", "b"])?;
+  /// let three = add.call(None, &[env.create_uint32(1)?, env.create_uint32(2)?])?;
+  /// ```
+  pub fn compile_function(&self, source: &str, param_names: &[&str]) -> Result<JsFunction> {
+    let key = CacheKey::CompiledFunction(
+      param_names.iter().map(|name| name.to_string()).collect(),
+      source.to_owned(),
+    );
+    if let Some(function) = env_cache::get(self.0, &key)? {
+      return Ok(unsafe { JsFunction::from_raw_unchecked(self.0, function) });
+    }
+    let function_constructor: JsFunction = self
+      .get_global()?
+      .get_named_property_unchecked("Function")?;
+    let args = param_names
+      .iter()
+      .chain(std::iter::once(&source))
+      .map(|arg| self.create_string(arg))
+      .collect::<Result<Vec<_>>>()?;
+    let function = function_constructor.call(None, &args)?;
+    env_cache::insert(self.0, key, unsafe { function.raw() })?;
+    Ok(unsafe { function.cast() })
+  }
+
   /// `process.versions.napi`
   pub fn get_napi_version(&self) -> Result<u32> {
     let global = self.get_global()?;
@@ -994,9 +1429,22 @@ impl Env {
       .map_err(|e| Error::new(Status::InvalidArg, format!("{}", e)))
   }
 
+  /// <https://nodejs.org/api/n-api.html#napi_get_uv_event_loop>
+  ///
+  /// Get the libuv loop of the current env, to drive other event sources, like a Rust async runtime, from the Node.js event loop
+  /// instead of a separate thread.
+  ///
+  /// The loop is opaque, cast it to the `uv_loop_t` of the libuv bindings you're using, e.g. the `libuv-sys2` crate.
+  /// napi-rs deliberately has no feature for libuv bindings, the libuv version and its `uv_loop_t` layout
+  /// are the ones Node.js is built with, so the bindings must be picked to match the targeted Node.js versions.
+  /// Using it is `unsafe`, with the same rules as in C:
+  /// - The loop is owned by Node.js, never close, stop or free it, and don't call `uv_run` on it while it's already running.
+  /// - libuv is not thread safe, the loop may only be used on the JavaScript thread of this env, except for `uv_async_send`.
+  /// - The pointer is only valid while the env is alive, handles started on it must be closed in an env cleanup hook.
+  /// - Each worker thread has its own loop, don't share the pointer between envs.
   #[cfg(feature = "napi2")]
-  pub fn get_uv_event_loop(&self) -> Result<*mut sys::uv_loop_s> {
-    let mut uv_loop: *mut sys::uv_loop_s = ptr::null_mut();
+  pub fn get_uv_event_loop(&self) -> Result<*mut sys::uv_loop_t> {
+    let mut uv_loop: *mut sys::uv_loop_t = ptr::null_mut();
     check_status!(unsafe { sys::napi_get_uv_event_loop(self.0, &mut uv_loop) })?;
     Ok(uv_loop)
   }
@@ -1087,6 +1535,40 @@ impl Env {
     Ok(unsafe { JsObject::from_raw_unchecked(self.0, promise) })
   }
 
+  /// Create a JavaScript function which runs the future returned by `callback` on the tokio runtime
+  /// and returns a Promise settled with its output.
+  ///
+  /// `callback` runs on the JavaScript thread to read the arguments, an error returned from it is
+  /// thrown synchronously. Every call spawns its own future and returns a new Promise, so concurrent
+  /// calls don't wait for each other. The closure is dropped when the function is garbage collected,
+  /// the spawned futures keep running until they are settled.
+  ///
+  /// ```rust
+  /// #[napi(ts_return_type = "(value: number) => Promise<number>")]
+  /// pub fn create_delayed_adder(env: Env, base: u32) -> Result<JsFunction> {
+  ///   env.create_async_function("delayedAdd", move |ctx| {
+  ///     let value = ctx.get::<JsNumber>(0)?.get_uint32()?;
+  ///     Ok(async move {
+  ///       tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+  ///       Ok(base + value)
+  ///     })
+  ///   })
+  /// }
+  /// ```
+  #[cfg(all(feature = "tokio_rt", feature = "napi5"))]
+  pub fn create_async_function<T, Fut, F>(&self, name: &str, callback: F) -> Result<JsFunction>
+  where
+    T: 'static + Send + ToNapiValue,
+    Fut: 'static + Send + Future<Output = Result<T>>,
+    F: 'static + Fn(crate::CallContext<'_>) -> Result<Fut>,
+  {
+    self.create_function_from_closure(name, move |ctx| {
+      let env = *ctx.env;
+      let fut = callback(ctx)?;
+      env.spawn_future(fut)
+    })
+  }
+
   /// Creates a deferred promise, which can be resolved or rejected from a background thread.
   #[cfg(feature = "napi4")]
   pub fn create_deferred<Data: ToNapiValue, Resolver: FnOnce(Env) -> Result<Data>>(
@@ -1095,6 +1577,60 @@ impl Env {
     JsDeferred::new(self.raw())
   }
 
+  /// Creates an object implementing the async iterator protocol, which yields the values sent
+  /// to the channel of `receiver` from any thread and finishes once all senders are dropped.
+  ///
+  /// Every `next()` returns a Promise resolved from a background thread through a threadsafe
+  /// function. Calling `return()` or `throw()`, e.g. by `break` in a `for await` loop, finishes
+  /// the iterable and drops `receiver` once the pending `next()` calls are settled, so `send`
+  /// fails on the producer side, which can stop producing.
+  ///
+  /// ```rust
+  /// #[napi(ts_return_type = "AsyncIterable<number>")]
+  /// fn count(env: Env) -> Result<JsObject> {
+  ///   let (sender, receiver) = std::sync::mpsc::channel();
+  ///   std::thread::spawn(move || {
+  ///     let mut n = 0;
+  ///     while sender.send(n).is_ok() {
+  ///       n += 1;
+  ///     }
+  ///   });
+  ///   env.create_async_iterable(receiver)
+  /// }
+  /// ```
+  #[cfg(feature = "napi4")]
+  pub fn create_async_iterable<T: ToNapiValue + Send + 'static>(
+    &self,
+    receiver: std::sync::mpsc::Receiver<T>,
+  ) -> Result<JsObject> {
+    crate::bindgen_runtime::async_iterator::create_channel_iterable(self, receiver)
+  }
+
+  /// Creates a Web [`ReadableStream`](https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream)
+  /// which yields the items of `stream`, and errors with the first `Err` item.
+  ///
+  /// The stream is polled on the tokio runtime, once for each chunk read from the `ReadableStream`, so a
+  /// slow consumer applies backpressure to the Rust side. Cancelling the `ReadableStream`, e.g. by
+  /// `reader.cancel()` or `break` in a `for await` loop, drops `stream` once the pending reads are settled.
+  ///
+  /// `ReadableStream` must be a global of the runtime, which it is since Node.js 18,
+  /// otherwise a `TypeError` is returned.
+  ///
+  /// ```rust
+  /// #[napi(ts_return_type = "ReadableStream<string>")]
+  /// fn words(env: Env, words: Vec<String>) -> Result<JsObject> {
+  ///   env.create_readable_stream(futures::stream::iter(words.into_iter().map(Ok)))
+  /// }
+  /// ```
+  #[cfg(feature = "web_stream")]
+  pub fn create_readable_stream<T, S>(&self, stream: S) -> Result<JsObject>
+  where
+    T: ToNapiValue + Send + 'static,
+    S: futures_core::Stream<Item = Result<T>> + Send + 'static,
+  {
+    crate::bindgen_runtime::web_stream::create_readable_stream(self, stream)
+  }
+
   /// This API does not observe leap seconds; they are ignored, as ECMAScript aligns with POSIX time specification.
   ///
   /// This API allocates a JavaScript Date object.
@@ -1114,17 +1650,33 @@ impl Env {
   /// Any existing data associated with the currently running Agent which was set by means of a previous call to `Env::set_instance_data()` will be overwritten.
   ///
   /// If a `finalize_cb` was provided by the previous call, it will not be called.
+  ///
+  /// Returns an `InvalidArg` error if the existing data was set with a type other than `T`.
   pub fn set_instance_data<T, Hint, F>(&self, native: T, hint: Hint, finalize_cb: F) -> Result<()>
   where
     T: 'static,
     Hint: 'static,
     F: FnOnce(FinalizeContext<T, Hint>),
   {
+    let mut existing: *mut c_void = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_instance_data(self.0, &mut existing) })?;
+    if !existing.is_null() && unsafe { *(existing as *const TypeId) } != TypeId::of::<T>() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Instance data is already set with a type other than {}",
+          type_name::<T>()
+        ),
+      ));
+    }
     check_status!(unsafe {
       sys::napi_set_instance_data(
         self.0,
-        Box::leak(Box::new((TaggedObject::new(native), finalize_cb))) as *mut (TaggedObject<T>, F)
-          as *mut c_void,
+        Box::into_raw(Box::new(InstanceData {
+          value: TaggedObject::new(native),
+          finalize_cb,
+        }))
+        .cast(),
         Some(
           set_instance_finalize_callback::<T, Hint, F>
             as unsafe extern "C" fn(
@@ -1229,6 +1781,7 @@ impl Env {
   }
 
   #[cfg(feature = "napi9")]
+  /// Get the symbol for `description` from the global symbol registry, like `Symbol.for()`.
   pub fn symbol_for(&self, description: &str) -> Result<JsSymbol> {
     let mut result = ptr::null_mut();
     let len = description.len();
@@ -1325,6 +1878,31 @@ impl Env {
     Ok(result)
   }
 
+  /// Compare the values with `==`, the [Loose Equality](https://tc39.es/ecma262/#sec-islooselyequal) algorithm.
+  ///
+  /// Node-API has no counterpart of `napi_strict_equals` for it, so the comparison runs in a function compiled once per env.
+  /// It may call `valueOf` or `toString` of an object compared with a primitive, an exception thrown by them is returned as an `Err`.
+  pub fn loose_equals<A: NapiRaw, B: NapiRaw>(&self, a: A, b: B) -> Result<bool> {
+    let loose_equals = self.compile_function("return a == b", &["a", "b"])?;
+    let args = [unsafe { a.raw() }, unsafe { b.raw() }];
+    let mut undefined = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_undefined(self.0, &mut undefined) })?;
+    let mut result = ptr::null_mut();
+    check_status!(unsafe {
+      sys::napi_call_function(
+        self.0,
+        undefined,
+        loose_equals.raw(),
+        args.len(),
+        args.as_ptr(),
+        &mut result,
+      )
+    })?;
+    let mut equals = false;
+    check_status!(unsafe { sys::napi_get_value_bool(self.0, result, &mut equals) })?;
+    Ok(equals)
+  }
+
   pub fn get_node_version(&self) -> Result<NodeVersion> {
     let mut result = ptr::null();
     check_status!(unsafe { sys::napi_get_node_version(self.0, &mut result) })?;
@@ -1351,6 +1929,58 @@ unsafe extern "C" fn drop_buffer(
   mem::drop(unsafe { Vec::from_raw_parts(finalize_data as *mut u8, length, cap) });
 }
 
+/// # Safety
+///
+/// `external` must be an external value alive in `env`.
+pub(crate) unsafe fn external_as_mut<'a, T: 'static>(
+  env: sys::napi_env,
+  external: sys::napi_value,
+) -> Result<&'a mut T> {
+  let unknown_tagged_object =
+    unsafe { get_tagged_object_from_external(env, external, type_name::<T>()) }?;
+  let type_id = unknown_tagged_object as *const TypeId;
+  if unsafe { *type_id } == TypeId::of::<T>() {
+    let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
+    unsafe { (*tagged_object).object.as_mut() }.ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        "nothing attach to js_external".to_owned(),
+      )
+    })
+  } else {
+    Err(Error::new(
+      Status::InvalidArg,
+      "T on get_value_external is not the type of wrapped object".to_owned(),
+    ))
+  }
+}
+
+/// # Safety
+///
+/// `unknown_tagged_object` must be the pointer wrapped by [`Env::wrap`].
+pub(crate) unsafe fn tagged_object_as_mut<'a, T: 'static>(
+  unknown_tagged_object: *mut c_void,
+) -> Result<&'a mut T> {
+  let type_id = unknown_tagged_object as *const TypeId;
+  if unsafe { *type_id } == TypeId::of::<T>() {
+    let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
+    unsafe { (*tagged_object).object.as_mut() }.ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        "Invalid argument, nothing attach to js_object".to_owned(),
+      )
+    })
+  } else {
+    Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "Invalid argument, {} on unwrap is not the type of wrapped object",
+        type_name::<T>()
+      ),
+    ))
+  }
+}
+
 pub(crate) unsafe extern "C" fn raw_finalize<T>(
   env: sys::napi_env,
   finalize_data: *mut c_void,
@@ -1373,6 +2003,14 @@ pub(crate) unsafe extern "C" fn raw_finalize<T>(
   }
 }
 
+/// `value` must stay the first field so `get_instance_data` can read its `TypeId` tag.
+#[cfg(feature = "napi6")]
+#[repr(C)]
+struct InstanceData<T, F> {
+  value: TaggedObject<T>,
+  finalize_cb: F,
+}
+
 #[cfg(feature = "napi6")]
 unsafe extern "C" fn set_instance_finalize_callback<T, Hint, F>(
   raw_env: sys::napi_env,
@@ -1383,10 +2021,11 @@ unsafe extern "C" fn set_instance_finalize_callback<T, Hint, F>(
   Hint: 'static,
   F: FnOnce(FinalizeContext<T, Hint>),
 {
-  let (value, callback) = unsafe { *Box::from_raw(finalize_data as *mut (TaggedObject<T>, F)) };
+  let InstanceData { value, finalize_cb } =
+    unsafe { *Box::from_raw(finalize_data as *mut InstanceData<T, F>) };
   let hint = unsafe { *Box::from_raw(finalize_hint as *mut Hint) };
   let env = unsafe { Env::from_raw(raw_env) };
-  callback(FinalizeContext {
+  finalize_cb(FinalizeContext {
     value: value.object.unwrap(),
     hint,
     env,
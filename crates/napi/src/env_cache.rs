@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::ptr;
+
+use crate::{check_status, sys, Result};
+
+/// Compiled functions kept for each env, the oldest one is released to make room for a new one
+const MAX_COMPILED_FUNCTIONS: usize = 256;
+
+/// The values cached for each env by [`get`] and [`insert`]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) enum CacheKey {
+  /// A value looked up or created once, like a private symbol or a global function
+  Named(&'static str),
+  /// A function created by `Env::compile_function`, by its parameter names and source
+  CompiledFunction(Vec<String>, String),
+}
+
+#[derive(Default)]
+struct EnvCache {
+  refs: HashMap<CacheKey, sys::napi_ref>,
+  /// Insertion order of the compiled functions, to bound their number
+  compiled_functions: VecDeque<CacheKey>,
+}
+
+thread_local! {
+  /// References cached for the lifetime of an env. The cache of an env is dropped by an env cleanup hook,
+  /// the references are freed along with the env, so an env created at the same address starts empty.
+  static ENV_CACHES: RefCell<HashMap</* env */ usize, EnvCache>> = Default::default();
+}
+
+/// Get the value cached for `key` in `env`.
+pub(crate) fn get(env: sys::napi_env, key: &CacheKey) -> Result<Option<sys::napi_value>> {
+  let cached = ENV_CACHES.with(|caches| {
+    caches
+      .borrow()
+      .get(&(env as usize))
+      .and_then(|cache| cache.refs.get(key).copied())
+  });
+  let Some(value_ref) = cached else {
+    return Ok(None);
+  };
+  let mut value = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_reference_value(env, value_ref, &mut value) },
+    "Get cached value from reference failed"
+  )?;
+  Ok(Some(value))
+}
+
+/// Cache `value` for `key` until `env` is torn down.
+///
+/// Without env cleanup hooks the end of an env can't be observed and the cache lives as long as the thread,
+/// runtimes without them don't run addons in worker threads.
+pub(crate) fn insert(env: sys::napi_env, key: CacheKey, value: sys::napi_value) -> Result<()> {
+  let mut value_ref = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_reference(env, value, 1, &mut value_ref) },
+    "Create reference of cached value failed"
+  )?;
+  let (is_new_env, evicted) = ENV_CACHES.with(|caches| {
+    let mut caches = caches.borrow_mut();
+    let is_new_env = !caches.contains_key(&(env as usize));
+    let cache = caches.entry(env as usize).or_default();
+    let mut evicted = None;
+    if matches!(key, CacheKey::CompiledFunction(..)) {
+      if cache.compiled_functions.len() >= MAX_COMPILED_FUNCTIONS {
+        evicted = cache
+          .compiled_functions
+          .pop_front()
+          .and_then(|oldest| cache.refs.remove(&oldest));
+      }
+      cache.compiled_functions.push_back(key.clone());
+    }
+    let replaced = cache.refs.insert(key, value_ref);
+    (is_new_env, evicted.or(replaced))
+  });
+  if let Some(evicted) = evicted {
+    check_status!(
+      unsafe { sys::napi_delete_reference(env, evicted) },
+      "Delete reference of cached value failed"
+    )?;
+  }
+  #[cfg(feature = "napi3")]
+  if is_new_env {
+    check_status!(
+      unsafe { sys::napi_add_env_cleanup_hook(env, Some(drop_env_cache), env.cast()) },
+      "Add cleanup hook of the env cache failed"
+    )?;
+  }
+  #[cfg(not(feature = "napi3"))]
+  let _ = is_new_env;
+  Ok(())
+}
+
+#[cfg(feature = "napi3")]
+unsafe extern "C" fn drop_env_cache(env: *mut std::ffi::c_void) {
+  ENV_CACHES.with(|caches| caches.borrow_mut().remove(&(env as usize)));
+}
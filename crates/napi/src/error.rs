@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::convert::{From, TryFrom};
 use std::error;
 use std::ffi::CString;
@@ -7,12 +8,13 @@ use std::fmt::Display;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
 
+use once_cell::sync::OnceCell;
 #[cfg(feature = "serde-json")]
 use serde::{de, ser};
 #[cfg(feature = "serde-json")]
 use serde_json::Error as SerdeJSONError;
 
-use crate::bindgen_runtime::ToNapiValue;
+use crate::bindgen_runtime::{FromNapiValue, ToNapiValue};
 use crate::{check_status, sys, Env, JsUnknown, NapiValue, Status};
 
 pub type Result<T, S = Status> = std::result::Result<T, Error<S>>;
@@ -26,6 +28,21 @@ pub struct Error<S: AsRef<str> = Status> {
   pub reason: String,
   // Convert raw `JsError` into Error
   pub(crate) maybe_raw: sys::napi_ref,
+  pub(crate) cause: Option<Box<Error>>,
+  // The rarely set details, boxed to keep `Result<T>` small
+  pub(crate) extra: Option<Box<ErrorExtra>>,
+}
+
+/// The details of an [`Error`] which most errors don't have
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ErrorExtra {
+  // `name` and `code` of the JavaScript value the Error is converted from
+  pub(crate) js_name: Option<String>,
+  pub(crate) js_code: Option<String>,
+  // `Some` for an error created by `Error::transient`, the delay before retrying in milliseconds
+  pub(crate) retry_after_ms: Option<u32>,
+  // `Some` for an error created by `Error::validation`
+  pub(crate) fields: Option<Box<[FieldError]>>,
 }
 
 impl<S: AsRef<str>> ToNapiValue for Error<S> {
@@ -91,25 +108,62 @@ impl From<JsUnknown> for Error {
       );
     }
 
-    let maybe_error_message = value
+    let js_name = unsafe { get_string_property(value.0.env, value.0.value, "name\0") };
+    let js_code = unsafe { get_string_property(value.0.env, value.0.value, "code\0") };
+    let reason = value
       .coerce_to_string()
-      .and_then(|a| a.into_utf8().and_then(|a| a.into_owned()));
-    if let Ok(error_message) = maybe_error_message {
-      return Self {
-        status: Status::GenericFailure,
-        reason: error_message,
-        maybe_raw: result,
-      };
-    }
-
+      .and_then(|a| a.into_utf8().and_then(|a| a.into_owned()))
+      .unwrap_or_default();
     Self {
       status: Status::GenericFailure,
-      reason: "".to_string(),
+      reason,
       maybe_raw: result,
+      cause: None,
+      extra: (js_name.is_some() || js_code.is_some()).then(|| {
+        Box::new(ErrorExtra {
+          js_name,
+          js_code,
+          ..Default::default()
+        })
+      }),
     }
   }
 }
 
+/// Read a string property of a thrown value, `None` if the value is not an object or the property is not a string.
+///
+/// An exception thrown by a getter is cleared, so it doesn't replace the error being converted.
+pub(crate) unsafe fn get_string_property(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  key: &str,
+) -> Option<String> {
+  let mut value_type = 0;
+  if unsafe { sys::napi_typeof(env, value, &mut value_type) } != sys::Status::napi_ok
+    || value_type != sys::ValueType::napi_object
+  {
+    return None;
+  }
+  let mut property = ptr::null_mut();
+  let status =
+    unsafe { sys::napi_get_named_property(env, value, key.as_ptr().cast(), &mut property) };
+  if status == sys::Status::napi_pending_exception {
+    let mut exception = ptr::null_mut();
+    unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+    return None;
+  }
+  if status != sys::Status::napi_ok {
+    return None;
+  }
+  let mut property_type = 0;
+  if unsafe { sys::napi_typeof(env, property, &mut property_type) } != sys::Status::napi_ok
+    || property_type != sys::ValueType::napi_string
+  {
+    return None;
+  }
+  unsafe { String::from_napi_value(env, property) }.ok()
+}
+
 #[cfg(feature = "anyhow")]
 impl From<anyhow::Error> for Error {
   fn from(value: anyhow::Error) -> Self {
@@ -133,6 +187,8 @@ impl<S: AsRef<str>> Error<S> {
       status,
       reason: reason.to_string(),
       maybe_raw: ptr::null_mut(),
+      cause: None,
+      extra: None,
     }
   }
 
@@ -141,8 +197,36 @@ impl<S: AsRef<str>> Error<S> {
       status,
       reason: "".to_owned(),
       maybe_raw: ptr::null_mut(),
+      cause: None,
+      extra: None,
     }
   }
+
+  /// Create an `Error` with the message `Node-API call failed with status {status}`,
+  /// e.g. for a raw `sys` call which returned `status`.
+  ///
+  /// [`Error::from_status`] leaves the message empty instead.
+  pub fn from_failed_status(status: S) -> Self {
+    Error {
+      reason: format!("Node-API call failed with status {}", status.as_ref()),
+      status,
+      maybe_raw: ptr::null_mut(),
+      cause: None,
+      extra: None,
+    }
+  }
+
+  /// Set the `cause` of the JavaScript error created from this `Error`.
+  ///
+  /// On engines without support for `Error.prototype.cause`, the messages of the cause chain are appended to the message instead.
+  pub fn with_cause(mut self, cause: Error) -> Self {
+    self.cause = Some(Box::new(cause));
+    self
+  }
+
+  fn extra_mut(&mut self) -> &mut ErrorExtra {
+    self.extra.get_or_insert_with(Default::default)
+  }
 }
 
 impl Error {
@@ -151,6 +235,191 @@ impl Error {
       status: Status::GenericFailure,
       reason: reason.into(),
       maybe_raw: ptr::null_mut(),
+      cause: None,
+      extra: None,
+    }
+  }
+
+  /// Create an `Error` from a Rust error, the `source()` chain of it becomes the `cause` chain of the JavaScript error.
+  ///
+  /// ```rust
+  /// use napi::{Error, Result};
+  ///
+  /// fn read_config(path: &str) -> Result<String> {
+  ///   std::fs::read_to_string(path).map_err(|err| Error::from_error_chain(&err))
+  /// }
+  /// ```
+  pub fn from_error_chain<E: error::Error + ?Sized>(err: &E) -> Self {
+    let error = Error::from_reason(err.to_string());
+    match err.source() {
+      Some(source) => error.with_cause(Error::from_error_chain(source)),
+      None => error,
+    }
+  }
+
+  /// Create an `Error` for a failure which may succeed when it's retried, e.g. a timeout of a flaky resource.
+  ///
+  /// The JavaScript error has a `retryable` property set to `true` and a `retryAfterMs` property
+  /// with the delay to wait before retrying, so the retry logic in JavaScript can treat every
+  /// transient error the same way. Other errors don't have these properties.
+  ///
+  /// ```rust
+  /// use napi::{Error, Result};
+  /// # struct QuoteService;
+  /// # impl QuoteService {
+  /// #   fn fetch(&self) -> std::io::Result<String> {
+  /// #     Ok(String::new())
+  /// #   }
+  /// # }
+  ///
+  /// fn fetch_quote(service: &QuoteService) -> Result<String> {
+  ///   service
+  ///     .fetch()
+  ///     .map_err(|_| Error::transient("Quote service is busy", 500))
+  /// }
+  /// ```
+  pub fn transient<T: Into<String>>(reason: T, retry_after_ms: u32) -> Self {
+    let mut error = Error::from_reason(reason);
+    error.extra_mut().retry_after_ms = Some(retry_after_ms);
+    error
+  }
+
+  /// Create an `Error` for input which failed validation, with an entry for every invalid field.
+  ///
+  /// The JavaScript error has the `name` `"ValidationError"` and a `fields` property,
+  /// an array of `{ field, message }` objects, so a form can show every error next to its field.
+  /// A `#[napi]` function may also return `std::result::Result<T, Vec<FieldError>>`, the `Err` is thrown as this error.
+  ///
+  /// ```rust
+  /// use napi::{Error, FieldError, Result};
+  ///
+  /// fn check_age(age: i32) -> Result<u32> {
+  ///   u32::try_from(age).map_err(|_| {
+  ///     Error::validation(vec![FieldError::new("age", "Must not be negative")])
+  ///   })
+  /// }
+  /// ```
+  pub fn validation(fields: Vec<FieldError>) -> Self {
+    let names = fields
+      .iter()
+      .map(|field| field.field.as_str())
+      .collect::<Vec<_>>()
+      .join(", ");
+    let mut error = Error::new(
+      Status::InvalidArg,
+      format!("Validation failed for {}", names),
+    );
+    error.extra_mut().fields = Some(fields.into_boxed_slice());
+    error
+  }
+}
+
+/// An invalid field of the input, see [`Error::validation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+  pub field: String,
+  pub message: String,
+}
+
+impl FieldError {
+  pub fn new<F: Into<String>, M: Into<String>>(field: F, message: M) -> Self {
+    FieldError {
+      field: field.into(),
+      message: message.into(),
+    }
+  }
+}
+
+impl From<Vec<FieldError>> for Error {
+  fn from(fields: Vec<FieldError>) -> Self {
+    Error::validation(fields)
+  }
+}
+
+impl From<Vec<FieldError>> for JsError {
+  fn from(fields: Vec<FieldError>) -> Self {
+    JsError(Error::validation(fields))
+  }
+}
+
+/// The kind of a JavaScript error caught as an [`Error`], see [`Error::classify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+  /// A plain `Error` without a `code`
+  Error,
+  TypeError,
+  RangeError,
+  SyntaxError,
+  ReferenceError,
+  EvalError,
+  URIError,
+  AggregateError,
+  /// An error with a string `code`, e.g. `ERR_INVALID_ARG_TYPE` thrown by Node.js APIs or a custom code
+  Code(String),
+  /// An object with a `name` which is none of the above, e.g. an instance of a custom error class
+  Other(String),
+  /// Not converted from a thrown JavaScript value, e.g. created by [`Error::new`],
+  /// or a thrown value which is neither an object with a `name` nor with a `code`
+  Unknown,
+}
+
+impl<S: AsRef<str>> Error<S> {
+  /// The `name` of the JavaScript value this `Error` is converted from, like `"TypeError"`.
+  pub fn js_name(&self) -> Option<&str> {
+    self.extra.as_ref()?.js_name.as_deref()
+  }
+
+  /// The `code` of the JavaScript value this `Error` is converted from, if it's a string.
+  pub fn js_code(&self) -> Option<&str> {
+    self.extra.as_ref()?.js_code.as_deref()
+  }
+
+  /// Whether the error is created by [`Error::transient`], so the failed operation may be retried.
+  pub fn is_transient(&self) -> bool {
+    self.retry_after_ms().is_some()
+  }
+
+  /// The delay before retrying of an error created by [`Error::transient`], in milliseconds.
+  pub fn retry_after_ms(&self) -> Option<u32> {
+    self.extra.as_ref()?.retry_after_ms
+  }
+
+  /// The invalid fields of an error created by [`Error::validation`].
+  pub fn field_errors(&self) -> Option<&[FieldError]> {
+    self.extra.as_ref()?.fields.as_deref()
+  }
+
+  /// Classify the caught JavaScript error by its `name` and `code`.
+  ///
+  /// The built-in error types are matched by `name` first, so a `TypeError` thrown by Node.js
+  /// with the code `ERR_INVALID_ARG_TYPE` is an [`ErrorKind::TypeError`]; the code can still be read with [`Error::js_code`].
+  ///
+  /// ```rust
+  /// use napi::{ErrorKind, JsFunction, Result};
+  ///
+  /// fn call_parser(parse: JsFunction) -> Result<Option<String>> {
+  ///   match parse.call_without_args(None) {
+  ///     Ok(_) => Ok(None),
+  ///     Err(e) if e.classify() == ErrorKind::SyntaxError => Ok(Some(e.reason)),
+  ///     Err(e) => Err(e),
+  ///   }
+  /// }
+  /// ```
+  pub fn classify(&self) -> ErrorKind {
+    match self.js_name() {
+      Some("TypeError") => ErrorKind::TypeError,
+      Some("RangeError") => ErrorKind::RangeError,
+      Some("SyntaxError") => ErrorKind::SyntaxError,
+      Some("ReferenceError") => ErrorKind::ReferenceError,
+      Some("EvalError") => ErrorKind::EvalError,
+      Some("URIError") => ErrorKind::URIError,
+      Some("AggregateError") => ErrorKind::AggregateError,
+      name => match (self.js_code(), name) {
+        (Some(code), _) => ErrorKind::Code(code.to_owned()),
+        (None, Some("Error")) => ErrorKind::Error,
+        (None, Some(name)) => ErrorKind::Other(name.to_owned()),
+        (None, None) => ErrorKind::Unknown,
+      },
     }
   }
 }
@@ -161,6 +430,8 @@ impl From<std::ffi::NulError> for Error {
       status: Status::GenericFailure,
       reason: format!("{}", error),
       maybe_raw: ptr::null_mut(),
+      cause: None,
+      extra: None,
     }
   }
 }
@@ -171,6 +442,8 @@ impl From<std::io::Error> for Error {
       status: Status::GenericFailure,
       reason: format!("{}", error),
       maybe_raw: ptr::null_mut(),
+      cause: None,
+      extra: None,
     }
   }
 }
@@ -239,11 +512,25 @@ macro_rules! impl_object_methods {
           return err;
         }
 
+        let cause_supported = self.0.cause.is_some() && unsafe { is_error_cause_supported(env) };
+        let reason = match &self.0.cause {
+          Some(cause) if !cause_supported => {
+            let mut reason = self.0.reason.clone();
+            let mut cause = Some(cause);
+            while let Some(current) = cause {
+              reason.push_str("\nCaused by: ");
+              reason.push_str(&current.reason);
+              cause = current.cause.as_ref();
+            }
+            Cow::Owned(reason)
+          }
+          _ => Cow::Borrowed(self.0.reason.as_str()),
+        };
         let error_status = self.0.status.as_ref();
         let status_len = error_status.len();
         let error_code_string = CString::new(error_status).unwrap();
-        let reason_len = self.0.reason.len();
-        let reason = CString::new(self.0.reason.as_str()).unwrap();
+        let reason_len = reason.len();
+        let reason = CString::new(reason.as_ref()).unwrap();
         let mut error_code = ptr::null_mut();
         let mut reason_string = ptr::null_mut();
         let mut js_error = ptr::null_mut();
@@ -257,6 +544,43 @@ macro_rules! impl_object_methods {
         debug_assert!(create_reason_status == sys::Status::napi_ok);
         let create_error_status = unsafe { $kind(env, error_code, reason_string, &mut js_error) };
         debug_assert!(create_error_status == sys::Status::napi_ok);
+        if let Some(cause) = self.0.cause.filter(|_| cause_supported) {
+          let cause = unsafe { JsError::from(*cause).into_value(env) };
+          let set_cause_status = unsafe {
+            sys::napi_set_named_property(env, js_error, "cause\0".as_ptr().cast(), cause)
+          };
+          debug_assert!(set_cause_status == sys::Status::napi_ok);
+        }
+        let extra = self.0.extra.as_deref();
+        if let Some(retry_after_ms) = extra.and_then(|extra| extra.retry_after_ms) {
+          let mut retryable = ptr::null_mut();
+          let get_retryable_status = unsafe { sys::napi_get_boolean(env, true, &mut retryable) };
+          debug_assert!(get_retryable_status == sys::Status::napi_ok);
+          let set_retryable_status = unsafe {
+            sys::napi_set_named_property(env, js_error, "retryable\0".as_ptr().cast(), retryable)
+          };
+          debug_assert!(set_retryable_status == sys::Status::napi_ok);
+          let mut retry_after = ptr::null_mut();
+          let create_retry_after_status =
+            unsafe { sys::napi_create_uint32(env, retry_after_ms, &mut retry_after) };
+          debug_assert!(create_retry_after_status == sys::Status::napi_ok);
+          let set_retry_after_status = unsafe {
+            sys::napi_set_named_property(
+              env,
+              js_error,
+              "retryAfterMs\0".as_ptr().cast(),
+              retry_after,
+            )
+          };
+          debug_assert!(set_retry_after_status == sys::Status::napi_ok);
+        }
+        if let Some(fields) = extra.and_then(|extra| extra.fields.as_deref()) {
+          let set_fields_result = unsafe { set_field_errors(env, js_error, fields) };
+          debug_assert!(
+            set_fields_result.is_ok(),
+            "Set fields of ValidationError failed"
+          );
+        }
         js_error
       }
 
@@ -265,6 +589,9 @@ macro_rules! impl_object_methods {
         unsafe { JsUnknown::from_raw_unchecked(env.raw(), value) }
       }
 
+      /// Nothing is thrown if an exception is already pending, e.g. thrown with [`Env::throw`](crate::Env::throw),
+      /// the pending exception is kept.
+      ///
       /// # Safety
       ///
       /// This function is safety if env is not null ptr.
@@ -275,10 +602,21 @@ macro_rules! impl_object_methods {
         if status == Status::PendingException.as_ref() {
           return;
         }
+        let mut is_pending = false;
+        unsafe { sys::napi_is_exception_pending(env, &mut is_pending) };
+        if is_pending {
+          if !self.0.maybe_raw.is_null() {
+            unsafe { sys::napi_delete_reference(env, self.0.maybe_raw) };
+          }
+          return;
+        }
         let js_error = unsafe { self.into_value(env) };
         #[cfg(debug_assertions)]
         let throw_status = unsafe { sys::napi_throw(env, js_error) };
-        unsafe { sys::napi_throw(env, js_error) };
+        #[cfg(not(debug_assertions))]
+        unsafe {
+          sys::napi_throw(env, js_error)
+        };
         #[cfg(debug_assertions)]
         assert!(
           throw_status == sys::Status::napi_ok,
@@ -304,6 +642,77 @@ macro_rules! impl_object_methods {
   };
 }
 
+/// Set the `name` of a validation error to `"ValidationError"` and its `fields` to an array of `{ field, message }`
+unsafe fn set_field_errors(
+  env: sys::napi_env,
+  js_error: sys::napi_value,
+  fields: &[FieldError],
+) -> Result<()> {
+  let name = unsafe { String::to_napi_value(env, "ValidationError".to_owned()) }?;
+  check_status!(unsafe {
+    sys::napi_set_named_property(env, js_error, "name\0".as_ptr().cast(), name)
+  })?;
+  let mut array = ptr::null_mut();
+  check_status!(unsafe { sys::napi_create_array_with_length(env, fields.len(), &mut array) })?;
+  for (index, field_error) in fields.iter().enumerate() {
+    let mut entry = ptr::null_mut();
+    check_status!(unsafe { sys::napi_create_object(env, &mut entry) })?;
+    for (key, value) in [
+      ("field\0", &field_error.field),
+      ("message\0", &field_error.message),
+    ] {
+      let value = unsafe { String::to_napi_value(env, value.clone()) }?;
+      check_status!(unsafe {
+        sys::napi_set_named_property(env, entry, key.as_ptr().cast(), value)
+      })?;
+    }
+    check_status!(unsafe { sys::napi_set_element(env, array, index as u32, entry) })?;
+  }
+  check_status!(unsafe {
+    sys::napi_set_named_property(env, js_error, "fields\0".as_ptr().cast(), array)
+  })
+}
+
+static ERROR_CAUSE_SUPPORTED: OnceCell<bool> = OnceCell::new();
+
+/// Whether the engine keeps the `cause` option of the `Error` constructor
+unsafe fn is_error_cause_supported(env: sys::napi_env) -> bool {
+  if let Some(supported) = ERROR_CAUSE_SUPPORTED.get() {
+    return *supported;
+  }
+  // don't cache failures, the detection fails while an exception is pending
+  match unsafe { detect_error_cause(env) } {
+    Ok(supported) => *ERROR_CAUSE_SUPPORTED.get_or_init(|| supported),
+    Err(_) => false,
+  }
+}
+
+unsafe fn detect_error_cause(env: sys::napi_env) -> Result<bool> {
+  let mut global = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+  let mut error_ctor = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_get_named_property(env, global, "Error\0".as_ptr().cast(), &mut error_ctor)
+  })?;
+  let mut options = ptr::null_mut();
+  check_status!(unsafe { sys::napi_create_object(env, &mut options) })?;
+  let mut cause = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_boolean(env, true, &mut cause) })?;
+  check_status!(unsafe {
+    sys::napi_set_named_property(env, options, "cause\0".as_ptr().cast(), cause)
+  })?;
+  let mut message = ptr::null_mut();
+  check_status!(unsafe { sys::napi_create_string_utf8(env, "".as_ptr().cast(), 0, &mut message) })?;
+  let args = [message, options];
+  let mut error = ptr::null_mut();
+  check_status!(unsafe { sys::napi_new_instance(env, error_ctor, 2, args.as_ptr(), &mut error) })?;
+  let mut supported = false;
+  check_status!(unsafe {
+    sys::napi_has_named_property(env, error, "cause\0".as_ptr().cast(), &mut supported)
+  })?;
+  Ok(supported)
+}
+
 impl_object_methods!(JsError, sys::napi_create_error);
 impl_object_methods!(JsTypeError, sys::napi_create_type_error);
 impl_object_methods!(JsRangeError, sys::napi_create_range_error);
@@ -72,9 +72,23 @@ impl TypeName for JsDataView {
   }
 }
 
+impl ValidateNapiValue for JsDataView {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    let mut is_dataview = false;
+    check_status!(unsafe { sys::napi_is_dataview(env, napi_val, &mut is_dataview) })?;
+    if !is_dataview {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Value is not a DataView".to_owned(),
+      ));
+    }
+    Ok(ptr::null_mut())
+  }
+}
+
 pub struct JsDataViewValue {
   pub arraybuffer: JsArrayBuffer,
-  _data: *mut c_void,
+  data: *mut c_void,
   pub byte_offset: u64,
   pub length: u64,
 }
@@ -324,6 +338,10 @@ impl_as_ref!(i64, TypedArrayType::BigInt64);
 impl_as_ref!(u64, TypedArrayType::BigUint64);
 
 impl JsDataView {
+  /// get DataView info
+  /// <https://nodejs.org/api/n-api.html#napi_get_dataview_info>
+  ///
+  /// Returns an `Err` if the underlying `ArrayBuffer` is detached.
   pub fn into_value(self) -> Result<JsDataViewValue> {
     let mut length = 0u64;
     let mut byte_offset = 0u64;
@@ -340,11 +358,51 @@ impl JsDataView {
         &mut byte_offset as *mut u64 as *mut _,
       )
     })?;
+    let arraybuffer = unsafe { JsArrayBuffer::from_raw_unchecked(self.0.env, arraybuffer_value) };
+    #[cfg(feature = "napi7")]
+    if arraybuffer.is_detached()? {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The ArrayBuffer of the DataView is detached".to_owned(),
+      ));
+    }
     Ok(JsDataViewValue {
-      arraybuffer: unsafe { JsArrayBuffer::from_raw_unchecked(self.0.env, arraybuffer_value) },
+      arraybuffer,
       byte_offset,
       length,
-      _data: data,
+      data,
     })
   }
 }
+
+impl AsRef<[u8]> for JsDataViewValue {
+  fn as_ref(&self) -> &[u8] {
+    if self.length == 0 {
+      return &[];
+    }
+    unsafe { slice::from_raw_parts(self.data as *const u8, self.length as usize) }
+  }
+}
+
+impl AsMut<[u8]> for JsDataViewValue {
+  fn as_mut(&mut self) -> &mut [u8] {
+    if self.length == 0 {
+      return &mut [];
+    }
+    unsafe { slice::from_raw_parts_mut(self.data as *mut u8, self.length as usize) }
+  }
+}
+
+impl Deref for JsDataViewValue {
+  type Target = [u8];
+
+  fn deref(&self) -> &Self::Target {
+    self.as_ref()
+  }
+}
+
+impl DerefMut for JsDataViewValue {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.as_mut()
+  }
+}
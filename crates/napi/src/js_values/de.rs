@@ -3,6 +3,7 @@ use std::convert::TryInto;
 use serde::de::Visitor;
 use serde::de::{DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected, VariantAccess};
 
+use crate::bindgen_runtime::NestingGuard;
 #[cfg(feature = "napi6")]
 use crate::JsBigInt;
 use crate::{type_of, NapiValue, Value, ValueType};
@@ -41,6 +42,7 @@ impl<'x, 'de, 'env> serde::de::Deserializer<'x> for &'de mut De<'env> {
         visitor.visit_str(js_string.into_utf8()?.as_str()?)
       }
       ValueType::Object => {
+        let _nesting_guard = NestingGuard::enter()?;
         let js_object = unsafe { JsObject::from_raw_unchecked(self.0.env, self.0.value) };
         if js_object.is_array()? {
           let mut deserializer =
@@ -117,6 +119,7 @@ impl<'x, 'de, 'env> serde::de::Deserializer<'x> for &'de mut De<'env> {
         None,
       )),
       ValueType::Object => {
+        let _nesting_guard = NestingGuard::enter()?;
         let js_object = unsafe { JsObject::from_raw_unchecked(self.0.env, self.0.value) };
         let properties = js_object.get_property_names()?;
         let property_len = properties.get_array_length_unchecked()?;
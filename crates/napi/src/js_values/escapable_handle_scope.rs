@@ -24,7 +24,9 @@ impl<T: NapiRaw> EscapableHandleScope<T> {
   }
 
   pub fn close(self, env: Env) -> Result<()> {
-    check_status!(unsafe { sys::napi_close_escapable_handle_scope(env.0, self.handle_scope) })
+    check_status!(unsafe { sys::napi_close_escapable_handle_scope(env.0, self.handle_scope) })?;
+    crate::unwrap_cache::handle_scope_closed();
+    Ok(())
   }
 }
 
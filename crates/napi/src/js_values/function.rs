@@ -7,7 +7,7 @@ use crate::{
   threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction},
 };
 use crate::{bindgen_runtime::TypeName, JsString};
-use crate::{check_pending_exception, ValueType};
+use crate::{check_pending_exception, check_status, error::get_string_property, ValueType};
 use crate::{sys, Env, Error, JsObject, JsUnknown, NapiRaw, NapiValue, Result, Status};
 
 pub struct JsFunction(pub(crate) Value);
@@ -97,6 +97,99 @@ impl JsFunction {
     unsafe { JsUnknown::from_raw(self.0.env, return_value) }
   }
 
+  /// Call the function with `this` set to `undefined`, an exception it throws is cleared and returned as `Err`.
+  ///
+  /// The `reason` of the error is the `message` of a thrown `Error`, or the thrown value converted to a string.
+  /// Throwing the error again, e.g. by returning it from a `#[napi]` function, throws the original object,
+  /// a thrown primitive is thrown as an `Error` instead.
+  ///
+  /// ```rust,ignore
+  /// #[napi]
+  /// pub fn run_hook(env: Env, hook: JsFunction) -> Result<String> {
+  ///   match hook.call_catch(&[env.create_string("start")?]) {
+  ///     Ok(_) => Ok("ok".to_owned()),
+  ///     Err(e) => Ok(format!("hook failed: {}", e.reason)),
+  ///   }
+  /// }
+  /// ```
+  pub fn call_catch<V>(&self, args: &[V]) -> Result<JsUnknown>
+  where
+    V: NapiRaw,
+  {
+    let env = unsafe { Env::from_raw(self.0.env) };
+    let undefined = env.get_undefined()?;
+    let raw_args = args
+      .iter()
+      .map(|arg| unsafe { arg.raw() })
+      .collect::<Vec<sys::napi_value>>();
+    let mut return_value = ptr::null_mut();
+    let status = unsafe {
+      sys::napi_call_function(
+        self.0.env,
+        undefined.raw(),
+        self.0.value,
+        raw_args.len(),
+        raw_args.as_ptr(),
+        &mut return_value,
+      )
+    };
+    if env.is_exception_pending()? {
+      let mut exception = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_and_clear_last_exception(self.0.env, &mut exception) },
+        "Failed to clear the exception thrown by the function"
+      )?;
+      let exception = unsafe { JsUnknown::from_raw_unchecked(self.0.env, exception) };
+      if !matches!(
+        exception.get_type()?,
+        ValueType::Object | ValueType::Function
+      ) {
+        // a primitive can't be kept in a reference to throw it again
+        let reason = exception.coerce_to_string()?.into_utf8()?.into_owned()?;
+        return Err(Error::new(Status::GenericFailure, reason));
+      }
+      let message = unsafe { get_string_property(self.0.env, exception.raw(), "message\0") };
+      let mut error = Error::from(exception);
+      if let Some(message) = message {
+        error.reason = message;
+      }
+      return Err(error);
+    }
+    check_status!(status, "Failed to call the function")?;
+    unsafe { JsUnknown::from_raw(self.0.env, return_value) }
+  }
+
+  /// Create a new function with `this` bound and `args` prepended to the arguments of every call,
+  /// the same as [Function.prototype.bind](https://tc39.es/ecma262/#sec-function.prototype.bind) in JavaScript.
+  ///
+  /// `Function.prototype.bind` is looked up from the global object, so an overridden `bind` property on this function is ignored.
+  pub fn bind<V>(&self, this: &JsObject, args: &[V]) -> Result<JsFunction>
+  where
+    V: NapiRaw,
+  {
+    let env = unsafe { Env::from_raw(self.0.env) };
+    let bind = env
+      .get_global()?
+      .get_named_property_unchecked::<JsObject>("Function")?
+      .get_named_property_unchecked::<JsObject>("prototype")?
+      .get_named_property_unchecked::<JsFunction>("bind")?;
+    let raw_args = std::iter::once(unsafe { this.raw() })
+      .chain(args.iter().map(|arg| unsafe { arg.raw() }))
+      .collect::<Vec<sys::napi_value>>();
+    let mut bound = ptr::null_mut();
+    check_pending_exception!(self.0.env, unsafe {
+      sys::napi_call_function(
+        self.0.env,
+        self.0.value,
+        bind.0.value,
+        raw_args.len(),
+        raw_args.as_ptr(),
+        &mut bound,
+      )
+    })?;
+    Ok(unsafe { JsFunction::from_raw_unchecked(self.0.env, bound) })
+  }
+
   /// <https://nodejs.org/api/n-api.html#n_api_napi_new_instance>
   ///
   /// This method is used to instantiate a new `JavaScript` value using a given `JsFunction` that represents the constructor for the object.
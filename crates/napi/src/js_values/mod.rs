@@ -5,8 +5,10 @@ use std::ffi::CString;
 use std::ptr;
 
 use crate::{
-  bindgen_runtime::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue},
-  check_status, sys, type_of, Callback, Error, Result, Status, ValueType,
+  bindgen_runtime::{
+    get_class_constructor, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue,
+  },
+  check_status, sys, type_of, Callback, Env, Error, Result, Status, ValueType,
 };
 
 #[cfg(feature = "serde-json")]
@@ -57,7 +59,7 @@ pub use object_property::*;
 #[cfg(feature = "serde-json")]
 pub(crate) use ser::Ser;
 pub use string::*;
-pub(crate) use tagged_object::TaggedObject;
+pub(crate) use tagged_object::{get_tagged_object_from_external, tag_external, TaggedObject};
 pub use undefined::JsUndefined;
 pub(crate) use value::Value;
 pub use value_ref::*;
@@ -110,6 +112,21 @@ impl TypeName for JsExternal {
 
 impl ValidateNapiValue for JsExternal {}
 
+impl JsExternal {
+  /// Get the native object created by `Env::create_external`.
+  ///
+  /// Returns an `InvalidArg` error if `T` is not the type of the native object,
+  /// or if the external was not created by napi-rs.
+  pub fn get<T: 'static>(&self) -> Result<&T> {
+    unsafe { crate::env::external_as_mut::<T>(self.0.env, self.0.value) }.map(|object| &*object)
+  }
+
+  /// Get the native object created by `Env::create_external` mutably, see [`JsExternal::get`].
+  pub fn get_mut<T: 'static>(&mut self) -> Result<&mut T> {
+    unsafe { crate::env::external_as_mut(self.0.env, self.0.value) }
+  }
+}
+
 macro_rules! impl_napi_value_trait {
   ($js_value:ident, $value_type:ident) => {
     impl NapiValue for $js_value {
@@ -233,6 +250,12 @@ macro_rules! impl_js_value_methods {
         Ok(result)
       }
 
+      pub fn is_arraybuffer(&self) -> Result<bool> {
+        let mut result = false;
+        check_status!(unsafe { sys::napi_is_arraybuffer(self.0.env, self.0.value, &mut result) })?;
+        Ok(result)
+      }
+
       pub fn is_typedarray(&self) -> Result<bool> {
         let mut result = false;
         check_status!(unsafe { sys::napi_is_typedarray(self.0.env, self.0.value, &mut result) })?;
@@ -387,6 +410,9 @@ macro_rules! impl_object_methods {
         Ok(result)
       }
 
+      /// Delete the own property `name`, a string, number or symbol like [`JsSymbol`].
+      ///
+      /// Returns `false` if the property is not configurable, deleting a missing property returns `true`.
       pub fn delete_property<S>(&mut self, name: S) -> Result<bool>
       where
         S: NapiRaw,
@@ -398,6 +424,7 @@ macro_rules! impl_object_methods {
         Ok(result)
       }
 
+      /// [`delete_property`](Self::delete_property) with a string key
       pub fn delete_named_property(&mut self, name: &str) -> Result<bool> {
         let mut result = false;
         let key_str = CString::new(name)?;
@@ -411,6 +438,9 @@ macro_rules! impl_object_methods {
         Ok(result)
       }
 
+      /// Whether the object itself has the property `key`, like `Object.hasOwn(obj, key)`.
+      ///
+      /// Properties inherited from the prototype chain are not included, use [`has_property`](Self::has_property) for them.
       pub fn has_own_property(&self, key: &str) -> Result<bool> {
         let mut result = false;
         let string = CString::new(key)?;
@@ -424,6 +454,7 @@ macro_rules! impl_object_methods {
         Ok(result)
       }
 
+      /// [`has_own_property`](Self::has_own_property) with a JavaScript key, a string or a symbol like [`JsSymbol`]
       pub fn has_own_property_js<K>(&self, key: K) -> Result<bool>
       where
         K: NapiRaw,
@@ -435,6 +466,7 @@ macro_rules! impl_object_methods {
         Ok(result)
       }
 
+      /// Whether the object or its prototype chain has the property `name`, like `name in obj`
       pub fn has_property(&self, name: &str) -> Result<bool> {
         let string = CString::new(name)?;
         let mut js_key = ptr::null_mut();
@@ -448,6 +480,7 @@ macro_rules! impl_object_methods {
         Ok(result)
       }
 
+      /// [`has_property`](Self::has_property) with a JavaScript key, a string or a symbol like [`JsSymbol`]
       pub fn has_property_js<K>(&self, name: K) -> Result<bool>
       where
         K: NapiRaw,
@@ -509,6 +542,24 @@ macro_rules! impl_object_methods {
         Ok(unsafe { T::from_raw_unchecked(self.0.env, result) })
       }
 
+      /// Replace the prototype of the object with `proto`, an object or `null`, like `Object.setPrototypeOf`.
+      ///
+      /// Fails if the object is not extensible, or if `proto` would create a cycle in the prototype chain.
+      pub fn set_prototype<T>(&mut self, proto: T) -> Result<()>
+      where
+        T: NapiRaw,
+      {
+        let env = unsafe { Env::from_raw(self.0.env) };
+        let set_prototype_of: JsFunction = env
+          .get_global()?
+          .get_named_property_unchecked::<JsObject>("Object")?
+          .get_named_property_unchecked("setPrototypeOf")?;
+        let this = unsafe { JsUnknown::from_raw_unchecked(self.0.env, self.0.value) };
+        let proto = unsafe { JsUnknown::from_raw_unchecked(self.0.env, proto.raw()) };
+        set_prototype_of.call(None, &[this, proto])?;
+        Ok(())
+      }
+
       pub fn set_element<T>(&mut self, index: u32, value: T) -> Result<()>
       where
         T: NapiRaw,
@@ -734,6 +785,36 @@ impl JsUnknown {
   {
     unsafe { V::from_raw_unchecked(self.0.env, self.0.value) }
   }
+
+  /// Check if this value is an instance of the `#[napi]` class exported as `class_js_name`.
+  ///
+  /// Returns an error instead of `false` if the class is not registered in the current thread,
+  /// for example when the module was never loaded in this worker thread.
+  pub fn instance_of(&self, env: Env, class_js_name: &str) -> Result<bool> {
+    let class_js_name = class_js_name.trim_end_matches('\0');
+    let ctor_ref = get_class_constructor(&format!("{}\0", class_js_name)).ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        format!(
+          "Class `{}` is not registered in the current thread",
+          class_js_name
+        ),
+      )
+    })?;
+    let mut ctor = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env.0, ctor_ref, &mut ctor) },
+      "Failed to get constructor reference of class `{}`",
+      class_js_name
+    )?;
+    let mut is_instance_of = false;
+    check_status!(
+      unsafe { sys::napi_instanceof(env.0, self.0.value, ctor, &mut is_instance_of) },
+      "Failed to run instanceof for class `{}`",
+      class_js_name
+    )?;
+    Ok(is_instance_of)
+  }
 }
 
 #[cfg(feature = "napi5")]
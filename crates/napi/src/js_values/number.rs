@@ -1,8 +1,9 @@
 use std::convert::TryFrom;
 
-use super::Value;
-use crate::bindgen_runtime::{TypeName, ValidateNapiValue};
-use crate::{check_status, ValueType};
+use super::{JsBoolean, JsFunction, JsObject, Value};
+use crate::bindgen_runtime::strict_numbers::{get_strict_integer, range_error};
+use crate::bindgen_runtime::{strict_numbers, TypeName, ValidateNapiValue};
+use crate::{check_status, Env, ValueType};
 use crate::{sys, Error, Result};
 
 #[derive(Clone, Copy)]
@@ -20,39 +21,115 @@ impl TypeName for JsNumber {
 
 impl ValidateNapiValue for JsNumber {}
 
+/// The integer getters fail with a `RangeError` for `NaN` and `Infinity`, instead of returning `0` like N-API does.
+/// Other numbers are truncated and wrapped like N-API does, unless strict numbers are enabled,
+/// see [`set_strict_numbers`](crate::bindgen_prelude::set_strict_numbers).
 impl JsNumber {
   pub fn get_uint32(&self) -> Result<u32> {
+    if strict_numbers() {
+      return self
+        .get_strict_integer("u32", 0.0, u32::MAX as f64)
+        .map(|v| v as u32);
+    }
+    self.check_finite("u32")?;
     let mut result = 0;
     check_status!(unsafe { sys::napi_get_value_uint32(self.0.env, self.0.value, &mut result) })?;
     Ok(result)
   }
 
   pub fn get_int32(&self) -> Result<i32> {
+    if strict_numbers() {
+      return self
+        .get_strict_integer("i32", i32::MIN as f64, i32::MAX as f64)
+        .map(|v| v as i32);
+    }
+    self.check_finite("i32")?;
     let mut result = 0;
     check_status!(unsafe { sys::napi_get_value_int32(self.0.env, self.0.value, &mut result) })?;
     Ok(result)
   }
 
   pub fn get_int64(&self) -> Result<i64> {
+    if strict_numbers() {
+      return self
+        .get_strict_integer("i64", MIN_SAFE_INTEGER, MAX_SAFE_INTEGER)
+        .map(|v| v as i64);
+    }
+    self.check_finite("i64")?;
     let mut result = 0;
     check_status!(unsafe { sys::napi_get_value_int64(self.0.env, self.0.value, &mut result) })?;
     Ok(result)
   }
 
+  /// Get the number as an `i64`, and whether it's converted losslessly,
+  /// `false` if it's not an integer or out of the range of `i64`.
+  ///
+  /// Unlike [`get_int64`](JsNumber::get_int64) it doesn't fail in strict numbers mode, the `bool` reports the precision loss instead.
+  pub fn get_int64_lossless(&self) -> Result<(i64, bool)> {
+    let value = self.get_double()?;
+    if !value.is_finite() {
+      return Err(self.not_an_integer(value, "i64"));
+    }
+    let mut result = 0;
+    check_status!(unsafe { sys::napi_get_value_int64(self.0.env, self.0.value, &mut result) })?;
+    // `i64::MAX as f64` rounds up to 2^63, which is out of range
+    let lossless = value.trunc() == value && value >= i64::MIN as f64 && value < i64::MAX as f64;
+    Ok((result, lossless))
+  }
+
   pub fn get_double(&self) -> Result<f64> {
     let mut result = 0_f64;
     check_status!(unsafe { sys::napi_get_value_double(self.0.env, self.0.value, &mut result) })?;
     Ok(result)
   }
+
+  /// `Number.isSafeInteger`, whether the number is an integer from `Number.MIN_SAFE_INTEGER` to `Number.MAX_SAFE_INTEGER`
+  pub fn is_safe_integer(&self) -> Result<bool> {
+    let env = unsafe { Env::from_raw(self.0.env) };
+    let is_safe_integer = env
+      .get_global()?
+      .get_named_property_unchecked::<JsObject>("Number")?
+      .get_named_property_unchecked::<JsFunction>("isSafeInteger")?;
+    is_safe_integer
+      .call(None, &[*self])?
+      .coerce_to_bool()
+      .and_then(|result: JsBoolean| result.get_value())
+  }
+
+  fn check_finite(&self, name: &str) -> Result<()> {
+    let value = self.get_double()?;
+    if value.is_finite() {
+      Ok(())
+    } else {
+      Err(self.not_an_integer(value, name))
+    }
+  }
+
+  fn not_an_integer(&self, value: f64, name: &str) -> Error {
+    unsafe {
+      range_error(
+        self.0.env,
+        format!(
+          "Number {} is not an integer, expected rust type `{}`",
+          value, name
+        ),
+      )
+    }
+  }
+
+  fn get_strict_integer(&self, name: &str, min: f64, max: f64) -> Result<f64> {
+    unsafe { get_strict_integer(self.0.env, self.0.value, name, min, max) }
+  }
 }
 
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+const MIN_SAFE_INTEGER: f64 = -MAX_SAFE_INTEGER;
+
 impl TryFrom<JsNumber> for u32 {
   type Error = Error;
 
   fn try_from(value: JsNumber) -> Result<u32> {
-    let mut result = 0;
-    check_status!(unsafe { sys::napi_get_value_uint32(value.0.env, value.0.value, &mut result) })?;
-    Ok(result)
+    value.get_uint32()
   }
 }
 
@@ -60,9 +137,7 @@ impl TryFrom<JsNumber> for i32 {
   type Error = Error;
 
   fn try_from(value: JsNumber) -> Result<i32> {
-    let mut result = 0;
-    check_status!(unsafe { sys::napi_get_value_int32(value.0.env, value.0.value, &mut result) })?;
-    Ok(result)
+    value.get_int32()
   }
 }
 
@@ -70,9 +145,7 @@ impl TryFrom<JsNumber> for i64 {
   type Error = Error;
 
   fn try_from(value: JsNumber) -> Result<i64> {
-    let mut result = 0;
-    check_status!(unsafe { sys::napi_get_value_int64(value.0.env, value.0.value, &mut result) })?;
-    Ok(result)
+    value.get_int64()
   }
 }
 
@@ -80,8 +153,6 @@ impl TryFrom<JsNumber> for f64 {
   type Error = Error;
 
   fn try_from(value: JsNumber) -> Result<f64> {
-    let mut result = 0_f64;
-    check_status!(unsafe { sys::napi_get_value_double(value.0.env, value.0.value, &mut result) })?;
-    Ok(result)
+    value.get_double()
   }
 }
@@ -39,6 +39,7 @@ pub struct Property {
   attrs: PropertyAttributes,
   value: sys::napi_value,
   pub(crate) is_ctor: bool,
+  pub(crate) is_to_primitive: bool,
   #[cfg(feature = "napi5")]
   pub(crate) closures: PropertyClosures,
 }
@@ -53,6 +54,7 @@ impl Default for Property {
       attrs: Default::default(),
       value: ptr::null_mut(),
       is_ctor: Default::default(),
+      is_to_primitive: Default::default(),
       #[cfg(feature = "napi5")]
       closures: PropertyClosures::default(),
     }
@@ -173,4 +175,11 @@ impl Property {
     self.is_ctor = true;
     self
   }
+
+  /// Also define the method of this class property as `[Symbol.toPrimitive]` of the class
+  /// prototype, so it converts the instances in `String(instance)` or template literals.
+  pub fn with_to_primitive(mut self) -> Self {
+    self.is_to_primitive = true;
+    self
+  }
 }
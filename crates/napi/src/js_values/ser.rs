@@ -1,3 +1,5 @@
+use std::any::type_name;
+use std::cell::RefCell;
 use std::result::Result as StdResult;
 #[cfg(feature = "napi6")]
 use std::slice;
@@ -5,7 +7,46 @@ use std::slice;
 use serde::{ser, Serialize, Serializer};
 
 use super::*;
-use crate::{Env, Error, Result};
+use crate::{Env, Error, Result, Status};
+
+thread_local! {
+  /// Address and type of every value currently being serialized on this thread, outermost first.
+  static SERIALIZING: RefCell<Vec<(*const (), &'static str)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Marks `value` as being serialized until dropped.
+///
+/// Meeting the same value (same address and type) again while it is still on the stack means the
+/// structure is cyclic, e.g. through `Rc<RefCell<_>>`, and serializing it would never terminate.
+struct VisitGuard;
+
+impl VisitGuard {
+  fn enter<T: ?Sized>(value: &T) -> Result<Self> {
+    let key = (value as *const T as *const (), type_name::<T>());
+    SERIALIZING.with(|visited| {
+      let mut visited = visited.borrow_mut();
+      if visited.contains(&key) {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("Circular reference detected while serializing {}", key.1),
+        ));
+      }
+      visited.push(key);
+      Ok(VisitGuard)
+    })
+  }
+}
+
+impl Drop for VisitGuard {
+  fn drop(&mut self) {
+    SERIALIZING.with(|visited| visited.borrow_mut().pop());
+  }
+}
+
+fn serialize_nested<T: ?Sized + Serialize>(env: &Env, value: &T) -> Result<Value> {
+  let _guard = VisitGuard::enter(value)?;
+  value.serialize(Ser::new(env))
+}
 
 pub(crate) struct Ser<'env>(pub(crate) &'env Env);
 
@@ -171,6 +212,7 @@ impl<'env> Serializer for Ser<'env> {
   where
     T: Serialize,
   {
+    let _guard = VisitGuard::enter(value)?;
     value.serialize(self)
   }
 
@@ -230,6 +272,7 @@ impl<'env> Serializer for Ser<'env> {
   where
     T: Serialize,
   {
+    let _guard = VisitGuard::enter(value)?;
     value.serialize(self)
   }
 
@@ -244,7 +287,7 @@ impl<'env> Serializer for Ser<'env> {
     T: Serialize,
   {
     let mut obj = self.0.create_object()?;
-    obj.set_named_property(variant, JsUnknown(value.serialize(self)?))?;
+    obj.set_named_property(variant, JsUnknown(serialize_nested(self.0, value)?))?;
     Ok(obj.0)
   }
 
@@ -311,7 +354,7 @@ impl ser::SerializeSeq for SeqSerializer {
     let env = unsafe { Env::from_raw(self.array.0.env) };
     self.array.set_element(
       self.current_index as _,
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(serialize_nested(&env, value)?),
     )?;
     self.current_index += 1;
     Ok(())
@@ -334,7 +377,7 @@ impl ser::SerializeTuple for SeqSerializer {
     let env = unsafe { Env::from_raw(self.array.0.env) };
     self.array.set_element(
       self.current_index as _,
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(serialize_nested(&env, value)?),
     )?;
     self.current_index += 1;
     Ok(())
@@ -357,7 +400,7 @@ impl ser::SerializeTupleStruct for SeqSerializer {
     let env = unsafe { Env::from_raw(self.array.0.env) };
     self.array.set_element(
       self.current_index as _,
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(serialize_nested(&env, value)?),
     )?;
     self.current_index += 1;
     Ok(())
@@ -380,7 +423,7 @@ impl ser::SerializeTupleVariant for SeqSerializer {
     let env = unsafe { Env::from_raw(self.array.0.env) };
     self.array.set_element(
       self.current_index as _,
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(serialize_nested(&env, value)?),
     )?;
     self.current_index += 1;
     Ok(())
@@ -421,7 +464,7 @@ impl ser::SerializeMap for MapSerializer {
         value: self.key.0.value,
         value_type: ValueType::String,
       }),
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(serialize_nested(&env, value)?),
     )?;
     Ok(())
   }
@@ -438,7 +481,7 @@ impl ser::SerializeMap for MapSerializer {
     let env = unsafe { Env::from_raw(self.obj.0.env) };
     self.obj.set_property(
       JsString(key.serialize(Ser::new(&env))?),
-      JsUnknown(value.serialize(Ser::new(&env))?),
+      JsUnknown(serialize_nested(&env, value)?),
     )?;
     Ok(())
   }
@@ -464,7 +507,7 @@ impl ser::SerializeStruct for StructSerializer {
     let env = unsafe { Env::from_raw(self.obj.0.env) };
     self
       .obj
-      .set_named_property(key, JsUnknown(value.serialize(Ser::new(&env))?))?;
+      .set_named_property(key, JsUnknown(serialize_nested(&env, value)?))?;
     Ok(())
   }
 
@@ -485,7 +528,7 @@ impl ser::SerializeStructVariant for StructSerializer {
     let env = unsafe { Env::from_raw(self.obj.0.env) };
     self
       .obj
-      .set_named_property(key, JsUnknown(value.serialize(Ser::new(&env))?))?;
+      .set_named_property(key, JsUnknown(serialize_nested(&env, value)?))?;
     Ok(())
   }
 
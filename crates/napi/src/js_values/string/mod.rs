@@ -3,8 +3,9 @@ use std::ptr;
 
 use crate::bindgen_runtime::TypeName;
 use crate::bindgen_runtime::ValidateNapiValue;
+use crate::bindgen_runtime::{FromNapiValue, RawUtf16String};
 use crate::ValueType;
-use crate::{check_status, sys, Result, Value};
+use crate::{check_status, sys, Error, Result, Status, Value};
 
 pub use latin1::JsStringLatin1;
 pub use utf16::JsStringUtf16;
@@ -29,6 +30,24 @@ impl TypeName for JsString {
 
 impl ValidateNapiValue for JsString {}
 
+/// The length of the buffer for a string of `len` units of `T` and the trailing nul,
+/// fails instead of overflowing for a string too long to allocate a buffer for.
+pub(crate) fn nul_terminated_len<T>(len: usize) -> Result<usize> {
+  len
+    .checked_add(1)
+    .filter(|buf_len| {
+      buf_len
+        .checked_mul(mem::size_of::<T>())
+        .map_or(false, |size| size <= isize::MAX as usize)
+    })
+    .ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        format!("String of length {} is too long to read into a buffer", len),
+      )
+    })
+}
+
 impl JsString {
   pub fn utf8_len(&self) -> Result<usize> {
     let mut length = 0;
@@ -56,7 +75,7 @@ impl JsString {
 
   pub fn into_utf8(self) -> Result<JsStringUtf8> {
     let mut written_char_count = 0;
-    let len = self.utf8_len()? + 1;
+    let len = nul_terminated_len::<u8>(self.utf8_len()?)?;
     let mut result = Vec::with_capacity(len);
     let buf_ptr = result.as_mut_ptr();
     check_status!(unsafe {
@@ -77,7 +96,7 @@ impl JsString {
 
   pub fn into_utf16(self) -> Result<JsStringUtf16> {
     let mut written_char_count = 0usize;
-    let len = self.utf16_len()? + 1;
+    let len = nul_terminated_len::<u16>(self.utf16_len()?)?;
     let mut result = vec![0; len];
     let buf_ptr = result.as_mut_ptr();
     check_status!(unsafe {
@@ -96,9 +115,19 @@ impl JsString {
     })
   }
 
+  /// Read the string into a `String`, failing with `InvalidArg` if it contains an unpaired surrogate.
+  pub fn into_string(self) -> Result<String> {
+    unsafe { RawUtf16String::from_napi_value(self.0.env, self.0.value) }?.to_utf8()
+  }
+
+  /// Read the string into a `String`, replacing unpaired surrogates with `U+FFFD`.
+  pub fn into_string_lossy(self) -> Result<String> {
+    Ok(unsafe { RawUtf16String::from_napi_value(self.0.env, self.0.value) }?.to_utf8_lossy())
+  }
+
   pub fn into_latin1(self) -> Result<JsStringLatin1> {
     let mut written_char_count = 0usize;
-    let len = self.latin1_len()? + 1;
+    let len = nul_terminated_len::<u8>(self.latin1_len()?)?;
     let mut result = Vec::with_capacity(len);
     let buf_ptr = result.as_mut_ptr();
     check_status!(unsafe {
@@ -1,4 +1,17 @@
 use std::any::TypeId;
+use std::ffi::c_void;
+use std::ptr;
+
+use crate::{check_status, sys, Result};
+#[cfg(feature = "napi8")]
+use crate::{Error, Status};
+
+/// Type tag of the externals created by napi-rs, which all point to a `TaggedObject`.
+#[cfg(feature = "napi8")]
+const TAGGED_OBJECT_TYPE_TAG: sys::napi_type_tag = sys::napi_type_tag {
+  lower: 0x5f73_722d_6970_616e,
+  upper: 0x7463_656a_626f_5f64,
+};
 
 #[repr(C)]
 pub struct TaggedObject<T> {
@@ -14,3 +27,54 @@ impl<T: 'static> TaggedObject<T> {
     }
   }
 }
+
+/// Mark an external created from a `TaggedObject`, so [`get_tagged_object_from_external`] can tell it apart from foreign externals.
+pub(crate) unsafe fn tag_external(env: sys::napi_env, external: sys::napi_value) -> Result<()> {
+  #[cfg(feature = "napi8")]
+  {
+    let status = unsafe { sys::napi_type_tag_object(env, external, &TAGGED_OBJECT_TYPE_TAG) };
+    // Node.js versions which can only type tag objects leave the external untagged,
+    // retrieving it falls back to the `TypeId` check alone
+    if status != sys::Status::napi_object_expected {
+      check_status!(status, "Failed to type tag external")?;
+    }
+  }
+  #[cfg(not(feature = "napi8"))]
+  let _ = (env, external);
+  Ok(())
+}
+
+/// Get the pointer of an external, which is only read as a `TaggedObject` by the caller if it's tagged by [`tag_external`].
+///
+/// The type tag is verified before the pointer is dereferenced,
+/// so an external created by other native code results in an error instead of undefined behavior.
+pub(crate) unsafe fn get_tagged_object_from_external(
+  env: sys::napi_env,
+  external: sys::napi_value,
+  type_name: &str,
+) -> Result<*mut c_void> {
+  let mut unknown_tagged_object = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_value_external(env, external, &mut unknown_tagged_object) },
+    "Failed to get external value"
+  )?;
+  #[cfg(feature = "napi8")]
+  {
+    let mut is_tagged = false;
+    let status = unsafe {
+      sys::napi_check_object_type_tag(env, external, &TAGGED_OBJECT_TYPE_TAG, &mut is_tagged)
+    };
+    if status == sys::Status::napi_ok && !is_tagged {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "External is not created by napi-rs, it can't be read as `{}`",
+          type_name
+        ),
+      ));
+    }
+  }
+  #[cfg(not(feature = "napi8"))]
+  let _ = type_name;
+  Ok(unknown_tagged_object)
+}
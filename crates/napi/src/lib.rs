@@ -84,17 +84,23 @@ mod call_context;
 #[cfg(feature = "napi3")]
 mod cleanup_env;
 mod env;
+mod env_cache;
 mod error;
 mod js_values;
 mod status;
 mod task;
+#[cfg(feature = "napi5")]
+mod timer;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 mod tokio_runtime;
+mod unwrap_cache;
 mod value_type;
 #[cfg(feature = "napi3")]
 pub use cleanup_env::CleanupEnvHook;
 #[cfg(feature = "napi4")]
 pub mod threadsafe_function;
+#[cfg(feature = "napi5")]
+pub use timer::TimerHandle;
 
 mod version;
 
@@ -109,6 +115,7 @@ pub use error::*;
 pub use js_values::*;
 pub use status::Status;
 pub use task::Task;
+pub use unwrap_cache::UnwrapCache;
 pub use value_type::*;
 pub use version::NodeVersion;
 #[cfg(feature = "serde-json")]
@@ -175,7 +182,11 @@ pub mod bindgen_prelude {
 #[doc(hidden)]
 pub mod __private {
   pub use crate::bindgen_runtime::{
-    get_class_constructor, iterator::create_iterator, register_class, ___CALL_FROM_FACTORY,
+    async_iterator::create_async_iterator,
+    get_class_constructor,
+    iterator::create_iterator,
+    panic::{panic_to_error, reset_panic_location},
+    register_class, ___CALL_FROM_FACTORY,
   };
 
   use crate::sys;
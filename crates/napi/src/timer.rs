@@ -0,0 +1,34 @@
+use std::ptr;
+
+use crate::sys;
+
+/// Created by `Env::set_timeout`
+/// And used by `Env::clear_timeout`
+///
+/// Holds a reference to the value returned by `setTimeout`. Dropping the handle without clearing it
+/// lets the timer fire as usual, it only releases the reference.
+pub struct TimerHandle {
+  pub(crate) env: sys::napi_env,
+  pub(crate) timer: sys::napi_ref,
+}
+
+impl TimerHandle {
+  pub(crate) fn new(env: sys::napi_env, timer: sys::napi_value) -> crate::Result<Self> {
+    let mut timer_ref = ptr::null_mut();
+    crate::check_status!(
+      unsafe { sys::napi_create_reference(env, timer, 1, &mut timer_ref) },
+      "Create reference of timer failed"
+    )?;
+    Ok(Self {
+      env,
+      timer: timer_ref,
+    })
+  }
+}
+
+impl Drop for TimerHandle {
+  fn drop(&mut self) {
+    let status = unsafe { sys::napi_delete_reference(self.env, self.timer) };
+    debug_assert_eq!(status, sys::Status::napi_ok, "Drop TimerHandle failed");
+  }
+}
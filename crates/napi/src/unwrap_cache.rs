@@ -0,0 +1,88 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::ptr;
+
+use crate::{check_status, env::tagged_object_as_mut, sys, Env, JsObject, NapiRaw, Result};
+
+thread_local! {
+  /// Bumped whenever a handle scope opened by napi-rs is closed, see [`handle_scope_closed`]
+  static CLOSED_HANDLE_SCOPES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Forget the entries of every [`UnwrapCache`] on this thread, the handles of the closed scope may be reused for other objects.
+pub(crate) fn handle_scope_closed() {
+  CLOSED_HANDLE_SCOPES.with(|closed| closed.set(closed.get().wrapping_add(1)));
+}
+
+/// Memoizes the native pointers of [`Env::unwrap`], created by [`Env::with_unwrap_cache`].
+///
+/// Entries are keyed by the `napi_value` handle of the object, so only the first `unwrap` of a handle calls `napi_unwrap`.
+/// A handle keeps its object alive and refers to the same object while the handle scope it was created in is open,
+/// so the cached pointers can't be invalidated by finalization while the cache is alive.
+/// The entries are scoped to the innermost open handle scope: they are dropped once a nested scope is closed,
+/// e.g. by `Env::run_in_scope`, as its handles may be reused by other objects afterwards.
+/// Debug builds check every cached pointer against `napi_unwrap`.
+/// Use [`UnwrapCache::drop_wrapped`] instead of `Env::drop_wrapped` for the objects in the cache.
+pub struct UnwrapCache {
+  env: Env,
+  entries: RefCell<HashMap<sys::napi_value, *mut c_void>>,
+  /// [`CLOSED_HANDLE_SCOPES`] when the entries were added
+  closed_handle_scopes: Cell<u64>,
+}
+
+impl UnwrapCache {
+  pub(crate) fn new(env: Env) -> Self {
+    UnwrapCache {
+      env,
+      entries: RefCell::new(HashMap::new()),
+      closed_handle_scopes: Cell::new(CLOSED_HANDLE_SCOPES.with(Cell::get)),
+    }
+  }
+
+  /// Same as [`Env::unwrap`], but `napi_unwrap` is only called once for every handle.
+  #[allow(clippy::mut_from_ref)]
+  pub fn unwrap<T: 'static>(&self, js_object: &JsObject) -> Result<&mut T> {
+    let key = unsafe { js_object.raw() };
+    let closed_handle_scopes = CLOSED_HANDLE_SCOPES.with(Cell::get);
+    if self.closed_handle_scopes.replace(closed_handle_scopes) != closed_handle_scopes {
+      self.entries.borrow_mut().clear();
+    }
+    let cached = self.entries.borrow().get(&key).copied();
+    let unknown_tagged_object = match cached {
+      Some(unknown_tagged_object) => {
+        #[cfg(debug_assertions)]
+        {
+          let mut unwrapped = ptr::null_mut();
+          check_status!(unsafe { sys::napi_unwrap(self.env.0, key, &mut unwrapped) })?;
+          debug_assert_eq!(
+            unwrapped, unknown_tagged_object,
+            "UnwrapCache entry refers to another object, its handle was reused by a handle scope not opened by napi-rs"
+          );
+        }
+        unknown_tagged_object
+      }
+      None => {
+        let mut unknown_tagged_object = ptr::null_mut();
+        check_status!(unsafe { sys::napi_unwrap(self.env.0, key, &mut unknown_tagged_object) })?;
+        self.entries.borrow_mut().insert(key, unknown_tagged_object);
+        unknown_tagged_object
+      }
+    };
+    unsafe { tagged_object_as_mut(unknown_tagged_object) }
+  }
+
+  /// Same as [`Env::drop_wrapped`], and forget the cached pointer of `js_object`.
+  pub fn drop_wrapped<T: 'static>(&self, js_object: &JsObject) -> Result<()> {
+    let mut unknown_tagged_object = ptr::null_mut();
+    check_status!(unsafe {
+      sys::napi_unwrap(self.env.0, js_object.raw(), &mut unknown_tagged_object)
+    })?;
+    // the same object may be cached under other handles too
+    self
+      .entries
+      .borrow_mut()
+      .retain(|_, cached| *cached != unknown_tagged_object);
+    self.env.drop_wrapped::<T>(js_object)
+  }
+}
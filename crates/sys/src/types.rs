@@ -55,6 +55,8 @@ pub struct napi_deferred__ {
 pub struct uv_loop_s {
   _unused: [u8; 0],
 }
+/// Opaque on purpose, there are no libuv bindings in this crate, see `Env::get_uv_event_loop`
+pub type uv_loop_t = uv_loop_s;
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub enum uv_run_mode {
@@ -214,6 +216,13 @@ pub type napi_async_cleanup_hook_handle = *mut napi_async_cleanup_hook_handle__;
 #[cfg(feature = "napi8")]
 pub type napi_async_cleanup_hook =
   Option<unsafe extern "C" fn(handle: napi_async_cleanup_hook_handle, data: *mut c_void)>;
+#[cfg(feature = "napi8")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct napi_type_tag {
+  pub lower: u64,
+  pub upper: u64,
+}
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -30,7 +30,20 @@ test('should be able to re-create wrapped native value', (t) => {
   t.is(testClass.addNativeCount(0), 42)
 })
 
+test('should unwrap every instance with an unwrap cache', (t) => {
+  const TestClass = bindings.createTestClass()
+  const instances = [new TestClass(1), new TestClass(2), new TestClass(3)]
+  t.is(bindings.sumNativeValues(instances), 2 * (101 + 102 + 103))
+  instances[1].addNativeCount(10)
+  t.is(bindings.sumNativeValues(instances), 2 * (101 + 112 + 103))
+})
+
 test('should be able to new class instance in native side', (t) => {
   const instance = bindings.newTestClass()
   t.is(instance.count, 42)
 })
+
+test('should pass class data to the shared constructor', (t) => {
+  t.is(new bindings.ClassDataFoo().label, 'foo')
+  t.is(new bindings.ClassDataBar().label, 'bar')
+})
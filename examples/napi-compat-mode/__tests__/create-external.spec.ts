@@ -13,3 +13,22 @@ test('should create external with size hint', (t) => {
   const externalObject = bindings.createExternalWithHint(42)
   t.is(bindings.getExternalCount(externalObject), fixture)
 })
+
+test('should get external object back with JsExternal::get', (t) => {
+  const externalObject = bindings.createExternal(42)
+  t.is(bindings.getExternalCountChecked(externalObject), 42)
+})
+
+test('should update external object with JsExternal::get_mut', (t) => {
+  const externalObject = bindings.createExternal(42)
+  t.is(bindings.increaseExternalCount(externalObject), 43)
+  t.is(bindings.getExternalCountChecked(externalObject), 43)
+})
+
+test('should throw if the external holds another type', (t) => {
+  const externalObject = bindings.createExternalString('napi-rs')
+  t.throws(() => bindings.getExternalCountChecked(externalObject), {
+    code: 'InvalidArg',
+    message: 'T on get_value_external is not the type of wrapped object',
+  })
+})
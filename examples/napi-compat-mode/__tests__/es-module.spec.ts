@@ -0,0 +1,13 @@
+import test from 'ava'
+
+const bindings = require('../index.node')
+
+test('should mark exports as an ES module', (t) => {
+  t.true(bindings.__esModule)
+  t.false(Object.keys(bindings).includes('__esModule'))
+  t.throws(() => {
+    'use strict'
+    bindings.__esModule = false
+  })
+  t.true(bindings.__esModule)
+})
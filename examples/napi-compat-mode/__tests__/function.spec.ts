@@ -21,6 +21,20 @@ test('should set "this" properly', (t) => {
   })
 })
 
+test('should bind "this" and leading arguments', (t) => {
+  const obj = {}
+  const bound = bindings.testBindFunction(function (
+    this: typeof obj,
+    arg1: string,
+    arg2: string,
+  ) {
+    return { self: this, message: `${arg1} ${arg2}` }
+  }, obj)
+  const result = bound.call({ other: true }, 'world')
+  t.is(result.self, obj)
+  t.is(result.message, 'hello world')
+})
+
 test('should handle errors', (t) => {
   bindings.testCallFunctionError(
     () => {
@@ -22,3 +22,13 @@ test('should throw if get instance data type mismatched', (t) => {
     t.is(bindings.getWrongTypeInstanceData, undefined)
   }
 })
+
+test('should throw if instance data is set again with another type', (t) => {
+  if (napiVersion >= 6) {
+    bindings.setInstanceData()
+    t.throws(bindings.setWrongTypeInstanceData)
+    t.is(bindings.getInstanceData(), 1024)
+  } else {
+    t.is(bindings.setWrongTypeInstanceData, undefined)
+  }
+})
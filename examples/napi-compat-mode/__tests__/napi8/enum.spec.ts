@@ -0,0 +1,35 @@
+import ava from 'ava'
+
+import { napiVersion } from '../napi-version'
+
+const bindings = require('../../index.node')
+
+const test = napiVersion >= 8 ? ava : ava.skip
+
+test('should export enum with reverse mapping', (t) => {
+  t.deepEqual(
+    { ...bindings.FrozenDirection },
+    {
+      Up: 0,
+      Down: 1,
+      Left: -1,
+      '0': 'Up',
+      '1': 'Down',
+      '-1': 'Left',
+    },
+  )
+  t.is(bindings.FrozenDirection[bindings.FrozenDirection.Down], 'Down')
+  t.true(Object.isFrozen(bindings.FrozenDirection))
+})
+
+test('should export enum into namespace', (t) => {
+  t.is(bindings.testEnums.FrozenColor.Red, 1)
+  t.is(bindings.testEnums.FrozenColor[2], 'Blue')
+  t.true(Object.isFrozen(bindings.testEnums.FrozenColor))
+})
+
+test('should flatten namespaced exports to the top level', (t) => {
+  t.is(bindings.testEnums_FrozenColor, bindings.testEnums.FrozenColor)
+  t.deepEqual(bindings.__napiFlattenedExports, ['testEnums_FrozenColor'])
+  t.false(Object.keys(bindings).includes('__napiFlattenedExports'))
+})
@@ -32,3 +32,10 @@ test('serialize make_bytes_struct', (t) => {
     map: 'source map',
   })
 })
+
+test('serialize cyclic structure', (t) => {
+  t.throws(() => bindings.make_cyclic(), {
+    code: 'InvalidArg',
+    message: /^Circular reference detected while serializing/,
+  })
+})
@@ -20,3 +20,9 @@ test('should create symbol from JsString', (t) => {
   t.true(typeof symbol === 'symbol')
   t.is(symbol.toString(), `Symbol(${fixture})`)
 })
+
+test('should get symbol from the global symbol registry', (t) => {
+  t.is(bindings.symbolFor('x'), bindings.symbolFor('x'))
+  t.is(bindings.symbolFor('x'), Symbol.for('x'))
+  t.not(bindings.symbolFor('x'), bindings.symbolFor('y'))
+})
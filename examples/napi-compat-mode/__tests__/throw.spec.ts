@@ -11,6 +11,14 @@ test('should be able to throw error from native with reason', (t) => {
   t.throws(() => bindings.testThrowWithReason(reason), void 0, reason)
 })
 
+test('should throw error from raw napi status', (t) => {
+  t.throws(() => bindings.testThrowStatusError('1'), {
+    code: 'NumberExpected',
+    message: 'Node-API call failed with status NumberExpected',
+  })
+  t.notThrows(() => bindings.testThrowStatusError(1))
+})
+
 test('should throw if argument type is not match', (t) => {
   t.throws(() => bindings.testThrowWithReason(2))
 })
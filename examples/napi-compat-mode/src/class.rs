@@ -1,6 +1,12 @@
 use std::convert::TryInto;
 
-use napi::{CallContext, JsFunction, JsNumber, JsObject, JsUndefined, Property, Result};
+use std::ptr;
+
+use napi::{
+  bindgen_prelude::{CallbackInfo, ClassDataFactory},
+  sys, CallContext, Env, Error, JsError, JsFunction, JsNumber, JsObject, JsUndefined, NapiValue,
+  Property, Result, Status,
+};
 
 struct NativeClass {
   value: i32,
@@ -55,6 +61,25 @@ fn renew_wrapped(ctx: CallContext) -> Result<JsUndefined> {
   ctx.env.get_undefined()
 }
 
+#[js_function(1)]
+fn sum_native_values(ctx: CallContext) -> Result<JsNumber> {
+  let instances = ctx.get::<JsObject>(0)?;
+  let len = instances.get_array_length()?;
+  ctx.env.with_unwrap_cache(|cache| {
+    let mut sum = 0;
+    for _ in 0..2 {
+      for index in 0..len {
+        // every instance is read in a scope of its own, the handles of two instances may share a slot
+        sum += ctx.env.run_in_scope(|| {
+          let instance = instances.get_element::<JsObject>(index)?;
+          Ok(cache.unwrap::<NativeClass>(&instance)?.value)
+        })?;
+      }
+    }
+    ctx.env.create_int32(sum)
+  })
+}
+
 #[js_function(1)]
 fn new_test_class(ctx: CallContext) -> Result<JsObject> {
   let add_count_method = Property::new("addCount")?.with_method(add_count);
@@ -68,8 +93,47 @@ fn new_test_class(ctx: CallContext) -> Result<JsObject> {
   test_class.new_instance(&[ctx.env.create_int32(42)?])
 }
 
+/// Shared by `ClassDataFoo` and `ClassDataBar`, which are told apart by their class data
+unsafe extern "C" fn class_with_data_constructor(
+  env: sys::napi_env,
+  cb: sys::napi_callback_info,
+) -> sys::napi_value {
+  let result = CallbackInfo::<0>::new(env, cb, None, false).and_then(|cb| {
+    let label = unsafe { cb.class_data::<&'static str>() }
+      .ok_or_else(|| Error::new(Status::InvalidArg, "Missing class data".to_owned()))?;
+    let mut this = unsafe { JsObject::from_raw_unchecked(env, cb.this()) };
+    let env = unsafe { Env::from_raw(env) };
+    this.set_named_property("label", env.create_string(label)?)?;
+    Ok(cb.this())
+  });
+  result.unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
+
+fn register_class_with_data(js_name: &'static str, class_data: ClassDataFactory) {
+  napi::bindgen_prelude::register_class(
+    js_name,
+    None,
+    js_name,
+    vec![Property::new("constructor")
+      .unwrap()
+      .with_ctor(class_with_data_constructor)],
+    None,
+    Some(class_data),
+  );
+}
+
+#[napi::module_init]
+fn register_classes_with_data() {
+  register_class_with_data("ClassDataFoo\0", || Box::new("foo"));
+  register_class_with_data("ClassDataBar\0", || Box::new("bar"));
+}
+
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("createTestClass", create_test_class)?;
   exports.create_named_method("newTestClass", new_test_class)?;
+  exports.create_named_method("sumNativeValues", sum_native_values)?;
   Ok(())
 }
@@ -1,4 +1,7 @@
-use napi::{CallContext, Error, JsBoolean, JsObject, JsString, JsUnknown, Result, Status};
+use napi::{
+  sys, CallContext, Error, JsBoolean, JsObject, JsString, JsUndefined, JsUnknown, NapiRaw, Result,
+  Status,
+};
 
 #[js_function]
 fn test_throw(_ctx: CallContext) -> Result<JsUnknown> {
@@ -14,6 +17,15 @@ fn test_throw_with_reason(ctx: CallContext) -> Result<JsUnknown> {
   ))
 }
 
+#[js_function(1)]
+fn test_throw_status_error(ctx: CallContext) -> Result<JsUndefined> {
+  let value = ctx.get::<JsUnknown>(0)?;
+  let mut result = 0;
+  let status = unsafe { sys::napi_get_value_uint32(ctx.env.raw(), value.raw(), &mut result) };
+  ctx.env.throw_status_error(Status::from(status))?;
+  ctx.env.get_undefined()
+}
+
 #[js_function]
 pub fn test_throw_with_panic(_ctx: CallContext) -> Result<JsUnknown> {
   panic!("don't panic.");
@@ -28,6 +40,7 @@ pub fn is_error(ctx: CallContext) -> Result<JsBoolean> {
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("testThrow", test_throw)?;
   exports.create_named_method("testThrowWithReason", test_throw_with_reason)?;
+  exports.create_named_method("testThrowStatusError", test_throw_status_error)?;
   exports.create_named_method("isError", is_error)?;
   Ok(())
 }
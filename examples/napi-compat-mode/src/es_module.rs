@@ -0,0 +1,6 @@
+use napi::bindgen_prelude::mark_es_module;
+
+#[napi::module_init]
+fn init() {
+  mark_es_module();
+}
@@ -1,6 +1,6 @@
 use std::convert::TryInto;
 
-use napi::{CallContext, JsExternal, JsNumber, JsObject, Result};
+use napi::{CallContext, JsExternal, JsNumber, JsObject, JsString, Result};
 
 struct NativeObject {
   count: i32,
@@ -27,9 +27,33 @@ pub fn get_external_count(ctx: CallContext) -> Result<JsNumber> {
   ctx.env.create_int32(native_object.count)
 }
 
+#[js_function(1)]
+pub fn create_external_string(ctx: CallContext) -> Result<JsExternal> {
+  let content = ctx.get::<JsString>(0)?.into_utf8()?.into_owned()?;
+  ctx.env.create_external(content, None)
+}
+
+#[js_function(1)]
+pub fn get_external_count_checked(ctx: CallContext) -> Result<JsNumber> {
+  let attached_obj = ctx.get::<JsExternal>(0)?;
+  let native_object = attached_obj.get::<NativeObject>()?;
+  ctx.env.create_int32(native_object.count)
+}
+
+#[js_function(1)]
+pub fn increase_external_count(ctx: CallContext) -> Result<JsNumber> {
+  let mut attached_obj = ctx.get::<JsExternal>(0)?;
+  let native_object = attached_obj.get_mut::<NativeObject>()?;
+  native_object.count += 1;
+  ctx.env.create_int32(native_object.count)
+}
+
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("createExternal", create_external)?;
   exports.create_named_method("createExternalWithHint", create_external_with_hint)?;
   exports.create_named_method("getExternalCount", get_external_count)?;
+  exports.create_named_method("createExternalString", create_external_string)?;
+  exports.create_named_method("getExternalCountChecked", get_external_count_checked)?;
+  exports.create_named_method("increaseExternalCount", increase_external_count)?;
   Ok(())
 }
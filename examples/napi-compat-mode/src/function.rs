@@ -32,6 +32,15 @@ pub fn call_function_with_this(ctx: CallContext) -> Result<JsNull> {
   ctx.env.get_null()
 }
 
+#[js_function(2)]
+pub fn bind_function(ctx: CallContext) -> Result<JsFunction> {
+  let js_func = ctx.get::<JsFunction>(0)?;
+  let js_this = ctx.get::<JsObject>(1)?;
+  let js_string_hello = ctx.env.create_string("hello")?;
+
+  js_func.bind(&js_this, &[js_string_hello])
+}
+
 #[js_function(2)]
 pub fn call_function_error(ctx: CallContext) -> Result<JsUnknown> {
   let js_func = ctx.get::<JsFunction>(0)?;
@@ -63,6 +72,7 @@ pub fn register_js(exports: &mut JsObject) -> Result<()> {
     call_function_with_ref_arguments,
   )?;
   exports.create_named_method("testCallFunctionWithThis", call_function_with_this)?;
+  exports.create_named_method("testBindFunction", bind_function)?;
   exports.create_named_method("testCallFunctionError", call_function_error)?;
   exports.create_named_method(
     "testCreateFunctionFromClosure",
@@ -29,6 +29,7 @@ mod class;
 mod either;
 mod env;
 mod error;
+mod es_module;
 mod external;
 mod function;
 mod global;
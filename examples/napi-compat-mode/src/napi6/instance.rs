@@ -27,3 +27,9 @@ pub fn get_wrong_type_instance_data(env: Env) -> ContextlessResult<JsNumber> {
     Ok(None)
   }
 }
+
+#[contextless_function]
+pub fn set_wrong_type_instance_data(env: Env) -> ContextlessResult<JsUndefined> {
+  env.set_instance_data(1024i32, 0, |_ctx| {})?;
+  env.get_undefined().map(Some)
+}
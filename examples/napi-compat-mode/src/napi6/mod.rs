@@ -19,5 +19,6 @@ pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("setInstanceData", set_instance_data)?;
   exports.create_named_method("getInstanceData", get_instance_data)?;
   exports.create_named_method("getWrongTypeInstanceData", get_wrong_type_instance_data)?;
+  exports.create_named_method("setWrongTypeInstanceData", set_wrong_type_instance_data)?;
   Ok(())
 }
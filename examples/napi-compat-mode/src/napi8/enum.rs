@@ -0,0 +1,12 @@
+use napi::bindgen_prelude::{flatten_namespaces, register_enum};
+
+#[napi::module_init]
+fn init() {
+  register_enum(
+    None,
+    "FrozenDirection",
+    &[("Up", 0), ("Down", 1), ("Left", -1)],
+  );
+  register_enum(Some("testEnums"), "FrozenColor", &[("Red", 1), ("Blue", 2)]);
+  flatten_namespaces("_");
+}
@@ -1,6 +1,7 @@
 use napi::{JsObject, Result};
 
 mod async_cleanup;
+mod r#enum;
 mod object;
 
 use async_cleanup::*;
@@ -1,3 +1,5 @@
+use std::{cell::RefCell, rc::Rc};
+
 use napi::{CallContext, JsObject, JsString, JsUndefined, JsUnknown, Result};
 
 use serde_json::{from_str, to_string};
@@ -51,6 +53,12 @@ struct BytesObject<'a> {
   map: String,
 }
 
+#[derive(Serialize)]
+struct CyclicNode {
+  name: String,
+  next: RefCell<Option<Rc<CyclicNode>>>,
+}
+
 macro_rules! make_test {
   ($name:ident, $val:expr) => {
     #[js_function]
@@ -194,6 +202,23 @@ fn json_to_string(ctx: CallContext) -> Result<JsString> {
   ctx.env.create_string_from_std(json_string)
 }
 
+#[js_function]
+fn make_cyclic(ctx: CallContext) -> Result<JsUnknown> {
+  let a = Rc::new(CyclicNode {
+    name: "a".to_owned(),
+    next: RefCell::new(None),
+  });
+  let b = Rc::new(CyclicNode {
+    name: "b".to_owned(),
+    next: RefCell::new(Some(a.clone())),
+  });
+  a.next.replace(Some(b));
+  let result = ctx.env.to_js_value(&a);
+  // break the cycle so both nodes are freed
+  a.next.take();
+  result
+}
+
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("make_num_77", make_num_77)?;
   exports.create_named_method("make_num_32", make_num_32)?;
@@ -204,6 +229,7 @@ pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("make_object", make_object)?;
   exports.create_named_method("make_map", make_map)?;
   exports.create_named_method("make_bytes_struct", make_bytes_struct)?;
+  exports.create_named_method("make_cyclic", make_cyclic)?;
 
   exports.create_named_method("expect_hello_world", expect_hello_world)?;
   exports.create_named_method("expect_obj", expect_obj)?;
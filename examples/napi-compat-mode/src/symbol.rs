@@ -16,9 +16,16 @@ pub fn create_symbol_from_js_string(ctx: CallContext) -> Result<JsSymbol> {
   ctx.env.create_symbol_from_js_string(name)
 }
 
+#[js_function(1)]
+pub fn symbol_for(ctx: CallContext) -> Result<JsSymbol> {
+  let key = ctx.get::<JsString>(0)?.into_utf8()?;
+  ctx.env.symbol_for(key.as_str()?)
+}
+
 pub fn register_js(exports: &mut JsObject) -> Result<()> {
   exports.create_named_method("createNamedSymbol", create_named_symbol)?;
   exports.create_named_method("createUnnamedSymbol", create_unnamed_symbol)?;
   exports.create_named_method("createSymbolFromJsString", create_symbol_from_js_string)?;
+  exports.create_named_method("symbolFor", symbol_for)?;
   Ok(())
 }
@@ -0,0 +1,19 @@
+import test from 'ava'
+
+const bindings = require('../index.node')
+
+test('should keep the private data of an object without napi3', (t) => {
+  const visitor = new bindings.Visitor('Alice')
+  t.is(visitor.visit('Paris'), 1)
+  t.is(visitor.visit('Rome'), 2)
+  t.is(Object.getOwnPropertySymbols(visitor).length, 1)
+  visitor.forgetVisits()
+  t.is(visitor.visit('Oslo'), 1)
+  t.is(Object.getOwnPropertySymbols(visitor).length, 1)
+})
+
+test('should keep the external state of a class without napi3', (t) => {
+  const session = new bindings.Session()
+  t.is(session.request(), 1)
+  t.is(session.request(), 2)
+})
@@ -0,0 +1,56 @@
+//! Addon built without `napi3`, for the APIs which keep per-env state that would otherwise be
+//! dropped by env cleanup hooks.
+
+#[macro_use]
+extern crate napi_derive;
+
+use napi::{bindgen_prelude::This, Env, Result};
+
+/// Attached to `Visitor` instances with `Env::wrap_private_data`
+struct VisitLog {
+  visits: Vec<String>,
+}
+
+#[napi(constructor)]
+pub struct Visitor {
+  pub name: String,
+}
+
+#[napi]
+impl Visitor {
+  #[napi]
+  pub fn visit(&self, env: Env, mut this: This, place: String) -> Result<u32> {
+    if env.unwrap_private_data::<VisitLog>(&this)?.is_none() {
+      env.wrap_private_data(&mut this, VisitLog { visits: vec![] })?;
+    }
+    let log = env
+      .unwrap_private_data::<VisitLog>(&this)?
+      .expect("VisitLog is attached");
+    log.visits.push(place);
+    Ok(log.visits.len() as u32)
+  }
+
+  #[napi]
+  pub fn forget_visits(&self, env: Env, mut this: This) -> Result<()> {
+    env.wrap_private_data(&mut this, VisitLog { visits: vec![] })
+  }
+}
+
+#[napi(external_state)]
+pub struct Session {
+  requests: u32,
+}
+
+#[napi]
+impl Session {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Session { requests: 0 }
+  }
+
+  #[napi]
+  pub fn request(&mut self) -> u32 {
+    self.requests += 1;
+    self.requests
+  }
+}
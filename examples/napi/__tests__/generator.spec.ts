@@ -1,6 +1,17 @@
 import test from 'ava'
 
-const { Fib, Fib2, Fib3 } = (await import('../index.js')).default
+const {
+  Fib,
+  Fib2,
+  Fib3,
+  CountUp,
+  streamStrings,
+  createTicker,
+  isTickerStopped,
+  readableStreamStrings,
+  createWebTicker,
+  isWebTickerDropped,
+} = (await import('../index.js')).default
 
 for (const [index, factory] of [
   () => new Fib(),
@@ -55,3 +66,95 @@ for (const [index, factory] of [
     })
   })
 }
+
+test('should capture the return value of an async generator', async (t) => {
+  const seen: number[] = []
+  async function* delegate() {
+    return yield* new CountUp(1, 4)
+  }
+  const gen = delegate()
+  let result = await gen.next()
+  while (!result.done) {
+    seen.push(result.value)
+    result = await gen.next()
+  }
+  t.deepEqual(seen, [1, 2, 3, 4])
+  t.is(result.value, 10)
+  t.deepEqual(await gen.next(), { done: true, value: undefined })
+})
+
+test('should iterate an async generator with for await', async (t) => {
+  const seen: number[] = []
+  for await (const value of new CountUp(2, 4)) {
+    seen.push(value)
+  }
+  t.deepEqual(seen, [2, 3, 4])
+})
+
+test('should be able to return and throw to an async generator', async (t) => {
+  const iterator = new CountUp(1, 10)[Symbol.asyncIterator]()
+  t.deepEqual(await iterator.next(), { done: false, value: 1 })
+  t.deepEqual(await iterator.next(5), { done: false, value: 5 })
+  t.deepEqual(await iterator.return(42), { done: true, value: 42 })
+  t.deepEqual(await iterator.next(), { done: true, value: undefined })
+
+  const thrown = new CountUp(1, 10)[Symbol.asyncIterator]()
+  const error = new Error('stop')
+  await t.throwsAsync(() => thrown.throw(error), { is: error })
+  t.deepEqual(await thrown.next(), { done: true, value: undefined })
+})
+
+test('should iterate values sent from a Rust channel with for await', async (t) => {
+  const seen: string[] = []
+  for await (const value of streamStrings(['a', 'b', 'c'])) {
+    seen.push(value)
+  }
+  t.deepEqual(seen, ['a', 'b', 'c'])
+})
+
+test('should stop the Rust producer when the iteration is stopped', async (t) => {
+  const seen: number[] = []
+  for await (const tick of createTicker()) {
+    seen.push(tick)
+    if (tick === 2) {
+      break
+    }
+  }
+  t.deepEqual(seen, [0, 1, 2])
+  while (!isTickerStopped()) {
+    await new Promise((resolve) => setTimeout(resolve, 10))
+  }
+  t.true(isTickerStopped())
+
+  const ticker = createTicker()[Symbol.asyncIterator]()
+  t.deepEqual(await ticker.next(), { done: false, value: 0 })
+  const error = new Error('stop')
+  await t.throwsAsync(() => ticker.throw!(error), { is: error })
+  t.deepEqual(await ticker.next(), { done: true, value: undefined })
+})
+
+test('should read a Rust stream from a ReadableStream', async (t) => {
+  const stream = readableStreamStrings(['a', 'b', 'c'])
+  t.true(stream instanceof ReadableStream)
+  const reader = stream.getReader()
+  t.deepEqual(await reader.read(), { done: false, value: 'a' })
+  t.deepEqual(await reader.read(), { done: false, value: 'b' })
+  t.deepEqual(await reader.read(), { done: false, value: 'c' })
+  t.deepEqual(await reader.read(), { done: true, value: undefined })
+
+  const failing = readableStreamStrings(['a', 'b'], 'b').getReader()
+  t.deepEqual(await failing.read(), { done: false, value: 'a' })
+  await t.throwsAsync(() => failing.read(), { message: 'Failed at `b`' })
+})
+
+test('should drop the Rust stream when the ReadableStream is cancelled', async (t) => {
+  const reader = createWebTicker().getReader()
+  t.deepEqual(await reader.read(), { done: false, value: 0 })
+  t.deepEqual(await reader.read(), { done: false, value: 1 })
+  await reader.cancel()
+  t.deepEqual(await reader.read(), { done: true, value: undefined })
+  while (!isWebTickerDropped()) {
+    await new Promise((resolve) => setTimeout(resolve, 10))
+  }
+  t.true(isWebTickerDropped())
+})
@@ -0,0 +1,6 @@
+// installed before the addon is loaded, like by another addon
+globalThis.napiRsGreet = () => 'taken'
+
+import('../index.js').then(({ default: { getModuleInitCount } }) => {
+  console.info(getModuleInitCount())
+})
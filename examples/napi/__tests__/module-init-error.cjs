@@ -0,0 +1,3 @@
+import('../index.js').then(({ default: { getModuleInitCount } }) => {
+  console.info(getModuleInitCount())
+})
@@ -0,0 +1,13 @@
+import('../index.js').then(({ default: bindings }) => {
+  const isMain = typeof bindings === 'function'
+  console.info(
+    JSON.stringify({
+      type: typeof bindings,
+      name: isMain ? bindings.name : undefined,
+      greeting: isMain ? bindings('Ada') : undefined,
+      hasGreet: 'greet' in bindings,
+      add: bindings.add(1, 2),
+      esModule: '__esModule' in bindings,
+    }),
+  )
+})
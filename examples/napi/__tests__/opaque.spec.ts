@@ -0,0 +1,85 @@
+import { setFlagsFromString } from 'node:v8'
+import { runInNewContext } from 'node:vm'
+
+import test from 'ava'
+
+import type { ExternalObject, SharedResource } from '../index.js'
+
+const {
+  OpaqueHandle,
+  createOpaqueHandle,
+  getOpaqueHandleId,
+  getDroppedOpaqueHandleCount,
+  createSharedResource,
+  shareResource,
+  getSharedResourceId,
+  getSharedResourceStrongCount,
+  getDroppedSharedResourceCount,
+} = (await import('../index.js')).default
+
+setFlagsFromString('--expose_gc')
+const gc: () => void = runInNewContext('gc')
+
+test('should not be able to construct opaque handle from JavaScript', (t) => {
+  t.throws(() => {
+    // @ts-expect-error
+    new OpaqueHandle()
+  })
+})
+
+test('should pass opaque handle back to Rust', (t) => {
+  const handle = createOpaqueHandle(42)
+  t.true(handle instanceof OpaqueHandle)
+  t.deepEqual(Object.keys(handle), [])
+  t.is(getOpaqueHandleId(handle), 42)
+})
+
+test('should drop opaque handle on GC', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const droppedBefore = getDroppedOpaqueHandleCount()
+  ;(() => {
+    for (let i = 0; i < 10; i++) {
+      createOpaqueHandle(i)
+    }
+  })()
+  for (let i = 0; i < 100; i++) {
+    gc()
+    await new Promise((resolve) => setImmediate(resolve))
+    if (getDroppedOpaqueHandleCount() - droppedBefore >= 10) {
+      break
+    }
+  }
+  t.true(getDroppedOpaqueHandleCount() - droppedBefore >= 10)
+})
+
+test('should free shared resource after the last handle is collected', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const collect = async (done: () => boolean) => {
+    for (let i = 0; i < 100 && !done(); i++) {
+      gc()
+      await new Promise((resolve) => setImmediate(resolve))
+    }
+  }
+  const droppedBefore = getDroppedSharedResourceCount()
+  let first: ExternalObject<SharedResource> | null = createSharedResource(7)
+  let second: ExternalObject<SharedResource> | null = shareResource(first)
+  t.not(first, second)
+  t.is(getSharedResourceId(second), 7)
+  // two handles plus the one of the call
+  t.is(getSharedResourceStrongCount(second), 3)
+
+  first = null
+  await collect(() => getSharedResourceStrongCount(second!) < 3)
+  t.is(getSharedResourceStrongCount(second), 2)
+  t.is(getDroppedSharedResourceCount(), droppedBefore)
+
+  second = null
+  await collect(() => getDroppedSharedResourceCount() > droppedBefore)
+  t.is(getDroppedSharedResourceCount(), droppedBefore + 1)
+})
@@ -0,0 +1,20 @@
+globalThis.napiRsPluginHost = {
+  Plugin: class Plugin {
+    static kind = 'plugin'
+
+    describe() {
+      return `${this.name()} plugin`
+    }
+  },
+}
+
+import('../index.js').then(({ default: { JsonPlugin } }) => {
+  const { Plugin } = globalThis.napiRsPluginHost
+  const plugin = new JsonPlugin()
+  console.info(
+    plugin instanceof Plugin,
+    JsonPlugin.kind,
+    plugin.describe(),
+    plugin.name(),
+  )
+})
@@ -0,0 +1,3 @@
+import('../index.js').then(({ default: bindings }) => {
+  console.info(bindings.reentrantGetJsFunction)
+})
@@ -0,0 +1,3 @@
+import('../index.js').then(({ default: { Animal } }) => {
+  console.info(Animal)
+})
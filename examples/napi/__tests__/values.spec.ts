@@ -1,6 +1,8 @@
 import { exec } from 'node:child_process'
 import { join } from 'node:path'
+import { Duplex, Readable } from 'node:stream'
 import { fileURLToPath } from 'node:url'
+import { promisify } from 'node:util'
 
 import { spy } from 'sinon'
 
@@ -12,9 +14,21 @@ const __dirname = join(fileURLToPath(import.meta.url), '..')
 
 const {
   DEFAULT_COST,
+  LIMITS,
+  BUILD_CONFIG,
+  DEFAULT_OPTIONS,
   add,
+  addCents,
+  toCents,
+  fromCents,
   fibonacci,
+  echoId,
+  strictAddI32,
+  setStrictNumbersMode,
+  inspectNumber,
   call0,
+  callCatching,
+  callCatchingRethrow,
   call1,
   call2,
   apply0,
@@ -22,12 +36,24 @@ const {
   callFunction,
   callFunctionWithArg,
   callFunctionWithArgAndCtx,
+  joinNumbers,
+  divideWithCallback,
   createReferenceOnFunction,
   referenceAsCallback,
+  storeCallback,
+  callStoredCallback,
+  clearStoredCallback,
+  replayStoredCallback,
+  DelayedCall,
   contains,
   concatLatin1,
   concatStr,
   concatUtf16,
+  roundtripRawUtf16,
+  replaceLoneSurrogates,
+  countLoneSurrogates,
+  jsStringUtf8Len,
+  readJsString,
   roundtripStr,
   getNums,
   getWords,
@@ -42,8 +68,23 @@ const {
   CustomNumEnum,
   Context,
   GetterSetterWithClosures,
+  MessageBuilder,
+  Celsius,
   enumToI32,
+  flipDirection,
+  raiseLevel,
+  advanceJob,
+  failJob,
+  type JobState,
+  countLevels,
+  sumKindCounts,
   listObjKeys,
+  loadLazyConfig,
+  takeEvaluatedLazyFields,
+  countLazyConfigPlugins,
+  optionSource,
+  takeOptionFlag,
+  createCursor,
   createObj,
   mapOption,
   readFile,
@@ -63,6 +104,10 @@ const {
   withoutAbortController,
   withAbortController,
   asyncMultiTwo,
+  createDelayedAdder,
+  asyncUnit,
+  asyncResultUnit,
+  getAsyncUnitCalls,
   bigintAdd,
   createBigInt,
   createBigIntI64,
@@ -89,6 +134,7 @@ const {
   xxh3,
   xxh64Alias,
   tsRename,
+  isReadableStream,
   convertU32Array,
   createExternalTypedArray,
   mutateTypedArray,
@@ -108,6 +154,7 @@ const {
   getStrFromObject,
   returnJsFunction,
   testSerdeRoundtrip,
+  jsonNumberKinds,
   testSerdeBigNumberPrecision,
   createObjWithProperty,
   receiveObjectOnlyFromJs,
@@ -117,6 +164,11 @@ const {
   chronoDateAdd1Minute,
   bufferPassThrough,
   arrayBufferPassThrough,
+  reverseBytes,
+  scaleInto,
+  createDataView,
+  valueKinds,
+  parsePacketHeader,
   JsRepo,
   CssStyleSheet,
   CatchOnConstructor,
@@ -135,8 +187,16 @@ const {
   receiveDifferentClass,
   getNumArr,
   getNestedNumArr,
+  getSquares,
+  setNumbersFrom,
   CustomFinalize,
   plusOne,
+  Visitor,
+  Session,
+  openSession,
+  describeSession,
+  PooledConnection,
+  openPooledConnection,
   Width,
   captureErrorInCallback,
   bigintFromI128,
@@ -146,14 +206,56 @@ const {
   acceptThreadsafeFunctionTupleArgs,
   promiseInEither,
   runScript,
+  compileFunction,
+  strictEquals,
+  looseEquals,
+  hasUvEventLoop,
+  callCompiledFunction,
+  createObjectWithPrototype,
+  getObjectPrototype,
+  setObjectPrototype,
   tsfnReturnPromise,
   tsfnReturnPromiseTimeout,
   returnFromSharedCrate,
   chronoNativeDateTime,
   chronoNativeDateTimeReturn,
+  durationAddNanos,
+  preciseDurationAddNanos,
+  systemTimeAddNanos,
   throwAsyncError,
   getModuleFileName,
   throwSyntaxError,
+  optionBufferLen,
+  optionUint8ArrayLen,
+  isInstanceOfClass,
+  newAnimalAndCall,
+  newClassInstance,
+  getClassPropertyNames,
+  PropertyOrder,
+  getModuleInitCount,
+  getDynamicExportDoc,
+  getAnswerReads,
+  chain,
+  isModuleInitBeforeExports,
+  createObjWithNamedProperty,
+  serdeCompatToJson,
+  serdeCompatFromJson,
+  returnSharedStr,
+  returnCowStr,
+  lookupLengths,
+  invertMap,
+  scaleMapValues,
+  throwErrorWithCause,
+  connectFlaky,
+  validateSignUp,
+  validateServerOptions,
+  classifyThrownError,
+  throwValue,
+  rethrowCallbackException,
+  nestedObjectDepth,
+  setMaxObjectNestingDepth,
+  jsonValueDepth,
+  createLazyReport,
 } = (await import('../index.js')).default
 
 const Napi4Test = Number(process.versions.napi) >= 4 ? test : test.skip
@@ -162,6 +264,41 @@ test('export const', (t) => {
   t.is(DEFAULT_COST, 12)
 })
 
+test('frozen const', (t) => {
+  t.deepEqual(LIMITS, {
+    maxConnections: 64,
+    compression: true,
+    retry: { attempts: 3, backoffMs: 100 },
+  })
+  t.true(Object.isFrozen(LIMITS))
+  t.true(Object.isFrozen(LIMITS.retry))
+  t.throws(() => {
+    LIMITS.retry.attempts = 10
+  })
+  t.is(LIMITS.retry.attempts, 3)
+})
+
+test('json const', (t) => {
+  t.deepEqual(BUILD_CONFIG, {
+    version: '1.2.3',
+    features: { serde: true, tokio: false },
+    targets: ['x64', 'arm64'],
+  })
+  t.true(Object.isFrozen(BUILD_CONFIG))
+  t.true(Object.isFrozen(BUILD_CONFIG.features))
+  t.true(Object.isFrozen(BUILD_CONFIG.targets))
+  t.throws(() => {
+    BUILD_CONFIG.targets.push('ia32')
+  })
+})
+
+test('json const is not frozen without frozen', (t) => {
+  t.deepEqual(DEFAULT_OPTIONS, { retries: 3 })
+  t.false(Object.isFrozen(DEFAULT_OPTIONS))
+  DEFAULT_OPTIONS.retries = 5
+  t.is(DEFAULT_OPTIONS.retries, 5)
+})
+
 test('number', (t) => {
   t.is(add(1, 2), 3)
   t.is(fibonacci(5), 5)
@@ -174,6 +311,122 @@ test('number', (t) => {
   )
 })
 
+test('scaled number', (t) => {
+  t.is(addCents(0.1, 0.2), 0.3)
+  t.is(fromCents(1234), 12.34)
+  t.is(fromCents(-1), -0.01)
+  t.is(toCents(0.29, 'Floor'), 29)
+  t.is(toCents(-0.29, 'Ceil'), -29)
+
+  const ties = [1.005, 0.125, 0.135, -0.125]
+  t.deepEqual(
+    ties.map((v) => toCents(v, 'HalfAwayFromZero')),
+    [101, 13, 14, -13],
+  )
+  t.deepEqual(ties.map((v) => toCents(v, 'HalfEven')), [100, 12, 14, -12])
+  t.deepEqual(
+    [1.234, -1.236].map((v) => toCents(v, 'HalfAwayFromZero')),
+    [123, -124],
+  )
+  t.deepEqual([1.234, -1.236].map((v) => toCents(v, 'Floor')), [123, -124])
+  t.deepEqual([1.234, -1.236].map((v) => toCents(v, 'Ceil')), [124, -123])
+  t.deepEqual([1.239, -1.239].map((v) => toCents(v, 'TowardZero')), [123, -123])
+
+  const max = 2 ** 52
+  t.is(fromCents(max), 45035996273704.96)
+  t.is(toCents(fromCents(max), 'Floor'), max)
+  t.is(toCents(fromCents(-max), 'Ceil'), -max)
+  t.throws(() => fromCents(max + 1), {
+    message: 'Scaled<100>(4503599627370497) is out of the exactly representable range',
+  })
+  t.throws(() => addCents(45035996273704.96, 0.01))
+  t.throws(() => toCents(45035996273704.97, 'Floor'))
+  t.throws(() => toCents(Infinity, 'Floor'))
+  t.throws(() => toCents(NaN, 'Floor'))
+})
+
+test('strict numbers', (t) => {
+  t.is(echoId(Number.MAX_SAFE_INTEGER), Number.MAX_SAFE_INTEGER)
+  t.is(echoId(Number.MIN_SAFE_INTEGER), Number.MIN_SAFE_INTEGER)
+  for (const id of [2 ** 53, 2 ** 53 + 2, -(2 ** 53)]) {
+    t.throws(() => echoId(id), {
+      instanceOf: RangeError,
+      message: `Number ${id} is out of the range [-9007199254740991, 9007199254740991] of rust type \`i64\``,
+    })
+  }
+  t.throws(() => echoId(1.5), {
+    instanceOf: RangeError,
+    message: 'Number 1.5 is not an integer, expected rust type `i64`',
+  })
+  t.throws(() => echoId(NaN), { instanceOf: RangeError })
+
+  t.is(strictAddI32(2 ** 31 - 1, 1), 2 ** 31)
+  t.is(strictAddI32(-(2 ** 31), 0), -(2 ** 31))
+  t.throws(() => strictAddI32(2 ** 31, 0), {
+    instanceOf: RangeError,
+    message: 'Number 2147483648 is out of the range [-2147483648, 2147483647] of rust type `i32`',
+  })
+
+  // functions without `#[napi(strict_numbers)]` truncate unless strict mode is enabled for the whole addon
+  t.is(add(2.5, 1), 3)
+  t.false(setStrictNumbersMode(true))
+  try {
+    t.throws(() => add(2.5, 1), { instanceOf: RangeError })
+    t.throws(() => add(-1, 1), { instanceOf: RangeError })
+    t.is(add(2, 1), 3)
+  } finally {
+    setStrictNumbersMode(false)
+  }
+  t.is(add(2.5, 1), 3)
+})
+
+test('JsNumber integer extraction', (t) => {
+  t.deepEqual(inspectNumber(-1), {
+    int32: -1,
+    uint32: 2 ** 32 - 1,
+    int64: -1,
+    int64Lossless: true,
+    double: -1,
+    isSafeInteger: true,
+  })
+  const int32Max = inspectNumber(2 ** 31 - 1)
+  t.is(int32Max.int32, 2 ** 31 - 1)
+  t.is(inspectNumber(2 ** 31).int32, -(2 ** 31))
+  t.is(inspectNumber(2 ** 31).uint32, 2 ** 31)
+  t.is(inspectNumber(2 ** 32).uint32, 0)
+  t.is(inspectNumber(2 ** 32 + 5).int64, 2 ** 32 + 5)
+
+  const maxSafe = inspectNumber(Number.MAX_SAFE_INTEGER)
+  t.is(maxSafe.int64, Number.MAX_SAFE_INTEGER)
+  t.true(maxSafe.isSafeInteger)
+  const beyondSafe = inspectNumber(2 ** 53)
+  t.true(beyondSafe.int64Lossless)
+  t.false(beyondSafe.isSafeInteger)
+  t.false(inspectNumber(2 ** 63).int64Lossless)
+  t.true(inspectNumber(-(2 ** 63)).int64Lossless)
+
+  const fraction = inspectNumber(2.5)
+  t.is(fraction.int32, 2)
+  t.false(fraction.int64Lossless)
+  t.false(fraction.isSafeInteger)
+
+  for (const value of [NaN, Infinity, -Infinity]) {
+    t.throws(() => inspectNumber(value), { instanceOf: RangeError })
+  }
+
+  t.false(setStrictNumbersMode(true))
+  try {
+    t.throws(() => inspectNumber(2.5), {
+      instanceOf: RangeError,
+      message: 'Number 2.5 is not an integer, expected rust type `i32`',
+    })
+    t.throws(() => inspectNumber(-1), { instanceOf: RangeError })
+    t.is(inspectNumber(7).uint32, 7)
+  } finally {
+    setStrictNumbersMode(false)
+  }
+})
+
 test('string', (t) => {
   t.true(contains('hello', 'ell'))
   t.false(contains('John', 'jn'))
@@ -188,6 +441,33 @@ test('string', (t) => {
     roundtripStr('what up?!\u0000after the NULL'),
     'what up?!\u0000after the NULL',
   )
+  t.is(returnSharedStr('shared 你好 🦀'), 'shared 你好 🦀')
+  t.is(returnCowStr(true), 'borrowed 🦀')
+  t.is(returnCowStr(false), 'owned 🦀')
+
+  const loneSurrogate = 'lone \uD83E surrogate'
+  t.throws(() => concatUtf16(loneSurrogate), {
+    code: 'InvalidArg',
+    message: 'Failed to read utf16 string, invalid utf-16: lone surrogate found',
+  })
+  t.is(roundtripRawUtf16(loneSurrogate), loneSurrogate)
+  t.is(replaceLoneSurrogates(loneSurrogate), 'lone \uFFFD surrogate')
+  t.is(countLoneSurrogates(loneSurrogate), 1)
+  t.is(countLoneSurrogates('paired 🦀'), 0)
+})
+
+test('read a JsString with multi-byte characters and lone surrogates', (t) => {
+  t.is(jsStringUtf8Len('abc'), 3)
+  t.is(jsStringUtf8Len('你好 🦀'), 11)
+  t.is(readJsString('你好 🦀', false), '你好 🦀')
+  t.is(readJsString('', false), '')
+  const loneSurrogate = 'lone \uDC00 surrogate'
+  t.throws(() => readJsString(loneSurrogate, false), {
+    code: 'InvalidArg',
+    message: 'Failed to read utf16 string, invalid utf-16: lone surrogate found',
+  })
+  t.is(readJsString(loneSurrogate, true), 'lone \uFFFD surrogate')
+  t.is(readJsString('🦀\uD83E', true), '🦀\uFFFD')
 })
 
 test('array', (t) => {
@@ -199,9 +479,66 @@ test('array', (t) => {
   t.deepEqual(getNestedNumArr(), [[[1]], [[1]]])
 })
 
+test('set array elements in bulk', (t) => {
+  t.deepEqual(getSquares(5), [0, 1, 4, 9, 16])
+  t.deepEqual(getSquares(0), [])
+  const arr = [1, 2, 3]
+  t.is(setNumbersFrom(arr, 1, [9, 9, 9]), 4)
+  t.deepEqual(arr, [1, 9, 9, 9])
+  const sparse = [1]
+  t.is(setNumbersFrom(sparse, 3, [7]), 4)
+  t.is(sparse.length, 4)
+  t.is(sparse[3], 7)
+  t.false(1 in sparse)
+})
+
 test('map', (t) => {
   t.deepEqual(getMapping(), { a: 101, b: 102 })
   t.is(sumMapping({ a: 101, b: 102 }), 203)
+  const keys = Array.from({ length: 10000 }, (_, i) => `key-${i}`)
+  const lengths = lookupLengths([...keys, 'key-0', '你好🦀', 'with\u0000null'])
+  t.is(Object.keys(lengths).length, 10002)
+  t.true(keys.every((key) => lengths[key] === key.length))
+  t.is(lengths['你好🦀'], 3)
+  t.is(lengths['with\u0000null'], 9)
+})
+
+test('js Map with typed keys', (t) => {
+  t.deepEqual(
+    invertMap(
+      new Map([
+        [1, 'a'],
+        [2, 'b'],
+      ]),
+    ),
+    new Map([
+      ['a', 1],
+      ['b', 2],
+    ]),
+  )
+  const scaled = scaleMapValues(
+    new Map([
+      [1, 1.5],
+      [7, 2],
+    ]),
+    2,
+  )
+  t.true(scaled instanceof Map)
+  t.deepEqual(
+    [...scaled.entries()].sort(([a], [b]) => a - b),
+    [
+      [1, 3],
+      [7, 4],
+    ],
+  )
+  t.deepEqual(scaleMapValues(new Map(), 2), new Map())
+  t.throws(() => scaleMapValues({ 1: 1 } as any, 2), {
+    message: 'Value is not a Map',
+  })
+  t.throws(() => invertMap(new Map([['x', 'a']]) as any), {
+    message:
+      'Failed to convert key of Map. Failed to convert napi value String into rust type `u32`',
+  })
 })
 
 test('enum', (t) => {
@@ -209,6 +546,86 @@ test('enum', (t) => {
   t.is(enumToI32(CustomNumEnum.Eight), 8)
 })
 
+test('const enum', async (t) => {
+  const bindings = (await import('../index.js')).default
+  t.false('Direction' in bindings)
+  t.is(flipDirection(0), 1)
+  t.is(flipDirection(1), 0)
+})
+
+test('string enum with rename', async (t) => {
+  const bindings = (await import('../index.js')).default
+  t.false('Level' in bindings)
+  t.is(raiseLevel('low-priority'), 'high-priority')
+  t.is(raiseLevel('high-priority'), 'URGENT')
+  t.is(raiseLevel('URGENT'), 'URGENT')
+  t.throws(() => raiseLevel('LowPriority' as any), {
+    code: 'InvalidArg',
+  })
+  t.throws(() => raiseLevel('urgent' as any), {
+    code: 'InvalidArg',
+  })
+})
+
+test('enum with data as discriminated union', async (t) => {
+  const bindings = (await import('../index.js')).default
+  t.false('JobState' in bindings)
+
+  let job: JobState = { state: 'queued' }
+  job = advanceJob(job)
+  t.deepEqual(job, { state: 'running', pid: 1, progress: { done: 0, total: 2 } })
+  job = advanceJob(advanceJob(job))
+  t.deepEqual(job, { state: 'running', pid: 1, progress: { done: 2, total: 2 } })
+  job = advanceJob(job)
+  t.deepEqual(job, { state: 'done', outputPath: '/tmp/job-1.out' })
+  t.deepEqual(advanceJob(job), job)
+
+  t.deepEqual(failJob({ state: 'running', pid: 3, progress: { done: 0, total: 1 } }, 'oom'), {
+    state: 'failed',
+    reason: 'oom',
+    exitCode: 1,
+  })
+  const cancelled = failJob({ state: 'queued' }, 'cancelled')
+  t.deepEqual(cancelled, { state: 'failed', reason: 'cancelled' })
+  t.deepEqual(advanceJob(cancelled), cancelled)
+
+  t.throws(() => advanceJob({} as any), {
+    code: 'InvalidArg',
+    message: "Missing field `state` of `JobState`, expected one of 'queued', 'running', 'failed', 'done'",
+  })
+  t.throws(() => advanceJob({ state: 'paused' } as any), {
+    code: 'InvalidArg',
+    message: "state `paused` does not match any variant of `JobState`, expected one of 'queued', 'running', 'failed', 'done'",
+  })
+  // the fields are checked against the state
+  t.throws(() => advanceJob({ state: 'done', pid: 1 } as any), {
+    code: 'InvalidArg',
+    message: 'Missing field `outputPath` of state `done` of `JobState`',
+  })
+  t.throws(() => advanceJob({ state: 'running', pid: '1', progress: { done: 0, total: 1 } } as any), {
+    message: /^Failed to convert field `pid` of state `running` of `JobState`/,
+  })
+})
+
+test('enum map', (t) => {
+  const counts = countLevels(['URGENT', 'low-priority', 'URGENT'])
+  t.deepEqual(Object.keys(counts), ['low-priority', 'high-priority', 'URGENT'])
+  t.deepEqual(counts, {
+    'low-priority': 1,
+    'high-priority': 0,
+    URGENT: 2,
+  })
+  t.is(sumKindCounts({ [Kind.Dog]: 1, [Kind.Cat]: 2, [Kind.Duck]: 3 }), 6)
+  t.throws(
+    // @ts-expect-error
+    () => sumKindCounts({ [Kind.Dog]: 1 }),
+    {
+      message:
+        'Failed to convert property `1` of `EnumMap`. Failed to convert napi value Undefined into rust type `u32`',
+    },
+  )
+})
+
 test('function call', async (t) => {
   t.is(
     call0(() => 42),
@@ -262,6 +679,216 @@ test('function call', async (t) => {
   )
 })
 
+test('call a function and catch the exception it throws', (t) => {
+  t.is(
+    callCatching((value: number) => value * 2, 21),
+    'returned 42',
+  )
+  t.is(
+    callCatching(() => {
+      throw new TypeError('invalid input')
+    }, 1),
+    'threw invalid input',
+  )
+  t.is(
+    callCatching(() => {
+      throw 'plain'
+    }, 1),
+    'threw plain',
+  )
+  const error = new RangeError('original')
+  t.is(
+    t.throws(() =>
+      callCatchingRethrow(() => {
+        throw error
+      }),
+    ),
+    error,
+  )
+  t.throws(
+    () =>
+      callCatchingRethrow(() => {
+        throw 42
+      }),
+    { instanceOf: Error, message: '42' },
+  )
+  t.notThrows(() => callCatchingRethrow(() => {}))
+})
+
+test('collect rest arguments of any arity', (t) => {
+  const values = Array.from({ length: 63 }, (_, i) => i)
+  // 64 arguments with the label
+  t.is(joinNumbers('values', ...values), `values: ${values.join(',')}`)
+  t.is(joinNumbers('none'), 'none: ')
+  t.is(joinNumbers('one', 1), 'one: 1')
+  t.throws(() => joinNumbers('invalid', 1, 'x' as any), {
+    message: /Failed to convert rest argument 1/,
+  })
+})
+
+test('error-first callback', async (t) => {
+  const results: unknown[][] = []
+  divideWithCallback(7, 2, (...args) => results.push(args))
+  divideWithCallback(7, 0, (...args) => results.push(args))
+  t.deepEqual(results[0], [null, 3])
+  t.is(results[1].length, 1)
+  t.true(results[1][0] instanceof Error)
+  t.is((results[1][0] as Error).message, 'Division by zero')
+  // the callback convention works with `util.promisify`
+  const divide = promisify(divideWithCallback)
+  t.is(await divide(9, 3), 3)
+  await t.throwsAsync(() => divide(1, 0), { message: 'Division by zero' })
+})
+
+test('stored callback outlives the call', (t) => {
+  let calls = 0
+  storeCallback((arg) => {
+    calls++
+    return arg * 2
+  })
+  t.is(callStoredCallback(21), 42)
+  t.is(callStoredCallback(1), 2)
+  t.is(calls, 2)
+  clearStoredCallback()
+  t.throws(() => callStoredCallback(1), {
+    code: 'InvalidArg',
+    message: 'No callback stored',
+  })
+})
+
+test('stored callback can clear itself while it is called', (t) => {
+  storeCallback((arg) => {
+    clearStoredCallback()
+    return arg + 1
+  })
+  t.is(callStoredCallback(1), 2)
+  t.throws(() => callStoredCallback(1), {
+    code: 'InvalidArg',
+    message: 'No callback stored',
+  })
+})
+
+test('replay stored callback with multiple argument sets', (t) => {
+  const seen: number[] = []
+  storeCallback((arg) => {
+    seen.push(arg)
+    if (arg === 0) {
+      throw new Error('zero is not allowed')
+    }
+    return arg + 1
+  })
+  t.deepEqual(replayStoredCallback([1, 2, 3]), [2, 3, 4])
+  t.deepEqual(seen, [1, 2, 3])
+  seen.length = 0
+  t.throws(() => replayStoredCallback([4, 0, 5]), {
+    message: 'zero is not allowed',
+  })
+  t.deepEqual(seen, [4, 0])
+  clearStoredCallback()
+})
+
+test('set and clear a timeout from Rust', async (t) => {
+  const calls: string[] = []
+  const fired = new Promise<void>((resolve) => {
+    new DelayedCall(10, () => {
+      calls.push('fired')
+      resolve()
+    })
+  })
+  const cancelled = new DelayedCall(5, () => {
+    calls.push('cancelled')
+  })
+  t.true(cancelled.cancel())
+  t.false(cancelled.cancel())
+  await fired
+  await new Promise((resolve) => setTimeout(resolve, 20))
+  t.deepEqual(calls, ['fired'])
+})
+
+test('class properties keep their declaration order within each impl block', (t) => {
+  // the blocks are ordered by their first property: `impl { gamma }`, `impl { mu, beta }`, the fields
+  t.deepEqual(Object.getOwnPropertyNames(PropertyOrder.prototype), [
+    'gamma',
+    'mu',
+    'beta',
+    'zeta',
+    'alpha',
+    'constructor',
+  ])
+})
+
+test('instance of registered class', (t) => {
+  t.true(isInstanceOfClass(new Animal(Kind.Dog, '旺财'), 'Animal'))
+  t.false(isInstanceOfClass(new Dog('Doge'), 'Animal'))
+  t.false(isInstanceOfClass({}, 'Animal'))
+  t.throws(() => isInstanceOfClass({}, 'UnregisteredClass'), {
+    message: 'Class `UnregisteredClass` is not registered in the current thread',
+  })
+})
+
+test('construct registered class and call a method from Rust', (t) => {
+  t.is(newAnimalAndCall(Kind.Dog, '旺财', 'whoami'), 'Dog: 旺财')
+  t.is(newAnimalAndCall(Kind.Cat, '咪咪', 'whoami'), 'Cat: 咪咪')
+  // the exception thrown by the constructor is propagated
+  t.throws(() => newAnimalAndCall(42, '旺财', 'whoami'), {
+    code: 'InvalidArg',
+    message: 'value `42` does not match any variant of enum `Kind`',
+  })
+  t.throws(() => newAnimalAndCall(Kind.Dog, '旺财', 'getDogKind'), {
+    code: 'FunctionExpected',
+    message: '`Animal.prototype.getDogKind` is not a function',
+  })
+})
+
+test('construct registered class from Rust', (t) => {
+  const animal = newClassInstance('Animal', [Kind.Dog, '旺财']) as Animal
+  t.true(animal instanceof Animal)
+  t.is(animal.name, '旺财')
+  t.is(animal.whoami(), 'Dog: 旺财')
+  t.throws(() => newClassInstance('Animal', [42, '旺财']), {
+    code: 'InvalidArg',
+    message: 'value `42` does not match any variant of enum `Kind`',
+  })
+  t.throws(() => newClassInstance('UnregisteredClass', []), {
+    message: 'Class `UnregisteredClass` is not registered in the current thread',
+  })
+})
+
+test('list property names of registered class', (t) => {
+  t.deepEqual(getClassPropertyNames('PropertyOrder'), [
+    'alpha',
+    'beta',
+    'gamma',
+    'mu',
+    'zeta',
+  ])
+  t.is(getClassPropertyNames('UnregisteredClass'), null)
+})
+
+test('method taking self consumes the class instance', (t) => {
+  const builder = new MessageBuilder()
+  builder.push('hello')
+  builder.push('world')
+  t.is(builder.build(), 'hello world')
+  t.throws(() => builder.push('again'), {
+    message: '`MessageBuilder` instance is already consumed',
+  })
+  t.throws(() => builder.build(), {
+    message: '`MessageBuilder` instance is already consumed',
+  })
+  // marked by a symbol private to the addon, not by a string property
+  t.false('[[Consumed]]' in builder)
+  t.deepEqual(Object.keys(builder), [])
+})
+
+test('class method as Symbol.toPrimitive', (t) => {
+  const celsius = new Celsius(21.5)
+  t.is(`${celsius}`, '21.5°C')
+  t.is(String(celsius), '21.5°C')
+  t.is(celsius + '', '21.5°C')
+  t.is(celsius.format(), '21.5°C')
+})
+
 test('class', (t) => {
   const dog = new Animal(Kind.Dog, '旺财')
 
@@ -350,6 +977,26 @@ test('class factory', (t) => {
   )
 })
 
+test('class called without new', (t) => {
+  for (const [Class, name, args] of [
+    [Animal, 'Animal', [Kind.Dog, '旺财']],
+    [Dog, 'Dog', ['Doge']],
+  ] as const) {
+    t.throws(() => (Class as any)(...args), {
+      instanceOf: TypeError,
+      message: `Class constructor ${name} cannot be invoked without 'new'`,
+    })
+    t.true(new (Class as any)(...args) instanceof Class)
+  }
+  t.throws(() => (ClassWithFactory as any)(), {
+    instanceOf: TypeError,
+    message:
+      "Class constructor ClassWithFactory cannot be invoked without 'new'",
+  })
+  class SubAnimal extends Animal {}
+  t.is(new SubAnimal(Kind.Cat, 'sub').name, 'sub')
+})
+
 test('async class factory', async (t) => {
   const instance = await ClassWithFactory.with4Name('foo')
   t.is(instance.name, 'foo-4')
@@ -357,6 +1004,96 @@ test('async class factory', async (t) => {
   t.is(instance2.name, 'foo-4')
 })
 
+test('class instance private data', (t) => {
+  const visitor = new Visitor('Alice')
+  const other = new Visitor('Bob')
+  t.is(visitor.visited(), null)
+  t.is(visitor.visit('Paris'), 1)
+  t.is(visitor.visit('Rome'), 2)
+  t.deepEqual(visitor.visited(), ['Paris', 'Rome'])
+  t.is(other.visited(), null)
+  // the private data doesn't replace the wrapped struct
+  t.is(visitor.name, 'Alice')
+  t.deepEqual(Object.keys(visitor), [])
+  visitor.forgetVisits()
+  t.deepEqual(visitor.visited(), [])
+})
+
+test('class with external state', (t) => {
+  const session = new Session('token')
+  t.true(session instanceof Session)
+  t.is(session.request(), 1)
+  t.is(session.token, 'token')
+  t.is(JSON.stringify(session), '{}')
+  t.deepEqual(Object.keys(session), [])
+  // a copy of the properties carries the external state
+  const copy = { ...session }
+  t.is(describeSession(copy as Session), 'token made 1 requests')
+  session.request()
+  t.is(describeSession(Object.assign({}, session)), 'token made 2 requests')
+  const anonymous = Session.anonymous()
+  anonymous.request()
+  t.is(describeSession(anonymous), 'anonymous made 1 requests')
+  t.is(describeSession(openSession('opened')), 'opened made 0 requests')
+  t.throws(() => describeSession({} as Session), {
+    message: /^Value has no external state of `.*Session`/,
+  })
+})
+
+test('class with a private constructor', (t) => {
+  t.throws(
+    () => {
+      // @ts-expect-error
+      new PooledConnection()
+    },
+    {
+      instanceOf: TypeError,
+      message:
+        'Class `PooledConnection` has a private constructor, create it with its factory instead',
+    },
+  )
+  t.throws(() => (PooledConnection as any)(), {
+    instanceOf: TypeError,
+    message:
+      "Class constructor PooledConnection cannot be invoked without 'new'",
+  })
+  const connection = openPooledConnection('db://localhost')
+  t.true(connection instanceof PooledConnection)
+  t.is(connection.url, 'db://localhost')
+  t.is(connection.describe(), 'db://localhost (4 connections)')
+  connection.poolSize = 8
+  t.is(connection.describe(), 'db://localhost (8 connections)')
+})
+
+test('lazy class is defined on first access', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const bindings = (await import('../index.js')).default
+  const pending = Object.getOwnPropertyDescriptor(bindings, 'LazyThumbnail')
+  t.is(typeof pending?.get, 'function')
+  t.true(pending?.enumerable)
+  const thumbnail = new bindings.LazyThumbnail(3, 4)
+  t.is(thumbnail.area(), 12)
+  t.true(thumbnail instanceof bindings.LazyThumbnail)
+  const defined = Object.getOwnPropertyDescriptor(bindings, 'LazyThumbnail')
+  t.is(defined?.get, undefined)
+  t.is(defined?.value, bindings.LazyThumbnail)
+})
+
+test('lazy class is defined when an instance is returned from Rust', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const report = createLazyReport('weekly')
+  t.is(report.title, 'weekly')
+  const bindings = (await import('../index.js')).default
+  t.true(report instanceof bindings.LazyReport)
+  t.is(Object.getPrototypeOf(report), bindings.LazyReport.prototype)
+})
+
 test('class constructor return Result', (t) => {
   const c = new Context()
   t.is(c.method(), 'not empty')
@@ -385,6 +1122,429 @@ test('custom finalize class', (t) => {
   t.notThrows(() => new CustomFinalize(200, 200))
 })
 
+test('module init runs once before exports are installed', (t) => {
+  t.is(getModuleInitCount(), 1)
+  t.true(isModuleInitBeforeExports())
+})
+
+test('exported function returns itself', (t) => {
+  t.is(chain(), chain)
+  t.is(chain()()(), chain)
+})
+
+test('module accessor runs its getter on every read', async (t) => {
+  const bindings = (await import('../index.js')).default as unknown as {
+    answer: number
+  }
+  const reads = getAnswerReads()
+  t.is(bindings.answer, 42)
+  t.is(bindings.answer, 42)
+  t.is(getAnswerReads(), reads + 2)
+  t.true(Object.keys(bindings).includes('answer'))
+})
+
+
+test('exports named at runtime are installed', async (t) => {
+  const bindings = (await import('../index.js')).default as unknown as {
+    dynamicExport1: string
+    dynamicExport2: string
+    dynamicNamespace: { value: string }
+  }
+  t.is(bindings.dynamicExport1, 'dynamic')
+  t.is(bindings.dynamicExport2, 'dynamic')
+  t.deepEqual(bindings.dynamicNamespace, { value: 'dynamic' })
+})
+
+test('doc comments of exports named at runtime', (t) => {
+  t.is(getDynamicExportDoc('dynamicExport1'), 'Dynamic export number 1')
+  t.is(getDynamicExportDoc('dynamicExport2'), 'Dynamic export number 2')
+  t.is(getDynamicExportDoc('value', 'dynamicNamespace'), null)
+  t.is(getDynamicExportDoc('dynamicExport1', 'dynamicNamespace'), null)
+  t.is(getDynamicExportDoc('missing'), null)
+})
+
+test('raw napi callbacks are exported as functions', async (t) => {
+  const bindings = (await import('../index.js')).default as unknown as {
+    addOneRaw: (value: number) => number
+    rawNamespace: { addOne: (value: number) => number }
+  }
+  t.is(bindings.addOneRaw.name, 'addOneRaw')
+  t.is(bindings.addOneRaw(41), 42)
+  t.is(bindings.rawNamespace.addOne(1.5), 2.5)
+  t.throws(() => bindings.addOneRaw('41' as unknown as number), {
+    instanceOf: TypeError,
+    message: 'Expected a number',
+  })
+})
+
+test('pre init callbacks run in registration order', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const bindings = (await import('../index.js')).default
+  t.is(Object.prototype.toString.call(bindings), '[object NapiRs Examples]')
+})
+
+test('should abort loading if module init failed', (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const p = exec('node ./module-init-error.cjs', {
+    cwd: __dirname,
+    env: {
+      ...process.env,
+      NAPI_RS_TEST_MODULE_INIT_ERROR: '1',
+    },
+  })
+  let stderr = Buffer.from([])
+  p.stderr?.on('data', (data) => {
+    stderr = Buffer.concat([stderr, Buffer.from(data)])
+  })
+  return new Promise<void>((resolve) => {
+    p.on('exit', (code) => {
+      t.is(code, 1)
+      t.true(stderr.toString('utf8').includes('Module init failed'))
+      resolve()
+    })
+  })
+})
+
+test('should throw instead of aborting if an export panics while loading', (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const p = exec('node ./module-init-error.cjs', {
+    cwd: __dirname,
+    env: {
+      ...process.env,
+      NAPI_RS_TEST_PANIC_IN_EXPORT: '1',
+    },
+  })
+  let stderr = Buffer.from([])
+  p.stderr?.on('data', (data) => {
+    stderr = Buffer.concat([stderr, Buffer.from(data)])
+  })
+  return new Promise<void>((resolve) => {
+    p.on('exit', (code) => {
+      t.is(code, 1)
+      t.true(
+        stderr
+          .toString('utf8')
+          .includes(
+            'Panic while registering export `panickingExport`: export callback panicked',
+          ),
+      )
+      resolve()
+    })
+  })
+})
+
+test('should only export enabled_if_env functions if the env var is truthy', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const load = (featureGate?: string) =>
+    new Promise<string>((resolve) => {
+      const env = { ...process.env }
+      delete env.NAPI_RS_TEST_FEATURE_GATE
+      if (featureGate !== undefined) {
+        env.NAPI_RS_TEST_FEATURE_GATE = featureGate
+      }
+      exec(
+        'node ./enabled-if-env.cjs',
+        { cwd: __dirname, env },
+        (_err, stdout) => {
+          resolve(stdout.trim())
+        },
+      )
+    })
+  t.is(await load('1'), 'function')
+  t.is(await load(), 'undefined')
+  t.is(await load('0'), 'undefined')
+})
+
+test('main function is the module with every export as a property', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const load = (main?: string) =>
+    new Promise<string>((resolve) => {
+      const env = { ...process.env }
+      delete env.NAPI_RS_TEST_MODULE_MAIN
+      if (main !== undefined) {
+        env.NAPI_RS_TEST_MODULE_MAIN = main
+      }
+      exec(
+        'node ./module-main.cjs',
+        { cwd: __dirname, env },
+        (_err, stdout) => {
+          resolve(stdout.trim())
+        },
+      )
+    })
+  t.deepEqual(JSON.parse(await load('1')), {
+    type: 'function',
+    name: 'greet',
+    greeting: 'Hello, Ada!',
+    hasGreet: false,
+    add: 3,
+    esModule: false,
+  })
+  t.deepEqual(JSON.parse(await load()), {
+    type: 'object',
+    hasGreet: false,
+    add: 3,
+    esModule: false,
+  })
+})
+
+test('should leave out the exports rejected by the export filter', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const load = (disabledExport?: string) =>
+    new Promise<string>((resolve) => {
+      const env = { ...process.env }
+      delete env.NAPI_RS_TEST_DISABLED_EXPORT
+      if (disabledExport !== undefined) {
+        env.NAPI_RS_TEST_DISABLED_EXPORT = disabledExport
+      }
+      exec(
+        'node ./export-filter.cjs',
+        { cwd: __dirname, env },
+        (_err, stdout) => {
+          resolve(stdout.trim())
+        },
+      )
+    })
+  t.is(await load(), 'function function')
+  t.is(await load('add'), 'undefined function')
+  t.is(await load('sumNums'), 'function undefined')
+})
+
+test('should return an error from get_js_function while registering the module', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const stdout = await new Promise<string>((resolve) => {
+    exec(
+      'node ./reentrant-get-js-function.cjs',
+      {
+        cwd: __dirname,
+        env: {
+          ...process.env,
+          NAPI_RS_TEST_REENTRANT_GET_JS_FUNCTION: '1',
+        },
+      },
+      (_err, stdout) => {
+        resolve(stdout.trim())
+      },
+    )
+  })
+  t.is(stdout, 'The module is still being registered on the current thread')
+})
+
+test('should reject referenced but unregistered class while loading', (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const p = exec('node ./unregistered-class-reference.cjs', {
+    cwd: __dirname,
+    env: {
+      ...process.env,
+      NAPI_RS_TEST_DISABLED_EXPORT: 'FilteredClass',
+    },
+  })
+  let stderr = Buffer.from([])
+  p.stderr?.on('data', (data) => {
+    stderr = Buffer.concat([stderr, Buffer.from(data)])
+  })
+  return new Promise<void>((resolve) => {
+    p.on('exit', (code) => {
+      t.is(code, 1)
+      const stderrMsg = stderr.toString('utf8')
+      t.true(
+        stderrMsg.includes(
+          'Classes referenced by exports are not registered: `FilteredClass` (referenced by `createFilteredClass`)',
+        ),
+      )
+      t.false(stderrMsg.includes('`Animal`'))
+      resolve()
+    })
+  })
+})
+
+test('should reject class and export names which are not identifiers while loading', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const load = (envVar: string) =>
+    new Promise<string>((resolve) => {
+      exec(
+        'node ./module-init-error.cjs',
+        { cwd: __dirname, env: { ...process.env, [envVar]: '1' } },
+        (_err, _stdout, stderr) => {
+          resolve(stderr)
+        },
+      )
+    })
+  t.true(
+    (await load('NAPI_RS_TEST_INVALID_CLASS_NAME')).includes(
+      'Class name `Invalid Class` of struct `InvalidClassName` is not a valid JavaScript identifier',
+    ),
+  )
+  t.true(
+    (await load('NAPI_RS_TEST_INVALID_EXPORT_NAME')).includes(
+      'Export name `invalid-export` of function `invalid_export_name` is not a valid JavaScript identifier',
+    ),
+  )
+})
+
+test('should refuse to register more exports than the export limit', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  await new Promise<void>((resolve) => {
+    exec(
+      'node ./module-init-error.cjs',
+      {
+        cwd: __dirname,
+        env: { ...process.env, NAPI_RS_TEST_EXPORT_LIMIT: '1' },
+      },
+      (err, _stdout, stderr) => {
+        t.truthy(err)
+        t.regex(
+          stderr,
+          /Module has \d+ exports and classes, more than the limit of 10 set by `set_export_limit`/,
+        )
+        resolve()
+      },
+    )
+  })
+})
+
+test('should install the exports registered after an anchor after it', async (t) => {
+  const bindings = (await import('../index.js')).default
+  const keys = Object.keys(bindings)
+  t.true(keys.indexOf('exportAnchor') < keys.indexOf('exportAfterAnchor'))
+  t.true(keys.indexOf('Animal') < keys.indexOf('Dog'))
+})
+
+test('should refuse to load exports registered after each other in a cycle', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  await new Promise<void>((resolve) => {
+    exec(
+      'node ./module-init-error.cjs',
+      {
+        cwd: __dirname,
+        env: { ...process.env, NAPI_RS_TEST_EXPORT_CYCLE: '1' },
+      },
+      (err, _stdout, stderr) => {
+        t.truthy(err)
+        t.regex(
+          stderr,
+          /The exports registered after each other form a cycle: `exportCycle[AB]` -> `exportCycle[AB]` -> `exportCycle[AB]`/,
+        )
+        resolve()
+      },
+    )
+  })
+})
+
+test('should extend a class exported by another module', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  const stdout = await new Promise<string>((resolve) => {
+    exec(
+      'node ./parent-module.cjs',
+      {
+        cwd: __dirname,
+        env: { ...process.env, NAPI_RS_TEST_PARENT_MODULE: '1' },
+      },
+      (_err, stdout) => {
+        resolve(stdout.trim())
+      },
+    )
+  })
+  t.is(stdout, 'true plugin json plugin json')
+  await new Promise<void>((resolve) => {
+    exec(
+      'node ./module-init-error.cjs',
+      {
+        cwd: __dirname,
+        env: { ...process.env, NAPI_RS_TEST_PARENT_MODULE: '1' },
+      },
+      (err, _stdout, stderr) => {
+        t.truthy(err)
+        t.regex(
+          stderr,
+          /Class `JsonPlugin` extends `Plugin` of the module `plugin-host`, which is not set/,
+        )
+        resolve()
+      },
+    )
+  })
+})
+
+test('globals are installed on globalThis', async (t) => {
+  const bindings = (await import('../index.js')).default
+  const { napiRsGreet } = globalThis as unknown as {
+    napiRsGreet: (name: string) => string
+  }
+  t.is(napiRsGreet('Ada'), 'Hello, Ada!')
+  t.false('napiRsGreet' in bindings)
+  t.false(Object.keys(globalThis).includes('napiRsGreet'))
+})
+
+test('should refuse to replace a global defined by someone else', async (t) => {
+  if (process.env.WASI_TEST) {
+    t.pass()
+    return
+  }
+  await new Promise<void>((resolve) => {
+    exec(
+      'node ./global-conflict.cjs',
+      {
+        cwd: __dirname,
+      },
+      (err, _stdout, stderr) => {
+        t.truthy(err)
+        t.regex(
+          stderr,
+          /Global `napiRsGreet` is already defined, it may be installed by another addon/,
+        )
+        resolve()
+      },
+    )
+  })
+})
+
+test('should register names which are not identifiers with any_js_name', async (t) => {
+  const bindings = (await import('../index.js')).default as unknown as {
+    'Weird Class': new (value: number) => { value: number }
+    'weird-export': () => number
+  }
+  const WeirdClass = bindings['Weird Class']
+  t.is(WeirdClass.name, 'Weird Class')
+  t.is(new WeirdClass(3).value, 3)
+  t.is(bindings['weird-export'](), 42)
+})
+
 test('should be able to create object reference and shared reference', (t) => {
   const repo = new JsRepo('.')
   t.is(repo.remote().name(), 'origin')
@@ -460,11 +1620,84 @@ Napi4Test('callback function return Promise and spawn', async (t) => {
   t.is(finalReturn, 'Hello world 😼')
 })
 
+test('object with lazy fields', (t) => {
+  takeEvaluatedLazyFields()
+  const config = loadLazyConfig('app')
+  t.is(config.name, 'app')
+  t.deepEqual(takeEvaluatedLazyFields(), [])
+  const maxConnections: number = config.maxConnections
+  t.is(maxConnections, 16)
+  t.is(config.maxConnections, 16)
+  t.deepEqual(takeEvaluatedLazyFields(), ['maxConnections'])
+  t.deepEqual(Object.keys(config), ['name', 'plugins', 'maxConnections', 'theme'])
+  t.deepEqual(takeEvaluatedLazyFields(), [])
+  t.deepEqual(
+    { ...config },
+    {
+      name: 'app',
+      plugins: ['auth', 'cache'],
+      maxConnections: 16,
+      theme: 'dark',
+    },
+  )
+  t.deepEqual(takeEvaluatedLazyFields(), ['plugins', 'theme'])
+  t.is(
+    countLazyConfigPlugins({
+      name: 'plain',
+      plugins: ['auth'],
+      maxConnections: 1,
+      theme: 'light',
+    }),
+    1,
+  )
+})
+
 test('object', (t) => {
   t.deepEqual(listObjKeys({ name: 'John Doe', age: 20 }), ['name', 'age'])
   t.deepEqual(createObj(), { test: 1 })
 })
 
+test('sealed object', (t) => {
+  const cursor = createCursor()
+  t.true(Object.isSealed(cursor))
+  t.false(Object.isFrozen(cursor))
+  cursor.line = 10
+  t.deepEqual(cursor, { line: 10, column: 1 })
+  // modules are in strict mode, so changing the shape throws
+  t.throws(
+    () => {
+      // @ts-expect-error
+      cursor.offset = 0
+    },
+    { instanceOf: TypeError },
+  )
+  t.throws(
+    () => {
+      // @ts-expect-error
+      delete cursor.column
+    },
+    { instanceOf: TypeError },
+  )
+})
+
+test('own and inherited option properties', (t) => {
+  const flag = Symbol('flag')
+  const defaults = { retries: 3, [flag]: true }
+  const options = Object.create(defaults)
+  options.timeout = 10
+  t.is(optionSource(options, 'timeout'), 'own')
+  t.is(optionSource(options, 'retries'), 'inherited')
+  t.is(optionSource(options, 'missing'), 'missing')
+  // an inherited symbol property is not deleted
+  t.false(takeOptionFlag(options, flag))
+  t.true(flag in options)
+
+  const own = { [flag]: true }
+  t.true(takeOptionFlag(own, flag))
+  t.false(flag in own)
+  t.false(takeOptionFlag(own, flag))
+})
+
 test('get str from object', (t) => {
   t.notThrows(() => getStrFromObject())
 })
@@ -475,6 +1708,10 @@ test('create object from Property', (t) => {
   t.is(obj.getter, 42)
 })
 
+test('create object from Property named by get_c_callback_with_name', (t) => {
+  t.is(createObjWithNamedProperty().getterFromObj, 42)
+})
+
 test('global', (t) => {
   t.is(getGlobal(), global)
 })
@@ -522,6 +1759,165 @@ test('Result', (t) => {
   }
 })
 
+test('panic error has rust location', (t) => {
+  if (process.env.SKIP_UNWIND_TEST) {
+    t.pass()
+    return
+  }
+  const err = t.throws(() => panic())
+  t.is(err?.message, `Don't panic`)
+  t.regex((err as any)?.rustLocation, /src[\\/]error\.rs:10$/)
+})
+
+test('error with cause chain', (t) => {
+  const err = t.throws(() => throwErrorWithCause())
+  t.is(err?.message, 'Failed to load config')
+  t.true(err?.cause instanceof Error)
+  t.is((err?.cause as Error).message, 'config.toml not found')
+  t.is((err?.cause as Error).cause, undefined)
+})
+
+test('transient error with a retry hint', (t) => {
+  const err = t.throws(() => connectFlaky(1)) as Error & {
+    retryable?: boolean
+    retryAfterMs?: number
+  }
+  t.is(err.message, 'Connection refused on attempt 1')
+  t.is(err.retryable, true)
+  t.is(err.retryAfterMs, 10)
+  const plain = t.throws(() => throwError())
+  t.false('retryable' in plain!)
+  t.false('retryAfterMs' in plain!)
+
+  let attempt = 1
+  const delays: number[] = []
+  for (;;) {
+    try {
+      t.is(connectFlaky(attempt), 'connected')
+      break
+    } catch (e: any) {
+      t.true(e.retryable)
+      delays.push(e.retryAfterMs)
+      attempt++
+    }
+  }
+  t.deepEqual(delays, [10, 20])
+})
+
+test('validation errors for every invalid field', (t) => {
+  t.is(validateSignUp({ email: 'a@example.com', age: 30 }), 'a@example.com')
+  const err = t.throws(() => validateSignUp({ email: 'nope', age: 12 })) as Error & {
+    fields?: { field: string; message: string }[]
+  }
+  t.true(err instanceof Error)
+  t.is(err.name, 'ValidationError')
+  t.is(err.message, 'Validation failed for email, age')
+  t.deepEqual(err.fields, [
+    { field: 'email', message: 'Must be an email address' },
+    { field: 'age', message: 'Must be at least 18' },
+  ])
+  const single = t.throws(() => validateSignUp({ email: 'a@example.com', age: 1 })) as Error & {
+    fields?: { field: string; message: string }[]
+  }
+  t.deepEqual(single.fields, [{ field: 'age', message: 'Must be at least 18' }])
+})
+
+test('aggregate error with an error for every invalid option', (t) => {
+  t.notThrows(() => validateServerOptions(80, 'localhost'))
+  const err = t.throws(() => validateServerOptions(0, ''), {
+    instanceOf: AggregateError,
+    message: 'Invalid server options',
+  })
+  t.deepEqual(
+    err.errors.map((e: Error & { code?: string }) => [e.message, e.code]),
+    [
+      ['port must not be 0', 'InvalidArg'],
+      ['host must not be empty', 'InvalidArg'],
+    ],
+  )
+  t.true(err.errors.every((e: unknown) => e instanceof Error))
+  t.is(t.throws(() => validateServerOptions(80, '')).errors.length, 1)
+
+  const { AggregateError: globalAggregateError } = globalThis
+  // @ts-expect-error
+  delete globalThis.AggregateError
+  try {
+    t.throws(() => validateServerOptions(0, ''), {
+      instanceOf: Error,
+      message:
+        'Invalid server options: port must not be 0; host must not be empty',
+    })
+  } finally {
+    globalThis.AggregateError = globalAggregateError
+  }
+})
+
+test('throw a pre-built value', (t) => {
+  const error = new TypeError('pre-built')
+  const object = { code: 1 }
+  for (const value of [error, object, 'not an error', 42, undefined]) {
+    try {
+      throwValue(value)
+      t.fail('should throw')
+    } catch (e) {
+      t.is(e, value)
+    }
+  }
+})
+
+test('rethrow the caught exception of a callback', (t) => {
+  const error = new RangeError('caught')
+  t.is(
+    t.throws(() =>
+      rethrowCallbackException(() => {
+        throw error
+      }),
+    ),
+    error,
+  )
+  t.false(rethrowCallbackException(() => {}))
+})
+
+test('classify caught JS errors by name and code', (t) => {
+  t.is(
+    classifyThrownError(() => {
+      (undefined as any).toString()
+    }),
+    'TypeError',
+  )
+  t.is(
+    classifyThrownError(() => {
+      new Array(-1)
+    }),
+    'RangeError',
+  )
+  t.is(
+    classifyThrownError(() => {
+      throw Object.assign(new Error('custom'), { code: 'ERR_CUSTOM' })
+    }),
+    'Code("ERR_CUSTOM")',
+  )
+  class ValidationError extends Error {
+    name = 'ValidationError'
+  }
+  t.is(
+    classifyThrownError(() => {
+      throw new ValidationError('invalid')
+    }),
+    'Other("ValidationError")',
+  )
+  t.is(
+    classifyThrownError(() => {
+      throw 'not an error'
+    }),
+    'Unknown',
+  )
+  t.is(
+    classifyThrownError(() => {}),
+    'None',
+  )
+})
+
 test('Async error with stack trace', async (t) => {
   const err = await t.throwsAsync(() => throwAsyncError())
   t.not(err?.stack, undefined)
@@ -542,6 +1938,13 @@ test('function ts type override', (t) => {
   t.deepEqual(tsRename({ foo: 1, bar: 2, baz: 2 }), ['foo', 'bar', 'baz'])
 })
 
+test('function ts type imported from another package', (t) => {
+  t.true(isReadableStream(Readable.from(['a'])))
+  t.true(isReadableStream(new Duplex()))
+  // @ts-expect-error
+  t.false(isReadableStream({ read: 1 }))
+})
+
 test('function individual ts arg type override', (t) => {
   t.is(
     overrideIndividualArgOnFunction('someStr', () => 'anotherStr', 42),
@@ -582,6 +1985,42 @@ test('aliased rust struct and enum', (t) => {
   t.notThrows(() => fnReceivedAliased(b, ALIAS.B))
 })
 
+test('max nesting depth', (t) => {
+  const nest = (depth: number) => {
+    let obj = {}
+    for (let i = 1; i < depth; i++) {
+      obj = { child: [obj] }
+    }
+    return obj
+  }
+  t.is(nestedObjectDepth(nest(256)), 256)
+  t.throws(() => nestedObjectDepth(nest(257)), {
+    code: 'InvalidArg',
+    message: 'maximum nesting depth exceeded',
+  })
+  const cyclic: any = {}
+  cyclic.child = [cyclic]
+  t.throws(() => nestedObjectDepth(cyclic), {
+    message: 'maximum nesting depth exceeded',
+  })
+  let arr: any = 1
+  for (let i = 0; i < 10000; i++) {
+    arr = [arr]
+  }
+  t.throws(() => jsonValueDepth(arr), {
+    message: 'maximum nesting depth exceeded',
+  })
+  const previous = setMaxObjectNestingDepth(10)
+  try {
+    t.is(nestedObjectDepth(nest(10)), 10)
+    t.throws(() => nestedObjectDepth(nest(11)), {
+      message: 'maximum nesting depth exceeded',
+    })
+  } finally {
+    setMaxObjectNestingDepth(previous)
+  }
+})
+
 test('serde-json', (t) => {
   if (process.env.WASI_TEST) {
     t.pass()
@@ -623,6 +2062,44 @@ test('serde-roundtrip', (t) => {
   t.is(err!.message, 'JS symbols cannot be represented as a serde_json::Value')
 })
 
+test('serde_json::Value round trips nested values', (t) => {
+  const value = {
+    name: 'napi',
+    tags: ['a', 'b'],
+    nested: { list: [1, -1, 1.5, { big: 2 ** 40, negative: -(2 ** 40) }] },
+    empty: null,
+    enabled: true,
+    skipped: undefined,
+  }
+  t.deepEqual(testSerdeRoundtrip(value), {
+    name: 'napi',
+    tags: ['a', 'b'],
+    nested: { list: [1, -1, 1.5, { big: 2 ** 40, negative: -(2 ** 40) }] },
+    empty: null,
+    enabled: true,
+  })
+  t.deepEqual(jsonNumberKinds(value), {
+    name: 'napi',
+    tags: ['a', 'b'],
+    nested: { list: ['u64', 'i64', 'f64', { big: 'u64', negative: 'i64' }] },
+    empty: null,
+    enabled: true,
+  })
+  // integers which may already be rounded are kept as floats
+  t.deepEqual(jsonNumberKinds([2 ** 53 - 1, 2 ** 53, -(2 ** 53)]), [
+    'u64',
+    'f64',
+    'f64',
+  ])
+  t.is(testSerdeRoundtrip(2 ** 53 - 1), 2 ** 53 - 1)
+})
+
+test('serde-compat rename and flatten', (t) => {
+  const pkg = { package_name: 'napi-rs', license: 'MIT' }
+  t.deepEqual(JSON.parse(serdeCompatToJson(pkg)), pkg)
+  t.deepEqual(serdeCompatFromJson(JSON.stringify(pkg)), pkg)
+})
+
 test('serde-large-number-precision', (t) => {
   t.is(testSerdeBigNumberPrecision('12345').number, 12345)
   t.is(
@@ -691,6 +2168,17 @@ test('deref uint8 array', (t) => {
   )
 })
 
+test('option buffer distinguishes null from empty', (t) => {
+  t.is(optionBufferLen(null), null)
+  t.is(optionBufferLen(undefined), null)
+  t.is(optionBufferLen(Buffer.alloc(0)), 0)
+  t.is(optionBufferLen(Buffer.from([1, 2, 3])), 3)
+  t.is(optionUint8ArrayLen(null), null)
+  t.is(optionUint8ArrayLen(new Uint8Array(0)), 0)
+  t.is(optionUint8ArrayLen(new Uint8Array(new ArrayBuffer(0), 0, 0)), 0)
+  t.is(optionUint8ArrayLen(new Uint8Array([1, 2])), 2)
+})
+
 test('async', async (t) => {
   if (process.env.WASI_TEST) {
     t.pass()
@@ -709,6 +2197,31 @@ test('async move', async (t) => {
   t.is(await asyncMultiTwo(2), 4)
 })
 
+test('async function created from a Rust closure', async (t) => {
+  const delayedAdd = createDelayedAdder(10, 50)
+  const first = delayedAdd(1)
+  const second = delayedAdd(2)
+  t.true(first instanceof Promise)
+  t.not(first, second)
+  t.deepEqual(await Promise.all([first, second]), [11, 12])
+  t.is(await delayedAdd(3), 13)
+  t.throws(() => delayedAdd('3' as any), { code: 'NumberExpected' })
+})
+
+test('async fn returning unit resolves with undefined', async (t) => {
+  const calls = getAsyncUnitCalls()
+  const promise = asyncUnit()
+  t.true(promise instanceof Promise)
+  t.is(await promise, undefined)
+  t.is(await asyncResultUnit(false), undefined)
+  t.is(getAsyncUnitCalls(), calls + 2)
+  await t.throwsAsync(() => asyncResultUnit(true), {
+    code: 'GenericFailure',
+    message: 'Async unit failed',
+  })
+  t.is(getAsyncUnitCalls(), calls + 2)
+})
+
 test('buffer passthrough', async (t) => {
   const fixture = Buffer.from('hello world')
   const ret = await bufferPassThrough(fixture)
@@ -721,6 +2234,80 @@ test('arraybuffer passthrough', async (t) => {
   t.deepEqual(ret, fixture)
 })
 
+test('return bytes as Uint8Array', (t) => {
+  const ret = reverseBytes(new Uint8Array([1, 2, 3]))
+  t.true(ret instanceof Uint8Array)
+  t.false(Buffer.isBuffer(ret))
+  t.deepEqual(ret, new Uint8Array([3, 2, 1]))
+  t.deepEqual(reverseBytes(Buffer.from('abc')), new Uint8Array([99, 98, 97]))
+  t.deepEqual(reverseBytes(new Uint8Array()), new Uint8Array())
+})
+
+test('write results into an output typed array', (t) => {
+  const output = new Float64Array(4)
+  for (const factor of [1, 2, 3]) {
+    t.is(scaleInto(new Float64Array([1, 2, 3]), factor, output), 3)
+    t.deepEqual(output, new Float64Array([factor, 2 * factor, 3 * factor, 0]))
+  }
+  t.is(scaleInto(new Float64Array([1, 2, 3, 4, 5]), 0.5, output), 4)
+  t.deepEqual(output, new Float64Array([0.5, 1, 1.5, 2]))
+  // a view writes into its part of the underlying buffer
+  const backing = new Float64Array(4)
+  t.is(scaleInto(new Float64Array([1, 2]), 10, backing.subarray(1, 3)), 2)
+  t.deepEqual(backing, new Float64Array([0, 10, 20, 0]))
+  t.is(scaleInto(new Float64Array(), 1, new Float64Array()), 0)
+  t.throws(() => scaleInto(new Float64Array(1), 1, new Float32Array(1) as any))
+})
+
+test('DataView round trip', (t) => {
+  const buf = new ArrayBuffer(10)
+  const view = createDataView(buf, 2, 6)
+  t.true(view instanceof DataView)
+  t.is(view.buffer, buf)
+  t.is(view.byteOffset, 2)
+  t.is(view.byteLength, 6)
+  view.setUint16(0, 0xbeef, false)
+  view.setUint32(2, 0x12345678, true)
+  t.deepEqual(parsePacketHeader(view), {
+    tag: 0xbeef,
+    payload: 0x12345678,
+    byteOffset: 2,
+    byteLength: 6,
+  })
+  t.deepEqual(parsePacketHeader(new DataView(buf, 2)).byteLength, 8)
+  t.throws(() => createDataView(buf, 8, 4), {
+    message:
+      'DataView of 4 bytes at offset 8 is out of the bounds of the ArrayBuffer of 10 bytes',
+  })
+  t.throws(() => createDataView(buf, 11, 0))
+  t.throws(() => parsePacketHeader(new DataView(buf, 6)), {
+    message: 'Packet header needs 6 bytes, got 4',
+  })
+  t.throws(() => parsePacketHeader(new Uint8Array(6) as any), {
+    message: 'Value is not a DataView',
+  })
+
+  structuredClone(buf, { transfer: [buf] })
+  t.throws(() => createDataView(buf, 0, 0), {
+    message: 'Cannot create a DataView on a detached ArrayBuffer',
+  })
+  t.throws(() => parsePacketHeader(view), {
+    message: 'The ArrayBuffer of the DataView is detached',
+  })
+})
+
+test('type guards', (t) => {
+  const buf = new ArrayBuffer(4)
+  t.deepEqual(valueKinds(buf), ['arraybuffer'])
+  t.deepEqual(valueKinds(new Uint8Array(buf)), ['typedarray', 'buffer'])
+  t.deepEqual(valueKinds(Buffer.from('napi')), ['typedarray', 'buffer'])
+  t.true(valueKinds(new DataView(buf)).includes('dataview'))
+  t.deepEqual(valueKinds([1, 2]), ['array'])
+  t.deepEqual(valueKinds(Promise.resolve(1)), ['promise'])
+  t.deepEqual(valueKinds({ byteLength: 4 }), [])
+  t.deepEqual(valueKinds('napi'), [])
+})
+
 test('async reduce buffer', async (t) => {
   const input = [1, 2, 3, 4, 5, 6]
   const fixture = Buffer.from(input)
@@ -812,6 +2399,46 @@ test('should be able to run script', async (t) => {
   t.is(await runScript(`Promise.resolve(1)`), 1)
 })
 
+test('should compile a function once and call it with different args', (t) => {
+  const add = compileFunction('return a + b', ['a', 'b'])
+  t.is(add(1, 2), 3)
+  t.is(add('a', 'b'), 'ab')
+  t.is(compileFunction('return a + b', ['a', 'b']), add)
+  t.not(compileFunction('return a + b', ['b', 'a']), add)
+  t.deepEqual(callCompiledFunction([1, 2, 3]), [2, 4, 6])
+  t.throws(() => compileFunction('return (', []), {
+    instanceOf: SyntaxError,
+  })
+})
+
+test('should compare values with strict and loose equality', (t) => {
+  const obj = {}
+  t.true(strictEquals(obj, obj))
+  t.false(strictEquals(obj, {}))
+  t.false(strictEquals(1, '1'))
+  t.false(strictEquals(NaN, NaN))
+  t.true(looseEquals(obj, obj))
+  t.false(looseEquals(obj, {}))
+  t.true(looseEquals(1, '1'))
+  t.true(looseEquals(null, undefined))
+  t.throws(
+    () =>
+      looseEquals(
+        {
+          valueOf() {
+            throw new Error('valueOf failed')
+          },
+        },
+        1,
+      ),
+    { message: 'valueOf failed' },
+  )
+})
+
+test('should get the uv event loop of the env', (t) => {
+  t.true(hasUvEventLoop())
+})
+
 test('should be able to return object from shared crate', (t) => {
   t.deepEqual(returnFromSharedCrate(), {
     value: 42,
@@ -878,6 +2505,19 @@ BigIntTest('from i128 i64', (t) => {
   t.is(bigintFromI128(), BigInt('-100'))
 })
 
+BigIntTest('serde_json::Value converts BigInt in the 64-bit range', (t) => {
+  t.deepEqual(jsonNumberKinds([BigInt(2 ** 60), -BigInt(2 ** 60)]), [
+    'u64',
+    'i64',
+  ])
+  t.is(testSerdeRoundtrip(BigInt(2 ** 60)), BigInt(2 ** 60))
+  t.is(testSerdeRoundtrip(-BigInt(2 ** 60)), -BigInt(2 ** 60))
+  t.is(testSerdeRoundtrip(BigInt(5)), 5)
+  t.throws(() => testSerdeRoundtrip(BigInt(2 ** 64)), {
+    message: 'BigInt is out of the range of serde_json::Number',
+  })
+})
+
 Napi4Test('call thread safe function', (t) => {
   let i = 0
   let value = 0
@@ -1120,6 +2760,44 @@ Napi5Test('Date from chrono::NativeDateTime test', (t) => {
   t.is(fixture?.toISOString(), '2016-12-23T15:25:59.325Z')
 })
 
+test('Duration as milliseconds', (t) => {
+  t.is(durationAddNanos(1.5, 250_000), 1.75)
+  t.is(durationAddNanos(0, 1), 0.000001)
+  t.throws(() => durationAddNanos(-1, 0), {
+    message:
+      'Can not convert -1 milliseconds to `Duration`, it must be a non-negative finite number',
+  })
+  t.throws(() => durationAddNanos(NaN, 0))
+  t.throws(() => durationAddNanos(Infinity, 0))
+})
+
+test('PreciseDuration as secs and nanos', (t) => {
+  t.deepEqual(preciseDurationAddNanos({ secs: 1, nanos: 999_999_999 }, 2), {
+    secs: 2,
+    nanos: 1,
+  })
+  t.throws(() => preciseDurationAddNanos({ secs: -1, nanos: 0 }, 0), {
+    message:
+      '`secs` of `PreciseDuration` must be a non-negative safe integer, got -1',
+  })
+  t.throws(() => preciseDurationAddNanos({ secs: 1, nanos: 1e9 }, 0), {
+    message:
+      '`nanos` of `PreciseDuration` must be an integer in 0..1000000000, got 1000000000',
+  })
+})
+
+Napi5Test('SystemTime as Date', (t) => {
+  const beforeEpoch = new Date('1969-12-31T23:59:59.999Z')
+  t.is(systemTimeAddNanos(beforeEpoch, 0).getTime(), -1)
+  // the sub-millisecond part is rounded towards the past
+  t.is(systemTimeAddNanos(beforeEpoch, -1).getTime(), -2)
+  t.is(systemTimeAddNanos(beforeEpoch, 999_999).getTime(), -1)
+  t.is(systemTimeAddNanos(new Date(0), 1_000_000).getTime(), 1)
+  t.throws(() => systemTimeAddNanos(new Date(NaN), 0), {
+    message: 'Can not convert an invalid Date to `SystemTime`',
+  })
+})
+
 const Napi9Test = Number(process.versions.napi) >= 9 ? test : test.skip
 
 Napi9Test('create symbol for', (t) => {
@@ -1147,3 +2825,36 @@ test('throw syntax error', (t) => {
     message,
   )
 })
+
+test('should create object with prototype', (t) => {
+  let constructed = 0
+  class Foo {
+    constructor() {
+      constructed++
+    }
+
+    hello() {
+      return 'hello'
+    }
+  }
+  const foo = createObjectWithPrototype(Foo.prototype) as Foo
+  t.is(Object.getPrototypeOf(foo), Foo.prototype)
+  t.true(foo instanceof Foo)
+  t.is(foo.hello(), 'hello')
+  t.is(constructed, 0)
+  t.deepEqual(Object.keys(foo), [])
+  t.true(createObjectWithPrototype(Animal.prototype) instanceof Animal)
+})
+
+test('should get and set the prototype of an object', (t) => {
+  const base = { greet: () => 'hello' }
+  const obj = setObjectPrototype({ name: 'obj' }, base) as typeof base
+  t.is(getObjectPrototype(obj), base)
+  t.is(obj.greet(), 'hello')
+  t.false(Object.hasOwn(obj, 'greet'))
+  t.is(getObjectPrototype(setObjectPrototype(obj)), null)
+  t.is((obj as any).greet, undefined)
+  t.throws(() => setObjectPrototype(Object.preventExtensions({}), base), {
+    instanceOf: TypeError,
+  })
+})
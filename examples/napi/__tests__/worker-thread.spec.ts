@@ -4,7 +4,14 @@ import { Worker } from 'node:worker_threads'
 
 import test from 'ava'
 
-const { Animal, Kind, DEFAULT_COST } = (await import('../index.js')).default
+const {
+  Animal,
+  Kind,
+  DEFAULT_COST,
+  getAnimalClassFinalizedCount,
+  callSharedCallback,
+  clearSharedCallback,
+} = (await import('../index.js')).default
 
 const __dirname = join(fileURLToPath(import.meta.url), '..')
 
@@ -116,3 +123,65 @@ t('should be able to new Class in worker thread concurrently', async (t) => {
     }),
   )
 })
+
+t('class finalizer runs when worker thread exits', async (t) => {
+  const finalizedCount = getAnimalClassFinalizedCount()
+  const w = new Worker(join(__dirname, 'worker.cjs'), {
+    execArgv: ['--experimental-wasi-unstable-preview1'],
+    env: process.env,
+  })
+  await new Promise<void>((resolve, reject) => {
+    w.postMessage({ type: 'require' })
+    w.on('message', () => {
+      resolve()
+    })
+    w.on('error', (err) => {
+      reject(err)
+    })
+  })
+  await w.terminate()
+  t.true(getAnimalClassFinalizedCount() > finalizedCount)
+})
+
+t('should not call a FunctionRef after its worker thread exits', async (t) => {
+  const w = new Worker(join(__dirname, 'worker.cjs'), {
+    execArgv: ['--experimental-wasi-unstable-preview1'],
+    env: process.env,
+  })
+  await new Promise<void>((resolve, reject) => {
+    w.postMessage({ type: 'shared-callback' })
+    w.on('message', (msg) => {
+      t.is(msg, 2)
+      resolve()
+    })
+    w.on('error', (err) => {
+      reject(err)
+    })
+  })
+  await w.terminate()
+  t.throws(() => callSharedCallback(1), {
+    code: 'Closing',
+    message: 'The env of the FunctionRef has been torn down',
+  })
+  t.notThrows(() => clearSharedCallback())
+})
+
+t('should cache values again in a new worker thread', async (t) => {
+  for (let i = 0; i < 3; i++) {
+    const w = new Worker(join(__dirname, 'worker.cjs'), {
+      execArgv: ['--experimental-wasi-unstable-preview1'],
+      env: process.env,
+    })
+    await new Promise<void>((resolve, reject) => {
+      w.postMessage({ type: 'env-cache' })
+      w.on('message', (msg) => {
+        t.deepEqual(msg, [3, 1])
+        resolve()
+      })
+      w.on('error', (err) => {
+        reject(err)
+      })
+    })
+    await w.terminate()
+  }
+})
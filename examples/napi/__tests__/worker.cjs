@@ -46,6 +46,16 @@ import('../index.js')
           }
           parentPort.postMessage(ellie.name)
           break
+        case 'shared-callback':
+          native.storeSharedCallback((n) => n + 1)
+          parentPort.postMessage(native.callSharedCallback(1))
+          break
+        case 'env-cache':
+          parentPort.postMessage([
+            native.compileFunction('return a + b', ['a', 'b'])(1, 2),
+            new native.Visitor('Alice').visit('Paris'),
+          ])
+          break
         default:
           throw new TypeError(`Unknown message type: ${type}`)
       }
@@ -1,6 +1,8 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
 
+import type { Readable } from 'node:stream'
+
 
 export class ExternalObject<T> {
   readonly '': {
@@ -97,6 +99,13 @@ export class CatchOnConstructor2 {
   constructor()
 }
 
+export class Celsius {
+  degrees: number
+  constructor(degrees: number)
+  /** Also converts the instances in `String(celsius)` and template literals */
+  format(): string
+}
+
 export class ClassWithFactory {
   name: string
   static withName(name: string): ClassWithFactory
@@ -114,6 +123,12 @@ export class Context {
   method(): string
 }
 
+/** Counts from `start` up to `end` and finishes with the sum of the counted numbers. */
+export class CountUp {
+  [Symbol.asyncIterator](): AsyncGenerator<number, number, number>
+  constructor(start: number, end: number)
+}
+
 export class CssRuleList {
   getRules(): Array<string>
   get parentStyleSheet(): CSSStyleSheet
@@ -132,6 +147,13 @@ export class CustomFinalize {
   constructor(width: number, height: number)
 }
 
+/** Calls `callback` after `delay` milliseconds, unless it's cancelled before */
+export class DelayedCall {
+  constructor(delay: number, callback: () => void)
+  /** `false` if it was already cancelled */
+  cancel(): boolean
+}
+
 export class Dog {
   name: string
   constructor(name: string)
@@ -154,6 +176,11 @@ export class Fib3 {
   [Symbol.iterator](): Iterator<number, void, number>
 }
 
+/** Left out by the export filter in a test, which fails loading the module as `createFilteredClass` returns it */
+export class FilteredClass {
+
+}
+
 export class GetterSetterWithClosures {
   constructor()
 }
@@ -162,6 +189,12 @@ export class JsClassForEither {
   constructor()
 }
 
+/** Extends `Plugin` of the module `plugin-host` if `NAPI_RS_TEST_PARENT_MODULE` is set when the module is loaded */
+export class JsonPlugin {
+  constructor()
+  name(): string
+}
+
 export class JsRemote {
   name(): string
 }
@@ -171,6 +204,26 @@ export class JsRepo {
   remote(): JsRemote
 }
 
+export class LazyReport {
+  title: string
+}
+
+/** Defined when it's first accessed instead of when the module is loaded */
+export class LazyThumbnail {
+  width: number
+  height: number
+  constructor(width: number, height: number)
+  area(): number
+}
+
+/** Collects words until it is consumed by `build` */
+export class MessageBuilder {
+  constructor()
+  push(word: string): void
+  /** Takes the words out of the builder, later calls on it throw */
+  build(): string
+}
+
 export class NinjaTurtle {
   name: string
   static isInstanceOf(value: unknown): boolean
@@ -187,6 +240,11 @@ export class NotWritableClass {
   setName(name: string): void
 }
 
+/** Opaque native handle, only usable by passing it back to Rust */
+export class OpaqueHandle {
+  private constructor()
+}
+
 export class Optional {
   static optionEnd(required: string, optional?: string | undefined | null): string
   static optionStart(optional: string | undefined | null, required: string): string
@@ -194,6 +252,23 @@ export class Optional {
   static optionOnly(optional?: string | undefined | null): string
 }
 
+/** Only created by `openPooledConnection`, `new PooledConnection()` throws */
+export class PooledConnection {
+  url: string
+  poolSize: number
+  private constructor()
+  describe(): string
+}
+
+export class PropertyOrder {
+  zeta: number
+  alpha: number
+  constructor(zeta: number, alpha: number)
+  mu(): number
+  beta(): number
+  get gamma(): number
+}
+
 export class Selector {
   orderBy: Array<string>
   select: Array<string>
@@ -202,6 +277,23 @@ export class Selector {
   constructor(orderBy: Array<string>, select: Array<string>, struct: string, where?: string)
 }
 
+/** Keeps its value in an external on the instance, so a copy of its properties still carries it */
+export class Session {
+  constructor(token: string)
+  static anonymous(): Session
+  request(): number
+  get token(): string
+}
+
+export class Visitor {
+  name: string
+  constructor(name: string)
+  visit(this: this, place: string): number
+  /** `null` before the first visit */
+  visited(this: this): Array<string> | null
+  forgetVisits(this: this): void
+}
+
 export class Width {
   value: number
   constructor(value: number)
@@ -219,6 +311,10 @@ export function acceptThreadsafeFunctionTupleArgs(func: (err: Error | null, arg0
 
 export function add(a: number, b: number): number
 
+export function addCents(a: number, b: number): number
+
+export function advanceJob(job: JobState): JobState
+
 export const enum ALIAS {
   A = 0,
   B = 1
@@ -250,10 +346,16 @@ export function asyncPlus100(p: Promise<number>): Promise<number>
 
 export function asyncReduceBuffer(buf: Buffer): Promise<number>
 
+/** Resolves with `undefined`, or rejects if `fail` is true */
+export function asyncResultUnit(fail: boolean): Promise<void>
+
 export function asyncTaskOptionalReturn(): Promise<number | null>
 
 export function asyncTaskVoidReturn(): Promise<void>
 
+/** Resolves with `undefined` once the work is done */
+export function asyncUnit(): Promise<void>
+
 export interface B {
   bar: number
 }
@@ -268,6 +370,9 @@ export function bigintGetU64AsString(bi: bigint): string
 
 export function bufferPassThrough(buf: Buffer): Promise<Buffer>
 
+/** Parsed once at load and exported deeply frozen */
+export const BUILD_CONFIG: { version: string, features: Record<string, boolean>, targets: string[] }
+
 export interface C {
   baz: number
 }
@@ -282,6 +387,14 @@ export function callbackReturnPromise<T>(functionInput: () => T | Promise<T>, ca
 
 export function callbackReturnPromiseAndSpawn(jsFunc: (arg0: string) => Promise<string>): Promise<string>
 
+/** Describes what `callback` returned or threw when it's called with `value` */
+export function callCatching(callback: (...args: any[]) => any, value: number): string
+
+/** Calls `callback`, the exception it throws is thrown again as is */
+export function callCatchingRethrow(callback: (...args: any[]) => any): void
+
+export function callCompiledFunction(values: Array<number>): Array<number>
+
 export function callFunction(cb: () => number): number
 
 export function callFunctionWithArg(cb: (arg0: number, arg1: number) => number, arg0: number, arg1: number): number
@@ -290,10 +403,17 @@ export function callFunctionWithArgAndCtx(ctx: Animal, cb: (arg: string) => void
 
 export function callLongThreadsafeFunction(callback: (...args: any[]) => any): void
 
+export function callSharedCallback(arg: number): number
+
+export function callStoredCallback(arg: number): number
+
 export function callThreadsafeFunction(callback: (...args: any[]) => any): void
 
 export function captureErrorInCallback(cb1: () => void, cb2: (arg0: Error) => void): void
 
+/** Returns itself, so it can be called again */
+export function chain(): (...args: any[]) => any
+
 export function chronoDateAdd1Minute(input: Date): Date
 
 export function chronoDateToMillis(input: Date): number
@@ -302,38 +422,88 @@ export function chronoNativeDateTime(date: Date): number
 
 export function chronoNativeDateTimeReturn(): Date | null
 
+export function classifyThrownError(callback: (...args: any[]) => any): string
+
+export function clearSharedCallback(): void
+
+export function clearStoredCallback(): void
+
+export function compileFunction(source: string, paramNames: Array<string>): (...args: any[]) => any
+
 export function concatLatin1(s: string): string
 
 export function concatStr(s: string): string
 
 export function concatUtf16(s: string): string
 
+/** Fails with a transient error before the third attempt */
+export function connectFlaky(attempt: number): string
+
 export function contains(source: string, target: string): boolean
 
 export function convertU32Array(input: Uint32Array): Array<number>
 
+export function countLazyConfigPlugins(config: LazyConfig): number
+
+export function countLevels(levels: Array<Level>): Record<Level, number>
+
+export function countLoneSurrogates(s: string): number
+
 export function createBigInt(): bigint
 
 export function createBigIntI64(): bigint
 
+export function createCursor(): Cursor
+
+/** Create a `DataView` over `length` bytes of `buf` from `byte_offset` */
+export function createDataView(buf: ArrayBuffer, byteOffset: number, length: number): DataView
+
+/** Returns an async function resolving with `base + value` after `delay` milliseconds */
+export function createDelayedAdder(base: number, delay: number): (value: number) => Promise<number>
+
 export function createExternal(size: number): ExternalObject<number>
 
 export function createExternalString(content: string): ExternalObject<string>
 
 export function createExternalTypedArray(): Uint32Array
 
+export function createFilteredClass(): FilteredClass
+
+/** Returns an instance of `LazyReport`, which defines the class if it's not accessed yet */
+export function createLazyReport(title: string): LazyReport
+
 export function createObj(): object
 
 export function createObjectWithClassField(): ObjectFieldClassInstance
 
+export function createObjectWithPrototype(proto: object): object
+
+export function createObjWithNamedProperty(): { get getterFromObj(): number }
+
 export function createObjWithProperty(): { value: ArrayBuffer, get getter(): number }
 
+export function createOpaqueHandle(id: number): OpaqueHandle
+
 export function createReferenceOnFunction(cb: () => void): object
 
+export function createSharedResource(id: number): ExternalObject<SharedResource>
+
 export function createSymbol(): symbol
 
 export function createSymbolFor(desc: string): symbol
 
+/** Yields `0, 1, 2, ...` sent from a background thread until the iteration is stopped. */
+export function createTicker(): AsyncIterable<number>
+
+/** Yields `0, 1, 2, ...` as a Web `ReadableStream` until the stream is cancelled. */
+export function createWebTicker(): ReadableStream<number>
+
+/** Sealed: the fields can be changed, but no property can be added or removed */
+export interface Cursor {
+  line: number
+  column: number
+}
+
 /** You could break the step and for an new continuous value. */
 export const enum CustomNumEnum {
   One = 1,
@@ -358,8 +528,27 @@ export function dateToNumber(input: Date): number
 /** This is a const */
 export const DEFAULT_COST: number
 
+/** Parsed once at load, the parsed object can be changed */
+export const DEFAULT_OPTIONS: { retries: number }
+
 export function derefUint8Array(a: Uint8Array, b: Uint8ClampedArray): number
 
+export function describeSession(session: Session): string
+
+/** Only exists in the type definitions, consumers inline the numbers. */
+export const enum Direction {
+  Up = 0,
+  Down = 1
+}
+
+/** Calls back `(null, quotient)`, or `(err)` for a division by zero */
+export function divideWithCallback(a: number, b: number, callback: (err: Error | null, result: number) => void): void
+
+export function durationAddNanos(duration: number, nanos: number): number
+
+/** Ids beyond `Number.MAX_SAFE_INTEGER` throw a `RangeError` instead of being rounded */
+export function echoId(id: number): number
+
 export function either3(input: string | number | boolean): number
 
 export function either4(input: string | number | boolean | Obj): number
@@ -378,14 +567,38 @@ export const enum Empty {
 
 export function enumToI32(e: CustomNumEnum): number
 
+export function failJob(job: JobState, reason: string): JobState
+
+/** Only exported if `NAPI_RS_TEST_FEATURE_GATE` is truthy when the module is loaded */
+export function featureGatedExport(): string
+
 export function fibonacci(n: number): number
 
+export function flipDirection(direction: Direction): Direction
+
 export function fnReceivedAliased(s: AliasedStruct, e: ALIAS): void
 
+export function fromCents(cents: number): number
+
+export function getAnimalClassFinalizedCount(): number
+
+export function getAnswerReads(): number
+
+export function getAsyncUnitCalls(): number
+
 export function getBuffer(): Buffer
 
+export function getClassPropertyNames(rustName: string): Array<string> | null
+
 export function getCwd(callback: (arg0: string) => void): void
 
+export function getDroppedOpaqueHandleCount(): number
+
+export function getDroppedSharedResourceCount(): number
+
+/** Doc comment of an export registered at runtime */
+export function getDynamicExportDoc(name: string, jsMod?: string | undefined | null): string | null
+
 export function getEmptyBuffer(): Buffer
 
 export function getExternal(external: ExternalObject<number>): number
@@ -396,6 +609,8 @@ export function getMapping(): Record<string, number>
 
 export function getModuleFileName(): string
 
+export function getModuleInitCount(): number
+
 export function getNestedNumArr(): number[][][]
 
 export function getNull(): null
@@ -405,8 +620,20 @@ export function getNumArr(): number[]
 /** Gets some numbers */
 export function getNums(): Array<number>
 
+export function getObjectPrototype(obj: object): unknown
+
+export function getOpaqueHandleId(handle: OpaqueHandle): number
+
 export function getPackageJsonName(packageJson: PackageJson): string
 
+export function getSharedResourceId(handle: ExternalObject<SharedResource>): number
+
+/** The references to the resource, including the one held during this call */
+export function getSharedResourceStrongCount(handle: ExternalObject<SharedResource>): number
+
+/** Squares of `0..len`, set at once into an array created with its final length */
+export function getSquares(len: number): number[]
+
 export function getStrFromObject(): void
 
 export function getterFromObj(): number
@@ -415,6 +642,46 @@ export function getUndefined(): void
 
 export function getWords(): Array<string>
 
+/** The module itself if `NAPI_RS_TEST_MODULE_MAIN` is set, every other export is a property of it */
+export default function greet(name: string): string
+
+export function hasUvEventLoop(): boolean
+
+/** Throws a `RangeError` for `NaN` and `Infinity` */
+export function inspectNumber(value: number): NumberInfo
+
+/** Swap the keys and the values of a `Map` with numeric keys */
+export function invertMap(map: Map<number, string>): Map<string, number>
+
+export function isInstanceOfClass(value: unknown, className: string): boolean
+
+export function isModuleInitBeforeExports(): boolean
+
+/** Whether `stream` has a `read` method */
+export function isReadableStream(stream: Readable | import('node:stream').Duplex): boolean
+
+export function isTickerStopped(): boolean
+
+export function isWebTickerDropped(): boolean
+
+export interface JobProgress {
+  done: number
+  total: number
+}
+
+/** A state machine, converted from and to an object with the state in the `state` field */
+export type JobState = { state: 'queued' } | { state: 'running'; pid: number; progress: JobProgress } | { state: 'failed'; reason: string; exitCode?: number } | { state: 'done'; outputPath: string }
+
+/** Collects any number of arguments after `label` */
+export function joinNumbers(label: string, ...values: number[]): string
+
+/** Replace every number with the kind of `serde_json::Number` it's converted into */
+export function jsonNumberKinds(value: any): any
+
+export function jsonValueDepth(value: any): number
+
+export function jsStringUtf8Len(s: string): number
+
 /** default enum values are continuos i32s start from 0 */
 export const enum Kind {
   /** Barks */
@@ -425,14 +692,58 @@ export const enum Kind {
   Duck = 2
 }
 
+export interface LazyConfig {
+  name: string
+  plugins: Array<string>
+  maxConnections: number
+  theme: string
+}
+
+/** Only exists in the type definitions as a union of the values. */
+export type Level = 'low-priority' | 'high-priority' | 'URGENT'
+
+export interface Limits {
+  maxConnections: number
+  compression: boolean
+  retry: RetryPolicy
+}
+
+/** Exported deeply frozen */
+export const LIMITS: Limits
+
 export function listObjKeys(obj: object): Array<string>
 
+export function loadLazyConfig(name: string): LazyConfig
+
+export function lookupLengths(keys: Array<string>): Record<string, number>
+
+export function looseEquals(a: unknown, b: unknown): boolean
+
 export function mapOption(val?: number | undefined | null): number | null
 
 export function mutateExternal(external: ExternalObject<number>, newVal: number): void
 
 export function mutateTypedArray(input: Float32Array): void
 
+export interface NestedObject {
+  child?: Array<NestedObject>
+}
+
+export function nestedObjectDepth(obj: NestedObject): number
+
+export function newAnimalAndCall(kind: number, name: string, method: string): string
+
+export function newClassInstance(className: string, args: Array<unknown>): object
+
+export interface NumberInfo {
+  int32: number
+  uint32: number
+  int64: number
+  int64Lossless: boolean
+  double: number
+  isSafeInteger: boolean
+}
+
 export interface Obj {
   v: string | number
 }
@@ -446,14 +757,25 @@ export interface ObjectOnlyFromJs {
   callback: (err: Error | null, arg: number) => any
 }
 
+export function openPooledConnection(url: string): PooledConnection
+
+export function openSession(token: string): Session
+
+export function optionBufferLen(buf?: Buffer | undefined | null): number | null
+
 export function optionEnd(callback: (arg0: string, arg1?: string | undefined | null) => void): void
 
 export function optionOnly(callback: (arg0?: string | undefined | null) => void): void
 
+/** Where `key` of the options is set: `own`, `inherited` from the prototype or `missing` */
+export function optionSource(options: object, key: string): string
+
 export function optionStart(callback: (arg0: string | undefined | null, arg1: string) => void): void
 
 export function optionStartEnd(callback: (arg0: string | undefined | null, arg1: string, arg2?: string | undefined | null) => void): void
 
+export function optionUint8ArrayLen(input?: Uint8Array | undefined | null): number | null
+
 export function overrideIndividualArgOnFunction(notOverridden: string, f: () => string, notOverridden2: number): string
 
 export function overrideIndividualArgOnFunctionWithCbArg(callback: (town: string, name?: string | undefined | null) => string, notOverridden: number): object
@@ -467,17 +789,38 @@ export interface PackageJson {
   devDependencies?: Record<string, any>
 }
 
+export interface PacketHeader {
+  /** Big-endian `u16` at the start of the view */
+  tag: number
+  /** Little-endian `u32` following the tag */
+  payload: number
+  byteOffset: number
+  byteLength: number
+}
+
 export function panic(): void
 
+/** Parse a header with mixed endianness from the bytes of `view` */
+export function parsePacketHeader(view: DataView): PacketHeader
+
 export function plusOne(this: Width): number
 
+export function preciseDurationAddNanos(duration: { secs: number, nanos: number }, nanos: number): { secs: number, nanos: number }
+
 export function promiseInEither(input: number | Promise<number>): Promise<boolean>
 
+export function raiseLevel(level: Level): Level
+
+/** Yields the `values` as a Web `ReadableStream`, and errors instead of yielding `fail_at` if it's given. */
+export function readableStreamStrings(values: Array<string>, failAt?: string | undefined | null): ReadableStream<string>
+
 /** napi = { version = 2, features = ["serde-json"] } */
 export function readFile(callback: (arg0: Error | undefined, arg1?: string | undefined | null) => void): void
 
 export function readFileAsync(path: string): Promise<Buffer>
 
+export function readJsString(s: string, lossy: boolean): string
+
 export function readPackageJson(): PackageJson
 
 export function receiveAllOptionalObject(obj?: AllOptionalObject | undefined | null): void
@@ -498,6 +841,20 @@ export function receiveString(s: string): string
 
 export function referenceAsCallback(callback: (arg0: number, arg1: number) => number, arg0: number, arg1: number): number
 
+export function replaceLoneSurrogates(s: string): string
+
+export function replayStoredCallback(args: Array<number>): Array<number>
+
+/** Throws the exact value thrown by `callback`, returns `false` if it doesn't throw */
+export function rethrowCallbackException(callback: (...args: any[]) => any): boolean
+
+export interface RetryPolicy {
+  attempts: number
+  backoffMs: number
+}
+
+export function returnCowStr(borrowed: boolean): string
+
 export function returnEither(input: number): string | number
 
 export function returnEitherClass(input: number): number | JsClassForEither
@@ -508,36 +865,111 @@ export function returnJsFunction(): (...args: any[]) => any
 
 export function returnNull(): null
 
+export function returnSharedStr(s: string): string
+
 export function returnUndefined(): void
 
 export function returnUndefinedIfInvalid(input: boolean): boolean
 
 export function returnUndefinedIfInvalidPromise(input: Promise<boolean>): Promise<boolean>
 
+export function reverseBytes(input: Uint8Array): Uint8Array
+
+export function roundtripRawUtf16(s: string): string
+
 export function roundtripStr(s: string): string
 
 export function runScript(script: string): unknown
 
+export const enum ScaledRounding {
+  HalfAwayFromZero = 'HalfAwayFromZero',
+  HalfEven = 'HalfEven',
+  Floor = 'Floor',
+  Ceil = 'Ceil',
+  TowardZero = 'TowardZero'
+}
+
+/** Write `input * factor` into `output` in place, returning the count written */
+export function scaleInto(input: Float64Array, factor: number, output: Float64Array): number
+
+export function scaleMapValues(map: Map<number, number>, factor: number): Map<number, number>
+
+export function serdeCompatFromJson(json: string): SerdeCompatPackage
+
+export interface SerdeCompatMeta {
+  license: string
+}
+
+export interface SerdeCompatPackage extends SerdeCompatMeta {
+  package_name: string
+}
+
+export function serdeCompatToJson(pkg: SerdeCompatPackage): string
+
+export function setMaxObjectNestingDepth(depth: number): number
+
+/** Overwrite the elements of `arr` from `start` on with `items`, growing it if needed */
+export function setNumbersFrom(arr: number[], start: number, items: number[]): number
+
+/** Sets the prototype of `obj` to `proto`, or to `null` if it's missing */
+export function setObjectPrototype(obj: object, proto?: object | undefined | null): object
+
+export function setStrictNumbersMode(enabled: boolean): boolean
+
 export function setSymbolInObj(symbol: symbol): object
 
 export interface Shared {
   value: number
 }
 
+/** Only declares the type of the `ExternalObject`s it's shared by, see `createSharedResource` */
+export interface SharedResource {
+  id: number
+}
+
+/** Another JavaScript handle to the same resource */
+export function shareResource(handle: ExternalObject<SharedResource>): ExternalObject<SharedResource>
+
+export interface SignUpForm {
+  email: string
+  age: number
+}
+
 export const enum Status {
   Pristine = 'Pristine',
   Loading = 'Loading',
   Ready = 'Ready'
 }
 
+export function storeCallback(callback: (arg: number) => number): void
+
+export function storeSharedCallback(callback: (arg: number) => number): void
+
+/** Yields the `values` from a background thread and finishes after the last one. */
+export function streamStrings(values: Array<string>): AsyncIterable<string>
+
+export function strictAddI32(a: number, b: number): number
+
+export function strictEquals(a: unknown, b: unknown): boolean
+
 export interface StrictObject {
   name: string
 }
 
+export function sumKindCounts(counts: Record<Kind, number>): number
+
 export function sumMapping(nums: Record<string, number>): number
 
 export function sumNums(nums: Array<number>): number
 
+export function systemTimeAddNanos(time: Date, nanos: number): Date
+
+/** Names of the lazy fields evaluated since the last call */
+export function takeEvaluatedLazyFields(): Array<string>
+
+/** Remove the own `flag` property of the options, returning whether it was set */
+export function takeOptionFlag(options: object, flag: symbol): boolean
+
 export function testSerdeBigNumberPrecision(number: string): any
 
 export function testSerdeRoundtrip(data: any): any
@@ -554,8 +986,15 @@ export function throwAsyncError(): Promise<void>
 
 export function throwError(): void
 
+export function throwErrorWithCause(): void
+
 export function throwSyntaxError(error: string, code?: string | undefined | null): void
 
+/** Throws `value` as is, the returned error is not thrown over it */
+export function throwValue(value: unknown): void
+
+export function toCents(value: number, rounding: ScaledRounding): number
+
 export function toJsObj(): object
 
 export function tsfnAsyncCall(func: (...args: any[]) => any): Promise<void>
@@ -601,6 +1040,12 @@ export function validateOptional(input1?: string | undefined | null, input2?: bo
 
 export function validatePromise(p: Promise<number>): Promise<number>
 
+/** Throws an `AggregateError` with an error for every invalid option */
+export function validateServerOptions(port: number, host: string): void
+
+/** Throws a `ValidationError` with an entry in `fields` for every invalid field */
+export function validateSignUp(form: SignUpForm): string
+
 export function validateString(s: string): string
 
 export function validateSymbol(s: symbol): boolean
@@ -609,6 +1054,9 @@ export function validateTypedArray(input: Uint8Array): number
 
 export function validateUndefined(i: undefined): boolean
 
+/** Names of the `is_*` type guards the value passes */
+export function valueKinds(value: unknown): Array<string>
+
 export function withAbortController(a: number, b: number, signal: AbortSignal): Promise<number>
 
 export function withoutAbortController(a: number, b: number): Promise<number>
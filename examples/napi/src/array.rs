@@ -1,4 +1,4 @@
-use napi::{Env, JsObject};
+use napi::{bindgen_prelude::Array, Env, JsObject};
 
 #[napi]
 pub fn get_words() -> Vec<&'static str> {
@@ -33,3 +33,18 @@ fn get_num_arr() -> [u32; 2] {
 fn get_nested_num_arr() -> [[[u32; 1]; 1]; 2] {
   [[[1]], [[1]]]
 }
+
+/// Squares of `0..len`, set at once into an array created with its final length
+#[napi(ts_return_type = "number[]")]
+fn get_squares(env: Env, len: u32) -> napi::Result<Array> {
+  let mut arr = env.create_array(len)?;
+  arr.set_elements(0, (0..len).map(|i| i * i))?;
+  Ok(arr)
+}
+
+/// Overwrite the elements of `arr` from `start` on with `items`, growing it if needed
+#[napi(ts_args_type = "arr: number[], start: number, items: number[]")]
+fn set_numbers_from(mut arr: Array, start: u32, items: Vec<u32>) -> napi::Result<u32> {
+  arr.set_elements(start, items)?;
+  Ok(arr.len())
+}
@@ -4,6 +4,7 @@ use napi::bindgen_prelude::*;
 use napi::tokio;
 #[cfg(not(target_family = "wasm"))]
 use napi::tokio::fs;
+use napi::{JsFunction, JsNumber};
 
 #[napi]
 async fn read_file_async(path: String) -> Result<Buffer> {
@@ -32,3 +33,43 @@ async fn async_multi_two(arg: u32) -> Result<u32> {
     .await
     .unwrap()
 }
+
+static ASYNC_UNIT_CALLS: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+/// Resolves with `undefined` once the work is done
+#[napi]
+async fn async_unit() {
+  tokio::task::yield_now().await;
+  ASYNC_UNIT_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Resolves with `undefined`, or rejects if `fail` is true
+#[napi]
+async fn async_result_unit(fail: bool) -> Result<()> {
+  tokio::task::yield_now().await;
+  if fail {
+    return Err(Error::new(
+      Status::GenericFailure,
+      "Async unit failed".to_owned(),
+    ));
+  }
+  ASYNC_UNIT_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+  Ok(())
+}
+
+#[napi]
+fn get_async_unit_calls() -> u32 {
+  ASYNC_UNIT_CALLS.load(std::sync::atomic::Ordering::SeqCst)
+}
+
+/// Returns an async function resolving with `base + value` after `delay` milliseconds
+#[napi(ts_return_type = "(value: number) => Promise<number>")]
+fn create_delayed_adder(env: Env, base: u32, delay: u32) -> Result<JsFunction> {
+  env.create_async_function("delayedAdd", move |ctx| {
+    let value = ctx.get::<JsNumber>(0)?.get_uint32()?;
+    Ok(async move {
+      tokio::time::sleep(std::time::Duration::from_millis(delay as u64)).await;
+      Ok(base + value)
+    })
+  })
+}
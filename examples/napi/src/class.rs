@@ -1,6 +1,8 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
 use napi::{
   bindgen_prelude::{Buffer, ClassInstance, ObjectFinalize, This, Uint8Array, Unknown},
-  Env, Property, Result,
+  Env, JsObject, JsUnknown, Property, Result,
 };
 
 use crate::r#enum::Kind;
@@ -8,7 +10,7 @@ use crate::r#enum::Kind;
 /// `constructor` option for `struct` requires all fields to be public,
 /// otherwise tag impl fn as constructor
 /// #[napi(constructor)]
-#[napi]
+#[napi(class_finalizer = "finalize_animal_class")]
 pub struct Animal {
   #[napi(readonly)]
   /// Kind of animal
@@ -413,6 +415,138 @@ pub fn plus_one(this: This<&Width>) -> i32 {
   this.value + 1
 }
 
+/// Bookkeeping kept out of the `Visitor` struct, attached with `Env::wrap_private_data`
+struct VisitLog {
+  visits: Vec<String>,
+}
+
+#[napi(constructor)]
+pub struct Visitor {
+  pub name: String,
+}
+
+#[napi]
+impl Visitor {
+  #[napi]
+  pub fn visit(&self, env: Env, mut this: This, place: String) -> Result<u32> {
+    if env.unwrap_private_data::<VisitLog>(&this)?.is_none() {
+      env.wrap_private_data(&mut this, VisitLog { visits: vec![] })?;
+    }
+    let log = env
+      .unwrap_private_data::<VisitLog>(&this)?
+      .expect("VisitLog is attached");
+    log.visits.push(place);
+    Ok(log.visits.len() as u32)
+  }
+
+  /// `null` before the first visit
+  #[napi]
+  pub fn visited(&self, env: Env, this: This) -> Result<Option<Vec<String>>> {
+    Ok(
+      env
+        .unwrap_private_data::<VisitLog>(&this)?
+        .map(|log| log.visits.clone()),
+    )
+  }
+
+  #[napi]
+  pub fn forget_visits(&self, env: Env, mut this: This) -> Result<()> {
+    env.wrap_private_data(&mut this, VisitLog { visits: vec![] })
+  }
+}
+
+/// Keeps its value in an external on the instance, so a copy of its properties still carries it
+#[napi(external_state)]
+pub struct Session {
+  token: String,
+  requests: u32,
+}
+
+#[napi]
+impl Session {
+  #[napi(constructor)]
+  pub fn new(token: String) -> Self {
+    Session { token, requests: 0 }
+  }
+
+  #[napi(factory)]
+  pub fn anonymous() -> Self {
+    Session::new("anonymous".to_owned())
+  }
+
+  #[napi]
+  pub fn request(&mut self) -> u32 {
+    self.requests += 1;
+    self.requests
+  }
+
+  #[napi(getter)]
+  pub fn token(&self) -> String {
+    self.token.clone()
+  }
+}
+
+#[napi]
+pub fn open_session(token: String) -> Session {
+  Session::new(token)
+}
+
+#[napi]
+pub fn describe_session(session: &Session) -> String {
+  format!("{} made {} requests", session.token, session.requests)
+}
+
+/// Only created by `openPooledConnection`, `new PooledConnection()` throws
+#[napi(private_constructor)]
+pub struct PooledConnection {
+  pub url: String,
+  pub pool_size: u32,
+}
+
+#[napi]
+impl PooledConnection {
+  #[napi]
+  pub fn describe(&self) -> String {
+    format!("{} ({} connections)", self.url, self.pool_size)
+  }
+}
+
+#[napi]
+pub fn open_pooled_connection(url: String) -> PooledConnection {
+  PooledConnection { url, pool_size: 4 }
+}
+
+/// Defined when it's first accessed instead of when the module is loaded
+#[napi(lazy)]
+pub struct LazyThumbnail {
+  pub width: u32,
+  pub height: u32,
+}
+
+#[napi]
+impl LazyThumbnail {
+  #[napi(constructor)]
+  pub fn new(width: u32, height: u32) -> Self {
+    Self { width, height }
+  }
+
+  #[napi]
+  pub fn area(&self) -> u32 {
+    self.width * self.height
+  }
+}
+
+#[napi(lazy)]
+pub struct LazyReport {
+  pub title: String,
+}
+
+/// Returns an instance of `LazyReport`, which defines the class if it's not accessed yet
+#[napi]
+pub fn create_lazy_report(title: String) -> LazyReport {
+  LazyReport { title }
+}
+
 #[napi]
 pub struct GetterSetterWithClosures {}
 
@@ -454,3 +588,179 @@ impl CatchOnConstructor2 {
     panic!("CatchOnConstructor2 panic");
   }
 }
+
+#[napi(constructor)]
+pub struct PropertyOrder {
+  pub zeta: u32,
+  pub alpha: u32,
+}
+
+#[napi]
+impl PropertyOrder {
+  #[napi]
+  pub fn mu(&self) -> u32 {
+    self.zeta
+  }
+
+  #[napi]
+  pub fn beta(&self) -> u32 {
+    self.alpha
+  }
+}
+
+#[napi]
+impl PropertyOrder {
+  #[napi(getter)]
+  pub fn gamma(&self) -> u32 {
+    self.alpha + self.zeta
+  }
+}
+
+static OPAQUE_HANDLE_DROPPED: AtomicU32 = AtomicU32::new(0);
+
+/// Opaque native handle, only usable by passing it back to Rust
+#[napi(opaque)]
+pub struct OpaqueHandle {
+  id: u32,
+}
+
+impl Drop for OpaqueHandle {
+  fn drop(&mut self) {
+    OPAQUE_HANDLE_DROPPED.fetch_add(1, Ordering::SeqCst);
+  }
+}
+
+#[napi]
+pub fn create_opaque_handle(id: u32) -> OpaqueHandle {
+  OpaqueHandle { id }
+}
+
+#[napi]
+pub fn get_opaque_handle_id(handle: &OpaqueHandle) -> u32 {
+  handle.id
+}
+
+#[napi]
+pub fn get_dropped_opaque_handle_count() -> u32 {
+  OPAQUE_HANDLE_DROPPED.load(Ordering::SeqCst)
+}
+
+/// Left out by the export filter in a test, which fails loading the module as `createFilteredClass` returns it
+#[napi]
+pub struct FilteredClass {}
+
+#[napi]
+pub fn create_filtered_class() -> FilteredClass {
+  FilteredClass {}
+}
+
+/// Fails loading the module, it's left out by the export filter unless `NAPI_RS_TEST_INVALID_CLASS_NAME` is set
+#[napi(constructor, js_name = "Invalid Class")]
+pub struct InvalidClassName {
+  pub value: u32,
+}
+
+/// Fails loading the module, it's left out by the export filter unless `NAPI_RS_TEST_INVALID_EXPORT_NAME` is set
+#[napi(js_name = "invalid-export")]
+pub fn invalid_export_name() -> u32 {
+  1
+}
+
+/// Only accessible with brackets, `exports['Weird Class']`
+#[napi(constructor, js_name = "Weird Class", any_js_name)]
+pub struct WeirdClassName {
+  pub value: u32,
+}
+
+#[napi(js_name = "weird-export", any_js_name)]
+pub fn weird_export() -> u32 {
+  42
+}
+
+#[napi]
+pub fn is_instance_of_class(env: Env, value: JsUnknown, class_name: String) -> Result<bool> {
+  value.instance_of(env, &class_name)
+}
+
+#[napi]
+pub fn new_animal_and_call(env: Env, kind: u32, name: String, method: String) -> Result<String> {
+  env.new_and_call("Animal", (kind, name), &method, ())
+}
+
+#[napi]
+pub fn new_class_instance(env: Env, class_name: String, args: Vec<Unknown>) -> Result<JsObject> {
+  env.new_instance(&class_name, &args)
+}
+
+#[napi]
+pub fn get_class_property_names(rust_name: String) -> Option<Vec<String>> {
+  napi::bindgen_prelude::class_property_names(&rust_name)
+}
+
+static ANIMAL_CLASS_FINALIZED: AtomicU32 = AtomicU32::new(0);
+
+fn finalize_animal_class() {
+  ANIMAL_CLASS_FINALIZED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[napi]
+pub fn get_animal_class_finalized_count() -> u32 {
+  ANIMAL_CLASS_FINALIZED.load(Ordering::SeqCst)
+}
+
+/// Collects words until it is consumed by `build`
+#[napi]
+pub struct MessageBuilder {
+  words: Vec<String>,
+}
+
+#[napi]
+impl MessageBuilder {
+  #[napi(constructor)]
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    MessageBuilder { words: vec![] }
+  }
+
+  #[napi]
+  pub fn push(&mut self, word: String) {
+    self.words.push(word);
+  }
+
+  /// Takes the words out of the builder, later calls on it throw
+  #[napi]
+  pub fn build(self) -> String {
+    self.words.join(" ")
+  }
+}
+
+#[napi(constructor)]
+pub struct Celsius {
+  pub degrees: f64,
+}
+
+#[napi]
+impl Celsius {
+  /// Also converts the instances in `String(celsius)` and template literals
+  #[napi(to_primitive)]
+  pub fn format(&self) -> String {
+    format!("{}°C", self.degrees)
+  }
+}
+
+/// Extends `Plugin` of the module `plugin-host` if `NAPI_RS_TEST_PARENT_MODULE` is set when the module is loaded
+#[napi]
+pub struct JsonPlugin {}
+
+#[napi]
+impl JsonPlugin {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    JsonPlugin {}
+  }
+
+  #[napi]
+  pub fn name(&self) -> String {
+    "json".to_owned()
+  }
+}
@@ -33,3 +33,23 @@ pub fn chrono_native_date_time(date: chrono::NaiveDateTime) -> i64 {
 pub fn chrono_native_date_time_return() -> Option<chrono::NaiveDateTime> {
   chrono::NaiveDateTime::from_str("2016-12-23T15:25:59.325").ok()
 }
+
+#[napi]
+pub fn duration_add_nanos(duration: std::time::Duration, nanos: u32) -> std::time::Duration {
+  duration + std::time::Duration::from_nanos(u64::from(nanos))
+}
+
+#[napi]
+pub fn precise_duration_add_nanos(duration: PreciseDuration, nanos: u32) -> PreciseDuration {
+  PreciseDuration(duration.0 + std::time::Duration::from_nanos(u64::from(nanos)))
+}
+
+#[napi]
+pub fn system_time_add_nanos(time: std::time::SystemTime, nanos: i64) -> std::time::SystemTime {
+  let delta = std::time::Duration::from_nanos(nanos.unsigned_abs());
+  if nanos < 0 {
+    time - delta
+  } else {
+    time + delta
+  }
+}
@@ -1,3 +1,5 @@
+use napi::bindgen_prelude::EnumMap;
+
 /// default enum values are continuos i32s start from 0
 #[napi]
 pub enum Kind {
@@ -19,6 +21,38 @@ pub enum Status {
   Ready,
 }
 
+/// Only exists in the type definitions, consumers inline the numbers.
+#[napi(const_enum)]
+pub enum Direction {
+  Up,
+  Down,
+}
+
+#[napi]
+fn flip_direction(direction: Direction) -> Direction {
+  match direction {
+    Direction::Up => Direction::Down,
+    Direction::Down => Direction::Up,
+  }
+}
+
+/// Only exists in the type definitions as a union of the values.
+#[napi(string_enum, ts_union, rename_all = "kebab-case")]
+pub enum Level {
+  LowPriority,
+  HighPriority,
+  #[napi(rename = "URGENT")]
+  Urgent,
+}
+
+#[napi]
+fn raise_level(level: Level) -> Level {
+  match level {
+    Level::LowPriority => Level::HighPriority,
+    Level::HighPriority | Level::Urgent => Level::Urgent,
+  }
+}
+
 /// You could break the step and for an new continuous value.
 #[napi]
 pub enum CustomNumEnum {
@@ -44,3 +78,75 @@ pub enum SkippedEnums {
   Two,
   Tree,
 }
+
+#[napi]
+fn count_levels(levels: Vec<Level>) -> EnumMap<Level, u32> {
+  let mut counts = EnumMap::default();
+  for level in levels {
+    counts[level] += 1;
+  }
+  counts
+}
+
+#[napi]
+fn sum_kind_counts(counts: EnumMap<Kind, u32>) -> u32 {
+  counts.iter().map(|(_, count)| count).sum()
+}
+
+#[napi(object)]
+pub struct JobProgress {
+  pub done: u32,
+  pub total: u32,
+}
+
+/// A state machine, converted from and to an object with the state in the `state` field
+#[napi(discriminant = "state", rename_all = "lowercase")]
+pub enum JobState {
+  Queued,
+  Running {
+    pid: u32,
+    progress: JobProgress,
+  },
+  Failed {
+    reason: String,
+    exit_code: Option<i32>,
+  },
+  Done {
+    output_path: String,
+  },
+}
+
+#[napi]
+fn advance_job(job: JobState) -> JobState {
+  match job {
+    JobState::Queued => JobState::Running {
+      pid: 1,
+      progress: JobProgress { done: 0, total: 2 },
+    },
+    JobState::Running { pid, progress } if progress.done < progress.total => JobState::Running {
+      pid,
+      progress: JobProgress {
+        done: progress.done + 1,
+        total: progress.total,
+      },
+    },
+    JobState::Running { pid, .. } => JobState::Done {
+      output_path: format!("/tmp/job-{}.out", pid),
+    },
+    done_or_failed => done_or_failed,
+  }
+}
+
+#[napi]
+fn fail_job(job: JobState, reason: String) -> JobState {
+  match job {
+    JobState::Running { .. } => JobState::Failed {
+      reason,
+      exit_code: Some(1),
+    },
+    _ => JobState::Failed {
+      reason,
+      exit_code: None,
+    },
+  }
+}
@@ -1,4 +1,4 @@
-use napi::{bindgen_prelude::*, Env};
+use napi::{bindgen_prelude::*, Env, JsFunction, JsObject, JsUnknown};
 
 #[napi]
 pub fn run_script(env: Env, script: String) -> Result<Unknown> {
@@ -14,3 +14,66 @@ pub fn get_module_file_name(env: Env) -> Result<String> {
 pub fn throw_syntax_error(env: Env, error: String, code: Option<String>) {
   env.throw_syntax_error(error, code);
 }
+
+#[napi]
+pub fn compile_function(env: Env, source: String, param_names: Vec<String>) -> Result<JsFunction> {
+  let param_names = param_names
+    .iter()
+    .map(|name| name.as_str())
+    .collect::<Vec<_>>();
+  env.compile_function(&source, &param_names)
+}
+
+#[napi]
+pub fn call_compiled_function(env: Env, values: Vec<u32>) -> Result<Vec<u32>> {
+  values
+    .into_iter()
+    .map(|value| {
+      let double = env.compile_function("return n * 2", &["n"])?;
+      double
+        .call(None, &[env.create_uint32(value)?])?
+        .coerce_to_number()?
+        .get_uint32()
+    })
+    .collect()
+}
+
+#[napi]
+pub fn create_object_with_prototype(env: Env, proto: JsObject) -> Result<JsObject> {
+  env.create_object_with_prototype(&proto)
+}
+
+#[napi]
+pub fn get_object_prototype(obj: JsObject) -> Result<JsUnknown> {
+  obj.get_prototype()
+}
+
+/// Sets the prototype of `obj` to `proto`, or to `null` if it's missing
+#[napi]
+pub fn set_object_prototype(
+  env: Env,
+  mut obj: JsObject,
+  proto: Option<JsObject>,
+) -> Result<JsObject> {
+  match proto {
+    Some(proto) => obj.set_prototype(proto)?,
+    None => obj.set_prototype(env.get_null()?)?,
+  }
+  Ok(obj)
+}
+
+#[napi]
+pub fn has_uv_event_loop(env: Env) -> Result<bool> {
+  let uv_loop = env.get_uv_event_loop()?;
+  Ok(!uv_loop.is_null() && uv_loop == env.get_uv_event_loop()?)
+}
+
+#[napi]
+pub fn strict_equals(env: Env, a: Unknown, b: Unknown) -> Result<bool> {
+  env.strict_equals(a, b)
+}
+
+#[napi]
+pub fn loose_equals(env: Env, a: Unknown, b: Unknown) -> Result<bool> {
+  env.loose_equals(a, b)
+}
@@ -38,3 +38,112 @@ pub fn custom_status_code() -> Result<(), CustomError> {
 pub async fn throw_async_error() -> Result<()> {
   Err(Error::new(Status::InvalidArg, "Async Error".to_owned()))
 }
+
+#[derive(Debug)]
+struct ConfigError {
+  source: std::io::Error,
+}
+
+impl std::fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Failed to load config")
+  }
+}
+
+impl std::error::Error for ConfigError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    Some(&self.source)
+  }
+}
+
+#[napi]
+pub fn throw_error_with_cause() -> Result<()> {
+  let err = ConfigError {
+    source: std::io::Error::new(std::io::ErrorKind::NotFound, "config.toml not found"),
+  };
+  Err(Error::from_error_chain(&err))
+}
+
+/// Fails with a transient error before the third attempt
+#[napi]
+pub fn connect_flaky(attempt: u32) -> Result<String> {
+  if attempt < 3 {
+    return Err(Error::transient(
+      format!("Connection refused on attempt {}", attempt),
+      attempt * 10,
+    ));
+  }
+  Ok("connected".to_owned())
+}
+
+#[napi]
+pub fn classify_thrown_error(callback: JsFunction) -> Result<String> {
+  match callback.call_without_args(None) {
+    Ok(_) => Ok("None".to_owned()),
+    Err(e) => Ok(format!("{:?}", e.classify())),
+  }
+}
+
+/// Throws `value` as is, the returned error is not thrown over it
+#[napi]
+pub fn throw_value(env: Env, value: Unknown) -> Result<()> {
+  env.throw(value)?;
+  Err(Error::new(Status::GenericFailure, "Not thrown".to_owned()))
+}
+
+/// Throws the exact value thrown by `callback`, returns `false` if it doesn't throw
+#[napi]
+pub fn rethrow_callback_exception(env: Env, callback: JsFunction) -> Result<bool> {
+  match callback.call_without_args(None) {
+    Ok(_) => Ok(false),
+    Err(e) => {
+      env.throw(JsError::from(e).into_unknown(env))?;
+      Ok(env.is_exception_pending()?)
+    }
+  }
+}
+
+#[napi(object)]
+pub struct SignUpForm {
+  pub email: String,
+  pub age: i32,
+}
+
+/// Throws a `ValidationError` with an entry in `fields` for every invalid field
+#[napi]
+pub fn validate_sign_up(form: SignUpForm) -> std::result::Result<String, Vec<FieldError>> {
+  let mut errors = Vec::new();
+  if !form.email.contains('@') {
+    errors.push(FieldError::new("email", "Must be an email address"));
+  }
+  if form.age < 18 {
+    errors.push(FieldError::new("age", "Must be at least 18"));
+  }
+  if errors.is_empty() {
+    Ok(form.email)
+  } else {
+    Err(errors)
+  }
+}
+
+/// Throws an `AggregateError` with an error for every invalid option
+#[napi]
+pub fn validate_server_options(env: Env, port: u32, host: String) -> Result<()> {
+  let mut errors = Vec::new();
+  if port == 0 {
+    errors.push(Error::new(
+      Status::InvalidArg,
+      "port must not be 0".to_owned(),
+    ));
+  }
+  if host.is_empty() {
+    errors.push(Error::new(
+      Status::InvalidArg,
+      "host must not be empty".to_owned(),
+    ));
+  }
+  if !errors.is_empty() {
+    env.throw_aggregate_error(errors, "Invalid server options")?;
+  }
+  Ok(())
+}
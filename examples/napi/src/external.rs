@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
 use napi::bindgen_prelude::*;
 
 #[napi]
@@ -19,3 +21,44 @@ pub fn get_external(external: External<u32>) -> u32 {
 pub fn mutate_external(mut external: External<u32>, new_val: u32) {
   *external = new_val;
 }
+
+static SHARED_RESOURCE_DROPPED: AtomicU32 = AtomicU32::new(0);
+
+/// Only declares the type of the `ExternalObject`s it's shared by, see `createSharedResource`
+#[napi(object, object_to_js = false)]
+pub struct SharedResource {
+  pub id: u32,
+}
+
+impl Drop for SharedResource {
+  fn drop(&mut self) {
+    SHARED_RESOURCE_DROPPED.fetch_add(1, Ordering::SeqCst);
+  }
+}
+
+#[napi]
+pub fn create_shared_resource(id: u32) -> RcHandle<SharedResource> {
+  RcHandle::new(SharedResource { id })
+}
+
+/// Another JavaScript handle to the same resource
+#[napi]
+pub fn share_resource(handle: RcHandle<SharedResource>) -> RcHandle<SharedResource> {
+  handle
+}
+
+#[napi]
+pub fn get_shared_resource_id(handle: RcHandle<SharedResource>) -> u32 {
+  handle.id
+}
+
+/// The references to the resource, including the one held during this call
+#[napi]
+pub fn get_shared_resource_strong_count(handle: RcHandle<SharedResource>) -> u32 {
+  RcHandle::strong_count(&handle) as u32
+}
+
+#[napi]
+pub fn get_dropped_shared_resource_count() -> u32 {
+  SHARED_RESOURCE_DROPPED.load(Ordering::SeqCst)
+}
@@ -1,4 +1,4 @@
-use napi::bindgen_prelude::{Object, Result};
+use napi::bindgen_prelude::{Object, Result, Unknown, ValueType};
 use napi::JsFunction;
 
 #[napi(ts_args_type = "a: { foo: number }", ts_return_type = "string[]")]
@@ -34,3 +34,15 @@ fn override_individual_arg_on_function_with_cb_arg<
 ) -> Result<Object> {
   callback(format!("World({})", not_overridden), None)
 }
+
+/// Whether `stream` has a `read` method
+#[napi(
+  ts_import = "import type { Readable } from 'node:stream'",
+  ts_args_type = "stream: Readable | import('node:stream').Duplex"
+)]
+fn is_readable_stream(stream: Object) -> Result<bool> {
+  match stream.get::<_, Unknown>("read")? {
+    Some(read) => Ok(read.get_type()? == ValueType::Function),
+    None => Ok(false),
+  }
+}
@@ -1,6 +1,10 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Mutex;
+
 use napi::{
-  bindgen_prelude::{ClassInstance, Function, FunctionRef},
-  Env, JsFunction, JsObject, Result,
+  bindgen_prelude::{ClassInstance, Function, FunctionRef, NodeCallback, Rest},
+  Env, Error, JsFunction, JsObject, JsUnknown, Result, Status, TimerHandle,
 };
 
 use crate::class::Animal;
@@ -20,6 +24,25 @@ pub fn call2(callback: JsFunction, arg1: u32, arg2: u32) -> Result<u32> {
   callback.call2(arg1, arg2)
 }
 
+/// Describes what `callback` returned or threw when it's called with `value`
+#[napi]
+pub fn call_catching(env: Env, callback: JsFunction, value: u32) -> Result<String> {
+  match callback.call_catch(&[env.create_uint32(value)?]) {
+    Ok(returned) => Ok(format!(
+      "returned {}",
+      returned.coerce_to_string()?.into_utf8()?.as_str()?
+    )),
+    Err(e) => Ok(format!("threw {}", e.reason)),
+  }
+}
+
+/// Calls `callback`, the exception it throws is thrown again as is
+#[napi]
+pub fn call_catching_rethrow(callback: JsFunction) -> Result<()> {
+  callback.call_catch::<JsUnknown>(&[])?;
+  Ok(())
+}
+
 #[napi]
 pub fn apply0(ctx: ClassInstance<Animal>, callback: JsFunction) -> Result<()> {
   callback.apply0(ctx)
@@ -74,3 +97,109 @@ pub fn reference_as_callback(
 ) -> Result<u32> {
   callback.borrow_back(&env)?.call((arg0, arg1))
 }
+
+thread_local! {
+  static STORED_CALLBACK: RefCell<Option<Rc<FunctionRef<u32, u32>>>> = const { RefCell::new(None) };
+}
+
+/// The stored callback, cloned out so the callback can store or clear it while it's called
+fn get_stored_callback() -> Result<Rc<FunctionRef<u32, u32>>> {
+  STORED_CALLBACK
+    .with(|stored| stored.borrow().clone())
+    .ok_or_else(|| Error::new(Status::InvalidArg, "No callback stored".to_owned()))
+}
+
+#[napi]
+pub fn store_callback(callback: FunctionRef<u32, u32>) {
+  STORED_CALLBACK.with(|stored| stored.replace(Some(Rc::new(callback))));
+}
+
+#[napi]
+pub fn call_stored_callback(env: Env, arg: u32) -> Result<u32> {
+  get_stored_callback()?.call(&env, arg)
+}
+
+#[napi]
+pub fn replay_stored_callback(env: Env, args: Vec<u32>) -> Result<Vec<u32>> {
+  get_stored_callback()?.call_many(&env, args)
+}
+
+#[napi]
+pub fn clear_stored_callback() {
+  STORED_CALLBACK.with(|stored| stored.take());
+}
+
+/// `FunctionRef` kept across envs, only used to call it after the env it was created in is torn down
+struct SharedCallback(FunctionRef<u32, u32>);
+
+unsafe impl Send for SharedCallback {}
+
+static SHARED_CALLBACK: Mutex<Option<SharedCallback>> = Mutex::new(None);
+
+#[napi]
+pub fn store_shared_callback(callback: FunctionRef<u32, u32>) {
+  *SHARED_CALLBACK.lock().unwrap() = Some(SharedCallback(callback));
+}
+
+#[napi]
+pub fn call_shared_callback(env: Env, arg: u32) -> Result<u32> {
+  match SHARED_CALLBACK.lock().unwrap().as_ref() {
+    Some(SharedCallback(callback)) => callback.call(&env, arg),
+    None => Err(Error::new(
+      Status::InvalidArg,
+      "No callback stored".to_owned(),
+    )),
+  }
+}
+
+#[napi]
+pub fn clear_shared_callback() {
+  SHARED_CALLBACK.lock().unwrap().take();
+}
+
+/// Collects any number of arguments after `label`
+#[napi]
+pub fn join_numbers(label: String, values: Rest<u32>) -> String {
+  let values = values
+    .iter()
+    .map(|v| v.to_string())
+    .collect::<Vec<_>>()
+    .join(",");
+  format!("{}: {}", label, values)
+}
+
+/// Calls back `(null, quotient)`, or `(err)` for a division by zero
+#[napi]
+pub fn divide_with_callback(a: u32, b: u32, callback: NodeCallback<u32>) -> Result<()> {
+  callback.call_result(
+    a.checked_div(b)
+      .ok_or_else(|| Error::new(Status::InvalidArg, "Division by zero".to_owned())),
+  )
+}
+
+/// Calls `callback` after `delay` milliseconds, unless it's cancelled before
+#[napi]
+pub struct DelayedCall {
+  timer: Option<TimerHandle>,
+}
+
+#[napi]
+impl DelayedCall {
+  #[napi(constructor)]
+  pub fn new(env: Env, delay: u32, callback: FunctionRef<(), ()>) -> Result<Self> {
+    let timer = env.set_timeout(delay, move |env| callback.call(&env, ()))?;
+    Ok(Self { timer: Some(timer) })
+  }
+
+  /// `false` if it was already cancelled
+  #[napi]
+  pub fn cancel(&mut self, env: Env) -> Result<bool> {
+    match self.timer.take() {
+      Some(timer) => {
+        env.clear_timeout(timer)?;
+        Ok(true)
+      }
+      None => Ok(false),
+    }
+  }
+}
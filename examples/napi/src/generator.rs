@@ -1,4 +1,6 @@
-use napi::bindgen_prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use napi::{bindgen_prelude::*, JsObject};
 
 #[napi(iterator)]
 pub struct Fib {
@@ -109,3 +111,135 @@ impl Generator for Fib3 {
     Some(self.current)
   }
 }
+
+/// Counts from `start` up to `end` and finishes with the sum of the counted numbers.
+#[napi(async_iterator)]
+pub struct CountUp {
+  current: u32,
+  end: u32,
+  sum: u32,
+}
+
+#[napi]
+impl AsyncGenerator for CountUp {
+  type Yield = u32;
+  type Next = u32;
+  type Return = u32;
+
+  fn next(
+    &mut self,
+    value: Option<Self::Next>,
+  ) -> Result<GeneratorStep<Self::Yield, Self::Return>> {
+    if let Some(n) = value {
+      self.current = n;
+    }
+    if self.current > self.end {
+      return Ok(GeneratorStep::Return(self.sum));
+    }
+    let current = self.current;
+    self.sum += current;
+    self.current += 1;
+    Ok(GeneratorStep::Yield(current))
+  }
+}
+
+#[napi]
+impl CountUp {
+  #[napi(constructor)]
+  pub fn new(start: u32, end: u32) -> Result<Self> {
+    if start > end {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("start {} is greater than end {}", start, end),
+      ));
+    }
+    Ok(CountUp {
+      current: start,
+      end,
+      sum: 0,
+    })
+  }
+}
+
+static TICKER_STOPPED: AtomicBool = AtomicBool::new(false);
+
+/// Yields `0, 1, 2, ...` sent from a background thread until the iteration is stopped.
+#[napi(ts_return_type = "AsyncIterable<number>")]
+pub fn create_ticker(env: Env) -> Result<JsObject> {
+  let (sender, receiver) = std::sync::mpsc::channel();
+  TICKER_STOPPED.store(false, Ordering::SeqCst);
+  std::thread::spawn(move || {
+    let mut tick = 0u32;
+    while sender.send(tick).is_ok() {
+      tick += 1;
+      std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    TICKER_STOPPED.store(true, Ordering::SeqCst);
+  });
+  env.create_async_iterable(receiver)
+}
+
+#[napi]
+pub fn is_ticker_stopped() -> bool {
+  TICKER_STOPPED.load(Ordering::SeqCst)
+}
+
+/// Yields the `values` from a background thread and finishes after the last one.
+#[napi(ts_return_type = "AsyncIterable<string>")]
+pub fn stream_strings(env: Env, values: Vec<String>) -> Result<JsObject> {
+  let (sender, receiver) = std::sync::mpsc::channel();
+  std::thread::spawn(move || {
+    for value in values {
+      if sender.send(value).is_err() {
+        break;
+      }
+    }
+  });
+  env.create_async_iterable(receiver)
+}
+
+/// Yields the `values` as a Web `ReadableStream`, and errors instead of yielding `fail_at` if it's given.
+#[napi(ts_return_type = "ReadableStream<string>")]
+pub fn readable_stream_strings(
+  env: Env,
+  values: Vec<String>,
+  fail_at: Option<String>,
+) -> Result<JsObject> {
+  env.create_readable_stream(futures::stream::iter(values.into_iter().map(
+    move |value| match &fail_at {
+      Some(fail_at) if *fail_at == value => {
+        Err(Error::from_reason(format!("Failed at `{}`", value)))
+      }
+      _ => Ok(value),
+    },
+  )))
+}
+
+static WEB_TICKER_DROPPED: AtomicBool = AtomicBool::new(false);
+
+struct WebTicker(u32);
+
+impl Drop for WebTicker {
+  fn drop(&mut self) {
+    WEB_TICKER_DROPPED.store(true, Ordering::SeqCst);
+  }
+}
+
+/// Yields `0, 1, 2, ...` as a Web `ReadableStream` until the stream is cancelled.
+#[napi(ts_return_type = "ReadableStream<number>")]
+pub fn create_web_ticker(env: Env) -> Result<JsObject> {
+  WEB_TICKER_DROPPED.store(false, Ordering::SeqCst);
+  env.create_readable_stream(futures::stream::unfold(
+    WebTicker(0),
+    |mut ticker| async move {
+      let tick = ticker.0;
+      ticker.0 += 1;
+      Some((Ok(tick), ticker))
+    },
+  ))
+}
+
+#[napi]
+pub fn is_web_ticker_dropped() -> bool {
+  WEB_TICKER_DROPPED.load(Ordering::SeqCst)
+}
@@ -39,6 +39,7 @@ mod function;
 mod generator;
 mod js_mod;
 mod map;
+mod module_init;
 mod nullable;
 mod number;
 mod object;
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use napi::bindgen_prelude::JsMap;
+
 #[napi]
 fn get_mapping() -> HashMap<String, u32> {
   let mut map = HashMap::new();
@@ -12,3 +14,33 @@ fn get_mapping() -> HashMap<String, u32> {
 fn sum_mapping(nums: HashMap<String, u32>) -> u32 {
   nums.into_values().sum()
 }
+
+#[napi]
+fn lookup_lengths(keys: Vec<String>) -> HashMap<String, u32> {
+  keys
+    .into_iter()
+    .map(|key| {
+      let len = key.chars().count() as u32;
+      (key, len)
+    })
+    .collect()
+}
+
+/// Swap the keys and the values of a `Map` with numeric keys
+#[napi]
+fn invert_map(map: JsMap<u32, String>) -> JsMap<String, u32> {
+  map
+    .into_inner()
+    .into_iter()
+    .map(|(k, v)| (v, k))
+    .collect::<HashMap<_, _>>()
+    .into()
+}
+
+#[napi(strict)]
+fn scale_map_values(mut map: JsMap<u32, f64>, factor: f64) -> JsMap<u32, f64> {
+  for value in map.values_mut() {
+    *value *= factor;
+  }
+  map
+}
@@ -0,0 +1,297 @@
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use napi::{
+  bindgen_prelude::{
+    export_doc, get_class_constructor, get_exported_js_function, get_js_function,
+    keep_export_reference, mark_es_module, register_class_after, register_class_parent,
+    register_global, register_module_accessor, register_module_export,
+    register_module_export_after, register_module_export_dynamic,
+    register_module_export_dynamic_with_doc, register_module_export_raw, register_pre_init,
+    set_export_filter, set_export_limit, set_parent_module, ToNapiValue,
+  },
+  sys, Env, Error, JsFunction, JsObject, JsString, NapiRaw, NapiValue, Result, Status,
+};
+
+static MODULE_INIT_COUNT: AtomicU32 = AtomicU32::new(0);
+static MODULE_INIT_BEFORE_EXPORTS: AtomicBool = AtomicBool::new(false);
+
+#[napi(module_init)]
+fn init() -> Result<()> {
+  if std::env::var("NAPI_RS_TEST_MODULE_INIT_ERROR").is_ok() {
+    return Err(Error::new(
+      Status::GenericFailure,
+      "Module init failed".to_owned(),
+    ));
+  }
+  MODULE_INIT_COUNT.fetch_add(1, Ordering::SeqCst);
+  MODULE_INIT_BEFORE_EXPORTS.store(
+    get_class_constructor("Animal\0").is_none(),
+    Ordering::SeqCst,
+  );
+  Ok(())
+}
+
+#[napi::module_init]
+fn limit_exports() {
+  if std::env::var("NAPI_RS_TEST_EXPORT_LIMIT").is_ok() {
+    set_export_limit(10);
+  }
+}
+
+/// Leaves out the top level export named in `NAPI_RS_TEST_DISABLED_EXPORT`,
+/// and the exports with an invalid name unless their test is running
+#[napi::module_init]
+fn filter_exports() {
+  register_pre_init(|_env, _exports| {
+    set_export_filter(|js_mod, name| match name {
+      "Invalid Class" => std::env::var("NAPI_RS_TEST_INVALID_CLASS_NAME").is_ok(),
+      "invalid-export" => std::env::var("NAPI_RS_TEST_INVALID_EXPORT_NAME").is_ok(),
+      _ => js_mod.is_some() || std::env::var("NAPI_RS_TEST_DISABLED_EXPORT").as_deref() != Ok(name),
+    });
+    Ok(())
+  });
+}
+
+#[napi::module_init]
+fn register_panicking_export() {
+  if std::env::var("NAPI_RS_TEST_PANIC_IN_EXPORT").is_ok() {
+    napi::bindgen_prelude::register_module_export(None, "panickingExport\0", |_env| {
+      panic!("export callback panicked")
+    });
+  }
+}
+
+unsafe fn create_dynamic_export(env: sys::napi_env) -> Result<sys::napi_value> {
+  unsafe { String::to_napi_value(env, "dynamic".to_owned()) }
+}
+
+/// Exports named at runtime, like names read from a config file
+#[napi::module_init]
+fn register_dynamic_exports() {
+  for i in 1..=2 {
+    register_module_export_dynamic_with_doc(
+      None,
+      format!("dynamicExport{}", i),
+      format!("Dynamic export number {}", i),
+      create_dynamic_export,
+    )
+    .unwrap();
+  }
+  register_module_export_dynamic(
+    Some("dynamicNamespace".to_owned()),
+    "value".to_owned(),
+    create_dynamic_export,
+  )
+  .unwrap();
+}
+
+/// Doc comment of an export registered at runtime
+#[napi]
+pub fn get_dynamic_export_doc(name: String, js_mod: Option<String>) -> Option<String> {
+  export_doc(js_mod.as_deref(), &name)
+}
+
+#[napi]
+pub fn get_module_init_count() -> u32 {
+  MODULE_INIT_COUNT.load(Ordering::SeqCst)
+}
+
+#[napi]
+pub fn is_module_init_before_exports() -> bool {
+  MODULE_INIT_BEFORE_EXPORTS.load(Ordering::SeqCst)
+}
+
+fn to_string_tag(env: &Env) -> Result<napi::JsSymbol> {
+  env
+    .get_global()?
+    .get_named_property::<JsFunction>("Symbol")?
+    .coerce_to_object()?
+    .get_named_property("toStringTag")
+}
+
+#[napi::module_init]
+fn register_pre_init_callbacks() {
+  register_pre_init(|env, exports| {
+    let env = unsafe { Env::from_raw(env) };
+    let mut exports = unsafe { JsObject::from_raw_unchecked(env.raw(), exports) };
+    exports.set_property(to_string_tag(&env)?, env.create_string("NapiRs")?)
+  });
+  register_pre_init(|env, exports| {
+    let env = unsafe { Env::from_raw(env) };
+    let mut exports = unsafe { JsObject::from_raw_unchecked(env.raw(), exports) };
+    let tag = to_string_tag(&env)?;
+    let name = exports
+      .get_property::<_, JsString>(tag)?
+      .into_utf8()?
+      .into_owned()?;
+    exports.set_property(tag, env.create_string(&format!("{} Examples", name))?)
+  });
+}
+
+/// Only exported if `NAPI_RS_TEST_FEATURE_GATE` is truthy when the module is loaded
+#[napi(enabled_if_env = "NAPI_RS_TEST_FEATURE_GATE")]
+pub fn feature_gated_export() -> &'static str {
+  "enabled"
+}
+
+#[napi::module_init]
+fn register_reentrant_get_js_function() {
+  if std::env::var("NAPI_RS_TEST_REENTRANT_GET_JS_FUNCTION").is_ok() {
+    napi::bindgen_prelude::register_module_export(None, "reentrantGetJsFunction\0", |env| {
+      let env = unsafe { Env::from_raw(env) };
+      let message = match get_js_function(&env, get_module_init_count_js_function) {
+        Ok(_) => "ok".to_owned(),
+        Err(e) => e.reason,
+      };
+      env.create_string(&message).map(|s| unsafe { s.raw() })
+    });
+  }
+}
+
+static ANSWER_READS: AtomicU32 = AtomicU32::new(0);
+
+unsafe extern "C" fn get_answer(
+  env: sys::napi_env,
+  _info: sys::napi_callback_info,
+) -> sys::napi_value {
+  ANSWER_READS.fetch_add(1, Ordering::SeqCst);
+  let mut answer = ptr::null_mut();
+  unsafe { sys::napi_create_uint32(env, 42, &mut answer) };
+  answer
+}
+
+#[napi::module_init]
+fn register_answer() {
+  register_module_accessor(None, "answer", Some(get_answer), None);
+}
+
+#[napi]
+pub fn get_answer_reads() -> u32 {
+  ANSWER_READS.load(Ordering::SeqCst)
+}
+
+#[napi::module_init]
+fn keep_chain_reference() {
+  keep_export_reference(chain_js_function);
+}
+
+/// Returns itself, so it can be called again
+#[napi]
+pub fn chain(env: Env) -> Result<JsFunction> {
+  get_exported_js_function(&env, chain_js_function)
+}
+
+/// Registered without the `#[napi]` trampoline, throws a `TypeError` if the argument is not a number
+unsafe extern "C" fn add_one_raw(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut argc = 1;
+  let mut arg = ptr::null_mut();
+  let mut value = 0.0;
+  let mut ret = ptr::null_mut();
+  unsafe {
+    sys::napi_get_cb_info(
+      env,
+      info,
+      &mut argc,
+      &mut arg,
+      ptr::null_mut(),
+      ptr::null_mut(),
+    );
+    if argc < 1 || sys::napi_get_value_double(env, arg, &mut value) != sys::Status::napi_ok {
+      sys::napi_throw_type_error(env, ptr::null(), "Expected a number\0".as_ptr().cast());
+      return ptr::null_mut();
+    }
+    sys::napi_create_double(env, value + 1.0, &mut ret);
+  }
+  ret
+}
+
+#[napi::module_init]
+fn register_raw_exports() {
+  unsafe {
+    register_module_export_raw(None, "addOneRaw", add_one_raw);
+    register_module_export_raw(Some("rawNamespace"), "addOne", add_one_raw);
+  }
+}
+
+unsafe fn create_greet(env: sys::napi_env) -> Result<sys::napi_value> {
+  let env = unsafe { Env::from_raw(env) };
+  let greet = env.create_function_from_closure("napiRsGreet", |ctx| {
+    let name = ctx.get::<JsString>(0)?.into_utf8()?.into_owned()?;
+    Ok(format!("Hello, {}!", name))
+  })?;
+  Ok(unsafe { greet.raw() })
+}
+
+unsafe fn create_export_order(env: sys::napi_env) -> Result<sys::napi_value> {
+  unsafe { String::to_napi_value(env, "ordered".to_owned()) }
+}
+
+/// `exportAfterAnchor` is registered first but installed after `exportAnchor`,
+/// with `NAPI_RS_TEST_EXPORT_CYCLE` two exports are registered after each other
+#[napi::module_init]
+fn register_ordered_exports() {
+  register_module_export_after(
+    (None, "exportAnchor"),
+    None,
+    "exportAfterAnchor",
+    create_export_order,
+  );
+  register_module_export(None, "exportAnchor\0", create_export_order);
+  register_class_after("Dog", "Animal");
+  if std::env::var("NAPI_RS_TEST_EXPORT_CYCLE").is_ok() {
+    register_module_export_after(
+      (None, "exportCycleB"),
+      None,
+      "exportCycleA",
+      create_export_order,
+    );
+    register_module_export_after(
+      (None, "exportCycleA"),
+      None,
+      "exportCycleB",
+      create_export_order,
+    );
+  }
+}
+
+/// `JsonPlugin` extends `Plugin` of the module the JavaScript side puts on `globalThis.napiRsPluginHost`
+#[napi::module_init]
+fn register_plugin_parent() {
+  if std::env::var("NAPI_RS_TEST_PARENT_MODULE").is_ok() {
+    register_class_parent("JsonPlugin", "plugin-host", "Plugin");
+  }
+  register_pre_init(|env, _exports| {
+    let env = unsafe { Env::from_raw(env) };
+    let host = env
+      .get_global()?
+      .get_named_property_unchecked::<napi::JsUnknown>("napiRsPluginHost")?;
+    if host.get_type()? != napi::ValueType::Object {
+      return Ok(());
+    }
+    unsafe { set_parent_module(env.raw(), "plugin-host", host.raw()) }
+  });
+}
+
+/// Installed on `globalThis` rather than on the exports, like a polyfill
+#[napi::module_init]
+fn register_globals() {
+  register_global("napiRsGreet", create_greet);
+}
+
+/// The module itself if `NAPI_RS_TEST_MODULE_MAIN` is set, every other export is a property of it
+#[napi(main, enabled_if_env = "NAPI_RS_TEST_MODULE_MAIN")]
+pub fn greet(name: String) -> String {
+  format!("Hello, {}!", name)
+}
+
+#[napi::module_init]
+fn mark_es_module_with_main() {
+  // `__esModule` is not copied to the main function
+  if std::env::var("NAPI_RS_TEST_MODULE_MAIN").is_ok() {
+    mark_es_module();
+  }
+}
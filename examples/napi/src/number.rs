@@ -1,3 +1,6 @@
+use napi::bindgen_prelude::{set_strict_numbers, strict_numbers, Result, Rounding, Scaled};
+use napi::JsNumber;
+
 #[napi]
 fn add(a: u32, b: u32) -> u32 {
   a + b
@@ -10,3 +13,81 @@ fn fibonacci(n: u32) -> u32 {
     _ => fibonacci(n - 1) + fibonacci(n - 2),
   }
 }
+
+#[napi(string_enum)]
+pub enum ScaledRounding {
+  HalfAwayFromZero,
+  HalfEven,
+  Floor,
+  Ceil,
+  TowardZero,
+}
+
+impl From<ScaledRounding> for Rounding {
+  fn from(rounding: ScaledRounding) -> Self {
+    match rounding {
+      ScaledRounding::HalfAwayFromZero => Rounding::HalfAwayFromZero,
+      ScaledRounding::HalfEven => Rounding::HalfEven,
+      ScaledRounding::Floor => Rounding::Floor,
+      ScaledRounding::Ceil => Rounding::Ceil,
+      ScaledRounding::TowardZero => Rounding::TowardZero,
+    }
+  }
+}
+
+#[napi]
+fn add_cents(a: Scaled<100>, b: Scaled<100>) -> Scaled<100> {
+  Scaled(a.0 + b.0)
+}
+
+#[napi]
+fn to_cents(value: f64, rounding: ScaledRounding) -> Result<i64> {
+  Scaled::<100>::from_f64(value, rounding.into()).map(|cents| cents.0)
+}
+
+#[napi]
+fn from_cents(cents: i64) -> Scaled<100> {
+  Scaled(cents)
+}
+
+/// Ids beyond `Number.MAX_SAFE_INTEGER` throw a `RangeError` instead of being rounded
+#[napi(strict_numbers)]
+fn echo_id(id: i64) -> i64 {
+  id
+}
+
+#[napi(strict_numbers)]
+fn strict_add_i32(a: i32, b: i32) -> i64 {
+  a as i64 + b as i64
+}
+
+#[napi]
+fn set_strict_numbers_mode(enabled: bool) -> bool {
+  let previous = strict_numbers();
+  set_strict_numbers(enabled);
+  previous
+}
+
+#[napi(object)]
+pub struct NumberInfo {
+  pub int32: i32,
+  pub uint32: u32,
+  pub int64: i64,
+  pub int64_lossless: bool,
+  pub double: f64,
+  pub is_safe_integer: bool,
+}
+
+/// Throws a `RangeError` for `NaN` and `Infinity`
+#[napi]
+fn inspect_number(value: JsNumber) -> Result<NumberInfo> {
+  let (int64, int64_lossless) = value.get_int64_lossless()?;
+  Ok(NumberInfo {
+    int32: value.get_int32()?,
+    uint32: value.get_uint32()?,
+    int64,
+    int64_lossless,
+    double: value.get_double()?,
+    is_safe_integer: value.is_safe_integer()?,
+  })
+}
@@ -1,6 +1,6 @@
 use napi::{
   bindgen_prelude::*, threadsafe_function::ThreadsafeFunction, JsGlobal, JsNull, JsObject,
-  JsUndefined, Property,
+  JsSymbol, JsUndefined, Property,
 };
 
 #[napi]
@@ -105,6 +105,14 @@ fn getter_from_obj() -> u32 {
   42
 }
 
+#[napi(ts_return_type = "{ get getterFromObj(): number }")]
+pub fn create_obj_with_named_property(env: Env) -> Result<JsObject> {
+  let mut obj = env.create_object()?;
+  let (getter, name) = get_c_callback_with_name(getter_from_obj_js_function)?;
+  obj.define_properties(&[Property::new(name)?.with_getter(getter)])?;
+  Ok(obj)
+}
+
 #[napi(object, object_to_js = false)]
 struct ObjectOnlyFromJs {
   pub count: u32,
@@ -124,3 +132,140 @@ fn receive_object_only_from_js(
     );
   });
 }
+
+#[napi(object)]
+pub struct NestedObject {
+  pub child: Option<Vec<NestedObject>>,
+}
+
+#[napi]
+fn nested_object_depth(obj: NestedObject) -> u32 {
+  let mut depth = 1;
+  let mut current = obj;
+  while let Some(child) = current
+    .child
+    .and_then(|children| children.into_iter().next())
+  {
+    depth += 1;
+    current = child;
+  }
+  depth
+}
+
+#[napi]
+fn set_max_object_nesting_depth(depth: u32) -> u32 {
+  let previous = max_nesting_depth() as u32;
+  set_max_nesting_depth(depth as usize);
+  previous
+}
+
+#[napi(object)]
+pub struct RetryPolicy {
+  pub attempts: u32,
+  pub backoff_ms: u32,
+}
+
+#[napi(object)]
+pub struct Limits {
+  pub max_connections: u32,
+  pub compression: bool,
+  pub retry: RetryPolicy,
+}
+
+/// Exported deeply frozen
+#[napi(frozen)]
+pub const LIMITS: Limits = Limits {
+  max_connections: 64,
+  compression: true,
+  retry: RetryPolicy {
+    attempts: 3,
+    backoff_ms: 100,
+  },
+};
+
+/// Parsed once at load and exported deeply frozen
+#[napi(
+  json,
+  frozen,
+  ts_type = "{ version: string, features: Record<string, boolean>, targets: string[] }"
+)]
+pub const BUILD_CONFIG: &str =
+  r#"{"version":"1.2.3","features":{"serde":true,"tokio":false},"targets":["x64","arm64"]}"#;
+
+/// Parsed once at load, the parsed object can be changed
+#[napi(json, ts_type = "{ retries: number }")]
+pub const DEFAULT_OPTIONS: &str = r#"{"retries":3}"#;
+
+/// Where `key` of the options is set: `own`, `inherited` from the prototype or `missing`
+#[napi]
+fn option_source(options: Object, key: String) -> Result<String> {
+  let source = if options.has_own_property(&key)? {
+    "own"
+  } else if options.has_property(&key)? {
+    "inherited"
+  } else {
+    "missing"
+  };
+  Ok(source.to_owned())
+}
+
+/// Remove the own `flag` property of the options, returning whether it was set
+#[napi]
+fn take_option_flag(mut options: Object, flag: JsSymbol) -> Result<bool> {
+  if !options.has_own_property_js(flag)? {
+    return Ok(false);
+  }
+  options.delete_property(flag)
+}
+
+/// Sealed: the fields can be changed, but no property can be added or removed
+#[napi(object, seal)]
+pub struct Cursor {
+  pub line: u32,
+  pub column: u32,
+}
+
+#[napi]
+fn create_cursor() -> Cursor {
+  Cursor { line: 1, column: 1 }
+}
+
+thread_local! {
+  static EVALUATED_LAZY_FIELDS: std::cell::RefCell<Vec<String>> = Default::default();
+}
+
+fn evaluate_lazy_field<T>(field: &str, value: T) -> Result<T> {
+  EVALUATED_LAZY_FIELDS.with(|fields| fields.borrow_mut().push(field.to_owned()));
+  Ok(value)
+}
+
+#[napi(object)]
+pub struct LazyConfig {
+  pub name: String,
+  pub plugins: LazyField<Vec<String>>,
+  pub max_connections: LazyField<u32>,
+  pub theme: LazyField<String>,
+}
+
+#[napi]
+pub fn load_lazy_config(name: String) -> LazyConfig {
+  LazyConfig {
+    name,
+    plugins: LazyField::new(|| {
+      evaluate_lazy_field("plugins", vec!["auth".to_owned(), "cache".to_owned()])
+    }),
+    max_connections: LazyField::new(|| evaluate_lazy_field("maxConnections", 16)),
+    theme: LazyField::new(|| evaluate_lazy_field("theme", "dark".to_owned())),
+  }
+}
+
+/// Names of the lazy fields evaluated since the last call
+#[napi]
+pub fn take_evaluated_lazy_fields() -> Vec<String> {
+  EVALUATED_LAZY_FIELDS.with(|fields| fields.take())
+}
+
+#[napi]
+pub fn count_lazy_config_plugins(config: LazyConfig) -> Result<u32> {
+  Ok(config.plugins.evaluate()?.len() as u32)
+}
@@ -31,8 +31,64 @@ fn test_serde_roundtrip(data: Value) -> Value {
   data
 }
 
+/// Replace every number with the kind of `serde_json::Number` it's converted into
+#[napi]
+fn json_number_kinds(value: Value) -> Value {
+  match value {
+    Value::Number(n) if n.is_u64() => Value::from("u64"),
+    Value::Number(n) if n.is_i64() => Value::from("i64"),
+    Value::Number(_) => Value::from("f64"),
+    Value::Array(items) => items.into_iter().map(json_number_kinds).collect(),
+    Value::Object(map) => map
+      .into_iter()
+      .map(|(k, v)| (k, json_number_kinds(v)))
+      .collect(),
+    value => value,
+  }
+}
+
 #[napi]
 fn test_serde_big_number_precision(number: String) -> Value {
   let data = format!("{{\"number\":{}}}", number);
   serde_json::from_str(&data).unwrap()
 }
+
+#[napi(object)]
+#[derive(Serialize, Deserialize)]
+pub struct SerdeCompatMeta {
+  pub license: String,
+}
+
+#[napi(object)]
+#[derive(Serialize, Deserialize)]
+pub struct SerdeCompatPackage {
+  #[serde(rename = "package_name")]
+  pub name: String,
+  #[serde(flatten)]
+  pub meta: SerdeCompatMeta,
+}
+
+#[napi]
+fn serde_compat_to_json(pkg: SerdeCompatPackage) -> Result<String> {
+  Ok(serde_json::to_string(&pkg)?)
+}
+
+#[napi]
+fn serde_compat_from_json(json: String) -> Result<SerdeCompatPackage> {
+  Ok(serde_json::from_str(&json)?)
+}
+
+#[napi]
+fn json_value_depth(value: Value) -> u32 {
+  match value {
+    Value::Array(items) => 1 + items.into_iter().map(json_value_depth).max().unwrap_or(0),
+    Value::Object(map) => {
+      1 + map
+        .into_iter()
+        .map(|(_, v)| json_value_depth(v))
+        .max()
+        .unwrap_or(0)
+    }
+    _ => 0,
+  }
+}
@@ -1,4 +1,4 @@
-use napi::bindgen_prelude::*;
+use napi::{bindgen_prelude::*, JsString};
 
 #[napi]
 fn contains(source: String, target: String) -> bool {
@@ -16,6 +16,37 @@ fn concat_utf16(s: Utf16String) -> Utf16String {
   Utf16String::from(format!("{} + Rust 🦀 string!", s))
 }
 
+#[napi]
+fn roundtrip_raw_utf16(s: RawUtf16String) -> RawUtf16String {
+  s
+}
+
+#[napi]
+fn replace_lone_surrogates(s: RawUtf16String) -> String {
+  s.to_utf8_lossy()
+}
+
+#[napi]
+fn count_lone_surrogates(s: RawUtf16String) -> u32 {
+  char::decode_utf16(s.iter().copied())
+    .filter(|c| c.is_err())
+    .count() as u32
+}
+
+#[napi]
+fn js_string_utf8_len(s: JsString) -> Result<u32> {
+  Ok(s.utf8_len()? as u32)
+}
+
+#[napi]
+fn read_js_string(s: JsString, lossy: bool) -> Result<String> {
+  if lossy {
+    s.into_string_lossy()
+  } else {
+    s.into_string()
+  }
+}
+
 #[napi]
 fn concat_latin1(s: Latin1String) -> String {
   format!("{} + Rust 🦀 string!", s)
@@ -25,3 +56,17 @@ fn concat_latin1(s: Latin1String) -> String {
 pub fn roundtrip_str(s: String) -> String {
   s
 }
+
+#[napi]
+pub fn return_shared_str(s: String) -> std::sync::Arc<str> {
+  std::sync::Arc::from(s)
+}
+
+#[napi]
+pub fn return_cow_str(borrowed: bool) -> std::borrow::Cow<'static, str> {
+  if borrowed {
+    std::borrow::Cow::Borrowed("borrowed 🦀")
+  } else {
+    std::borrow::Cow::Owned(format!("{} 🦀", "owned"))
+  }
+}
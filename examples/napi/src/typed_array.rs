@@ -1,4 +1,4 @@
-use napi::{bindgen_prelude::*, JsArrayBuffer};
+use napi::{bindgen_prelude::*, JsArrayBuffer, JsDataView};
 
 #[napi]
 fn get_buffer() -> Buffer {
@@ -76,3 +76,89 @@ fn async_reduce_buffer(buf: Buffer) -> Result<AsyncTask<AsyncBuffer>> {
 fn async_buffer_to_array(buf: JsArrayBuffer) -> Result<Vec<u8>> {
   Ok(buf.into_value()?.as_ref().to_vec())
 }
+
+#[napi]
+fn option_buffer_len(buf: Option<Buffer>) -> Option<u32> {
+  buf.map(|buf| buf.len() as u32)
+}
+
+#[napi]
+fn option_uint8_array_len(input: Option<Uint8Array>) -> Option<u32> {
+  input.map(|input| input.len() as u32)
+}
+
+#[napi]
+fn reverse_bytes(input: Uint8Array) -> Uint8Array {
+  let mut bytes = input.to_vec();
+  bytes.reverse();
+  Uint8Array::from(bytes)
+}
+
+/// Write `input * factor` into `output` in place, returning the count written
+#[napi]
+fn scale_into(input: &[f64], factor: f64, output: &mut [f64]) -> u32 {
+  let count = input.len().min(output.len());
+  for (out, value) in output.iter_mut().zip(input) {
+    *out = value * factor;
+  }
+  count as u32
+}
+
+/// Create a `DataView` over `length` bytes of `buf` from `byte_offset`
+#[napi]
+fn create_data_view(
+  env: Env,
+  buf: JsArrayBuffer,
+  byte_offset: u32,
+  length: u32,
+) -> Result<JsDataView> {
+  env.create_dataview(&buf, byte_offset as usize, length as usize)
+}
+
+#[napi(object)]
+pub struct PacketHeader {
+  /// Big-endian `u16` at the start of the view
+  pub tag: u16,
+  /// Little-endian `u32` following the tag
+  pub payload: u32,
+  pub byte_offset: u32,
+  pub byte_length: u32,
+}
+
+/// Parse a header with mixed endianness from the bytes of `view`
+#[napi(strict)]
+fn parse_packet_header(view: JsDataView) -> Result<PacketHeader> {
+  let view = view.into_value()?;
+  if view.len() < 6 {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Packet header needs 6 bytes, got {}", view.len()),
+    ));
+  }
+  Ok(PacketHeader {
+    tag: u16::from_be_bytes([view[0], view[1]]),
+    payload: u32::from_le_bytes([view[2], view[3], view[4], view[5]]),
+    byte_offset: view.byte_offset as u32,
+    byte_length: view.length as u32,
+  })
+}
+
+/// Names of the `is_*` type guards the value passes
+#[napi]
+fn value_kinds(value: Unknown) -> Result<Vec<&'static str>> {
+  let checks: [(&'static str, fn(&Unknown) -> Result<bool>); 6] = [
+    ("array", Unknown::is_array),
+    ("arraybuffer", Unknown::is_arraybuffer),
+    ("typedarray", Unknown::is_typedarray),
+    ("buffer", Unknown::is_buffer),
+    ("dataview", Unknown::is_dataview),
+    ("promise", Unknown::is_promise),
+  ];
+  let mut kinds = vec![];
+  for (kind, check) in checks {
+    if check(&value)? {
+      kinds.push(kind);
+    }
+  }
+  Ok(kinds)
+}